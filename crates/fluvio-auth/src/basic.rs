@@ -0,0 +1,257 @@
+//!
+//! # Policy based authorization
+//!
+//! Evaluates [`BasicRbacPolicy`] against the x509 identity forwarded by the TLS proxy.
+//! Used by both SC and SPU, so the same policy file governs control plane and data plane.
+//!
+use std::sync::Arc;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::convert::TryFrom;
+
+use async_lock::RwLock;
+use async_trait::async_trait;
+use tracing::{debug, info, error, instrument};
+
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_socket::FluvioSocket;
+
+use crate::{AuthContext, Authorization, TypeAction, InstanceAction, AuthError};
+use crate::rbac::BasicRbacPolicy;
+use crate::x509::X509Identity;
+
+/// how often policy file is checked for changes
+pub const POLICY_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+type SharedPolicy = Arc<RwLock<Arc<BasicRbacPolicy>>>;
+
+#[derive(Debug, Clone)]
+pub struct BasicAuthorization {
+    policy: SharedPolicy,
+    reload_interval: Duration,
+}
+
+impl BasicAuthorization {
+    pub fn new(policy: BasicRbacPolicy) -> Self {
+        Self {
+            policy: Arc::new(RwLock::new(Arc::new(policy))),
+            reload_interval: POLICY_RELOAD_INTERVAL,
+        }
+    }
+
+    /// set how often policy file is checked by [`Self::watch`]
+    pub fn with_reload_interval(mut self, interval: Duration) -> Self {
+        self.reload_interval = interval;
+        self
+    }
+
+    /// reload policy whenever policy file is modified.
+    /// contexts of existing connections see reloaded policy as well
+    pub fn watch(&self, path: PathBuf) {
+        // compare content rather than modification time, which may have coarse granularity
+        let content = std::fs::read(&path).ok();
+        spawn(reload_policy_loop(
+            self.policy.clone(),
+            path,
+            content,
+            self.reload_interval,
+        ));
+    }
+}
+
+async fn reload_policy_loop(
+    policy: SharedPolicy,
+    path: PathBuf,
+    mut last_content: Option<Vec<u8>>,
+    interval: Duration,
+) {
+    info!(?path, ?interval, "watching authorization policy");
+
+    loop {
+        sleep(interval).await;
+
+        let content = std::fs::read(&path).ok();
+        if content.is_none() || content == last_content {
+            continue;
+        }
+        last_content = content;
+
+        match BasicRbacPolicy::try_from(path.clone()) {
+            Ok(new_policy) => {
+                info!(?path, "authorization policy reloaded");
+                *policy.write().await = Arc::new(new_policy);
+            }
+            Err(err) => {
+                error!(?path, %err, "invalid authorization policy, keeping previous one");
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Authorization for BasicAuthorization {
+    type Context = BasicAuthContext;
+
+    #[instrument(level = "trace", skip(self, socket))]
+    async fn create_auth_context(
+        &self,
+        socket: &mut FluvioSocket,
+    ) -> Result<Self::Context, AuthError> {
+        let identity = X509Identity::create_from_connection(socket)
+            .await
+            .map_err(|err| {
+                tracing::error!(%err, "failed to create x509 identity");
+                err
+            })?;
+        Ok(BasicAuthContext {
+            identity,
+            policy: self.policy.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct BasicAuthContext {
+    identity: X509Identity,
+    policy: SharedPolicy,
+}
+
+impl BasicAuthContext {
+    async fn current_policy(&self) -> Arc<BasicRbacPolicy> {
+        self.policy.read().await.clone()
+    }
+}
+
+#[async_trait]
+impl AuthContext for BasicAuthContext {
+    async fn allow_type_action(
+        &self,
+        ty: ObjectType,
+        action: TypeAction,
+    ) -> Result<bool, AuthError> {
+        self.current_policy()
+            .await
+            .evaluate(action.into(), ty, None, &self.identity)
+            .await
+    }
+
+    /// check if specific instance of spec can be permitted
+    async fn allow_instance_action(
+        &self,
+        ty: ObjectType,
+        action: InstanceAction,
+        key: &str,
+    ) -> Result<bool, AuthError> {
+        let allowed = self
+            .current_policy()
+            .await
+            .evaluate(action.into(), ty, Some(key), &self.identity)
+            .await?;
+        debug!(principal = %self.identity.principal, key, allowed, "instance action");
+        Ok(allowed)
+    }
+
+    fn principal(&self) -> Option<&str> {
+        Some(&self.identity.principal)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::{Duration, Instant};
+    use std::collections::HashMap;
+
+    use fluvio_future::timer::sleep;
+
+    use crate::rbac::{Action, ActionUrn};
+    use crate::x509::X509Identity;
+    use crate::{AuthContext, InstanceAction, TypeAction};
+
+    use super::{BasicAuthContext, BasicAuthorization, BasicRbacPolicy, ObjectType};
+
+    fn team_policy(pattern: &str) -> BasicRbacPolicy {
+        let mut policy = BasicRbacPolicy::default();
+        let mut role = HashMap::new();
+        role.insert(
+            ObjectType::Topic,
+            vec![
+                ActionUrn::new(Action::Read, None),
+                ActionUrn::new(Action::All, Some(pattern.to_owned())),
+            ],
+        );
+        policy.0.insert(String::from("TeamA"), role);
+        policy
+    }
+
+    fn team_context(authorization: &BasicAuthorization) -> BasicAuthContext {
+        BasicAuthContext {
+            identity: X509Identity::new("alice".to_owned(), vec!["TeamA".to_owned()]),
+            policy: authorization.policy.clone(),
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_basic_context_instance_action() {
+        let authorization = BasicAuthorization::new(team_policy("team-a-*"));
+        let auth_context = team_context(&authorization);
+
+        assert!(
+            auth_context
+                .allow_type_action(ObjectType::Topic, TypeAction::Read)
+                .await
+                .expect("eval")
+        );
+        assert!(
+            auth_context
+                .allow_instance_action(ObjectType::Topic, InstanceAction::Delete, "team-a-orders")
+                .await
+                .expect("eval")
+        );
+        assert!(
+            !auth_context
+                .allow_instance_action(ObjectType::Topic, InstanceAction::Delete, "team-b-orders")
+                .await
+                .expect("eval")
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_policy_reload() {
+        let policy_file = tempfile::NamedTempFile::new().expect("policy file");
+        serde_json::to_writer(policy_file.as_file(), &team_policy("team-a-*"))
+            .expect("write policy");
+
+        let authorization = BasicAuthorization::new(team_policy("team-a-*"))
+            .with_reload_interval(Duration::from_millis(10));
+        authorization.watch(policy_file.path().to_owned());
+        let auth_context = team_context(&authorization);
+
+        std::fs::write(
+            policy_file.path(),
+            serde_json::to_vec(&team_policy("team-b-*")).expect("encode policy"),
+        )
+        .expect("write policy");
+
+        let start = Instant::now();
+        while !auth_context
+            .allow_instance_action(ObjectType::Topic, InstanceAction::Delete, "team-b-orders")
+            .await
+            .expect("eval")
+        {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "policy was not reloaded"
+            );
+            sleep(Duration::from_millis(10)).await;
+        }
+        assert!(
+            !auth_context
+                .allow_instance_action(ObjectType::Topic, InstanceAction::Delete, "team-a-orders")
+                .await
+                .expect("eval")
+        );
+    }
+}
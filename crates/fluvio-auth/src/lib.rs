@@ -1,6 +1,8 @@
 mod policy;
 mod error;
 
+pub mod basic;
+pub mod rbac;
 pub mod root;
 pub mod x509;
 
@@ -14,9 +14,14 @@ pub enum TypeAction {
     Read,
 }
 
+#[derive(Debug, Clone, PartialEq, Hash, Eq)]
 pub enum InstanceAction {
+    Create,
+    Read,
     Delete,
     Update,
+    Produce,
+    Consume,
 }
 
 #[async_trait]
@@ -35,6 +40,11 @@ pub trait AuthContext: Debug + Send + Sync + 'static {
         action: InstanceAction,
         key: &str,
     ) -> Result<bool, AuthError>;
+
+    /// principal of authenticated identity, if any
+    fn principal(&self) -> Option<&str> {
+        None
+    }
 }
 
 #[async_trait]
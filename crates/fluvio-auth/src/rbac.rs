@@ -0,0 +1,556 @@
+//!
+//! # Role based access control policy
+//!
+//! Policy maps a role (x509 scope) to a set of permissions per object type.
+//! Each permission is written as an action urn: `[!]<Action>[:<pattern>]`.
+//!
+//! * `Read` allows read on every instance of the object type
+//! * `Delete:team-a-*` allows delete on instances whose name starts with `team-a-`
+//! * `Produce:orders` allows produce to the instance named `orders` only
+//! * `!Delete:prod-*` denies delete on instances whose name starts with `prod-`
+//!
+//! Deny rules always take precedence over allow rules.
+//!
+use std::fmt;
+use std::fs::read;
+use std::str::FromStr;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::convert::TryFrom;
+
+use tracing::debug;
+use serde::{Serialize, Deserialize};
+
+use fluvio_controlplane_metadata::extended::ObjectType;
+
+use crate::{AuthError, TypeAction, InstanceAction};
+use crate::x509::X509Identity;
+
+pub type Role = String;
+
+const DENY_PREFIX: char = '!';
+const WILDCARD: &str = "*";
+
+#[derive(Debug, Clone, Copy, PartialEq, Hash, Eq, Deserialize, Serialize)]
+pub enum Action {
+    Create,
+    Read,
+    Update,
+    Delete,
+    Produce,
+    Consume,
+    All,
+}
+
+impl From<TypeAction> for Action {
+    fn from(action: TypeAction) -> Self {
+        match action {
+            TypeAction::Create => Action::Create,
+            TypeAction::Read => Action::Read,
+        }
+    }
+}
+
+impl From<InstanceAction> for Action {
+    fn from(action: InstanceAction) -> Self {
+        match action {
+            InstanceAction::Create => Action::Create,
+            InstanceAction::Read => Action::Read,
+            InstanceAction::Delete => Action::Delete,
+            InstanceAction::Update => Action::Update,
+            InstanceAction::Produce => Action::Produce,
+            InstanceAction::Consume => Action::Consume,
+        }
+    }
+}
+
+impl FromStr for Action {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "create" => Ok(Self::Create),
+            "read" => Ok(Self::Read),
+            "update" => Ok(Self::Update),
+            "delete" => Ok(Self::Delete),
+            "produce" => Ok(Self::Produce),
+            "consume" => Ok(Self::Consume),
+            "all" => Ok(Self::All),
+            _ => Err(format!(
+                "unknown action: {s}. Supported: create, read, update, delete, produce, consume, all"
+            )),
+        }
+    }
+}
+
+/// Whether a matching permission grants or revokes access
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Effect {
+    #[default]
+    Allow,
+    Deny,
+}
+
+/// Name pattern used to match object instances
+///
+/// `*` matches any instance, a trailing `*` matches by prefix,
+/// anything else must match the instance name exactly.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ResourcePattern {
+    Any,
+    Prefix(String),
+    Exact(String),
+}
+
+impl ResourcePattern {
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Prefix(prefix) => name.starts_with(prefix.as_str()),
+            Self::Exact(exact) => name == exact,
+        }
+    }
+}
+
+impl From<&str> for ResourcePattern {
+    fn from(pattern: &str) -> Self {
+        if pattern == WILDCARD {
+            Self::Any
+        } else if let Some(prefix) = pattern.strip_suffix(WILDCARD) {
+            Self::Prefix(prefix.to_owned())
+        } else {
+            Self::Exact(pattern.to_owned())
+        }
+    }
+}
+
+impl fmt::Display for ResourcePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "{WILDCARD}"),
+            Self::Prefix(prefix) => write!(f, "{prefix}{WILDCARD}"),
+            Self::Exact(exact) => write!(f, "{exact}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ActionUrn {
+    pub action: Action,
+    pub instance: Option<String>,
+    pub effect: Effect,
+}
+
+impl ActionUrn {
+    pub fn new(action: Action, instance: Option<String>) -> Self {
+        Self {
+            action,
+            instance,
+            effect: Effect::Allow,
+        }
+    }
+
+    /// create urn which revokes action
+    pub fn deny(action: Action, instance: Option<String>) -> Self {
+        Self {
+            action,
+            instance,
+            effect: Effect::Deny,
+        }
+    }
+
+    pub fn pattern(&self) -> Option<ResourcePattern> {
+        self.instance.as_deref().map(ResourcePattern::from)
+    }
+
+    /// check if urn applies to action on optional instance.
+    /// type level checks (no instance) only match urns without instance pattern
+    fn applies(&self, action: Action, instance: Option<&str>) -> bool {
+        if self.action != action && self.action != Action::All {
+            return false;
+        }
+
+        match (self.pattern(), instance) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(pattern), Some(name)) => pattern.matches(name),
+        }
+    }
+}
+
+impl Serialize for ActionUrn {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let action_str = serde_json::to_string(&self.action).map_err(serde::ser::Error::custom)?;
+        let action_str = action_str.trim_matches('"');
+        let effect = match self.effect {
+            Effect::Allow => String::new(),
+            Effect::Deny => DENY_PREFIX.to_string(),
+        };
+        let urn = match &self.instance {
+            Some(instance) => format!("{effect}{action_str}:{instance}"),
+            None => format!("{effect}{action_str}"),
+        };
+        serializer.serialize_str(&urn)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ActionUrn {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+        let urn = String::deserialize(deserializer)?;
+
+        let (effect, urn) = match urn.strip_prefix(DENY_PREFIX) {
+            Some(rest) => (Effect::Deny, rest),
+            None => (Effect::Allow, urn.as_str()),
+        };
+
+        let mut parts = urn.splitn(2, ':');
+
+        let action_str = parts
+            .next()
+            .filter(|action| !action.is_empty())
+            .ok_or(Error::custom("missing action"))?;
+        let action =
+            serde_json::from_str(format!("\"{action_str}\"").as_str()).map_err(Error::custom)?;
+
+        let instance = parts.next().map(|instance| instance.to_string());
+
+        Ok(Self {
+            action,
+            instance,
+            effect,
+        })
+    }
+}
+
+/// Parse resource in the form of `<object type>[:<instance>]`, e.g. `topic:team-a-orders`
+pub fn parse_resource(resource: &str) -> Result<(ObjectType, Option<String>), String> {
+    let mut parts = resource.splitn(2, ':');
+    let ty_str = parts.next().unwrap_or_default();
+    let ty = match ty_str.to_lowercase().as_str() {
+        "spu" => ObjectType::Spu,
+        "custom-spu" | "customspu" => ObjectType::CustomSpu,
+        "spu-group" | "spugroup" | "spg" => ObjectType::SpuGroup,
+        "topic" => ObjectType::Topic,
+        "partition" => ObjectType::Partition,
+        "connector" | "managedconnector" => ObjectType::ManagedConnector,
+        "smartmodule" | "sm" => ObjectType::SmartModule,
+        "table-format" | "tableformat" | "tf" => ObjectType::TableFormat,
+        "derived-stream" | "derivedstream" => ObjectType::DerivedStream,
+        "mirror" | "remote" => ObjectType::Mirror,
+        "dictionary" | "dict" => ObjectType::Dictionary,
+        _ => return Err(format!("unknown object type: {ty_str}")),
+    };
+    let instance = parts
+        .next()
+        .filter(|instance| !instance.is_empty())
+        .map(|instance| instance.to_owned());
+    Ok((ty, instance))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub struct BasicRbacPolicy(pub HashMap<Role, HashMap<ObjectType, Vec<ActionUrn>>>);
+
+impl From<HashMap<Role, HashMap<ObjectType, Vec<ActionUrn>>>> for BasicRbacPolicy {
+    fn from(map: HashMap<Role, HashMap<ObjectType, Vec<ActionUrn>>>) -> Self {
+        Self(map)
+    }
+}
+
+impl TryFrom<PathBuf> for BasicRbacPolicy {
+    type Error = std::io::Error;
+    fn try_from(path: PathBuf) -> Result<Self, Self::Error> {
+        debug!("reading basic policy: {:#?}", path);
+        let file = read(path)?;
+        let policy: BasicRbacPolicy = serde_json::from_slice(&file)?;
+        Ok(policy)
+    }
+}
+
+impl BasicRbacPolicy {
+    pub async fn evaluate(
+        &self,
+        action: Action,
+        object_type: ObjectType,
+        instance: Option<&str>,
+        identity: &X509Identity,
+    ) -> Result<bool, AuthError> {
+        Ok(self.is_allowed(action, &object_type, instance, identity.scopes()))
+    }
+
+    /// check if any of the scopes is permitted to perform action.
+    /// Action is denied if any matching permission denies it,
+    /// otherwise it is allowed if at least one matching permission allows it.
+    pub fn is_allowed(
+        &self,
+        action: Action,
+        object_type: &ObjectType,
+        instance: Option<&str>,
+        scopes: &[String],
+    ) -> bool {
+        let mut allowed = false;
+
+        let permissions = scopes
+            .iter()
+            .filter_map(|scope| self.0.get(scope))
+            .filter_map(|objects| objects.get(object_type))
+            .flatten()
+            .filter(|permission| permission.applies(action, instance));
+
+        for permission in permissions {
+            match permission.effect {
+                Effect::Deny => return false,
+                Effect::Allow => allowed = true,
+            }
+        }
+
+        allowed
+    }
+}
+
+impl Default for BasicRbacPolicy {
+    // default only allows the `Root` role to have full permissions;
+    fn default() -> Self {
+        let mut root_policy: HashMap<ObjectType, Vec<ActionUrn>> = HashMap::new();
+
+        root_policy.insert(ObjectType::Spu, vec![ActionUrn::new(Action::All, None)]);
+        root_policy.insert(
+            ObjectType::CustomSpu,
+            vec![ActionUrn::new(Action::All, None)],
+        );
+        root_policy.insert(
+            ObjectType::SpuGroup,
+            vec![ActionUrn::new(Action::All, None)],
+        );
+        root_policy.insert(ObjectType::Topic, vec![ActionUrn::new(Action::All, None)]);
+        root_policy.insert(
+            ObjectType::Partition,
+            vec![ActionUrn::new(Action::All, None)],
+        );
+        root_policy.insert(
+            ObjectType::TableFormat,
+            vec![ActionUrn::new(Action::All, None)],
+        );
+        root_policy.insert(
+            ObjectType::Mirror,
+            vec![
+                ActionUrn::new(Action::All, Some("user1".to_string())),
+                ActionUrn::new(Action::All, Some("user2".to_string())),
+            ],
+        );
+
+        let mut policy = HashMap::new();
+
+        policy.insert(String::from("Root"), root_policy);
+
+        Self(policy)
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_action_urn_serialization() {
+        let action_urn = ActionUrn::new(Action::Read, Some("user1".to_string()));
+        let serialized =
+            serde_json::to_string(&action_urn).expect("failed to serialize action urn");
+        assert_eq!(serialized, r#""Read:user1""#);
+
+        let deny_urn = ActionUrn::deny(Action::Delete, Some("prod-*".to_string()));
+        let serialized = serde_json::to_string(&deny_urn).expect("failed to serialize action urn");
+        assert_eq!(serialized, r#""!Delete:prod-*""#);
+    }
+
+    #[test]
+    fn test_action_urn_deserialization() {
+        let deserialized: ActionUrn =
+            serde_json::from_str(r#""Read:user1""#).expect("failed to deserialize action urn");
+        assert_eq!(
+            deserialized,
+            ActionUrn::new(Action::Read, Some("user1".to_string()))
+        );
+
+        let deserialized: ActionUrn =
+            serde_json::from_str(r#""!Produce:team-a-*""#).expect("failed to deserialize");
+        assert_eq!(
+            deserialized,
+            ActionUrn::deny(Action::Produce, Some("team-a-*".to_string()))
+        );
+
+        assert!(serde_json::from_str::<ActionUrn>(r#""!""#).is_err());
+    }
+
+    #[test]
+    fn test_resource_pattern() {
+        assert!(ResourcePattern::from("*").matches("anything"));
+        assert!(ResourcePattern::from("team-a-*").matches("team-a-orders"));
+        assert!(!ResourcePattern::from("team-a-*").matches("team-b-orders"));
+        assert!(ResourcePattern::from("orders").matches("orders"));
+        assert!(!ResourcePattern::from("orders").matches("orders-v2"));
+        assert_eq!(ResourcePattern::from("team-a-*").to_string(), "team-a-*");
+    }
+
+    #[test]
+    fn test_parse_resource() {
+        assert_eq!(
+            parse_resource("topic:team-a-orders").expect("parse"),
+            (ObjectType::Topic, Some("team-a-orders".to_owned()))
+        );
+        assert_eq!(
+            parse_resource("smartmodule").expect("parse"),
+            (ObjectType::SmartModule, None)
+        );
+        assert!(parse_resource("unknown:foo").is_err());
+    }
+
+    #[test]
+    fn test_policy_serialization() {
+        let mut policy = BasicRbacPolicy::default();
+
+        let mut default_role = HashMap::new();
+
+        default_role.insert(ObjectType::Topic, vec![ActionUrn::new(Action::All, None)]);
+        default_role.insert(
+            ObjectType::Partition,
+            vec![ActionUrn::new(Action::All, None)],
+        );
+        default_role.insert(
+            ObjectType::SpuGroup,
+            vec![ActionUrn::new(Action::Read, None)],
+        );
+        default_role.insert(
+            ObjectType::CustomSpu,
+            vec![ActionUrn::new(Action::Read, None)],
+        );
+        default_role.insert(ObjectType::Spu, vec![ActionUrn::new(Action::Read, None)]);
+        default_role.insert(
+            ObjectType::Mirror,
+            vec![
+                ActionUrn::new(Action::Read, Some("remote1".to_string())),
+                ActionUrn::new(Action::Read, Some("remote2".to_string())),
+                ActionUrn::deny(Action::Delete, Some("remote*".to_string())),
+            ],
+        );
+
+        policy.0.insert(String::from("Default"), default_role);
+
+        let tmp = tempfile::NamedTempFile::new().expect("failed to create policy file");
+        serde_json::to_writer(tmp.as_file(), &policy)
+            .expect("failed to serialize policy to json file");
+
+        let recovered_policy = BasicRbacPolicy::try_from(tmp.path().to_owned())
+            .expect("failed to parse policy from file");
+
+        assert_eq!(
+            policy, recovered_policy,
+            "serialized and deserialized policies from file should match"
+        )
+    }
+
+    #[fluvio_future::test]
+    async fn test_policy_enforcement_simple() {
+        let mut policy = BasicRbacPolicy::default();
+        let identity = X509Identity::new("User".to_owned(), vec!["Default".to_owned()]);
+
+        let mut role1 = HashMap::new();
+        role1.insert(
+            ObjectType::Topic,
+            vec![
+                ActionUrn::new(Action::Delete, None),
+                ActionUrn::new(Action::Read, None),
+            ],
+        );
+        role1.insert(
+            ObjectType::Mirror,
+            vec![
+                ActionUrn::new(Action::Update, Some("user1".to_string())),
+                ActionUrn::new(Action::Update, Some("user2".to_string())),
+            ],
+        );
+
+        policy.0.insert(String::from("Default"), role1);
+
+        assert!(
+            !policy
+                .evaluate(Action::Create, ObjectType::CustomSpu, None, &identity)
+                .await
+                .expect("eval")
+        );
+        assert!(
+            !policy
+                .evaluate(Action::Create, ObjectType::Topic, None, &identity)
+                .await
+                .expect("eval")
+        );
+        assert!(
+            policy
+                .evaluate(Action::Read, ObjectType::Topic, None, &identity)
+                .await
+                .expect("eval")
+        );
+        assert!(
+            policy
+                .evaluate(Action::Delete, ObjectType::Topic, Some("test"), &identity)
+                .await
+                .expect("eval")
+        );
+        assert!(
+            policy
+                .evaluate(Action::Update, ObjectType::Mirror, Some("user1"), &identity)
+                .await
+                .expect("eval")
+        );
+        assert!(
+            policy
+                .evaluate(Action::Update, ObjectType::Mirror, Some("user2"), &identity)
+                .await
+                .expect("eval")
+        );
+        assert!(
+            !policy
+                .evaluate(Action::Update, ObjectType::Mirror, Some("user3"), &identity)
+                .await
+                .expect("eval")
+        );
+    }
+
+    #[test]
+    fn test_policy_prefix_and_deny() {
+        let mut policy = BasicRbacPolicy::default();
+        let scopes = vec!["TeamA".to_owned(), "Auditor".to_owned()];
+
+        let mut team_a = HashMap::new();
+        team_a.insert(
+            ObjectType::Topic,
+            vec![
+                ActionUrn::new(Action::All, Some("team-a-*".to_string())),
+                ActionUrn::deny(Action::Delete, Some("team-a-prod-*".to_string())),
+            ],
+        );
+        policy.0.insert(String::from("TeamA"), team_a);
+
+        let mut auditor = HashMap::new();
+        auditor.insert(ObjectType::Topic, vec![ActionUrn::new(Action::Read, None)]);
+        policy.0.insert(String::from("Auditor"), auditor);
+
+        let topic = ObjectType::Topic;
+
+        assert!(policy.is_allowed(Action::Produce, &topic, Some("team-a-orders"), &scopes));
+        assert!(policy.is_allowed(Action::Consume, &topic, Some("team-a-orders"), &scopes));
+        assert!(policy.is_allowed(Action::Delete, &topic, Some("team-a-orders"), &scopes));
+        assert!(!policy.is_allowed(Action::Delete, &topic, Some("team-a-prod-orders"), &scopes));
+        assert!(!policy.is_allowed(Action::Produce, &topic, Some("team-b-orders"), &scopes));
+        assert!(policy.is_allowed(Action::Read, &topic, None, &scopes));
+        assert!(!policy.is_allowed(Action::Create, &topic, None, &scopes));
+        assert!(!policy.is_allowed(Action::Read, &topic, None, &["Unknown".to_owned()]));
+    }
+}
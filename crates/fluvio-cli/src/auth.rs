@@ -0,0 +1,94 @@
+//!
+//! # Auth CLI
+//!
+//! CLI commands to inspect authorization policies offline
+//!
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::{anyhow, Result};
+
+use fluvio_auth::rbac::{Action, BasicRbacPolicy, parse_resource};
+
+use crate::common::output::Terminal;
+use crate::common::t_println;
+
+#[derive(Debug, Parser)]
+pub enum AuthCmd {
+    /// Check if identity is allowed to perform an action on a resource
+    ///
+    /// Policy is evaluated locally using the same rules as the SC.
+    #[command(name = "check")]
+    Check(CheckOpt),
+}
+
+impl AuthCmd {
+    pub async fn process<O: Terminal>(self, out: Arc<O>) -> Result<()> {
+        match self {
+            Self::Check(check) => check.process(out),
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct CheckOpt {
+    /// Resource in the form of `<type>[:<name>]`, for example `topic:team-a-orders`
+    #[arg(value_name = "resource")]
+    resource: String,
+
+    /// Action to check: create, read, update, delete, produce or consume
+    #[arg(long, short)]
+    action: Action,
+
+    /// Path to authorization policy
+    #[arg(long, value_name = "authorization policy path")]
+    policy: PathBuf,
+
+    /// Principal of identity, resolved to scopes with `--scopes-file`
+    #[arg(long, requires = "scopes_file")]
+    principal: Option<String>,
+
+    /// Path to scope bindings file that maps principals to scopes
+    #[arg(long, value_name = "authorization scopes path")]
+    scopes_file: Option<PathBuf>,
+
+    /// Scope of identity, can be specified multiple times
+    #[arg(long = "scope", value_name = "scope")]
+    scopes: Vec<String>,
+}
+
+impl CheckOpt {
+    fn process<O: Terminal>(self, out: Arc<O>) -> Result<()> {
+        let policy = BasicRbacPolicy::try_from(self.policy)?;
+        let (object_type, instance) = parse_resource(&self.resource).map_err(|err| anyhow!(err))?;
+
+        let mut scopes = self.scopes;
+        if let (Some(principal), Some(path)) = (&self.principal, &self.scopes_file) {
+            let bindings: HashMap<String, Vec<String>> =
+                serde_json::from_str(&std::fs::read_to_string(path)?)?;
+            scopes.extend(bindings.get(principal).cloned().unwrap_or_default());
+        }
+
+        if scopes.is_empty() {
+            return Err(anyhow!(
+                "identity has no scopes, use --scope or --principal with --scopes-file"
+            ));
+        }
+
+        let allowed = policy.is_allowed(self.action, &object_type, instance.as_deref(), &scopes);
+        let identity = self.principal.unwrap_or_else(|| scopes.join(","));
+        let verdict = if allowed { "ALLOWED" } else { "DENIED" };
+
+        t_println!(
+            out,
+            "{verdict}: {identity} {:?} {}",
+            self.action,
+            self.resource
+        );
+        Ok(())
+    }
+}
@@ -156,6 +156,11 @@ mod cmd {
         #[arg(long, value_parser=parse_isolation)]
         pub isolation: Option<Isolation>,
 
+        /// Rack of the consumer. Committed records are read from an in-sync follower
+        /// in the same rack, falling back to the leader. Implies read_committed isolation
+        #[arg(long, value_name = "rack")]
+        pub rack: Option<String>,
+
         /// Suppress items items that have an unknown output type
         #[arg(long = "suppress-unknown")]
         pub suppress_unknown: bool,
@@ -387,6 +392,18 @@ mod cmd {
                 builder.isolation(isolation);
             }
 
+            if let Some(rack) = &self.rack {
+                // only committed records are read from follower
+                if self.isolation == Some(Isolation::ReadUncommitted) {
+                    return Err(CliError::InvalidArg(
+                        "--rack requires read_committed isolation".to_owned(),
+                    )
+                    .into());
+                }
+                builder.isolation(Isolation::ReadCommitted);
+                builder.rack(rack.clone());
+            }
+
             let consume_config = builder.build()?;
             debug!("consume config: {:#?}", consume_config);
 
@@ -829,6 +846,7 @@ mod cmd {
                 aggregate_initial: Default::default(),
                 params: Default::default(),
                 isolation: Default::default(),
+                rack: Default::default(),
                 beginning: Default::default(),
                 transforms: Default::default(),
                 transforms_line: Default::default(),
@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use clap::Parser;
+use anyhow::Result;
+use humantime::parse_duration;
+
+use fluvio::Fluvio;
+use fluvio_future::timer::sleep;
+
+use crate::common::output::Terminal;
+use crate::common::OutputFormat;
+
+/// Option for Consumer Lag
+#[derive(Debug, Parser)]
+pub struct LagConsumerOpt {
+    #[clap(flatten)]
+    output: OutputFormat,
+
+    /// Only show lag of this consumer
+    #[arg(short, long)]
+    consumer: Option<String>,
+
+    /// Only show lag of this topic
+    #[arg(short, long)]
+    topic: Option<String>,
+
+    /// Keep refreshing the lag until interrupted
+    #[arg(short, long)]
+    watch: bool,
+
+    /// Time between refreshes in watch mode
+    #[arg(long, value_parser = parse_duration, default_value = "5s", requires = "watch")]
+    interval: Duration,
+}
+
+impl LagConsumerOpt {
+    pub async fn process<O>(self, out: std::sync::Arc<O>, fluvio: &Fluvio) -> Result<()>
+    where
+        O: Terminal,
+    {
+        loop {
+            let mut lags = vec![];
+            let mut errors = vec![];
+            for lag in fluvio.consumer_lag().await? {
+                let (consumer_id, topic) = match &lag {
+                    Ok(lag) => (&lag.consumer_id, &lag.topic),
+                    Err(err) => (&err.consumer_id, &err.topic),
+                };
+                if self.consumer.as_ref().is_some_and(|c| c != consumer_id)
+                    || self.topic.as_ref().is_some_and(|t| t != topic)
+                {
+                    continue;
+                }
+                match lag {
+                    Ok(lag) => lags.push(lag),
+                    Err(err) => errors.push(err),
+                }
+            }
+            display::format_response_output(out.clone(), lags, errors, self.output.format)?;
+
+            if !self.watch {
+                return Ok(());
+            }
+            sleep(self.interval).await;
+            if self.output.format.is_table() {
+                out.println("");
+            }
+        }
+    }
+}
+
+mod display {
+
+    use std::time::Duration;
+
+    use comfy_table::{Row, Cell};
+
+    use fluvio::consumer::{ConsumerLag, ConsumerLagError};
+    use serde::Serialize;
+
+    use crate::common::t_println;
+    use crate::common::output::{OutputType, OutputError, Terminal, TableOutputHandler};
+
+    #[derive(Serialize)]
+    struct ListConsumerLag {
+        lags: Vec<ConsumerLag>,
+        /// partitions whose lag could not be fetched
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        errors: Vec<ConsumerLagError>,
+    }
+
+    pub fn format_response_output<O>(
+        out: std::sync::Arc<O>,
+        lags: Vec<ConsumerLag>,
+        errors: Vec<ConsumerLagError>,
+        output_type: OutputType,
+    ) -> Result<(), OutputError>
+    where
+        O: Terminal,
+    {
+        if !lags.is_empty() || !errors.is_empty() || !output_type.is_table() {
+            out.render_list(&ListConsumerLag { lags, errors }, output_type)?;
+        } else {
+            t_println!(out, "No consumers found");
+        }
+
+        Ok(())
+    }
+
+    impl TableOutputHandler for ListConsumerLag {
+        fn header(&self) -> Row {
+            Row::from([
+                "CONSUMER",
+                "TOPIC",
+                "PARTITION",
+                "OFFSET",
+                "HW",
+                "LEO",
+                "LAG",
+                "TIME LAG",
+            ])
+        }
+
+        fn errors(&self) -> Vec<String> {
+            self.errors.iter().map(ToString::to_string).collect()
+        }
+
+        fn content(&self) -> Vec<Row> {
+            self.lags
+                .iter()
+                .map(|lag| {
+                    let time_lag = lag
+                        .time_lag_ms
+                        .map(|ms| humantime::Duration::from(Duration::from_millis(ms)).to_string())
+                        .unwrap_or_else(|| "-".to_owned());
+                    Row::from([
+                        Cell::new(&lag.consumer_id),
+                        Cell::new(&lag.topic),
+                        Cell::new(lag.partition),
+                        Cell::new(lag.offset),
+                        Cell::new(lag.high_watermark),
+                        Cell::new(lag.log_end_offset),
+                        Cell::new(lag.lag),
+                        Cell::new(time_lag),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
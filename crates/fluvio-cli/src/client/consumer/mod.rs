@@ -1,5 +1,6 @@
 mod list;
 mod delete;
+mod lag;
 
 pub use cmd::ConsumerCmd;
 
@@ -19,6 +20,7 @@ mod cmd {
     use crate::common::FluvioExtensionMetadata;
 
     use super::delete::DeleteConsumerOpt;
+    use super::lag::LagConsumerOpt;
     use super::list::ListConsumerOpt;
 
     #[derive(Debug, Parser)]
@@ -36,6 +38,12 @@ mod cmd {
             help_template = crate::common::COMMAND_TEMPLATE,
         )]
         Delete(DeleteConsumerOpt),
+        /// Show how far Consumers are behind the end of their partitions
+        #[command(
+            name = "lag",
+            help_template = crate::common::COMMAND_TEMPLATE,
+        )]
+        Lag(LagConsumerOpt),
     }
 
     #[async_trait]
@@ -52,6 +60,9 @@ mod cmd {
                 Self::Delete(delete) => {
                     delete.process(out, fluvio).await?;
                 }
+                Self::Lag(lag) => {
+                    lag.process(out, fluvio).await?;
+                }
             }
 
             Ok(())
@@ -0,0 +1,79 @@
+//!
+//! # Create a compression dictionary
+//!
+//! CLI tree to create zstd dictionary from dictionary file or train it from sample files
+//!
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::{Fluvio, ZstdDictionary};
+use fluvio::metadata::dictionary::DictionarySpec;
+
+/// default size of trained dictionary, same as zstd cli
+const DEFAULT_MAX_SIZE: usize = 112640;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct CreateDictionaryOpt {
+    /// The name of the dictionary to create
+    #[arg(value_name = "name")]
+    pub name: String,
+
+    /// Path to dictionary trained with `zstd --train`
+    #[arg(
+        short,
+        long,
+        conflicts_with = "train",
+        required_unless_present = "train"
+    )]
+    pub file: Option<PathBuf>,
+
+    /// Train dictionary from sample files, each file is one sample record
+    #[arg(long, num_args = 1.., value_name = "sample")]
+    pub train: Vec<PathBuf>,
+
+    /// Maximum size of trained dictionary in bytes
+    #[arg(long, requires = "train", default_value_t = DEFAULT_MAX_SIZE)]
+    pub max_size: usize,
+
+    /// Zstd level records are compressed with, clients use their default level if not set
+    #[arg(long, allow_negative_numbers = true)]
+    pub level: Option<i32>,
+}
+
+impl CreateDictionaryOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let dictionary = match self.file {
+            Some(path) => ZstdDictionary::new(std::fs::read(path)?)?,
+            None => {
+                let samples = self
+                    .train
+                    .iter()
+                    .map(std::fs::read)
+                    .collect::<Result<Vec<_>, _>>()?;
+                ZstdDictionary::train(&samples, self.max_size)?
+            }
+        };
+
+        let spec =
+            DictionarySpec::new(dictionary.id(), dictionary.data().to_vec()).with_level(self.level);
+        debug!(name = %self.name, %spec, "creating dictionary");
+
+        let admin = fluvio.admin().await;
+        admin.create(self.name.clone(), false, spec).await?;
+        println!(
+            "dictionary \"{}\" created with id {}",
+            self.name,
+            dictionary.id()
+        );
+
+        Ok(())
+    }
+}
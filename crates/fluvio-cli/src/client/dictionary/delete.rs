@@ -0,0 +1,29 @@
+//!
+//! # Delete compression dictionary
+//!
+//! CLI tree to delete compression dictionary
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::dictionary::DictionarySpec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct DeleteDictionaryOpt {
+    /// The name of the dictionary to delete
+    name: String,
+}
+
+impl DeleteDictionaryOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin.delete::<DictionarySpec>(&self.name).await?;
+        println!("dictionary \"{}\" deleted", self.name);
+        Ok(())
+    }
+}
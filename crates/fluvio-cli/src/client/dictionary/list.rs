@@ -0,0 +1,112 @@
+//! # List compression dictionaries CLI
+//!
+//! CLI tree and processing to list compression dictionaries
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::dictionary::DictionarySpec;
+
+use fluvio_extension_common::Terminal;
+use fluvio_extension_common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct ListDictionariesOpt {
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl ListDictionariesOpt {
+    /// Process list dictionaries cli request
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let lists = admin.all::<DictionarySpec>().await?;
+
+        output::dictionaries_response_to_output(out, lists, self.output.format)
+    }
+}
+
+mod output {
+
+    //!
+    //! # Fluvio SC - output processing
+    //!
+
+    use comfy_table::{Row, Cell};
+    use comfy_table::CellAlignment;
+    use tracing::debug;
+    use serde::Serialize;
+    use anyhow::Result;
+
+    use fluvio_extension_common::output::OutputType;
+    use fluvio_extension_common::Terminal;
+    use fluvio::metadata::objects::Metadata;
+    use fluvio::metadata::dictionary::DictionarySpec;
+    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::t_println;
+
+    #[derive(Serialize)]
+    struct ListDictionaries(Vec<Metadata<DictionarySpec>>);
+
+    // -----------------------------------
+    // Format Output
+    // -----------------------------------
+
+    /// Format dictionary list
+    pub fn dictionaries_response_to_output<O: Terminal>(
+        out: std::sync::Arc<O>,
+        list_dictionaries: Vec<Metadata<DictionarySpec>>,
+        output_type: OutputType,
+    ) -> Result<()> {
+        debug!("dictionaries: {:#?}", list_dictionaries);
+
+        if !list_dictionaries.is_empty() {
+            let dictionaries = ListDictionaries(list_dictionaries);
+            out.render_list(&dictionaries, output_type)?;
+            Ok(())
+        } else {
+            t_println!(out, "no dictionaries");
+            Ok(())
+        }
+    }
+
+    // -----------------------------------
+    // Output Handlers
+    // -----------------------------------
+    impl TableOutputHandler for ListDictionaries {
+        /// dictionary header implementation
+        fn header(&self) -> Row {
+            Row::from(["NAME", "ID", "SIZE", "LEVEL"])
+        }
+
+        /// return errors in string format
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        /// table content implementation for dictionary
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|r| {
+                    Row::from([
+                        Cell::new(&r.name).set_alignment(CellAlignment::Left),
+                        Cell::new(r.spec.id).set_alignment(CellAlignment::Right),
+                        Cell::new(bytesize::ByteSize::b(r.spec.data.len() as u64))
+                            .set_alignment(CellAlignment::Right),
+                        Cell::new(
+                            r.spec
+                                .level
+                                .map_or("-".to_owned(), |level| level.to_string()),
+                        )
+                        .set_alignment(CellAlignment::Right),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
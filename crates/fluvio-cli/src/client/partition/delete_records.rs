@@ -0,0 +1,37 @@
+//!
+//! # Delete Records
+//!
+//! CLI tree to delete records before an offset in a partition
+//!
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::{Fluvio, PartitionId};
+
+/// Option for Deleting Records
+#[derive(Debug, Parser)]
+pub struct DeleteRecordsOpt {
+    /// Topic of the partition
+    #[arg(value_name = "topic")]
+    topic: String,
+    /// Partition to delete records from
+    #[arg(short, long, default_value = "0")]
+    partition: PartitionId,
+    /// Delete all records before this offset, can't be greater than high watermark
+    #[arg(short, long, value_name = "offset")]
+    before: i64,
+}
+
+impl DeleteRecordsOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let log_start_offset = fluvio
+            .delete_records(self.topic.clone(), self.partition, self.before)
+            .await?;
+        println!(
+            "records before offset {log_start_offset} deleted on topic \"{}\" and partition \"{}\"",
+            self.topic, self.partition
+        );
+        Ok(())
+    }
+}
@@ -105,6 +105,7 @@ mod display {
                 "HW",
                 "LEO",
                 "LRS",
+                "ISR",
                 "FOLLOWER OFFSETS",
             ])
         }
@@ -151,8 +152,8 @@ mod display {
                         Cell::new(format!("{:?}", status.base_offset)),
                         Cell::new(status.leader.hw.to_string()),
                         Cell::new(status.leader.leo.to_string()),
-                        Cell::new(status.leader.leo.to_string()),
                         Cell::new(status.lrs().to_string()),
+                        Cell::new(format!("{:?}", status.isr())),
                         Cell::new(format!("{:?}", status.replicas)),
                     ])
                 })
@@ -1,4 +1,5 @@
 mod list;
+mod delete_records;
 
 pub use cmd::PartitionCmd;
 
@@ -18,6 +19,7 @@ mod cmd {
     use crate::common::FluvioExtensionMetadata;
 
     use super::list::ListPartitionOpt;
+    use super::delete_records::DeleteRecordsOpt;
 
     #[derive(Debug, Parser)]
     #[command(name = "partition", about = "Partition operations")]
@@ -28,6 +30,13 @@ mod cmd {
             help_template = crate::common::COMMAND_TEMPLATE,
         )]
         List(ListPartitionOpt),
+
+        /// Delete all records before an offset in a Partition
+        #[command(
+            name = "delete-records",
+            help_template = crate::common::COMMAND_TEMPLATE,
+        )]
+        DeleteRecords(DeleteRecordsOpt),
     }
 
     #[async_trait]
@@ -41,6 +50,9 @@ mod cmd {
                 Self::List(list) => {
                     list.process(out, fluvio).await?;
                 }
+                Self::DeleteRecords(delete_records) => {
+                    delete_records.process(fluvio).await?;
+                }
             }
 
             Ok(())
@@ -24,6 +24,7 @@ mod cmd {
     use fluvio::{
         Compression, Fluvio, FluvioError, TopicProducerPool, TopicProducerConfigBuilder, RecordKey,
         ProduceOutput, DeliverySemantic, SmartModuleContextData, Isolation, SmartModuleInvocation,
+        PartitionerKind,
     };
     use fluvio_extension_common::Terminal;
     use fluvio_types::{print_cli_ok, PartitionId};
@@ -174,6 +175,12 @@ mod cmd {
         #[arg(long, conflicts_with_all = &["smartmodule_group", "transforms"], alias = "transform")]
         pub transforms_line: Vec<String>,
 
+        /// Partitioner that assigns partitions to records.
+        /// Supported values: siphash (default), sticky - keyless records sent to one partition per batch,
+        /// murmur2 - keys hashed like Kafka clients, consistent - keys hashed with consistent hashing.
+        #[arg(long, conflicts_with_all = &["partition", "mirror"])]
+        pub partitioner: Option<PartitionerKind>,
+
         /// Partition id
         #[arg(short = 'p', long, value_name = "integer", conflicts_with = "mirror")]
         pub partition: Option<PartitionId>,
@@ -220,6 +227,10 @@ mod cmd {
             if let Some(isolation) = self.isolation {
                 config_builder.isolation(isolation);
             }
+            // Partitioner
+            if let Some(partitioner) = self.partitioner {
+                config_builder.set_partitioner_kind(partitioner);
+            }
             // Delivery Semantic
             if self.delivery_semantic == DeliverySemantic::AtMostOnce && self.isolation.is_some() {
                 warn!("Isolation is ignored for AtMostOnce delivery semantic");
@@ -260,7 +260,13 @@ impl CreateTopicOpt {
             topic_spec.set_deduplication(Some(deduplication));
         }
 
+        if let Some(dictionary) = self.setting.compression_dictionary {
+            topic_spec.set_compression_dictionary(Some(dictionary));
+        }
+        topic_spec.set_recompress(self.setting.recompress);
+
         topic_spec.set_system(self.setting.system);
+        topic_spec.set_min_in_sync_replicas(self.setting.min_in_sync_replicas);
 
         if self.setting.segment_size.is_some() || self.setting.max_partition_size.is_some() {
             let mut storage = TopicStorageConfig::default();
@@ -321,6 +327,15 @@ pub struct TopicConfigOpt {
     #[arg(long, value_name = "compression")]
     compression_type: Option<CompressionAlgorithm>,
 
+    /// Name of zstd dictionary used to compress records of the topic
+    #[arg(long, value_name = "dictionary")]
+    compression_dictionary: Option<String>,
+
+    /// Recompress produced batches to the topic compression on ingest
+    /// instead of rejecting batches with different compression
+    #[arg(long)]
+    recompress: bool,
+
     /// Max partition size (by default measured in bytes)
     /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
     #[arg(long, value_name = "bytes")]
@@ -339,6 +354,11 @@ pub struct TopicConfigOpt {
     #[arg(long, value_name = "time", value_parser=parse_duration, requires = "dedup", default_value = "5s")]
     dedup_age: Duration,
 
+    /// Minimum number of in-sync replicas, including leader, required to accept
+    /// produce requests waiting for full acknowledgement
+    #[arg(long, value_name = "integer")]
+    min_in_sync_replicas: Option<u16>,
+
     /// Flag to create a system topic
     /// System topics are for internal operations
     #[arg(long, short = 's', hide = true)]
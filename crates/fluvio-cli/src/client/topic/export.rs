@@ -0,0 +1,44 @@
+//!
+//! # Export Topic
+//!
+//! CLI tree to export a Topic into an archive file
+//!
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use anyhow::{Context, Result};
+use futures_util::io::BufWriter;
+
+use fluvio::Fluvio;
+use fluvio::archive::export_topic;
+use fluvio_future::fs::File;
+
+#[derive(Debug, Parser)]
+pub struct ExportTopicOpt {
+    /// The name of the Topic to export
+    #[arg(value_name = "name")]
+    topic: String,
+
+    /// Path of the archive file to write
+    #[arg(short, long, value_name = "file")]
+    output: PathBuf,
+}
+
+impl ExportTopicOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let file = File::create(&self.output)
+            .await
+            .with_context(|| format!("unable to create {}", self.output.display()))?;
+        let header = export_topic(fluvio, &self.topic, BufWriter::new(file)).await?;
+        println!(
+            "topic \"{}\" exported to {}: {} partitions, {} records, {} consumer offsets",
+            header.topic,
+            self.output.display(),
+            header.partitions.len(),
+            header.records(),
+            header.consumers.len()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+//!
+//! # Import Topic
+//!
+//! CLI tree to create a Topic from an archive file
+//!
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use anyhow::{Context, Result};
+use futures_util::io::BufReader;
+
+use fluvio::Fluvio;
+use fluvio::archive::{ImportConfig, import_topic};
+use fluvio_future::fs::File;
+
+#[derive(Debug, Parser)]
+pub struct ImportTopicOpt {
+    /// Path of the archive file created by topic export
+    #[arg(value_name = "file")]
+    input: PathBuf,
+
+    /// Name of the created Topic, the exported Topic name by default
+    #[arg(short, long, value_name = "name")]
+    topic: Option<String>,
+
+    /// Keep offsets of the exported records and restore consumer offsets
+    #[arg(long)]
+    preserve_offsets: bool,
+}
+
+impl ImportTopicOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let file = File::open(&self.input)
+            .await
+            .with_context(|| format!("unable to open {}", self.input.display()))?;
+
+        let mut builder = ImportConfig::builder();
+        builder.preserve_offsets(self.preserve_offsets);
+        if let Some(topic) = self.topic {
+            builder.topic(topic);
+        }
+        let header = import_topic(fluvio, BufReader::new(file), builder.build()?).await?;
+        println!(
+            "topic \"{}\" imported from {}: {} partitions, {} records",
+            header.topic,
+            self.input.display(),
+            header.partitions.len(),
+            header.records()
+        );
+        Ok(())
+    }
+}
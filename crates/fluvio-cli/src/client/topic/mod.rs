@@ -4,6 +4,8 @@ mod describe;
 mod list;
 mod add_partition;
 mod add_mirror;
+mod export;
+mod import;
 
 pub use cmd::TopicCmd;
 
@@ -28,6 +30,8 @@ mod cmd {
     use super::create::CreateTopicOpt;
     use super::delete::DeleteTopicOpt;
     use super::describe::DescribeTopicsOpt;
+    use super::export::ExportTopicOpt;
+    use super::import::ImportTopicOpt;
     use super::list::ListTopicsOpt;
 
     #[derive(Debug, Parser)]
@@ -74,6 +78,20 @@ mod cmd {
             help_template = COMMAND_TEMPLATE,
         )]
         AddMirror(AddMirrorOpt),
+
+        /// Export a Topic with its records and offsets into an archive file
+        #[command(
+            name = "export",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Export(ExportTopicOpt),
+
+        /// Create a Topic from an archive file created by export
+        #[command(
+            name = "import",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Import(ImportTopicOpt),
     }
 
     #[async_trait]
@@ -102,6 +120,12 @@ mod cmd {
                 Self::AddMirror(add_mirror) => {
                     add_mirror.process(fluvio).await?;
                 }
+                Self::Export(export) => {
+                    export.process(fluvio).await?;
+                }
+                Self::Import(import) => {
+                    import.process(fluvio).await?;
+                }
             }
 
             Ok(())
@@ -3,6 +3,7 @@
 //! CLI configurations at the top of the tree
 
 mod error;
+mod auth;
 pub mod client;
 pub mod install;
 mod profile;
@@ -81,6 +82,7 @@ mod root {
     use fluvio_channel::{FLUVIO_RELEASE_CHANNEL, LATEST_CHANNEL_NAME};
 
     use crate::profile::ProfileOpt;
+    use crate::auth::AuthCmd;
     use crate::install::opts::InstallOpt;
     use crate::client::FluvioCmd;
     use crate::metadata::{MetadataOpt, subcommand_metadata};
@@ -147,6 +149,13 @@ mod root {
         #[command(name = "profile")]
         Profile(ProfileOpt),
 
+        /// Inspect authorization policies
+        ///
+        /// Evaluate an SC authorization policy locally to test whether an identity
+        /// can perform an action on a resource.
+        #[command(subcommand, name = "auth")]
+        Auth(AuthCmd),
+
         /// Install or uninstall Fluvio cluster
         ///
         #[cfg(feature = "k8s")]
@@ -197,6 +206,9 @@ mod root {
                 Self::Profile(profile) => {
                     profile.process(out).await?;
                 }
+                Self::Auth(auth) => {
+                    auth.process(out).await?;
+                }
                 #[cfg(feature = "k8s")]
                 Self::Cluster(cluster) => {
                     if let Ok(channel_name) = std::env::var(FLUVIO_RELEASE_CHANNEL) {
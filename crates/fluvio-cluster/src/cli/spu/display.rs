@@ -38,7 +38,9 @@ where
 impl TableOutputHandler for ListSpus {
     /// table header implementation
     fn header(&self) -> Row {
-        Row::from(["ID", "NAME", "STATUS", "TYPE", "RACK", "PUBLIC", "PRIVATE"])
+        Row::from([
+            "ID", "NAME", "STATUS", "TYPE", "RACK", "PUBLIC", "PRIVATE", "DIRS", "USED",
+        ])
     }
 
     /// return errors in string format
@@ -51,6 +53,19 @@ impl TableOutputHandler for ListSpus {
             .iter()
             .map(|metadata| {
                 let spu = &metadata.spec;
+                let status = &metadata.status;
+                let (dirs, used) = if status.storage_dirs.is_empty() {
+                    ("-".to_string(), "-".to_string())
+                } else {
+                    (
+                        format!(
+                            "{}/{}",
+                            status.online_storage_dirs(),
+                            status.storage_dirs.len()
+                        ),
+                        bytesize::ByteSize::b(status.storage_used_bytes()).to_string(),
+                    )
+                };
                 Row::from([
                     Cell::new(spu.id),
                     Cell::new(metadata.name.to_string()),
@@ -59,6 +74,8 @@ impl TableOutputHandler for ListSpus {
                     Cell::new(spu.rack.as_ref().unwrap_or(&"-".to_string())),
                     Cell::new(spu.public_endpoint.to_string()),
                     Cell::new(spu.private_endpoint.to_string()),
+                    Cell::new(dirs),
+                    Cell::new(used),
                 ])
             })
             .collect()
@@ -3,8 +3,9 @@ use color_eyre::owo_colors::OwoColorize;
 use colored::Colorize;
 use fluvio_extension_common::installation::InstallationType;
 use fluvio_sc_schema::{
-    mirror::MirrorSpec, partition::PartitionSpec, smartmodule::SmartModuleSpec, spg::SpuGroupSpec,
-    spu::SpuSpec, store::NameSpace, tableformat::TableFormatSpec, topic::TopicSpec,
+    dictionary::DictionarySpec, mirror::MirrorSpec, partition::PartitionSpec,
+    smartmodule::SmartModuleSpec, spg::SpuGroupSpec, spu::SpuSpec, store::NameSpace,
+    tableformat::TableFormatSpec, topic::TopicSpec,
 };
 use fluvio_stream_dispatcher::metadata::{local::LocalMetadataStorage, MetadataClient};
 use fluvio_types::config_file::SaveLoadConfig;
@@ -165,6 +166,9 @@ async fn check_all_metadata(pb: &ProgressRenderer, client: &LocalMetadataStorage
         .retrieve_items::<TableFormatSpec>(&NameSpace::All)
         .await?;
     let _ = client.retrieve_items::<MirrorSpec>(&NameSpace::All).await?;
+    let _ = client
+        .retrieve_items::<DictionarySpec>(&NameSpace::All)
+        .await?;
 
     pb.println(format!("✅ {}", "Checked All Metadata".bold()));
     Ok(())
@@ -0,0 +1,238 @@
+//!
+//! # Zstd dictionaries
+//!
+//! Small records compress poorly on their own, a dictionary trained from sample records
+//! gives zstd the shared context.  Id of the dictionary is written into every zstd frame
+//! compressed with it, so frames are decompressed with dictionary registered under that id.
+//!
+use std::collections::HashMap;
+use std::fmt;
+#[cfg(feature = "zstd")]
+use std::sync::{Arc, OnceLock};
+use std::sync::{LazyLock, RwLock};
+
+use bytes::Bytes;
+#[cfg(feature = "zstd")]
+use ::zstd::dict::{DecoderDictionary, EncoderDictionary};
+
+use crate::error::CompressionError;
+
+/// zstd compression level used unless configured otherwise
+pub const DEFAULT_ZSTD_LEVEL: i32 = 1;
+
+/// magic number of zstd dictionary format
+const DICTIONARY_MAGIC: u32 = 0xEC30A437;
+
+/// magic number of zstd frame
+const FRAME_MAGIC: u32 = 0xFD2FB528;
+
+/// dictionaries known to this process, keyed by dictionary id
+static DICTIONARIES: LazyLock<RwLock<HashMap<u32, ZstdDictionary>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Zstd dictionary with id read from its header.
+/// Dictionary is prepared for compression and decompression on first use,
+/// clones share prepared dictionaries
+#[derive(Clone)]
+pub struct ZstdDictionary {
+    id: u32,
+    data: Bytes,
+    level: i32,
+    #[cfg(feature = "zstd")]
+    prepared: Arc<PreparedDictionary>,
+}
+
+#[cfg(feature = "zstd")]
+#[derive(Default)]
+struct PreparedDictionary {
+    encoder: OnceLock<EncoderDictionary<'static>>,
+    decoder: OnceLock<DecoderDictionary<'static>>,
+}
+
+impl fmt::Debug for ZstdDictionary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ZstdDictionary")
+            .field("id", &self.id)
+            .field("len", &self.data.len())
+            .field("level", &self.level)
+            .finish()
+    }
+}
+
+impl PartialEq for ZstdDictionary {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.level == other.level && self.data == other.data
+    }
+}
+
+impl Eq for ZstdDictionary {}
+
+impl ZstdDictionary {
+    /// Dictionary must be in zstd dictionary format with non zero id,
+    /// raw content dictionaries can't be referenced from frames.
+    pub fn new(data: impl Into<Bytes>) -> Result<Self, CompressionError> {
+        let data = data.into();
+        if data.len() < 8 {
+            return Err(CompressionError::InvalidDictionary(
+                "dictionary is too short".to_owned(),
+            ));
+        }
+        let magic = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
+        if magic != DICTIONARY_MAGIC {
+            return Err(CompressionError::InvalidDictionary(
+                "not a zstd dictionary".to_owned(),
+            ));
+        }
+        let id = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+        if id == 0 {
+            return Err(CompressionError::InvalidDictionary(
+                "dictionary has no id".to_owned(),
+            ));
+        }
+        Ok(Self {
+            id,
+            data,
+            level: DEFAULT_ZSTD_LEVEL,
+            #[cfg(feature = "zstd")]
+            prepared: Default::default(),
+        })
+    }
+
+    /// compression level used when compressing with this dictionary
+    pub fn with_level(self, level: i32) -> Self {
+        Self {
+            level,
+            #[cfg(feature = "zstd")]
+            prepared: Default::default(),
+            ..self
+        }
+    }
+
+    /// train dictionary of at most `max_size` bytes from sample records
+    #[cfg(feature = "zstd")]
+    pub fn train<S: AsRef<[u8]>>(samples: &[S], max_size: usize) -> Result<Self, CompressionError> {
+        let data = ::zstd::dict::from_samples(samples, max_size)?;
+        Self::new(data)
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn level(&self) -> i32 {
+        self.level
+    }
+
+    #[cfg(feature = "zstd")]
+    pub(crate) fn encoder(&self) -> &EncoderDictionary<'static> {
+        self.prepared
+            .encoder
+            .get_or_init(|| EncoderDictionary::copy(&self.data, self.level))
+    }
+
+    #[cfg(feature = "zstd")]
+    pub(crate) fn decoder(&self) -> &DecoderDictionary<'static> {
+        self.prepared
+            .decoder
+            .get_or_init(|| DecoderDictionary::copy(&self.data))
+    }
+}
+
+/// register dictionary, so frames compressed with it can be decompressed.
+/// Registered dictionary is kept if it's same, so it stays prepared
+pub fn register_dictionary(dictionary: ZstdDictionary) {
+    let mut dictionaries = write_dictionaries();
+    if dictionaries.get(&dictionary.id) != Some(&dictionary) {
+        dictionaries.insert(dictionary.id, dictionary);
+    }
+}
+
+pub fn unregister_dictionary(id: u32) -> Option<ZstdDictionary> {
+    write_dictionaries().remove(&id)
+}
+
+pub fn lookup_dictionary(id: u32) -> Option<ZstdDictionary> {
+    DICTIONARIES
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&id)
+        .cloned()
+}
+
+/// id of dictionary referenced by frame header, none if frame doesn't need dictionary
+pub fn frame_dictionary_id(src: &[u8]) -> Option<u32> {
+    if src.len() < 5 || u32::from_le_bytes([src[0], src[1], src[2], src[3]]) != FRAME_MAGIC {
+        return None;
+    }
+    let descriptor = src[4];
+    let single_segment = descriptor & 0x20 != 0;
+    let id_size = match descriptor & 0x03 {
+        0 => return None,
+        1 => 1,
+        2 => 2,
+        _ => 4,
+    };
+    let start = if single_segment { 5 } else { 6 };
+    let id_bytes = src.get(start..start + id_size)?;
+    let mut id = [0u8; 4];
+    id[..id_size].copy_from_slice(id_bytes);
+    Some(u32::from_le_bytes(id)).filter(|id| *id != 0)
+}
+
+fn write_dictionaries() -> std::sync::RwLockWriteGuard<'static, HashMap<u32, ZstdDictionary>> {
+    DICTIONARIES
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dictionary_bytes(id: u32) -> Vec<u8> {
+        let mut data = DICTIONARY_MAGIC.to_le_bytes().to_vec();
+        data.extend_from_slice(&id.to_le_bytes());
+        data.extend_from_slice(b"content");
+        data
+    }
+
+    #[test]
+    fn test_dictionary_id() {
+        let dictionary = ZstdDictionary::new(dictionary_bytes(42)).expect("dictionary");
+        assert_eq!(dictionary.id(), 42);
+
+        assert!(ZstdDictionary::new(dictionary_bytes(0)).is_err());
+        assert!(ZstdDictionary::new(b"raw content dictionary".to_vec()).is_err());
+        assert!(ZstdDictionary::new(vec![0x37, 0xA4]).is_err());
+    }
+
+    #[test]
+    fn test_registry() {
+        let dictionary = ZstdDictionary::new(dictionary_bytes(7001)).expect("dictionary");
+        assert!(lookup_dictionary(7001).is_none());
+        register_dictionary(dictionary.clone());
+        assert_eq!(lookup_dictionary(7001), Some(dictionary));
+        assert!(unregister_dictionary(7001).is_some());
+        assert!(lookup_dictionary(7001).is_none());
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_register_keeps_prepared_dictionary() {
+        let dictionary = ZstdDictionary::new(dictionary_bytes(7002)).expect("dictionary");
+        register_dictionary(dictionary.clone());
+        register_dictionary(ZstdDictionary::new(dictionary_bytes(7002)).expect("dictionary"));
+        let registered = lookup_dictionary(7002).expect("registered");
+        assert!(Arc::ptr_eq(&registered.prepared, &dictionary.prepared));
+
+        register_dictionary(dictionary.clone().with_level(5));
+        let registered = lookup_dictionary(7002).expect("registered");
+        assert_eq!(registered.level(), 5);
+        assert!(!Arc::ptr_eq(&registered.prepared, &dictionary.prepared));
+        unregister_dictionary(7002);
+    }
+}
@@ -15,6 +15,10 @@ pub enum CompressionError {
     UnreachableError,
     #[error("unknown compression format: {0}")]
     UnknownCompressionFormat(String),
+    #[error("invalid compression dictionary: {0}")]
+    InvalidDictionary(String),
+    #[error("compression dictionary with id {0} is not registered")]
+    DictionaryNotFound(u32),
     #[error("error flushing Snap encoder: {0}")]
     #[cfg(feature = "compress")]
     SnapError(#[from] Box<IntoInnerError<FrameEncoder<Writer<BytesMut>>>>),
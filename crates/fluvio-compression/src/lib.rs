@@ -1,6 +1,7 @@
 use std::str::FromStr;
 
 mod error;
+mod dictionary;
 
 use bytes::Bytes;
 
@@ -17,6 +18,10 @@ mod lz4;
 mod zstd;
 
 pub use error::CompressionError;
+pub use dictionary::{
+    DEFAULT_ZSTD_LEVEL, ZstdDictionary, frame_dictionary_id, lookup_dictionary,
+    register_dictionary, unregister_dictionary,
+};
 use serde::{Serialize, Deserialize};
 
 /// The compression algorithm used to compress and decompress records in fluvio batches
@@ -108,6 +113,22 @@ impl Compression {
         }
     }
 
+    /// Compress the given data with zstd dictionary, other algorithms ignore dictionary
+    #[allow(unused_variables)]
+    pub fn compress_with_dictionary(
+        &self,
+        src: &[u8],
+        dictionary: Option<&ZstdDictionary>,
+    ) -> Result<Bytes, CompressionError> {
+        match (*self, dictionary) {
+            #[cfg(feature = "zstd")]
+            (Compression::Zstd, Some(dictionary)) => {
+                zstd::compress_with_dictionary(src, dictionary)
+            }
+            _ => self.compress(src),
+        }
+    }
+
     /// Uncompresss the given data, returning the uncompressed data if any compression was applied, otherwise returns None
     #[allow(unused_variables)]
     pub fn uncompress(&self, src: &[u8]) -> Result<Option<Vec<u8>>, CompressionError> {
@@ -130,7 +151,15 @@ impl Compression {
             }
             #[cfg(feature = "zstd")]
             Compression::Zstd => {
-                let output = zstd::uncompress(src)?;
+                // frame compressed with dictionary carries its id
+                let output = match frame_dictionary_id(src) {
+                    Some(id) => {
+                        let dictionary = lookup_dictionary(id)
+                            .ok_or(CompressionError::DictionaryNotFound(id))?;
+                        zstd::uncompress_with_dictionary(src, &dictionary)?
+                    }
+                    None => zstd::uncompress(src)?,
+                };
                 Ok(Some(output))
             }
         }
@@ -160,6 +189,37 @@ impl From<fluvio_types::compression::Compression> for Compression {
 mod tests {
     use super::Compression;
 
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn uncompress_with_registered_dictionary() {
+        use super::{ZstdDictionary, register_dictionary, unregister_dictionary};
+
+        let samples: Vec<String> = (0..2000)
+            .map(|i| {
+                format!(
+                    "{{\"order\":{i},\"item\":\"item-{}\",\"qty\":{}}}",
+                    i % 23,
+                    i % 7
+                )
+            })
+            .collect();
+        let dictionary = ZstdDictionary::train(&samples, 2048).expect("train");
+        let record = samples[100].as_bytes();
+        let compressed = Compression::Zstd
+            .compress_with_dictionary(record, Some(&dictionary))
+            .expect("compress");
+
+        assert!(Compression::Zstd.uncompress(&compressed).is_err());
+
+        register_dictionary(dictionary.clone());
+        let uncompressed = Compression::Zstd
+            .uncompress(&compressed)
+            .expect("uncompress")
+            .expect("output");
+        assert_eq!(uncompressed, record);
+        unregister_dictionary(dictionary.id());
+    }
+
     #[test]
     fn converts_from_fluvio_compression() {
         use fluvio_types::compression::Compression as CompressionType;
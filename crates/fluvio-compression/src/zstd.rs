@@ -3,10 +3,22 @@ use std::io::{Read, Write};
 use bytes::{BufMut, Bytes, BytesMut};
 use zstd::{Decoder, Encoder};
 
+use crate::dictionary::{DEFAULT_ZSTD_LEVEL, ZstdDictionary};
 use crate::error::CompressionError;
 
 pub fn compress(src: &[u8]) -> Result<Bytes, CompressionError> {
-    let mut encoder = Encoder::new(BytesMut::new().writer(), 1)?;
+    let mut encoder = Encoder::new(BytesMut::new().writer(), DEFAULT_ZSTD_LEVEL)?;
+    encoder.write_all(src)?;
+    Ok(encoder.finish()?.into_inner().freeze())
+}
+
+/// compress with prepared dictionary at level of the dictionary
+pub fn compress_with_dictionary(
+    src: &[u8],
+    dictionary: &ZstdDictionary,
+) -> Result<Bytes, CompressionError> {
+    let mut encoder =
+        Encoder::with_prepared_dictionary(BytesMut::new().writer(), dictionary.encoder())?;
     encoder.write_all(src)?;
     Ok(encoder.finish()?.into_inner().freeze())
 }
@@ -18,9 +30,20 @@ pub fn uncompress<T: Read>(src: T) -> Result<Vec<u8>, CompressionError> {
     Ok(buffer)
 }
 
+pub fn uncompress_with_dictionary(
+    src: &[u8],
+    dictionary: &ZstdDictionary,
+) -> Result<Vec<u8>, CompressionError> {
+    let mut decoder = Decoder::with_prepared_dictionary(src, dictionary.decoder())?;
+    let mut buffer: Vec<u8> = Vec::new();
+    decoder.read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Buf;
+    use crate::dictionary::frame_dictionary_id;
     use super::*;
 
     #[test]
@@ -30,8 +53,33 @@ mod tests {
 
         assert!(compressed.len() < text.len());
 
+        assert_eq!(frame_dictionary_id(&compressed), None);
+
         let uncompressed = String::from_utf8(uncompress(compressed.reader()).unwrap()).unwrap();
 
         assert_eq!(uncompressed, text);
     }
+
+    #[test]
+    fn test_compress_decompress_with_dictionary() {
+        let samples: Vec<String> = (0..2000u32)
+            .map(|i| {
+                format!(
+                    "{{\"id\":{i},\"sensor\":\"sensor-{}\",\"temperature\":{},\"status\":\"{}\"}}",
+                    i % 17,
+                    i.wrapping_mul(2654435761) % 1000,
+                    if i % 3 == 0 { "ok" } else { "degraded" }
+                )
+            })
+            .collect();
+        let dictionary = ZstdDictionary::train(&samples, 4096).expect("train");
+
+        let record = samples[42].as_bytes();
+        let compressed = compress_with_dictionary(record, &dictionary).unwrap();
+        assert!(compressed.len() < compress(record).unwrap().len());
+        assert_eq!(frame_dictionary_id(&compressed), Some(dictionary.id()));
+
+        let uncompressed = uncompress_with_dictionary(&compressed, &dictionary).unwrap();
+        assert_eq!(uncompressed, record);
+    }
 }
@@ -1,18 +1,64 @@
 use std::{
+    fmt::Display,
+    future::Future,
     io::{Error as IoError, ErrorKind},
     sync::atomic::{AtomicBool, Ordering},
 };
 use std::time::Duration;
 
-use fluvio::consumer::{BoxConsumerStream, ConsumerConfigExtBuilder, OffsetManagementStrategy};
-use fluvio::{Fluvio, FluvioClusterConfig, Offset};
-use fluvio_connector_package::config::{ConsumerPartitionConfig, OffsetConfig, OffsetStrategyConfig};
+use fluvio::consumer::{
+    BoxConsumerStream, ConsumerConfigExtBuilder, ConsumerRecord, DeadLetterPolicy,
+    OffsetManagementStrategy,
+};
+use fluvio::metadata::topic::TopicSpec;
+use fluvio::{Fluvio, FluvioAdmin, FluvioClusterConfig, Offset};
+use fluvio_connector_package::config::{
+    ConsumerDeadLetterConfig, ConsumerPartitionConfig, OffsetConfig, OffsetStrategyConfig,
+};
 use crate::{config::ConnectorConfig, Result};
-use crate::ensure_topic_exists;
+use crate::{ensure_topic_exists, is_topic_already_exists};
 use crate::smartmodule::smartmodule_vec_from_config;
 
 pub use fluvio::consumer::ConsumerStream;
 
+fn dead_letter_policy(config: &ConsumerDeadLetterConfig) -> DeadLetterPolicy {
+    let mut policy = DeadLetterPolicy::new(&config.topic);
+    if let Some(max_attempts) = config.max_attempts {
+        policy = policy.max_attempts(max_attempts);
+    }
+    if let Some(backoff) = config.backoff {
+        policy = policy.backoff(backoff);
+    }
+    policy
+}
+
+async fn ensure_dead_letter_topic_exists(topic: &str) -> Result<()> {
+    let admin = FluvioAdmin::connect().await?;
+    let topics = admin
+        .list::<TopicSpec, String>(vec![topic.to_owned()])
+        .await?;
+    if !topics.iter().any(|t| t.name.eq(topic)) {
+        match admin
+            .create(
+                topic.to_owned(),
+                false,
+                TopicSpec::new_computed(1, 1, Some(false)),
+            )
+            .await
+        {
+            Ok(_) => tracing::info!(topic, "dead-letter topic successfully created"),
+            Err(err) if is_topic_already_exists(&err) => {
+                tracing::info!(topic, "dead-letter topic created by another client")
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+/// With `dead-letter` consumer config, records failing in SmartModules are retried and published
+/// to the dead-letter topic by the returned stream. Records the sink fails to write are handled by
+/// passing the write to [`sink_record`].
 pub async fn consumer_stream_from_config(
     config: &ConnectorConfig,
 ) -> Result<(Fluvio, BoxConsumerStream)> {
@@ -72,6 +118,14 @@ pub async fn consumer_stream_from_config(
     if let Some(smartmodules) = smartmodule_vec_from_config(config) {
         builder.smartmodule(smartmodules);
     }
+    if let Some(dead_letter) = config
+        .meta()
+        .consumer()
+        .and_then(|c| c.dead_letter.as_ref())
+    {
+        ensure_dead_letter_topic_exists(&dead_letter.topic).await?;
+        builder.dead_letter(dead_letter_policy(dead_letter));
+    }
     tracing::info!("Building config");
     let cfg = builder.build().map_err(|e| {
         tracing::error!("Config build error: {e}");
@@ -82,6 +136,30 @@ pub async fn consumer_stream_from_config(
     Ok((fluvio, Box::pin(stream)))
 }
 
+/// Writes the record with `sink`. If the stream has a dead-letter queue, a failing write is retried
+/// with the `max-attempts` and `backoff` of the config and then published to the dead-letter topic,
+/// in which case `None` is returned and the connector can move on to the next record.
+/// Without a dead-letter queue, the error of the write is returned.
+pub async fn sink_record<S, F, Fut, T, E>(
+    stream: &S,
+    record: &ConsumerRecord,
+    mut sink: F,
+) -> Result<Option<T>>
+where
+    S: ConsumerStream + ?Sized,
+    F: FnMut(&ConsumerRecord) -> Fut,
+    Fut: Future<Output = std::result::Result<T, E>>,
+    E: Display,
+{
+    match stream.dead_letter() {
+        Some(dead_letter) => dead_letter.process(record, sink).await,
+        None => sink(record)
+            .await
+            .map(Some)
+            .map_err(|err| anyhow::anyhow!("{err}")),
+    }
+}
+
 pub fn init_ctrlc() -> Result<async_channel::Receiver<()>> {
     let (s, r) = async_channel::bounded(1);
     let invoked = AtomicBool::new(false);
@@ -105,3 +183,26 @@ pub fn init_ctrlc() -> Result<async_channel::Receiver<()>> {
     }
     Ok(r)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dead_letter_policy_from_config() {
+        let defaults = dead_letter_policy(&ConsumerDeadLetterConfig {
+            topic: "dlq".to_owned(),
+            max_attempts: None,
+            backoff: None,
+        });
+        let configured = dead_letter_policy(&ConsumerDeadLetterConfig {
+            topic: "dlq".to_owned(),
+            max_attempts: Some(5),
+            backoff: Some(Duration::from_secs(1)),
+        });
+
+        assert_eq!(defaults, DeadLetterPolicy::new("dlq"));
+        assert_eq!(configured.max_attempts, 5);
+        assert_eq!(configured.backoff, Duration::from_secs(1));
+    }
+}
@@ -11,6 +11,8 @@ pub use fluvio_connector_package::secret;
 pub use fluvio_connector_derive::connector;
 
 use fluvio::{Offset, metadata::topic::TopicSpec};
+use fluvio_sc_schema::ApiError;
+use fluvio_sc_schema::errors::ErrorCode;
 use futures::stream::LocalBoxStream;
 use async_trait::async_trait;
 use ::tracing::{info, error};
@@ -61,6 +63,7 @@ pub async fn ensure_topic_exists(config: &config::ConnectorConfig) -> Result<()>
             .await
         {
             Ok(_) => info!(topic, "successfully created"),
+            Err(err) if is_topic_already_exists(&err) => info!(topic, "created by another client"),
             Err(err) => {
                 error!("unable to create topic {topic}: {err}");
                 return Err(err);
@@ -69,3 +72,11 @@ pub async fn ensure_topic_exists(config: &config::ConnectorConfig) -> Result<()>
     }
     Ok(())
 }
+
+/// topic may be created by another client between listing and creating it
+pub(crate) fn is_topic_already_exists(error: &Error) -> bool {
+    matches!(
+        error.root_cause().downcast_ref::<ApiError>(),
+        Some(ApiError::Code(ErrorCode::TopicAlreadyExists, _))
+    )
+}
@@ -290,6 +290,24 @@ pub struct ConsumerParameters {
     pub id: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub offset: Option<ConsumerOffsetConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dead_letter: Option<ConsumerDeadLetterConfig>,
+}
+
+/// Records failing in the connector are retried and then published to the dead-letter topic
+/// SmartModule failures are covered only when the connector consumes a single partition.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ConsumerDeadLetterConfig {
+    pub topic: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_attempts: Option<u32>,
+    #[serde(
+        with = "humantime_serde",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub backoff: Option<Duration>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
@@ -708,6 +726,7 @@ mod tests {
                     max_bytes: Some(ByteSize::mb(1)),
                     id: None,
                     offset: None,
+                    dead_letter: None,
                 }),
                 secrets: Some(vec![SecretConfig {
                     name: "secret1".parse().unwrap(),
@@ -751,6 +770,7 @@ mod tests {
                         count: Some(3),
                         max_size: Some(bytesize::ByteSize(1000)),
                         replication: Some(2),
+                        min_in_sync_replicas: None,
                         ignore_rack_assignment: Some(true),
                         maps: None,
                     },
@@ -760,6 +780,8 @@ mod tests {
                     },
                     compression: CompressionConfig {
                         type_: CompressionAlgorithm::Lz4,
+                        dictionary: None,
+                        recompress: false,
                     },
                     deduplication: Some(Deduplication {
                         bounds: Bounds {
@@ -790,6 +812,7 @@ mod tests {
                         strategy: OffsetStrategyConfig::Auto,
                         flush_period: Some(Duration::from_secs(160)),
                     }),
+                    dead_letter: None,
                 }),
                 secrets: Some(vec![SecretConfig {
                     name: "secret1".parse().unwrap(),
@@ -997,6 +1020,7 @@ mod tests {
                     partition: Default::default(),
                     id: None,
                     offset: None,
+                    dead_letter: None,
                 }),
                 secrets: None,
             },
@@ -1221,6 +1245,7 @@ mod tests {
                     partition: Default::default(),
                     id: None,
                     offset: None,
+                    dead_letter: None,
                 }),
                 secrets: None,
             },
@@ -1307,12 +1332,14 @@ mod tests {
             max_bytes: Default::default(),
             id: None,
             offset: None,
+            dead_letter: None,
         };
         let many = ConsumerParameters {
             partition: ConsumerPartitionConfig::Many(vec![2, 3]),
             max_bytes: Default::default(),
             id: None,
             offset: None,
+            dead_letter: None,
         };
 
         let all = ConsumerParameters {
@@ -1320,6 +1347,7 @@ mod tests {
             max_bytes: Default::default(),
             id: None,
             offset: None,
+            dead_letter: None,
         };
 
         //when
@@ -1445,4 +1473,30 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_deser_consumer_dead_letter_config() {
+        //given
+        //when
+        let config: ConsumerParameters = serde_yaml::from_str(
+            r#"
+            partition: 0
+            dead-letter:
+              topic: failed-records
+              max-attempts: 5
+              backoff: 500ms
+        "#,
+        )
+        .expect("config");
+
+        //then
+        assert_eq!(
+            config.dead_letter,
+            Some(ConsumerDeadLetterConfig {
+                topic: "failed-records".to_owned(),
+                max_attempts: Some(5),
+                backoff: Some(Duration::from_millis(500)),
+            })
+        );
+    }
 }
@@ -0,0 +1,25 @@
+use crate::k8_types::{Crd, GROUP, V1, CrdNames, Spec, Status, DefaultHeader};
+
+use super::DictionaryStatus;
+use super::DictionarySpec;
+
+const DICTIONARY_API: Crd = Crd {
+    group: GROUP,
+    version: V1,
+    names: CrdNames {
+        kind: "Dictionary",
+        plural: "dictionaries",
+        singular: "dictionary",
+    },
+};
+
+impl Spec for DictionarySpec {
+    type Status = DictionaryStatus;
+    type Header = DefaultHeader;
+
+    fn metadata() -> &'static Crd {
+        &DICTIONARY_API
+    }
+}
+
+impl Status for DictionaryStatus {}
@@ -0,0 +1,48 @@
+//!
+//! # Dictionary Spec
+//!
+//! Trained zstd dictionary which topics can reference to compress small records.
+//!
+use std::fmt;
+
+use fluvio_protocol::{ByteBuf, Encoder, Decoder};
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct DictionarySpec {
+    /// id from dictionary header, written into every frame compressed with it
+    pub id: u32,
+    #[cfg_attr(feature = "use_serde", serde(with = "crate::smartmodule::base64"))]
+    pub data: ByteBuf,
+    /// zstd level records are compressed with, client default is used if not set
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub level: Option<i32>,
+}
+
+impl DictionarySpec {
+    pub fn new(id: u32, data: impl Into<ByteBuf>) -> Self {
+        Self {
+            id,
+            data: data.into(),
+            level: None,
+        }
+    }
+
+    pub fn with_level(mut self, level: Option<i32>) -> Self {
+        self.level = level;
+        self
+    }
+}
+
+impl fmt::Display for DictionarySpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Dictionary({}, {} bytes)", self.id, self.data.len())
+    }
+}
@@ -0,0 +1,17 @@
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+#[derive(Default, Decoder, Encoder, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct DictionaryStatus;
+
+impl fmt::Display for DictionaryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "DictionaryStatus")
+    }
+}
@@ -33,6 +33,19 @@ pub struct PartitionSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 14)]
     pub mirror: Option<PartitionMirrorConfig>,
+    /// incremented every time leader changes, used by followers to detect divergent logs
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 20)]
+    pub leader_epoch: i32,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 21)]
+    pub min_in_sync_replicas: Option<u16>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 23)]
+    pub compression_dictionary: Option<String>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 23)]
+    pub recompress: bool,
 }
 
 impl PartitionSpec {
@@ -61,6 +74,10 @@ impl PartitionSpec {
             compression_type: topic.get_compression_type().clone(),
             deduplication: topic.get_deduplication().cloned(),
             system: topic.is_system(),
+            leader_epoch: 0,
+            min_in_sync_replicas: topic.get_min_in_sync_replicas(),
+            compression_dictionary: topic.get_compression_dictionary().map(str::to_owned),
+            recompress: topic.is_recompress(),
         }
     }
 
@@ -41,6 +41,10 @@ pub struct PartitionStatus {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 16)]
     pub base_offset: i64,
+    /// replicas in sync with leader, including leader
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 21)]
+    pub isr: Vec<SpuId>,
 }
 
 impl Default for PartitionStatus {
@@ -53,6 +57,7 @@ impl Default for PartitionStatus {
             replicas: Default::default(),
             is_being_deleted: Default::default(),
             base_offset: Default::default(),
+            isr: Default::default(),
         }
     }
 }
@@ -134,6 +139,11 @@ impl PartitionStatus {
         self.lsr
     }
 
+    /// replicas in sync with leader, including leader
+    pub fn isr(&self) -> &[SpuId] {
+        &self.isr
+    }
+
     pub fn replica_iter(&self) -> Iter<ReplicaStatus> {
         self.replicas.iter()
     }
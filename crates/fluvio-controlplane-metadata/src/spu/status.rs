@@ -8,11 +8,24 @@
 use std::fmt;
 
 use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::record::ReplicaKey;
 
 #[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
-#[cfg_attr(feature = "use_serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct SpuStatus {
     pub resolution: SpuStatusResolution,
+    /// usage of each data directory, reported by SPU
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 22)]
+    pub storage_dirs: Vec<StorageDirStatus>,
+    /// replicas (as partition names) which are unavailable because their data directory failed
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 22)]
+    pub offline_replicas: Vec<String>,
 }
 
 impl fmt::Display for SpuStatus {
@@ -26,6 +39,7 @@ impl SpuStatus {
     pub fn offline() -> Self {
         Self {
             resolution: SpuStatusResolution::Offline,
+            ..Default::default()
         }
     }
     /// Resolution to string label
@@ -59,6 +73,48 @@ impl SpuStatus {
     pub fn set_offline(&mut self) {
         self.resolution = SpuStatusResolution::Offline;
     }
+
+    /// Checks if replica is unavailable on this SPU because of failed data directory
+    pub fn is_replica_offline(&self, replica: &ReplicaKey) -> bool {
+        let name = replica.to_string();
+        self.offline_replicas.iter().any(|offline| *offline == name)
+    }
+
+    /// Number of data directories which are online
+    pub fn online_storage_dirs(&self) -> usize {
+        self.storage_dirs.iter().filter(|dir| dir.online).count()
+    }
+
+    /// Total bytes used by all data directories
+    pub fn storage_used_bytes(&self) -> u64 {
+        self.storage_dirs.iter().map(|dir| dir.used_bytes).sum()
+    }
+}
+
+/// Status of single data directory of SPU
+#[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct StorageDirStatus {
+    pub path: String,
+    pub online: bool,
+    /// number of replicas stored in directory
+    pub replicas: u32,
+    pub used_bytes: u64,
+}
+
+impl fmt::Display for StorageDirStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let state = if self.online { "online" } else { "offline" };
+        write!(
+            f,
+            "{} ({state}, replicas: {}, used: {} bytes)",
+            self.path, self.replicas, self.used_bytes
+        )
+    }
 }
 
 #[derive(Decoder, Encoder, Debug, Clone, Eq, PartialEq)]
@@ -86,6 +86,12 @@ pub struct PartitionConfig {
     )]
     pub replication: Option<ReplicationFactor>,
 
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub min_in_sync_replicas: Option<u16>,
+
     #[cfg_attr(
         feature = "use_serde",
         serde(skip_serializing_if = "Option::is_none", default)
@@ -136,6 +142,19 @@ pub struct RetentionConfig {
 pub struct CompressionConfig {
     #[cfg_attr(feature = "use_serde", serde(rename = "type", default))]
     pub type_: CompressionAlgorithm,
+
+    /// name of zstd dictionary
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "Option::is_none", default)
+    )]
+    pub dictionary: Option<String>,
+
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(skip_serializing_if = "crate::is_false", default)
+    )]
+    pub recompress: bool,
 }
 
 impl TopicConfig {
@@ -159,6 +178,7 @@ impl Default for PartitionConfig {
         Self {
             count: Some(DEFAULT_PARTITION_COUNT),
             replication: Some(DEFAULT_REPLICATION_FACTOR),
+            min_in_sync_replicas: Default::default(),
             ignore_rack_assignment: Some(DEFAULT_IGNORE_RACK_ASSIGMENT),
             max_size: Default::default(),
             maps: Default::default(),
@@ -193,7 +213,10 @@ impl From<TopicConfig> for TopicSpec {
         };
 
         topic_spec.set_compression_type(config.compression.type_);
+        topic_spec.set_compression_dictionary(config.compression.dictionary);
+        topic_spec.set_recompress(config.compression.recompress);
         topic_spec.set_deduplication(config.deduplication);
+        topic_spec.set_min_in_sync_replicas(config.partition.min_in_sync_replicas);
 
         if segment_size.is_some() || max_partition_size.is_some() {
             topic_spec.set_storage(TopicStorageConfig {
@@ -227,6 +250,7 @@ partition:
   count: 3
   max-size: 1.0 KB
   replication: 2
+  min-in-sync-replicas: 2
   ignore-rack-assignment: true
   maps:
   - id: 1
@@ -299,6 +323,30 @@ compression:
         );
     }
 
+    #[cfg(feature = "use_serde")]
+    #[test]
+    fn test_compression_dictionary_config() {
+        //given
+        let input = r#"meta:
+  name: test_topic
+compression:
+  type: Zstd
+  dictionary: events
+  recompress: true
+"#;
+
+        //when
+        use std::str::FromStr;
+
+        let spec: TopicSpec = TopicConfig::from_str(input).expect("deserialized").into();
+
+        //then
+        assert_eq!(spec.get_compression_type(), &CompressionAlgorithm::Zstd);
+        assert_eq!(spec.get_compression_dictionary(), Some("events"));
+        assert!(spec.is_recompress());
+        assert!(spec.validate_config().is_none());
+    }
+
     #[test]
     fn test_default_config_to_spec() {
         //given
@@ -341,6 +389,7 @@ compression:
             max_partition_size: Some(1000),
         });
         test_spec.set_deduplication(Some(test_deduplication()));
+        test_spec.set_min_in_sync_replicas(Some(2));
 
         assert_eq!(spec, test_spec);
     }
@@ -355,6 +404,7 @@ compression:
                 count: Some(3),
                 max_size: Some(bytesize::ByteSize(1000)),
                 replication: Some(2),
+                min_in_sync_replicas: Some(2),
                 ignore_rack_assignment: Some(true),
                 maps: Some(vec![PartitionMap {
                     id: 1,
@@ -368,6 +418,8 @@ compression:
             },
             compression: CompressionConfig {
                 type_: CompressionAlgorithm::Lz4,
+                dictionary: None,
+                recompress: false,
             },
             deduplication: Some(test_deduplication()),
         }
@@ -39,6 +39,18 @@ pub struct TopicSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 13)]
     system: bool,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[fluvio(min_version = 21)]
+    min_in_sync_replicas: Option<u16>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[fluvio(min_version = 23)]
+    compression_dictionary: Option<String>,
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[cfg_attr(feature = "use_serde", serde(skip_serializing_if = "crate::is_false"))]
+    #[fluvio(min_version = 23)]
+    recompress: bool,
 }
 
 impl From<ReplicaSpec> for TopicSpec {
@@ -147,6 +159,33 @@ impl TopicSpec {
         self.system = system;
     }
 
+    /// minimum number of in-sync replicas, including leader, required to accept fully acknowledged produce
+    pub fn get_min_in_sync_replicas(&self) -> Option<u16> {
+        self.min_in_sync_replicas
+    }
+
+    pub fn set_min_in_sync_replicas(&mut self, min_in_sync_replicas: Option<u16>) {
+        self.min_in_sync_replicas = min_in_sync_replicas;
+    }
+
+    /// name of zstd dictionary used to compress records of this topic
+    pub fn get_compression_dictionary(&self) -> Option<&str> {
+        self.compression_dictionary.as_deref()
+    }
+
+    pub fn set_compression_dictionary(&mut self, dictionary: Option<String>) {
+        self.compression_dictionary = dictionary;
+    }
+
+    /// if set, SPU recompresses batches which don't match topic compression instead of rejecting them
+    pub fn is_recompress(&self) -> bool {
+        self.recompress
+    }
+
+    pub fn set_recompress(&mut self, recompress: bool) {
+        self.recompress = recompress;
+    }
+
     /// get retention secs that can be displayed
     pub fn retention_secs(&self) -> u32 {
         self.get_clean_policy()
@@ -189,6 +228,32 @@ impl TopicSpec {
             }
         }
 
+        if let Some(min_in_sync_replicas) = self.min_in_sync_replicas {
+            if min_in_sync_replicas == 0 {
+                return Some("min_in_sync_replicas must be at least 1".to_owned());
+            }
+            if let ReplicaSpec::Computed(param) = &self.replicas {
+                if min_in_sync_replicas as u32 > param.replication_factor {
+                    return Some(format!(
+                        "min_in_sync_replicas {min_in_sync_replicas} is greater than replication factor {}",
+                        param.replication_factor
+                    ));
+                }
+            }
+        }
+
+        if self.compression_dictionary.is_some()
+            && !matches!(
+                self.compression_type,
+                CompressionAlgorithm::Zstd | CompressionAlgorithm::Any
+            )
+        {
+            return Some(format!(
+                "compression dictionary requires zstd compression, topic uses {}",
+                self.compression_type
+            ));
+        }
+
         None
     }
 }
@@ -1261,6 +1326,19 @@ mod test {
         assert!(topic_spec_decoded.deduplication.is_none());
     }
 
+    #[test]
+    fn test_validate_min_in_sync_replicas() {
+        let mut topic_spec: TopicSpec = ReplicaSpec::Computed((2, 3, false).into()).into();
+        topic_spec.set_min_in_sync_replicas(Some(2));
+        assert!(topic_spec.validate_config().is_none());
+
+        topic_spec.set_min_in_sync_replicas(Some(0));
+        assert!(topic_spec.validate_config().is_some());
+
+        topic_spec.set_min_in_sync_replicas(Some(4));
+        assert!(topic_spec.validate_config().is_some());
+    }
+
     #[test]
     fn test_partition_map_str() {
         // Test multiple
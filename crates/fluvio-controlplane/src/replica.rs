@@ -25,6 +25,14 @@ pub struct Replica {
     pub storage: Option<TopicStorageConfig>,
     pub compression_type: CompressionAlgorithm,
     pub deduplication: Option<Deduplication>,
+    #[fluvio(min_version = 20)]
+    pub leader_epoch: i32,
+    #[fluvio(min_version = 21)]
+    pub min_in_sync_replicas: Option<u16>,
+    #[fluvio(min_version = 23)]
+    pub compression_dictionary: Option<String>,
+    #[fluvio(min_version = 23)]
+    pub recompress: bool,
 }
 
 impl Replica {
@@ -69,6 +77,10 @@ where
             storage: spec.storage,
             compression_type: spec.compression_type,
             deduplication: spec.deduplication,
+            leader_epoch: spec.leader_epoch,
+            min_in_sync_replicas: spec.min_in_sync_replicas,
+            compression_dictionary: spec.compression_dictionary,
+            recompress: spec.recompress,
         }
     }
 }
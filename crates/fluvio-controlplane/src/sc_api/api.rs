@@ -13,6 +13,7 @@ use fluvio_protocol::derive::Decoder;
 
 use crate::sc_api::update_mirror::UpdateMirrorStatRequest;
 use crate::sc_api::update_partition::UpdatePartitionStatRequest;
+use crate::sc_api::update_storage::UpdateStorageStatRequest;
 
 use super::register_spu::RegisterSpuRequest;
 use super::update_lrs::UpdateLrsRequest;
@@ -29,6 +30,7 @@ pub enum InternalScKey {
     ReplicaRemoved = 2002,
     UpdateMirror = 2003,
     UpdatePartition = 2004,
+    UpdateStorage = 2005,
 }
 
 /// Request made to Spu from Sc
@@ -44,6 +46,8 @@ pub enum InternalScRequest {
     UpdateMirrorStatRequest(RequestMessage<UpdateMirrorStatRequest>),
     #[fluvio(tag = 4)]
     UpdatePartitionStatRequest(RequestMessage<UpdatePartitionStatRequest>),
+    #[fluvio(tag = 5)]
+    UpdateStorageStatRequest(RequestMessage<UpdateStorageStatRequest>),
 }
 
 impl Default for InternalScRequest {
@@ -77,6 +81,9 @@ impl ApiMessage for InternalScRequest {
             InternalScKey::UpdatePartition => {
                 api_decode!(InternalScRequest, UpdatePartitionStatRequest, src, header)
             }
+            InternalScKey::UpdateStorage => {
+                api_decode!(InternalScRequest, UpdateStorageStatRequest, src, header)
+            }
         }
     }
 }
@@ -4,3 +4,4 @@ pub mod remove;
 pub mod update_lrs;
 pub mod update_mirror;
 pub mod update_partition;
+pub mod update_storage;
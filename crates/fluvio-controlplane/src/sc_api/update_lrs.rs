@@ -6,6 +6,7 @@ use fluvio_protocol::Decoder;
 use fluvio_protocol::Encoder;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_controlplane_metadata::partition::ReplicaStatus;
+use fluvio_types::SpuId;
 
 use super::api::InternalScKey;
 
@@ -44,7 +45,7 @@ impl fmt::Display for UpdateLrsRequest {
 impl Request for UpdateLrsRequest {
     const API_KEY: u16 = InternalScKey::UpdateLrs as u16;
     type Response = UpdateLrsResponse;
-    const DEFAULT_API_VERSION: i16 = 1;
+    const DEFAULT_API_VERSION: i16 = 2;
 }
 
 #[derive(Decoder, Encoder, Debug, Default, Clone)]
@@ -65,6 +66,9 @@ pub struct LrsRequest {
     pub size: i64,
     #[fluvio(min_version = 1)]
     pub base_offset: i64,
+    /// replicas in sync with leader, including leader
+    #[fluvio(min_version = 2)]
+    pub isr: Vec<SpuId>,
 }
 
 impl PartialEq for LrsRequest {
@@ -102,6 +106,12 @@ impl LrsRequest {
             replicas,
             size,
             base_offset,
+            isr: vec![],
         }
     }
+
+    pub fn with_isr(mut self, isr: Vec<SpuId>) -> Self {
+        self.isr = isr;
+        self
+    }
 }
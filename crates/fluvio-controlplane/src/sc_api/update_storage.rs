@@ -0,0 +1,45 @@
+use std::fmt;
+
+use fluvio_controlplane_metadata::spu::StorageDirStatus;
+use fluvio_protocol::api::Request;
+use fluvio_protocol::Decoder;
+use fluvio_protocol::Encoder;
+
+use super::api::InternalScKey;
+
+/// Storage status of SPU data directories.
+/// Each request replaces previously reported storage status
+#[derive(Decoder, Encoder, Debug, Default, Clone, Eq, PartialEq)]
+pub struct UpdateStorageStatRequest {
+    pub dirs: Vec<StorageDirStatus>,
+    /// replicas (as partition names) which can't be served because their directory failed
+    pub offline_replicas: Vec<String>,
+}
+
+impl UpdateStorageStatRequest {
+    pub fn new(dirs: Vec<StorageDirStatus>, offline_replicas: Vec<String>) -> Self {
+        Self {
+            dirs,
+            offline_replicas,
+        }
+    }
+}
+
+impl fmt::Display for UpdateStorageStatRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "storage dirs {}, offline replicas {}",
+            self.dirs.len(),
+            self.offline_replicas.len()
+        )
+    }
+}
+
+impl Request for UpdateStorageStatRequest {
+    const API_KEY: u16 = InternalScKey::UpdateStorage as u16;
+    type Response = UpdateStorageStatResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateStorageStatResponse {}
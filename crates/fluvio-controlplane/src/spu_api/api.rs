@@ -13,6 +13,8 @@ use super::update_mirror::UpdateMirrorRequest;
 use super::update_spu::UpdateSpuRequest;
 use super::update_replica::UpdateReplicaRequest;
 use super::update_smartmodule::UpdateSmartModuleRequest;
+use super::update_quota::UpdateQuotaRequest;
+use super::update_dictionary::UpdateDictionaryRequest;
 
 #[repr(u16)]
 #[derive(Eq, PartialEq, Debug, Encoder, Decoder, Clone, Copy)]
@@ -23,6 +25,8 @@ pub enum InternalSpuApi {
     UpdateSmartModule = 1003,
     // UpdateDerivedStream = 1004,
     UpdateMirror = 1004,
+    UpdateQuota = 1005,
+    UpdateDictionary = 1006,
 }
 
 impl Default for InternalSpuApi {
@@ -41,6 +45,10 @@ pub enum InternalSpuRequest {
     UpdateSmartModuleRequest(RequestMessage<UpdateSmartModuleRequest>),
     #[fluvio(tag = 3)]
     UpdateMirrorRequest(RequestMessage<UpdateMirrorRequest>),
+    #[fluvio(tag = 4)]
+    UpdateQuotaRequest(RequestMessage<UpdateQuotaRequest>),
+    #[fluvio(tag = 5)]
+    UpdateDictionaryRequest(RequestMessage<UpdateDictionaryRequest>),
 }
 
 // Added to satisfy Encoder/Decoder traits
@@ -74,6 +82,10 @@ impl ApiMessage for InternalSpuRequest {
             InternalSpuApi::UpdateMirror => {
                 api_decode!(Self, UpdateMirrorRequest, src, header)
             }
+            InternalSpuApi::UpdateQuota => api_decode!(Self, UpdateQuotaRequest, src, header),
+            InternalSpuApi::UpdateDictionary => {
+                api_decode!(Self, UpdateDictionaryRequest, src, header)
+            }
         }
     }
 }
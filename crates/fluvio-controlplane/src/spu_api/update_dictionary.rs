@@ -0,0 +1,41 @@
+use fluvio_controlplane_metadata::{
+    core::MetadataItem,
+    dictionary::DictionarySpec,
+    message::{Message, Messages},
+    store::MetadataStoreObject,
+};
+use fluvio_protocol::{Encoder, Decoder, api::Request};
+
+use crate::requests::ControlPlaneRequest;
+
+use super::api::InternalSpuApi;
+
+#[derive(Decoder, Encoder, Debug, Eq, PartialEq, Clone, Default)]
+pub struct Dictionary {
+    pub name: String,
+    pub spec: DictionarySpec,
+}
+
+pub type UpdateDictionaryRequest = ControlPlaneRequest<Dictionary>;
+
+impl Request for UpdateDictionaryRequest {
+    const API_KEY: u16 = InternalSpuApi::UpdateDictionary as u16;
+    type Response = UpdateDictionaryResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateDictionaryResponse {}
+
+pub type DictionaryMsg = Message<Dictionary>;
+pub type DictionaryMsgs = Messages<Dictionary>;
+
+impl<C> From<MetadataStoreObject<DictionarySpec, C>> for Dictionary
+where
+    C: MetadataItem,
+{
+    fn from(mso: MetadataStoreObject<DictionarySpec, C>) -> Self {
+        let name = mso.key;
+        let spec = mso.spec;
+        Self { name, spec }
+    }
+}
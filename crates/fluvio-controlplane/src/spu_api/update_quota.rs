@@ -0,0 +1,45 @@
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder, api::Request};
+
+use crate::requests::ControlPlaneRequest;
+
+use super::api::InternalSpuApi;
+
+/// client matched by quota when there is no quota for specific client
+pub const DEFAULT_QUOTA_CLIENT: &str = "*";
+
+/// Quotas are always sent as full set.
+/// SPU replaces its quotas with `all`, even when it is empty.
+pub type UpdateQuotaRequest = ControlPlaneRequest<ClientQuota>;
+
+impl Request for UpdateQuotaRequest {
+    const API_KEY: u16 = InternalSpuApi::UpdateQuota as u16;
+    type Response = UpdateQuotaResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateQuotaResponse {}
+
+/// Rate limits applied to single client on every SPU.
+/// Unset limit means unlimited.
+#[derive(Decoder, Encoder, Debug, Eq, PartialEq, Clone, Default)]
+pub struct ClientQuota {
+    /// client id or `*` for all clients without own quota
+    pub client: String,
+    pub produce_bytes_per_sec: Option<u64>,
+    pub fetch_bytes_per_sec: Option<u64>,
+    pub requests_per_sec: Option<u64>,
+}
+
+impl ClientQuota {
+    pub fn is_default(&self) -> bool {
+        self.client == DEFAULT_QUOTA_CLIENT
+    }
+}
+
+impl fmt::Display for ClientQuota {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ClientQuota({})", self.client)
+    }
+}
@@ -11,9 +11,9 @@ pub use crate::api_decode;
 #[macro_export]
 macro_rules! api_decode {
     ($api:ident,$req:ident,$src:expr,$header:expr) => {{
-        use fluvio_protocol::Decoder;
-        let request = $req::decode_from($src, $header.api_version())?;
-        Ok($api::$req(RequestMessage::new($header, request)))
+        Ok($api::$req(RequestMessage::<$req>::decode_with_header(
+            $src, $header,
+        )?))
     }};
 }
 
@@ -26,10 +26,10 @@ mod common {
     use std::io::{Cursor, Error as IoError, ErrorKind, Read};
     use std::convert::TryFrom;
 
-    use bytes::Buf;
+    use bytes::{Buf, BufMut};
     use tracing::{debug, trace};
 
-    use crate::{Encoder, Decoder};
+    use crate::{Encoder, Decoder, Version};
 
     const fn max(a: i16, b: i16) -> i16 {
         if a > b { a } else { b }
@@ -89,12 +89,75 @@ mod common {
 
     pub trait ApiKey: Sized + Encoder + Decoder + TryFrom<u16> {}
 
-    #[derive(Debug, Encoder, Decoder, Default, Clone)]
+    /// marks trace context written after request
+    const TRACE_CONTEXT_MARKER: u8 = 0x74;
+
+    /// W3C trace context of span which sent request
+    #[derive(Debug, Encoder, Decoder, Default, Clone, PartialEq, Eq)]
+    pub struct TraceContext {
+        traceparent: String,
+        tracestate: String,
+    }
+
+    impl TraceContext {
+        pub fn new(traceparent: impl Into<String>, tracestate: impl Into<String>) -> Self {
+            Self {
+                traceparent: traceparent.into(),
+                tracestate: tracestate.into(),
+            }
+        }
+
+        pub fn traceparent(&self) -> &str {
+            &self.traceparent
+        }
+
+        pub fn tracestate(&self) -> &str {
+            &self.tracestate
+        }
+    }
+
+    /// Trace context is not part of encoded header. It is written after request,
+    /// and only set when peer advertised it accepts trace context during version negotiation.
+    #[derive(Debug, Default, Clone)]
     pub struct RequestHeader {
         api_key: u16,
         api_version: i16,
         correlation_id: i32,
         client_id: String,
+        trace_context: Option<TraceContext>,
+    }
+
+    impl Encoder for RequestHeader {
+        fn write_size(&self, version: Version) -> usize {
+            self.api_key.write_size(version)
+                + self.api_version.write_size(version)
+                + self.correlation_id.write_size(version)
+                + self.client_id.write_size(version)
+        }
+
+        fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), IoError>
+        where
+            T: BufMut,
+        {
+            self.api_key.encode(dest, version)?;
+            self.api_version.encode(dest, version)?;
+            self.correlation_id.encode(dest, version)?;
+            self.client_id.encode(dest, version)?;
+            Ok(())
+        }
+    }
+
+    impl Decoder for RequestHeader {
+        fn decode<T>(&mut self, src: &mut T, version: Version) -> Result<(), IoError>
+        where
+            T: Buf,
+        {
+            self.api_key.decode(src, version)?;
+            self.api_version.decode(src, version)?;
+            self.correlation_id.decode(src, version)?;
+            self.client_id.decode(src, version)?;
+            Ok(())
+        }
     }
 
     impl fmt::Display for RequestHeader {
@@ -116,6 +179,7 @@ mod common {
                 correlation_id: 1,
 
                 client_id: client_id.into(),
+                trace_context: None,
             }
         }
 
@@ -152,6 +216,49 @@ mod common {
             self.client_id = client_id.into();
             self
         }
+
+        pub fn trace_context(&self) -> Option<&TraceContext> {
+            self.trace_context.as_ref()
+        }
+
+        pub fn set_trace_context(&mut self, trace_context: Option<TraceContext>) -> &mut Self {
+            self.trace_context = trace_context;
+            self
+        }
+
+        /// size of trace context written after request
+        pub fn trace_context_write_size(&self) -> usize {
+            self.trace_context
+                .as_ref()
+                .map(|trace_context| {
+                    TRACE_CONTEXT_MARKER.write_size(0) + trace_context.write_size(0)
+                })
+                .unwrap_or_default()
+        }
+
+        /// write trace context after request, if any
+        pub fn encode_trace_context<T>(&self, dest: &mut T) -> Result<(), IoError>
+        where
+            T: BufMut,
+        {
+            if let Some(trace_context) = &self.trace_context {
+                TRACE_CONTEXT_MARKER.encode(dest, 0)?;
+                trace_context.encode(dest, 0)?;
+            }
+            Ok(())
+        }
+
+        /// read trace context following request, bytes other than trace context are ignored
+        pub fn decode_trace_context<T>(&mut self, src: &mut T) -> Result<(), IoError>
+        where
+            T: Buf,
+        {
+            if src.remaining() > 0 && src.chunk()[0] == TRACE_CONTEXT_MARKER {
+                src.advance(1);
+                self.trace_context = Some(TraceContext::decode_from(src, 0)?);
+            }
+            Ok(())
+        }
     }
 
     impl From<&RequestHeader> for i32 {
@@ -117,6 +117,17 @@ where
         ResponseMessage::decode_from_file(file_name, version)
     }
 
+    /// decode request which follows already decoded header
+    pub fn decode_with_header<T>(src: &mut T, mut header: RequestHeader) -> Result<Self, IoError>
+    where
+        T: Buf,
+        R: Default,
+    {
+        let request = R::decode_from(src, header.api_version())?;
+        header.decode_trace_context(src)?;
+        Ok(Self { header, request })
+    }
+
     /// helper function to set client id
     #[allow(unused)]
     pub fn set_client_id<T>(mut self, client_id: T) -> Self
@@ -138,6 +149,7 @@ where
     {
         self.header.decode(src, version)?;
         self.request.decode(src, self.header.api_version())?;
+        self.header.decode_trace_context(src)?;
         Ok(())
     }
 }
@@ -147,7 +159,9 @@ where
     R: Request,
 {
     fn write_size(&self, version: Version) -> usize {
-        self.header.write_size(version) + self.request.write_size(self.header.api_version())
+        self.header.write_size(version)
+            + self.request.write_size(self.header.api_version())
+            + self.header.trace_context_write_size()
     }
 
     fn encode<T>(&self, out: &mut T, version: Version) -> Result<(), IoError>
@@ -167,6 +181,7 @@ where
 
         trace!("encoding request: {:#?}", &self.request);
         self.request.encode(out, self.header.api_version())?;
+        self.header.encode_trace_context(out)?;
         Ok(())
     }
 }
@@ -340,4 +355,30 @@ mod test {
         let msg = res_msg_result.unwrap();
         assert_eq!(msg.header.correlation_id(), 5);
     }
+
+    #[test]
+    fn test_encode_trace_context() {
+        let trace_context = crate::api::TraceContext::new(
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+            "",
+        );
+        let mut message = RequestMessage::new_request(ApiVersionRequest {});
+        message
+            .get_mut_header()
+            .set_trace_context(Some(trace_context.clone()));
+
+        let mut out = vec![];
+        message.encode(&mut out, 0).expect("encode work");
+        assert_eq!(out.len(), message.write_size(0));
+
+        let msg: RequestMessage<ApiVersionRequest> =
+            Decoder::decode_from(&mut Cursor::new(&out), 0).expect("decode");
+        assert_eq!(msg.header.trace_context(), Some(&trace_context));
+
+        // peer without trace context support reads header and request only
+        let mut src = Cursor::new(&out);
+        let header = RequestHeader::decode_from(&mut src, 0).expect("header");
+        ApiVersionRequest::decode_from(&mut src, header.api_version()).expect("request");
+        assert!(header.trace_context().is_none());
+    }
 }
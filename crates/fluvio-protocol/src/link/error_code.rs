@@ -63,6 +63,12 @@ pub enum ErrorCode {
     #[fluvio(tag = 73)]
     #[error("Partition is short-circuited")]
     PartitionShortCircuited,
+    #[fluvio(tag = 74)]
+    #[error("record batch is corrupt, CRC checksum mismatch")]
+    CorruptRecordBatch,
+    #[fluvio(tag = 75)]
+    #[error("not enough in-sync replicas: {in_sync} in sync, {min} required")]
+    NotEnoughInSyncReplicas { in_sync: u16, min: u16 },
 
     // Spu errors
     #[fluvio(tag = 1000)]
@@ -206,6 +212,18 @@ pub enum ErrorCode {
     #[fluvio(tag = 9000)]
     #[error("a compression error occurred in the SPU")]
     CompressionError,
+    #[fluvio(tag = 9001)]
+    #[error("the compression dictionary {0} was not found")]
+    CompressionDictionaryNotFound(String),
+    #[fluvio(tag = 9002)]
+    #[error("the compression dictionary already exists")]
+    CompressionDictionaryAlreadyExists,
+    #[fluvio(tag = 9003)]
+    #[error("the compression dictionary is invalid: {0}")]
+    CompressionDictionaryInvalid(String),
+    #[fluvio(tag = 9004)]
+    #[error("the compression dictionary {0} is used by topics")]
+    CompressionDictionaryInUse(String),
 
     // Deduplication
     #[fluvio(tag = 10000)]
@@ -314,6 +332,12 @@ mod test {
         assert_tag!(ErrorCode::MessageTooLarge, 10, 0);
         assert_tag!(ErrorCode::PermissionDenied, 13, 0);
         assert_tag!(ErrorCode::StorageError, 56, 0);
+        assert_tag!(ErrorCode::CorruptRecordBatch, 74, 0);
+        assert_tag!(
+            ErrorCode::NotEnoughInSyncReplicas { in_sync: 1, min: 2 },
+            75,
+            0
+        );
 
         // Spu errors
         assert_tag!(ErrorCode::SpuError, 1000, 0);
@@ -8,6 +8,8 @@ use super::ErrorCode;
 
 pub const VERSIONS_API_KEY: u16 = 18;
 pub const V10_PLATFORM: i16 = 2;
+/// first version where peer accepts trace context following request
+pub const TRACE_CONTEXT_VERSION: i16 = 3;
 
 // -----------------------------------
 // ApiVersionsRequest
@@ -25,7 +27,7 @@ pub struct ApiVersionsRequest {
 
 impl Request for ApiVersionsRequest {
     const API_KEY: u16 = VERSIONS_API_KEY;
-    const DEFAULT_API_VERSION: i16 = V10_PLATFORM;
+    const DEFAULT_API_VERSION: i16 = TRACE_CONTEXT_VERSION;
     type Response = ApiVersionsResponse;
 }
 
@@ -10,6 +10,7 @@ use tracing::trace;
 use fluvio_types::Timestamp;
 use fluvio_compression::Compression;
 use fluvio_compression::CompressionError;
+use fluvio_compression::ZstdDictionary;
 
 use crate::bytes::Buf;
 use crate::bytes::BufMut;
@@ -207,19 +208,30 @@ impl TryFrom<Batch<RawRecords>> for Batch {
 impl TryFrom<Batch> for Batch<RawRecords> {
     type Error = CompressionError;
     fn try_from(f: Batch) -> Result<Self, Self::Error> {
+        f.compress_with_dictionary(None)
+    }
+}
+
+impl Batch {
+    /// Encode records with compression set in the header.
+    /// Zstd uses dictionary if given, its id is carried in the compressed records.
+    pub fn compress_with_dictionary(
+        self,
+        dictionary: Option<&ZstdDictionary>,
+    ) -> Result<Batch<RawRecords>, CompressionError> {
         let mut buf = Vec::new();
-        f.records.encode(&mut buf, 0)?;
+        self.records.encode(&mut buf, 0)?;
 
-        let compression = f.get_compression()?;
-        let compressed_records = compression.compress(&buf)?;
+        let compression = self.get_compression()?;
+        let compressed_records = compression.compress_with_dictionary(&buf, dictionary)?;
         let compressed_records_len = compressed_records.len() as i32;
         let records = RawRecords(compressed_records);
-        let schema_id = f.schema_id();
+        let schema_id = self.schema_id();
 
         Ok(Batch {
-            base_offset: f.base_offset,
+            base_offset: self.base_offset,
             batch_len: compressed_records_len,
-            header: f.header,
+            header: self.header,
             schema_id,
             records,
         })
@@ -300,11 +312,61 @@ impl Batch {
                 offset: base_offset + relative as Offset,
                 timestamp_base: first_timestamp,
                 record,
+                topic: None,
             })
     }
 }
 
 impl Batch<RawRecords> {
+    /// check that crc in the header matches content of the batch.
+    /// Computed over raw bytes of records, which are not encoded again
+    pub fn validate_crc(&self) -> Result<(), Error> {
+        let mut fields: Vec<u8> = Vec::with_capacity(BATCH_HEADER_SIZE + size_of::<SchemaId>());
+        self.header.encode_crc_fields(&mut fields, 0)?;
+        if self.header.has_schema() {
+            self.schema_id.encode(&mut fields, 0)?;
+        }
+        let computed = crc32c::crc32c_append(crc32c::crc32c(&fields), &self.records.0);
+        if computed == self.header.crc {
+            Ok(())
+        } else {
+            Err(crc_mismatch(self.base_offset, self.header.crc, computed))
+        }
+    }
+
+    /// id of zstd dictionary which records are compressed with
+    pub fn dictionary_id(&self) -> Option<u32> {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "compress")] {
+                match self.get_compression() {
+                    Ok(Compression::Zstd) => fluvio_compression::frame_dictionary_id(&self.records.0),
+                    _ => None,
+                }
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Encode records again with different compression
+    pub fn recompress(
+        &self,
+        compression: Compression,
+        dictionary: Option<&ZstdDictionary>,
+    ) -> Result<Batch<RawRecords>, CompressionError> {
+        let records = self.memory_records()?;
+        let mut header = self.header.clone();
+        header.set_compression(compression);
+        let batch = Batch {
+            base_offset: self.base_offset,
+            batch_len: 0,
+            header,
+            schema_id: self.schema_id.clone(),
+            records,
+        };
+        batch.compress_with_dictionary(dictionary)
+    }
+
     pub fn memory_records(&self) -> Result<MemoryRecords, CompressionError> {
         let mut records: MemoryRecords = Default::default();
 
@@ -416,20 +478,7 @@ where
         self.header.partition_leader_epoch.encode(dest, version)?;
         self.header.magic.encode(dest, version)?;
 
-        let mut out: Vec<u8> = Vec::new();
-        let buf = &mut out;
-        self.header.attributes.encode(buf, version)?;
-        self.header.last_offset_delta.encode(buf, version)?;
-        self.header.first_timestamp.encode(buf, version)?;
-        self.header.max_time_stamp.encode(buf, version)?;
-        self.header.producer_id.encode(buf, version)?;
-        self.header.producer_epoch.encode(buf, version)?;
-        self.header.first_sequence.encode(buf, version)?;
-        if self.header.has_schema() {
-            self.schema_id.encode(buf, version)?;
-        }
-        self.records.encode(buf, version)?;
-
+        let out = self.crc_content(version)?;
         let crc = crc32c::crc32c(&out);
         crc.encode(dest, version)?;
         dest.put_slice(&out);
@@ -437,6 +486,33 @@ where
     }
 }
 
+impl<R: Encoder> Batch<R> {
+    /// bytes covered by crc: header after crc, schema id and records
+    fn crc_content(&self, version: Version) -> Result<Vec<u8>, Error> {
+        let mut out: Vec<u8> = Vec::new();
+        self.header.encode_crc_fields(&mut out, version)?;
+        if self.header.has_schema() {
+            self.schema_id.encode(&mut out, version)?;
+        }
+        self.records.encode(&mut out, version)?;
+        Ok(out)
+    }
+
+    /// crc computed from content of the batch, records are encoded to compute it
+    pub fn compute_crc(&self) -> Result<u32, Error> {
+        Ok(crc32c::crc32c(&self.crc_content(0)?))
+    }
+}
+
+fn crc_mismatch(base_offset: Offset, expected: u32, computed: u32) -> Error {
+    Error::new(
+        ErrorKind::InvalidData,
+        format!(
+            "batch at offset {base_offset} is corrupt, crc: {expected:#010x}, computed: {computed:#010x}"
+        ),
+    )
+}
+
 impl<R: Clone> Clone for Batch<R> {
     fn clone(&self) -> Self {
         Self {
@@ -486,6 +562,35 @@ impl BatchHeader {
     pub fn set_schema_id(&mut self) {
         self.attributes |= ATTR_SCHEMA_PRESENT;
     }
+
+    /// fields after crc, which are covered by crc
+    fn encode_crc_fields<T: BufMut>(&self, dest: &mut T, version: Version) -> Result<(), Error> {
+        self.attributes.encode(dest, version)?;
+        self.last_offset_delta.encode(dest, version)?;
+        self.first_timestamp.encode(dest, version)?;
+        self.max_time_stamp.encode(dest, version)?;
+        self.producer_id.encode(dest, version)?;
+        self.producer_epoch.encode(dest, version)?;
+        self.first_sequence.encode(dest, version)?;
+        Ok(())
+    }
+
+    /// crc computed from header and raw batch content following header (schema id and records)
+    pub fn compute_crc(&self, content: &[u8]) -> Result<u32, Error> {
+        let mut fields: Vec<u8> = Vec::with_capacity(BATCH_HEADER_SIZE);
+        self.encode_crc_fields(&mut fields, 0)?;
+        Ok(crc32c::crc32c_append(crc32c::crc32c(&fields), content))
+    }
+
+    /// check that crc matches raw batch content following header
+    pub fn validate_crc(&self, base_offset: Offset, content: &[u8]) -> Result<(), Error> {
+        let computed = self.compute_crc(content)?;
+        if computed == self.crc {
+            Ok(())
+        } else {
+            Err(crc_mismatch(base_offset, self.crc, computed))
+        }
+    }
 }
 impl Default for BatchHeader {
     fn default() -> Self {
@@ -644,6 +749,37 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_validate_crc() -> Result<(), IoError> {
+        let mut batch = Batch::from(vec![Record::new("hello"), Record::new("world")]);
+        batch.set_schema_id(SchemaId(7));
+        let mut bytes = batch.as_bytes(0)?.to_vec();
+
+        let raw = Batch::<RawRecords>::decode_from(&mut Cursor::new(&bytes), 0)?;
+        assert!(raw.validate_crc().is_ok());
+        let memory = Batch::<MemoryRecords>::decode_from(&mut Cursor::new(&bytes), 0)?;
+        assert_eq!(memory.compute_crc()?, memory.header.crc);
+        assert!(
+            raw.header
+                .validate_crc(0, &bytes[BATCH_FILE_HEADER_SIZE..])
+                .is_ok()
+        );
+
+        // flip bit in the last record value
+        let last = bytes.len() - 2;
+        bytes[last] ^= 0x01;
+        let corrupt = Batch::<RawRecords>::decode_from(&mut Cursor::new(&bytes), 0)?;
+        let err = corrupt.validate_crc().expect_err("crc mismatch");
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(
+            corrupt
+                .header
+                .validate_crc(0, &bytes[BATCH_FILE_HEADER_SIZE..])
+                .is_err()
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_batch_offset_delta() {
         let mut batch = Batch::<MemoryRecords>::default();
@@ -988,4 +1124,21 @@ mod test {
         assert_eq!(batch[2].value.as_ref(), b"c");
         assert_eq!(batch.len(), 3);
     }
+
+    #[test]
+    fn test_recompress_raw_records() {
+        let mem_records = vec![Record::new("a"), Record::new("b"), Record::new("c")];
+        let mut batch = Batch::from(mem_records);
+        batch.header.set_compression(Compression::Gzip);
+        let gzip: Batch<RawRecords> = Batch::try_from(batch).unwrap();
+
+        let zstd = gzip.recompress(Compression::Zstd, None).unwrap();
+        assert_eq!(zstd.get_compression().unwrap(), Compression::Zstd);
+        assert_eq!(zstd.dictionary_id(), None);
+        assert_eq!(zstd.last_offset_delta(), 2);
+
+        let records = zstd.memory_records().unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[2].value.as_ref(), b"c");
+    }
 }
@@ -6,6 +6,7 @@ use std::io::Error;
 use std::io::ErrorKind;
 use std::ops::Deref;
 use std::str::Utf8Error;
+use std::sync::Arc;
 
 use bytes::Bytes;
 use bytes::BytesMut;
@@ -576,6 +577,8 @@ pub struct ConsumerRecord {
     pub record: Record<RecordData>,
     /// Timestamp base of batch in which the records is present
     pub(crate) timestamp_base: Timestamp,
+    /// The topic where this Record is stored, set by the consumer stream
+    pub(crate) topic: Option<Arc<str>>,
 }
 
 impl ConsumerRecord {
@@ -589,6 +592,15 @@ impl ConsumerRecord {
         self.partition
     }
 
+    /// The topic where this Record is stored, if known.
+    pub fn topic(&self) -> Option<&str> {
+        self.topic.as_deref()
+    }
+
+    pub fn set_topic(&mut self, topic: Arc<str>) {
+        self.topic = Some(topic);
+    }
+
     /// Returns the inner representation of the Record
     pub fn into_inner(self) -> Record<RecordData> {
         self.record
@@ -776,6 +788,7 @@ mod test {
             offset: 0,
             partition: 0,
             record: Default::default(),
+            topic: None,
         };
 
         assert_eq!(record.timestamp(), NO_TIMESTAMP);
@@ -784,6 +797,7 @@ mod test {
             offset: 0,
             partition: 0,
             record: Default::default(),
+            topic: None,
         };
         assert_eq!(record.timestamp(), NO_TIMESTAMP);
     }
@@ -795,6 +809,7 @@ mod test {
             offset: 0,
             partition: 0,
             record: Default::default(),
+            topic: None,
         };
 
         assert_eq!(record.timestamp(), 1_000_000_000);
@@ -805,6 +820,7 @@ mod test {
             record: memory_record,
             offset: 0,
             partition: 0,
+            topic: None,
         };
         assert_eq!(record.timestamp(), 1_000_000_800);
     }
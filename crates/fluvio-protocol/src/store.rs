@@ -107,6 +107,7 @@ where
 
         trace!("encoding request");
         self.request.file_encode(dest, data, version)?;
+        self.header.encode_trace_context(dest)?;
         Ok(())
     }
 }
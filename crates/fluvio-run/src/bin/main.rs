@@ -4,6 +4,9 @@ use fluvio_run::RunCmd;
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cmd: RunCmd = RunCmd::parse();
 
+    #[cfg(feature = "otel")]
+    fluvio_run::telemetry::init_tracer("fluvio-run");
+    #[cfg(not(feature = "otel"))]
     fluvio_future::subscriber::init_tracer(None);
 
     cmd.process()?;
@@ -0,0 +1,327 @@
+//! In-process cluster for integration tests.
+//!
+//! [`EmbeddedCluster`] runs the SC with local metadata and N SPUs on the current executor.
+//! Servers listen on ephemeral ports of the loopback interface and store data in a temporary
+//! directory, so several clusters can run side by side in one test binary.
+//! [`EmbeddedCluster::shutdown`] stops the servers and removes the storage once their tasks ended.
+//! Requires the `embedded` feature.
+//!
+//! ```no_run
+//! use fluvio::metadata::topic::TopicSpec;
+//! use fluvio_run::embedded::EmbeddedCluster;
+//!
+//! async fn produce() -> anyhow::Result<()> {
+//!     let cluster = EmbeddedCluster::start(1).await?;
+//!     let admin = cluster.client().admin().await;
+//!     admin
+//!         .create("test".to_owned(), false, TopicSpec::new_computed(1, 1, None))
+//!         .await?;
+//!     let producer = cluster.client().topic_producer("test").await?;
+//!     producer.send("key", "value").await?.wait().await?;
+//!     cluster.shutdown().await
+//! }
+//! ```
+
+use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result, anyhow};
+use tempfile::TempDir;
+use tracing::{debug, info, warn};
+
+use fluvio::{Fluvio, FluvioClusterConfig};
+use fluvio::metadata::customspu::CustomSpuSpec;
+use fluvio::metadata::spu::SpuSpec;
+use fluvio_controlplane_metadata::spu::{Endpoint, IngressPort};
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_sc::config::ScConfig;
+use fluvio_sc::start::LocalSc;
+use fluvio_spu::{LocalSpu, SpuConfig, StartedSpu};
+use fluvio_types::SpuId;
+use fluvio_types::event::StickyEvent;
+
+/// port 0 binds ephemeral port
+const LOCAL_ANY_PORT: &str = "127.0.0.1:0";
+/// same id base as local cluster
+const BASE_SPU: SpuId = 5001;
+const START_TIMEOUT: Duration = Duration::from_secs(60);
+const STOP_TIMEOUT: Duration = Duration::from_secs(30);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// SC and SPUs running in the current process.
+///
+/// [`Self::shutdown`] stops servers, controllers and connection handlers, and removes the storage
+/// once their tasks ended. On drop, they are notified to stop and the storage is removed by a
+/// background task after they ended.
+pub struct EmbeddedCluster {
+    client: Fluvio,
+    sc_endpoint: String,
+    servers: Vec<Server>,
+    // taken by shutdown or drop, removed only after servers are stopped
+    data_dir: Option<TempDir>,
+}
+
+enum Server {
+    Sc(LocalSc),
+    Spu(StartedSpu),
+}
+
+impl Server {
+    fn shutdown(&self) -> &Arc<StickyEvent> {
+        match self {
+            Self::Sc(sc) => &sc.shutdown,
+            Self::Spu(spu) => &spu.shutdown,
+        }
+    }
+
+    fn is_stopped(&self) -> bool {
+        match self {
+            Self::Sc(sc) => sc.is_stopped(),
+            Self::Spu(spu) => spu.is_stopped(),
+        }
+    }
+}
+
+impl EmbeddedCluster {
+    /// Start SC and `spus` SPUs, and return once every SPU is online
+    pub async fn start(spus: u16) -> Result<Self> {
+        let data_dir = tempfile::Builder::new()
+            .prefix("fluvio-embedded")
+            .tempdir()?;
+
+        let sc_config = ScConfig {
+            public_endpoint: LOCAL_ANY_PORT.to_owned(),
+            private_endpoint: LOCAL_ANY_PORT.to_owned(),
+            ..Default::default()
+        };
+        let sc = fluvio_sc::start::start_local(sc_config, &data_dir.path().join("metadata"))
+            .await
+            .context("unable to start SC")?;
+        let sc_endpoint = sc.public_addr.to_string();
+        let sc_private_endpoint = sc.private_addr.to_string();
+        info!(%sc_endpoint, data_dir = ?data_dir.path(), "started embedded SC");
+
+        let client =
+            match Fluvio::connect_with_config(&FluvioClusterConfig::new(&sc_endpoint)).await {
+                Ok(client) => client,
+                Err(err) => {
+                    sc.shutdown.notify();
+                    wait_stopped(&[Server::Sc(sc)]).await;
+                    return Err(err)
+                        .with_context(|| format!("unable to connect to SC at {sc_endpoint}"));
+                }
+            };
+        let mut cluster = Self {
+            client,
+            sc_endpoint,
+            servers: vec![Server::Sc(sc)],
+            data_dir: Some(data_dir),
+        };
+
+        for id in (0..spus).map(|index| BASE_SPU + SpuId::from(index)) {
+            let spu = cluster.start_spu(id, &sc_private_endpoint).await?;
+            cluster.servers.push(Server::Spu(spu));
+        }
+        cluster.wait_for_spus(spus).await?;
+
+        Ok(cluster)
+    }
+
+    /// Stop servers and wait until their tasks ended, then remove the storage.
+    /// Clients of the cluster are disconnected.
+    pub async fn shutdown(mut self) -> Result<()> {
+        self.notify_shutdown();
+        if !wait_stopped(&self.servers).await {
+            // drop keeps waiting for servers in background before removing the storage
+            return Err(anyhow!("servers not stopped after {STOP_TIMEOUT:?}"));
+        }
+        if let Some(data_dir) = self.data_dir.take() {
+            data_dir
+                .close()
+                .context("unable to remove data directory")?;
+        }
+        Ok(())
+    }
+
+    /// client connected to the SC of the cluster
+    pub fn client(&self) -> &Fluvio {
+        &self.client
+    }
+
+    /// public endpoint of the SC, for more clients
+    pub fn sc_endpoint(&self) -> &str {
+        &self.sc_endpoint
+    }
+
+    /// bind SPU servers, register them as custom SPU with SC and start SPU controllers
+    async fn start_spu(&self, id: SpuId, sc_private_endpoint: &str) -> Result<StartedSpu> {
+        let mut spu_config = SpuConfig {
+            id,
+            public_endpoint: LOCAL_ANY_PORT.to_owned(),
+            private_endpoint: LOCAL_ANY_PORT.to_owned(),
+            sc_endpoint: sc_private_endpoint.to_owned(),
+            ..Default::default()
+        };
+        spu_config.log.base_dir = self.data_path().join(format!("spu-{id}"));
+        let spu = LocalSpu::bind(spu_config)
+            .await
+            .with_context(|| format!("unable to start spu {id}"))?;
+
+        let spec = CustomSpuSpec {
+            id,
+            public_endpoint: ingress_port(spu.public_addr()),
+            private_endpoint: endpoint(spu.private_addr()),
+            rack: None,
+            public_endpoint_local: None,
+        };
+        debug!(id, public_addr = %spu.public_addr(), private_addr = %spu.private_addr(), "creating custom spu");
+        if let Err(err) = self
+            .client
+            .admin()
+            .await
+            .create(format!("custom-spu-{id}"), false, spec)
+            .await
+        {
+            spu.stop();
+            return Err(err);
+        }
+
+        Ok(spu.start())
+    }
+
+    fn data_path(&self) -> &Path {
+        self.data_dir
+            .as_ref()
+            .map(TempDir::path)
+            .expect("data directory is removed only by shutdown or drop")
+    }
+
+    fn notify_shutdown(&self) {
+        debug!(sc_endpoint = %self.sc_endpoint, "stopping embedded cluster");
+        for server in &self.servers {
+            server.shutdown().notify();
+        }
+    }
+
+    async fn wait_for_spus(&self, spus: u16) -> Result<()> {
+        let admin = self.client.admin().await;
+        let start = Instant::now();
+        loop {
+            let online = admin
+                .all::<SpuSpec>()
+                .await?
+                .iter()
+                .filter(|spu| spu.status.is_online())
+                .count();
+            if online == spus as usize {
+                debug!(online, elapsed = ?start.elapsed(), "spus are online");
+                return Ok(());
+            }
+            if start.elapsed() > START_TIMEOUT {
+                return Err(anyhow!(
+                    "only {online} of {spus} spus online after {START_TIMEOUT:?}"
+                ));
+            }
+            sleep(POLL_INTERVAL).await;
+        }
+    }
+}
+
+impl Drop for EmbeddedCluster {
+    fn drop(&mut self) {
+        let Some(data_dir) = self.data_dir.take() else {
+            return;
+        };
+        self.notify_shutdown();
+        let servers = std::mem::take(&mut self.servers);
+        spawn(async move {
+            if wait_stopped(&servers).await {
+                drop(data_dir);
+            } else {
+                warn!(data_dir = ?data_dir.path(), "servers not stopped after {STOP_TIMEOUT:?}, keeping data directory");
+                // servers may still write to it
+                std::mem::forget(data_dir);
+            }
+        });
+    }
+}
+
+/// true if tasks of servers ended before timeout
+async fn wait_stopped(servers: &[Server]) -> bool {
+    let start = Instant::now();
+    while !servers.iter().all(Server::is_stopped) {
+        if start.elapsed() > STOP_TIMEOUT {
+            return false;
+        }
+        sleep(POLL_INTERVAL).await;
+    }
+    true
+}
+
+fn ingress_port(addr: SocketAddr) -> IngressPort {
+    IngressPort::from_port_host(addr.port(), addr.ip().to_string())
+}
+
+fn endpoint(addr: SocketAddr) -> Endpoint {
+    Endpoint::from_port_host(addr.port(), addr.ip().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio::Offset;
+    use fluvio::consumer::ConsumerConfigExtBuilder;
+    use fluvio::metadata::topic::TopicSpec;
+    use futures_util::StreamExt;
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_embedded_cluster_produce_consume() {
+        let cluster = EmbeddedCluster::start(2).await.expect("cluster");
+        let admin = cluster.client().admin().await;
+        admin
+            .create(
+                "embedded".to_owned(),
+                false,
+                TopicSpec::new_computed(2, 2, None),
+            )
+            .await
+            .expect("topic");
+        sleep(Duration::from_secs(1)).await;
+
+        let producer = cluster
+            .client()
+            .topic_producer("embedded")
+            .await
+            .expect("producer");
+        producer
+            .send("key", "hello")
+            .await
+            .expect("send")
+            .wait()
+            .await
+            .expect("ack");
+
+        let mut stream = cluster
+            .client()
+            .consumer_with_config(
+                ConsumerConfigExtBuilder::default()
+                    .topic("embedded")
+                    .offset_start(Offset::beginning())
+                    .build()
+                    .expect("config"),
+            )
+            .await
+            .expect("consumer");
+        let record = stream.next().await.expect("record").expect("ok");
+        assert_eq!(record.value(), b"hello");
+
+        let data_dir = cluster.data_path().to_owned();
+        drop(stream);
+        cluster.shutdown().await.expect("shutdown");
+        assert!(!data_dir.exists());
+    }
+}
@@ -1,6 +1,10 @@
 use clap::Parser;
 
 mod error;
+#[cfg(feature = "embedded")]
+pub mod embedded;
+#[cfg(feature = "otel")]
+pub mod telemetry;
 
 pub use error::RunnerError;
 use error::Result;
@@ -0,0 +1,15 @@
+pub use fluvio_controlplane_metadata::dictionary::*;
+
+use crate::{AdminSpec, CreatableAdminSpec, DeletableAdminSpec};
+use crate::objects::classic::ClassicCreatableAdminSpec;
+
+impl AdminSpec for DictionarySpec {}
+
+impl CreatableAdminSpec for DictionarySpec {}
+
+// dictionaries are newer than classic protocol
+impl ClassicCreatableAdminSpec for DictionarySpec {}
+
+impl DeletableAdminSpec for DictionarySpec {
+    type DeleteKey = String;
+}
@@ -10,6 +10,7 @@ pub mod shared;
 pub mod tableformat;
 pub mod mirror;
 pub mod mirroring;
+pub mod dictionary;
 
 pub mod remote_file;
 
@@ -25,7 +25,8 @@ use fluvio_future::openssl::TlsAcceptor;
 use fluvio_future::openssl::SslVerifyMode;
 
 use crate::services::auth::basic::BasicRbacPolicy;
-use crate::config::ScConfig;
+use crate::config::{ScConfig, AuditSinkConfig, load_client_quotas};
+use crate::config::{DEFAULT_AUDIT_LOG_MAX_BYTES, DEFAULT_AUDIT_LOG_MAX_FILES};
 
 type Config = (ScConfig, Option<BasicRbacPolicy>);
 
@@ -65,6 +66,40 @@ pub struct ScOpt {
     )]
     auth_policy: Option<PathBuf>,
 
+    /// write audit log of control plane mutations to rotating JSON lines file
+    #[arg(
+        long,
+        value_name = "audit log path",
+        env,
+        conflicts_with = "audit_topic"
+    )]
+    audit_log: Option<PathBuf>,
+
+    /// max size in bytes of audit log file before it is rotated
+    #[arg(long, default_value_t = DEFAULT_AUDIT_LOG_MAX_BYTES, env)]
+    audit_log_max_bytes: u64,
+
+    /// number of rotated audit log files to keep
+    #[arg(long, default_value_t = DEFAULT_AUDIT_LOG_MAX_FILES, env)]
+    audit_log_max_files: u32,
+
+    /// write audit log of control plane mutations to system topic
+    #[arg(long, value_name = "topic", env)]
+    audit_topic: Option<String>,
+
+    /// JSON file with per client produce, fetch and request rate quotas pushed to SPUs.
+    /// quotas are reloaded when file changes
+    #[arg(long, value_name = "client quotas path", env)]
+    client_quotas: Option<PathBuf>,
+
+    /// Address of OpenMetrics endpoint
+    #[arg(
+        long = "metrics-addr",
+        value_name = "host:port",
+        env = "FLV_METRICS_ADDR"
+    )]
+    bind_metrics: Option<String>,
+
     /// only allow white list of controllers
     #[arg(long)]
     white_list: Vec<String>,
@@ -127,14 +162,37 @@ impl ScOpt {
         }
 
         config.x509_auth_scopes = self.x509_auth_scopes;
+        config.audit = match (self.audit_log, self.audit_topic) {
+            (Some(path), _) => Some(AuditSinkConfig::File {
+                path,
+                max_bytes: self.audit_log_max_bytes,
+                max_files: self.audit_log_max_files,
+            }),
+            (None, Some(topic)) => Some(AuditSinkConfig::Topic(topic)),
+            (None, None) => None,
+        };
+        if let Some(path) = self.client_quotas {
+            config.client_quotas = load_client_quotas(&path)?;
+            info!(
+                ?path,
+                quotas = config.client_quotas.len(),
+                "loaded client quotas"
+            );
+            config.client_quotas_path = Some(path);
+        }
+        config.metrics_endpoint = self.bind_metrics;
         config.white_list = self.white_list.into_iter().collect();
         config.read_only_metadata = self.run_mode.read_only.is_some();
 
         // Set Configuration Authorization Policy
 
         let policy = match self.auth_policy {
-            // Lookup a policy from a path
-            Some(p) => Some(BasicRbacPolicy::try_from(p)?),
+            // Lookup a policy from a path, policy is reloaded when file changes
+            Some(p) => {
+                let policy = BasicRbacPolicy::try_from(p.clone())?;
+                config.auth_policy = Some(p);
+                Some(policy)
+            }
             // Use root-only default policy if no policy path is found;
             None => None,
         };
@@ -1,8 +1,11 @@
 mod sc_config;
 
 pub use self::sc_config::ScConfig;
+pub use self::sc_config::AuditSinkConfig;
 pub use self::sc_config::ScConfigBuilder;
+pub use self::sc_config::load_client_quotas;
 pub use self::sc_config::DEFAULT_NAMESPACE;
+pub use self::sc_config::{DEFAULT_AUDIT_LOG_MAX_BYTES, DEFAULT_AUDIT_LOG_MAX_FILES};
 
 macro_rules! whitelist {
     ($config:expr,$name:expr,$start:expr) => {
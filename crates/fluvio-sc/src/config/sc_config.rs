@@ -4,12 +4,18 @@
 //! Stores configuration parameter used by Streaming Controller module.
 //!
 use std::collections::HashSet;
+use std::path::Path;
 use std::{io::Error as IoError, path::PathBuf};
 
+use serde::Deserialize;
+
+use fluvio_controlplane::spu_api::update_quota::ClientQuota;
 use fluvio_types::defaults::SC_PUBLIC_PORT;
 use fluvio_types::defaults::SC_PRIVATE_PORT;
 
 pub const DEFAULT_NAMESPACE: &str = "default";
+pub const DEFAULT_AUDIT_LOG_MAX_BYTES: u64 = 100 * 1024 * 1024;
+pub const DEFAULT_AUDIT_LOG_MAX_FILES: u32 = 5;
 
 // -----------------------------------
 // Traits
@@ -28,9 +34,60 @@ pub struct ScConfig {
     pub private_endpoint: String,
     pub namespace: String,
     pub x509_auth_scopes: Option<PathBuf>,
+    pub auth_policy: Option<PathBuf>,
+    pub audit: Option<AuditSinkConfig>,
+    pub client_quotas: Vec<ClientQuota>,
+    /// quotas are reloaded when file changes
+    pub client_quotas_path: Option<PathBuf>,
+    /// address of OpenMetrics endpoint, disabled if not set
+    pub metrics_endpoint: Option<String>,
     pub white_list: HashSet<String>,
 }
 
+/// where audit events of control plane mutations are written
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum AuditSinkConfig {
+    /// rotating JSON lines file
+    File {
+        path: PathBuf,
+        max_bytes: u64,
+        max_files: u32,
+    },
+    /// system topic
+    Topic(String),
+}
+
+/// entry of client quotas file, limits are per second
+#[derive(Debug, Clone, Deserialize)]
+struct ClientQuotaConfig {
+    client: String,
+    #[serde(default)]
+    produce_bytes: Option<u64>,
+    #[serde(default)]
+    fetch_bytes: Option<u64>,
+    #[serde(default)]
+    requests: Option<u64>,
+}
+
+impl From<ClientQuotaConfig> for ClientQuota {
+    fn from(config: ClientQuotaConfig) -> Self {
+        Self {
+            client: config.client,
+            produce_bytes_per_sec: config.produce_bytes,
+            fetch_bytes_per_sec: config.fetch_bytes,
+            requests_per_sec: config.requests,
+        }
+    }
+}
+
+/// load client quotas from JSON file with list of quotas.
+/// quota with client `*` applies to every client without own quota
+pub fn load_client_quotas(path: &Path) -> Result<Vec<ClientQuota>, IoError> {
+    let file = std::fs::read_to_string(path)?;
+    let quotas: Vec<ClientQuotaConfig> = serde_json::from_str(&file)?;
+    Ok(quotas.into_iter().map(ClientQuota::from).collect())
+}
+
 impl ::std::default::Default for ScConfig {
     fn default() -> Self {
         Self {
@@ -39,6 +96,11 @@ impl ::std::default::Default for ScConfig {
             private_endpoint: format!("0.0.0.0:{SC_PRIVATE_PORT}"),
             namespace: DEFAULT_NAMESPACE.to_owned(),
             x509_auth_scopes: None,
+            auth_policy: None,
+            audit: None,
+            client_quotas: vec![],
+            client_quotas_path: None,
+            metrics_endpoint: None,
             white_list: HashSet::new(),
         }
     }
@@ -56,3 +118,33 @@ impl ScConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use std::io::Write;
+
+    use super::load_client_quotas;
+
+    #[test]
+    fn test_load_client_quotas() {
+        let path = std::env::temp_dir().join("sc_client_quotas.json");
+        let mut file = std::fs::File::create(&path).expect("create");
+        file.write_all(
+            br#"[
+                {"client": "*", "requests": 100},
+                {"client": "ingest", "produce_bytes": 1048576, "fetch_bytes": 2097152}
+            ]"#,
+        )
+        .expect("write");
+
+        let quotas = load_client_quotas(&path).expect("load");
+        assert_eq!(quotas.len(), 2);
+        assert!(quotas[0].is_default());
+        assert_eq!(quotas[0].requests_per_sec, Some(100));
+        assert_eq!(quotas[0].produce_bytes_per_sec, None);
+        assert_eq!(quotas[1].client, "ingest");
+        assert_eq!(quotas[1].produce_bytes_per_sec, Some(1048576));
+        assert_eq!(quotas[1].fetch_bytes_per_sec, Some(2097152));
+    }
+}
@@ -41,7 +41,7 @@ impl<C: MetadataItem> RemoteMirrorController<C> {
         };
 
         info!("starting mirroring controller");
-        spawn(controller.dispatch_loop());
+        spawn(ctx.shutdown().until_notified(controller.dispatch_loop()));
     }
 
     #[instrument(skip(self), name = "MirroringControllerLoop")]
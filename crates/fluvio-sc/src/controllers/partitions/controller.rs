@@ -2,6 +2,7 @@
 //! # Partition Controller
 //!
 
+use std::sync::Arc;
 use std::time::Duration;
 
 use fluvio_controlplane_metadata::store::ChangeListener;
@@ -9,6 +10,7 @@ use fluvio_future::timer::sleep;
 use tracing::{debug, trace, info, error, instrument};
 
 use fluvio_future::task::spawn;
+use fluvio_types::event::StickyEvent;
 use fluvio_controlplane_metadata::core::MetadataItem;
 use fluvio_controlplane_metadata::store::k8::K8MetaItem;
 
@@ -30,14 +32,18 @@ impl<C> PartitionController<C>
 where
     C: MetadataItem + 'static,
 {
-    pub fn start(partitions: StoreContext<PartitionSpec, C>, spus: StoreContext<SpuSpec, C>) {
+    pub fn start(
+        partitions: StoreContext<PartitionSpec, C>,
+        spus: StoreContext<SpuSpec, C>,
+        shutdown: &Arc<StickyEvent>,
+    ) {
         let controller = Self {
             reducer: PartitionReducer::new(partitions.store().clone(), spus.store().clone()),
             partitions,
             spus,
         };
 
-        spawn(controller.dispatch_loop());
+        spawn(shutdown.until_notified(controller.dispatch_loop()));
     }
 }
 
@@ -127,6 +127,8 @@ impl<C: MetadataItem> PartitionReducer<C> {
 
         // election due to online spu
         for online_spu in online_spus.into_iter() {
+            self.force_election_replicas_off(&online_spu, &mut actions)
+                .await;
             self.force_election_spu_on(online_spu, &mut actions).await;
         }
         actions
@@ -145,57 +147,95 @@ impl<C: MetadataItem> PartitionReducer<C> {
         );
         let offline_leader_spu_id = offline_spu.spec.id;
 
-        let spu_status = self.spu_store.online_status().await;
-
-        let policy = SimplePolicy::new();
-
         // go thru each partitions whose leader matches offline spu.
         for partition_kv_epoch in self.partition_store.read().await.values() {
             let partition_kv = partition_kv_epoch.inner();
             // find partition who's leader is same as offline spu
             if partition_kv.spec.leader == offline_leader_spu_id {
-                // find suitable leader
-                if let Some(candidate_leader) =
-                    partition_kv.status.candidate_leader(&spu_status, &policy)
-                {
-                    let mut part_kv_change = partition_kv.clone();
-                    part_kv_change.spec.leader = candidate_leader;
-
-                    // we only change leader, status happens next cycle
-                    actions.push(PartitionWSAction::UpdateSpec((
-                        part_kv_change.key_owned(),
-                        part_kv_change.spec,
-                    )));
-
-                    info!(
-                        partition = %partition_kv.key(),
-                        candidate_leader,
-                        "changing to new leader",
-                    );
-
-                // change the
-                } else {
-                    // check partition is already offline
-                    if partition_kv.status.is_online() {
-                        let mut part_kv_change = partition_kv.clone();
-                        part_kv_change.status.resolution = PartitionResolution::LeaderOffline;
-                        actions.push(PartitionWSAction::UpdateStatus((
-                            part_kv_change.key_owned(),
-                            part_kv_change.status,
-                        )));
-                        info!(
-                            partition = %partition_kv.key(),
-                            old_leader = offline_leader_spu_id,
-                            "setting partition to offline",
-                        );
-                    } else {
-                        debug!(
-                            partition = %partition_kv.key(),
-                            old_leader = offline_leader_spu_id,
-                            "no new online leader was found",
-                        );
-                    }
-                }
+                self.elect_other_leader(partition_kv, actions).await;
+            }
+        }
+    }
+
+    /// perform election when online spu can't serve some of its replicas
+    /// because their data directory failed
+    #[instrument(skip(self, spu, actions))]
+    async fn force_election_replicas_off(
+        &self,
+        spu: &SpuMetadata<C>,
+        actions: &mut Vec<PartitionWSAction<C>>,
+    ) {
+        if spu.status.offline_replicas.is_empty() {
+            return;
+        }
+        debug!(
+            spu = %spu.key(),
+            offline_replicas = spu.status.offline_replicas.len(),
+            "performing election check for offline replicas",
+        );
+
+        for partition_kv_epoch in self.partition_store.read().await.values() {
+            let partition_kv = partition_kv_epoch.inner();
+            if partition_kv.spec.leader == spu.spec.id
+                && spu.status.is_replica_offline(partition_kv.key())
+            {
+                self.elect_other_leader(partition_kv, actions).await;
+            }
+        }
+    }
+
+    /// move leadership to other suitable replica or set partition offline if there is none
+    async fn elect_other_leader(
+        &self,
+        partition_kv: &PartitionMetadata<C>,
+        actions: &mut Vec<PartitionWSAction<C>>,
+    ) {
+        let old_leader = partition_kv.spec.leader;
+        let spu_status = self
+            .spu_store
+            .replica_online_status(partition_kv.key())
+            .await;
+
+        // find suitable leader
+        if let Some(candidate_leader) = partition_kv
+            .status
+            .candidate_leader(&spu_status, &SimplePolicy::new())
+        {
+            let mut part_kv_change = partition_kv.clone();
+            part_kv_change.spec.leader = candidate_leader;
+            part_kv_change.spec.leader_epoch += 1;
+
+            // we only change leader, status happens next cycle
+            actions.push(PartitionWSAction::UpdateSpec((
+                part_kv_change.key_owned(),
+                part_kv_change.spec,
+            )));
+
+            info!(
+                partition = %partition_kv.key(),
+                candidate_leader,
+                "changing to new leader",
+            );
+        } else {
+            // check partition is already offline
+            if partition_kv.status.is_online() {
+                let mut part_kv_change = partition_kv.clone();
+                part_kv_change.status.resolution = PartitionResolution::LeaderOffline;
+                actions.push(PartitionWSAction::UpdateStatus((
+                    part_kv_change.key_owned(),
+                    part_kv_change.status,
+                )));
+                info!(
+                    partition = %partition_kv.key(),
+                    old_leader,
+                    "setting partition to offline",
+                );
+            } else {
+                debug!(
+                    partition = %partition_kv.key(),
+                    old_leader,
+                    "no new online leader was found",
+                );
             }
         }
     }
@@ -215,6 +255,9 @@ impl<C: MetadataItem> PartitionReducer<C> {
 
         for partition_kv_epoch in self.partition_store.read().await.values() {
             let partition_kv = partition_kv_epoch.inner();
+            if online_spu.status.is_replica_offline(partition_kv.key()) {
+                continue;
+            }
             if !partition_kv.status.is_readable() {
                 if partition_kv.spec.leader != online_leader_spu_id {
                     // switch leader if online leader is different
@@ -226,6 +269,7 @@ impl<C: MetadataItem> PartitionReducer<C> {
                         {
                             let mut part_kv_change = partition_kv.clone();
                             part_kv_change.spec.leader = online_leader_spu_id;
+                            part_kv_change.spec.leader_epoch += 1;
                             actions.push(PartitionWSAction::UpdateSpec((
                                 part_kv_change.key_owned(),
                                 part_kv_change.spec,
@@ -267,6 +311,82 @@ impl<C: MetadataItem> PartitionReducer<C> {
 #[cfg(test)]
 pub mod test {
 
+    use fluvio_controlplane_metadata::partition::ReplicaKey;
+
+    use crate::stores::partition::{DefaultPartitionStore, PartitionMd, PartitionStatus, ReplicaStatus};
+    use crate::stores::spu::{DefaultSpuStore, SpuMd};
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_election_for_offline_replica() {
+        let key = ReplicaKey::new("topic", 0u32);
+        let mut partition = PartitionMetadata::<u32>::with_replicas(key.clone(), vec![1, 2]);
+        partition.status = PartitionStatus::new2(
+            (1, 10, 10),
+            vec![ReplicaStatus::new(2, 10, 10)],
+            0,
+            PartitionResolution::Online,
+            0,
+        );
+        let mut other =
+            PartitionMetadata::<u32>::with_replicas(ReplicaKey::new("other", 0u32), vec![1, 2]);
+        other.status = partition.status.clone();
+
+        let mut spu_1 = SpuMetadata::<u32>::quick(("spu-1", 1, true, None));
+        spu_1.status.offline_replicas = vec![key.to_string()];
+        let spu_2 = SpuMetadata::<u32>::quick(("spu-2", 2, true, None));
+
+        let reducer = PartitionReducer::new(
+            DefaultPartitionStore::bulk_new(vec![partition, other]),
+            DefaultSpuStore::bulk_new(vec![spu_1.clone(), spu_2]),
+        );
+
+        let actions = reducer.update_election_from_spu_changes(vec![spu_1]).await;
+        assert_eq!(actions.len(), 1);
+        match &actions[0] {
+            PartitionWSAction::UpdateSpec((action_key, spec)) => {
+                assert_eq!(action_key, &key);
+                assert_eq!(spec.leader, 2);
+                assert_eq!(spec.leader_epoch, 1);
+            }
+            _ => panic!("expected leader change"),
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_no_election_to_spu_with_offline_replica() {
+        let key = ReplicaKey::new("topic", 0u32);
+        let mut partition = PartitionMetadata::<u32>::with_replicas(key.clone(), vec![1, 2]);
+        partition.status = PartitionStatus::new2(
+            (1, 10, 10),
+            vec![ReplicaStatus::new(2, 10, 10)],
+            0,
+            PartitionResolution::Online,
+            0,
+        );
+
+        let spu_1 = SpuMetadata::<u32>::quick(("spu-1", 1, false, None));
+        let mut spu_2 = SpuMetadata::<u32>::quick(("spu-2", 2, true, None));
+        spu_2.status.offline_replicas = vec![key.to_string()];
+
+        let reducer = PartitionReducer::new(
+            DefaultPartitionStore::bulk_new(vec![partition]),
+            DefaultSpuStore::bulk_new(vec![spu_1.clone(), spu_2]),
+        );
+
+        // only replica left can't be leader, so partition goes offline
+        let actions = reducer.update_election_from_spu_changes(vec![spu_1]).await;
+        assert_eq!(actions.len(), 1);
+        match &actions[0] {
+            PartitionWSAction::UpdateStatus((action_key, status)) => {
+                assert_eq!(action_key, &key);
+                assert_eq!(status.resolution, PartitionResolution::LeaderOffline);
+            }
+            _ => panic!("expected partition to be offline"),
+        }
+    }
+
     /*
     #[fluvio_future::test]
     async fn test_process_partition_actions_without_partitions()  {
@@ -31,7 +31,7 @@ impl<C: MetadataItem + 'static> SpuController<C> {
         };
 
         info!("starting spu controller");
-        spawn(controller.dispatch_loop());
+        spawn(ctx.shutdown().until_notified(controller.dispatch_loop()));
     }
 
     #[instrument(skip(self), name = "SpuControllerLoop")]
@@ -19,6 +19,7 @@ use tracing::{info, instrument, trace, debug};
 
 use fluvio_future::task::spawn;
 
+use crate::config::AuditSinkConfig;
 use crate::core::SharedContext;
 use crate::stores::topic::TopicSpec;
 use crate::stores::partition::PartitionSpec;
@@ -61,7 +62,7 @@ where
             spus,
         };
 
-        spawn(controller.dispatch_loop());
+        spawn(ctx.shutdown().until_notified(controller.dispatch_loop()));
     }
 }
 
@@ -168,6 +169,7 @@ impl<C: MetadataItem> TopicController<C> {
 #[derive(Debug)]
 pub struct SystemTopicController<C: MetadataItem = K8MetaItem> {
     topics: StoreContext<TopicSpec, C>,
+    audit_topic: Option<String>,
 }
 
 impl<C> SystemTopicController<C>
@@ -177,10 +179,17 @@ where
 {
     pub fn start(ctx: SharedContext<C>) {
         let topics = ctx.topics().clone();
+        let audit_topic = match &ctx.config().audit {
+            Some(AuditSinkConfig::Topic(topic)) => Some(topic.clone()),
+            _ => None,
+        };
 
-        let controller = Self { topics };
+        let controller = Self {
+            topics,
+            audit_topic,
+        };
 
-        spawn(controller.dispatch_loop());
+        spawn(ctx.shutdown().until_notified(controller.dispatch_loop()));
     }
 
     #[instrument(name = "SystemTopicController", skip(self))]
@@ -200,6 +209,7 @@ where
             debug!(interval_secs, "sleeping for");
             sleep(Duration::from_secs(interval_secs)).await;
             self.ensure_offsets_topic_exists().await;
+            self.ensure_audit_topic_exists().await;
             interval_secs = min(MAX_INTERVAL, interval_secs.add(INTERVAL_STEP));
         }
     }
@@ -233,4 +243,21 @@ where
             info!(CONSUMER_STORAGE_TOPIC, "topic created");
         }
     }
+
+    async fn ensure_audit_topic_exists(&mut self) {
+        let Some(audit_topic) = &self.audit_topic else {
+            return;
+        };
+
+        if self.topics.store().contains_key(audit_topic).await {
+            trace!(topic = %audit_topic, "topic exists");
+        } else {
+            let mut spec = TopicSpec::new_computed(1, 1, None);
+            spec.set_system(true);
+            self.topics
+                .send_action(WSAction::UpdateSpec((audit_topic.clone(), spec)))
+                .await;
+            info!(topic = %audit_topic, "audit topic created");
+        }
+    }
 }
@@ -7,14 +7,19 @@ use std::sync::Arc;
 
 use fluvio_sc_schema::mirror::MirrorSpec;
 use fluvio_stream_model::core::MetadataItem;
+use fluvio_types::event::StickyEvent;
 
 use crate::config::ScConfig;
+use crate::core::ClientQuotaStore;
+use crate::services::audit::AuditLog;
+use crate::services::metrics::ScMetrics;
 use crate::stores::spu::*;
 use crate::stores::partition::*;
 use crate::stores::topic::*;
 use crate::stores::spg::*;
 use crate::stores::smartmodule::*;
 use crate::stores::tableformat::*;
+use crate::stores::dictionary::*;
 use crate::stores::*;
 
 pub type SharedContext<C> = Arc<Context<C>>;
@@ -31,8 +36,13 @@ pub struct Context<C: MetadataItem> {
     smartmodules: StoreContext<SmartModuleSpec, C>,
     tableformats: StoreContext<TableFormatSpec, C>,
     mirrors: StoreContext<MirrorSpec, C>,
+    dictionaries: StoreContext<DictionarySpec, C>,
     health: SharedHealthCheck,
+    audit: AuditLog,
+    metrics: ScMetrics,
+    client_quotas: Arc<ClientQuotaStore>,
     config: ScConfig,
+    shutdown: Arc<StickyEvent>,
 }
 
 // -----------------------------------
@@ -46,6 +56,12 @@ impl<C: MetadataItem> Context<C> {
 
     /// private function to provision metadata
     fn new(config: ScConfig) -> Self {
+        let audit = config
+            .audit
+            .as_ref()
+            .map(|sink| AuditLog::start(sink, &config.public_endpoint))
+            .unwrap_or_default();
+
         Self {
             spus: StoreContext::new(),
             partitions: StoreContext::new(),
@@ -54,8 +70,13 @@ impl<C: MetadataItem> Context<C> {
             smartmodules: StoreContext::new(),
             tableformats: StoreContext::new(),
             mirrors: StoreContext::new(),
+            dictionaries: StoreContext::new(),
             health: HealthCheck::shared(),
+            audit,
+            metrics: ScMetrics::default(),
+            client_quotas: Arc::new(ClientQuotaStore::new(config.client_quotas.clone())),
             config,
+            shutdown: StickyEvent::shared(),
         }
     }
 
@@ -90,11 +111,29 @@ impl<C: MetadataItem> Context<C> {
         &self.mirrors
     }
 
+    pub fn dictionaries(&self) -> &StoreContext<DictionarySpec, C> {
+        &self.dictionaries
+    }
+
     /// spu health channel
     pub fn health(&self) -> &SharedHealthCheck {
         &self.health
     }
 
+    /// audit log of control plane mutations
+    pub fn audit(&self) -> &AuditLog {
+        &self.audit
+    }
+
+    pub fn metrics(&self) -> &ScMetrics {
+        &self.metrics
+    }
+
+    /// client quotas pushed to SPUs
+    pub fn client_quotas(&self) -> &Arc<ClientQuotaStore> {
+        &self.client_quotas
+    }
+
     /// reference to config
     pub fn config(&self) -> &ScConfig {
         &self.config
@@ -103,4 +142,9 @@ impl<C: MetadataItem> Context<C> {
     pub fn namespace(&self) -> &str {
         &self.config.namespace
     }
+
+    /// when notified, public and private servers stop accepting connections
+    pub fn shutdown(&self) -> &Arc<StickyEvent> {
+        &self.shutdown
+    }
 }
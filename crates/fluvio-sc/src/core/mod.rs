@@ -1,2 +1,4 @@
 mod context;
+mod quota;
 pub use self::context::*;
+pub use self::quota::*;
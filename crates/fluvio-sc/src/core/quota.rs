@@ -0,0 +1,148 @@
+//!
+//! # Client Quotas
+//!
+//! Quotas loaded from client quotas file. Every change increases epoch,
+//! so connected SPUs are sent the new set of quotas.
+//!
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use tracing::{debug, error, info};
+
+use fluvio_controlplane::spu_api::update_quota::ClientQuota;
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_stream_model::epoch::Epoch;
+use fluvio_types::event::offsets::{OffsetChangeListener, OffsetPublisher};
+
+use crate::config::load_client_quotas;
+
+/// how often client quotas file is checked for changes
+pub const CLIENT_QUOTAS_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+pub struct ClientQuotaStore {
+    quotas: RwLock<Vec<ClientQuota>>,
+    epoch: Arc<OffsetPublisher>,
+}
+
+impl ClientQuotaStore {
+    pub fn new(quotas: Vec<ClientQuota>) -> Self {
+        Self {
+            quotas: RwLock::new(quotas),
+            epoch: OffsetPublisher::shared(1),
+        }
+    }
+
+    /// epoch and full set of quotas
+    pub fn all(&self) -> (Epoch, Vec<ClientQuota>) {
+        let quotas = self.quotas.read().expect("Poisoned lock");
+        (self.epoch.current_value(), quotas.clone())
+    }
+
+    pub fn epoch(&self) -> Epoch {
+        self.epoch.current_value()
+    }
+
+    /// replace quotas, epoch is only increased if quotas are changed
+    pub fn sync_all(&self, quotas: Vec<ClientQuota>) -> bool {
+        let mut current = self.quotas.write().expect("Poisoned lock");
+        if *current == quotas {
+            return false;
+        }
+        *current = quotas;
+        self.epoch.update_increment();
+        true
+    }
+
+    /// listen for changes of epoch
+    pub fn change_listener(&self) -> OffsetChangeListener {
+        self.epoch.change_listener()
+    }
+
+    /// reload quotas whenever quotas file is modified
+    pub fn watch(self: &Arc<Self>, path: PathBuf, interval: Duration) {
+        spawn(reload_quotas_loop(self.clone(), path, interval));
+    }
+}
+
+async fn reload_quotas_loop(store: Arc<ClientQuotaStore>, path: PathBuf, interval: Duration) {
+    info!(?path, ?interval, "watching client quotas");
+
+    loop {
+        sleep(interval).await;
+
+        match load_client_quotas(&path) {
+            Ok(quotas) => {
+                if store.sync_all(quotas) {
+                    info!(?path, epoch = store.epoch(), "client quotas reloaded");
+                } else {
+                    debug!(?path, "client quotas not changed");
+                }
+            }
+            Err(err) => {
+                error!(?path, %err, "invalid client quotas, keeping previous ones");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use fluvio_controlplane::spu_api::update_quota::ClientQuota;
+    use fluvio_future::timer::sleep;
+
+    use super::ClientQuotaStore;
+
+    fn quota(client: &str, requests: u64) -> ClientQuota {
+        ClientQuota {
+            client: client.to_owned(),
+            requests_per_sec: Some(requests),
+            ..Default::default()
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_sync_all_increases_epoch_on_change() {
+        let store = ClientQuotaStore::new(vec![quota("*", 100)]);
+        let mut listener = store.change_listener();
+        assert_eq!(listener.listen().await, 1);
+
+        assert!(!store.sync_all(vec![quota("*", 100)]));
+        assert_eq!(store.epoch(), 1);
+
+        assert!(store.sync_all(vec![quota("*", 200)]));
+        assert_eq!(listener.listen().await, 2);
+        let (epoch, quotas) = store.all();
+        assert_eq!(epoch, 2);
+        assert_eq!(quotas, vec![quota("*", 200)]);
+    }
+
+    #[fluvio_future::test]
+    async fn test_quotas_reload() {
+        let quotas_file = tempfile::NamedTempFile::new().expect("quotas file");
+        std::fs::write(quotas_file.path(), r#"[{"client": "*", "requests": 100}]"#)
+            .expect("write quotas");
+
+        let store = Arc::new(ClientQuotaStore::new(vec![quota("*", 100)]));
+        store.watch(quotas_file.path().to_owned(), Duration::from_millis(10));
+
+        std::fs::write(quotas_file.path(), r#"[{"client": "*", "requests": 50}]"#)
+            .expect("write quotas");
+
+        let start = Instant::now();
+        while store.epoch() == 1 {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "quotas were not reloaded"
+            );
+            sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(store.all(), (2, vec![quota("*", 50)]));
+    }
+}
@@ -13,11 +13,13 @@ use fluvio_stream_model::core::MetadataItem;
 use crate::controllers::mirroring::controller::RemoteMirrorController;
 use crate::core::Context;
 use crate::core::SharedContext;
+use crate::core::CLIENT_QUOTAS_RELOAD_INTERVAL;
 use crate::controllers::partitions::PartitionController;
 use crate::controllers::spus::SpuController;
 use crate::controllers::topics::controller::{TopicController, SystemTopicController};
 use crate::config::ScConfig;
 use crate::services::start_internal_server;
+use crate::services::metrics::start_metrics_endpoint;
 use crate::dispatcher::dispatcher::MetadataDispatcher;
 use crate::services::auth::basic::BasicRbacPolicy;
 
@@ -25,6 +27,21 @@ pub async fn start_main_loop<C, M>(
     sc_config_policy: (ScConfig, Option<BasicRbacPolicy>),
     metadata_client: SharedClient<C>,
 ) -> crate::core::SharedContext<M>
+where
+    C: MetadataClient<M> + 'static,
+    M: MetadataItem,
+    M::UId: Send + Sync,
+{
+    start_main_loop_with_servers(sc_config_policy, metadata_client, true).await
+}
+
+/// start main loop, public and internal servers are only started if `servers` is set.
+/// Dispatchers, controllers and servers stop when context shutdown is notified.
+pub(crate) async fn start_main_loop_with_servers<C, M>(
+    sc_config_policy: (ScConfig, Option<BasicRbacPolicy>),
+    metadata_client: SharedClient<C>,
+    servers: bool,
+) -> crate::core::SharedContext<M>
 where
     C: MetadataClient<M> + 'static,
     M: MetadataItem,
@@ -36,61 +53,77 @@ where
     use crate::stores::spg::SpuGroupSpec;
     use crate::stores::tableformat::TableFormatSpec;
     use crate::stores::smartmodule::SmartModuleSpec;
+    use crate::stores::dictionary::DictionarySpec;
 
     let (sc_config, auth_policy) = sc_config_policy;
 
     let namespace = sc_config.namespace.clone();
     let ctx = Context::shared_metadata(sc_config);
 
-    MetadataDispatcher::<SpuSpec, C, M>::start(
+    MetadataDispatcher::<SpuSpec, C, M>::start_until(
         namespace.clone(),
         metadata_client.clone(),
         ctx.spus().clone(),
+        ctx.shutdown(),
     );
 
-    MetadataDispatcher::<TopicSpec, C, M>::start(
+    MetadataDispatcher::<TopicSpec, C, M>::start_until(
         namespace.clone(),
         metadata_client.clone(),
         ctx.topics().clone(),
+        ctx.shutdown(),
     );
 
-    MetadataDispatcher::<PartitionSpec, C, M>::start(
+    MetadataDispatcher::<PartitionSpec, C, M>::start_until(
         namespace.clone(),
         metadata_client.clone(),
         ctx.partitions().clone(),
+        ctx.shutdown(),
     );
 
-    MetadataDispatcher::<SpuGroupSpec, C, M>::start(
+    MetadataDispatcher::<SpuGroupSpec, C, M>::start_until(
         namespace.clone(),
         metadata_client.clone(),
         ctx.spgs().clone(),
+        ctx.shutdown(),
     );
 
-    MetadataDispatcher::<TableFormatSpec, C, M>::start(
+    MetadataDispatcher::<TableFormatSpec, C, M>::start_until(
         namespace.clone(),
         metadata_client.clone(),
         ctx.tableformats().clone(),
+        ctx.shutdown(),
     );
 
-    MetadataDispatcher::<SmartModuleSpec, C, M>::start(
+    MetadataDispatcher::<SmartModuleSpec, C, M>::start_until(
         namespace.clone(),
         metadata_client.clone(),
         ctx.smartmodules().clone(),
+        ctx.shutdown(),
     );
 
-    MetadataDispatcher::<MirrorSpec, C, M>::start(
+    MetadataDispatcher::<MirrorSpec, C, M>::start_until(
         namespace.clone(),
         metadata_client.clone(),
         ctx.mirrors().clone(),
+        ctx.shutdown(),
     );
 
-    start_main_loop_services(ctx, auth_policy).await
+    MetadataDispatcher::<DictionarySpec, C, M>::start_until(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.dictionaries().clone(),
+        ctx.shutdown(),
+    );
+
+    start_main_loop_services(ctx, auth_policy, servers).await
 }
 
 /// start the main loop
 async fn start_main_loop_services<C>(
     ctx: Arc<Context<C>>,
     auth_policy: Option<BasicRbacPolicy>,
+    servers: bool,
 ) -> SharedContext<C>
 where
     C: MetadataItem + 'static,
@@ -104,20 +137,27 @@ where
     whitelist!(
         config,
         "partition",
-        PartitionController::start(ctx.partitions().clone(), ctx.spus().clone())
+        PartitionController::start(ctx.partitions().clone(), ctx.spus().clone(), ctx.shutdown())
     );
 
-    whitelist!(config, "internal", start_internal_server(ctx.clone()));
-    whitelist!(
-        config,
-        "public",
-        pub_server::start(ctx.clone(), auth_policy)
-    );
+    if servers {
+        whitelist!(config, "internal", start_internal_server(ctx.clone()));
+        whitelist!(
+            config,
+            "public",
+            pub_server::start(ctx.clone(), auth_policy)
+        );
+    }
     whitelist!(
         config,
         "mirroring",
         RemoteMirrorController::start(ctx.clone())
     );
+    whitelist!(config, "metrics", start_metrics_endpoint(ctx.clone()));
+
+    if let Some(path) = config.client_quotas_path.clone() {
+        ctx.client_quotas().watch(path, CLIENT_QUOTAS_RELOAD_INTERVAL);
+    }
 
     mod pub_server {
 
@@ -139,10 +179,11 @@ where
         {
             if let Some(policy) = auth_policy_option {
                 info!("using basic authorization");
-                start_public_server(AuthGlobalContext::new(
-                    ctx,
-                    Arc::new(BasicAuthorization::new(policy)),
-                ));
+                let authorization = BasicAuthorization::new(policy);
+                if let Some(path) = ctx.config().auth_policy.clone() {
+                    authorization.watch(path);
+                }
+                start_public_server(AuthGlobalContext::new(ctx, Arc::new(authorization)));
             } else if ctx.config().read_only_metadata {
                 info!("using read-only authorization");
 
@@ -0,0 +1,141 @@
+use std::fs::{File, OpenOptions, rename, remove_file};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::{debug, info};
+
+use super::{AuditEvent, AuditSink};
+
+/// Writes audit events as JSON lines.
+/// When file exceeds `max_bytes`, it is rotated to `<path>.1`, previous `<path>.1`
+/// to `<path>.2` and so on. At most `max_files` rotated files are kept.
+#[derive(Debug)]
+pub struct FileAuditSink {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: u32,
+    file: Option<File>,
+    written: u64,
+}
+
+impl FileAuditSink {
+    pub fn new(path: PathBuf, max_bytes: u64, max_files: u32) -> Self {
+        Self {
+            path,
+            max_bytes,
+            max_files,
+            file: None,
+            written: 0,
+        }
+    }
+
+    fn rotated_path(&self, index: u32) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{index}"));
+        PathBuf::from(name)
+    }
+
+    fn open(&mut self) -> Result<&mut File> {
+        if self.file.is_none() {
+            if let Some(parent) = self.path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+            self.written = file.metadata()?.len();
+            debug!(path = ?self.path, written = self.written, "opened audit file");
+            self.file = Some(file);
+        }
+        Ok(self.file.as_mut().expect("audit file is open"))
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        self.file = None;
+
+        if self.max_files == 0 {
+            remove_file(&self.path)?;
+        } else {
+            let oldest = self.rotated_path(self.max_files);
+            if oldest.exists() {
+                remove_file(&oldest)?;
+            }
+            for index in (1..self.max_files).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    rename(&from, self.rotated_path(index + 1))?;
+                }
+            }
+            rename(&self.path, self.rotated_path(1))?;
+        }
+
+        info!(path = ?self.path, "audit file rotated");
+        self.written = 0;
+        Ok(())
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[async_trait]
+impl AuditSink for FileAuditSink {
+    async fn write(&mut self, event: &AuditEvent) -> Result<()> {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+
+        if self.written > 0 && self.written + line.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+
+        let file = self.open()?;
+        file.write_all(&line)?;
+        file.flush()?;
+        self.written += line.len() as u64;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::fs::read_to_string;
+    use std::path::Path;
+
+    use crate::services::audit::{AuditEvent, AuditOperation, AuditSink};
+
+    use super::FileAuditSink;
+
+    #[fluvio_future::test]
+    async fn test_file_sink_rotation() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("audit.log");
+
+        let event = AuditEvent::new(AuditOperation::Create, Some("alice"));
+        let line_len = serde_json::to_vec(&event).expect("serialize").len() as u64 + 1;
+
+        // room for two events per file, keep two rotated files
+        let mut sink = FileAuditSink::new(path.clone(), line_len * 2, 2);
+        for _ in 0..7 {
+            sink.write(&event).await.expect("write");
+        }
+
+        fn lines(path: impl AsRef<Path>) -> usize {
+            read_to_string(path).expect("read").lines().count()
+        }
+
+        assert_eq!(lines(sink.path()), 1);
+        assert_eq!(lines(sink.rotated_path(1)), 2);
+        assert_eq!(lines(sink.rotated_path(2)), 2);
+        assert!(!sink.rotated_path(3).exists());
+
+        let first = read_to_string(&path).expect("read");
+        let recovered: serde_json::Value = serde_json::from_str(first.trim()).expect("json line");
+        assert_eq!(recovered["principal"], "alice");
+        assert_eq!(recovered["operation"], "create");
+    }
+}
@@ -0,0 +1,222 @@
+//!
+//! # Audit log
+//!
+//! Records every mutating request received by public API together with
+//! identity of caller and outcome. Events are queued and written by background task
+//! so request handling is never blocked by slow sink.
+//!
+
+mod file;
+mod topic;
+
+use std::fmt::Display;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use async_channel::{Sender, Receiver, TrySendError};
+use async_trait::async_trait;
+use serde::Serialize;
+use tracing::{debug, error, info, warn};
+
+use fluvio_future::task::spawn;
+use fluvio_sc_schema::Status;
+
+use crate::config::AuditSinkConfig;
+
+pub use file::FileAuditSink;
+pub use topic::TopicAuditSink;
+
+/// principal recorded when request is not authenticated
+pub const ANONYMOUS_PRINCIPAL: &str = "anonymous";
+
+/// max events buffered before they are dropped
+const AUDIT_QUEUE_SIZE: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOperation {
+    Create,
+    Delete,
+    Update,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditResult {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Single mutation of control plane object
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AuditEvent {
+    /// milliseconds since unix epoch
+    pub timestamp: u64,
+    pub principal: String,
+    pub operation: AuditOperation,
+    pub object_type: String,
+    pub key: String,
+    pub summary: String,
+    pub result: AuditResult,
+}
+
+impl AuditEvent {
+    pub fn new(operation: AuditOperation, principal: Option<&str>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_millis() as u64)
+            .unwrap_or_default();
+
+        Self {
+            timestamp,
+            principal: principal.unwrap_or(ANONYMOUS_PRINCIPAL).to_owned(),
+            operation,
+            object_type: String::new(),
+            key: String::new(),
+            summary: String::new(),
+            result: AuditResult {
+                success: false,
+                error_code: None,
+                message: None,
+            },
+        }
+    }
+
+    /// set object type and key from request, with summary of request options.
+    /// Specs are never summarized, they may contain secrets such as credentials of remote clusters.
+    pub fn set_object(&mut self, object_type: &str, key: impl Display, summary: String) {
+        self.object_type = object_type.to_owned();
+        self.key = key.to_string();
+        self.summary = summary;
+    }
+
+    /// set outcome of request
+    pub fn set_result(&mut self, result: &Result<Status>) {
+        self.result = match result {
+            Ok(status) => AuditResult {
+                success: !status.is_error(),
+                error_code: status
+                    .is_error()
+                    .then(|| format!("{:?}", status.error_code)),
+                message: status.error_message.clone(),
+            },
+            Err(err) => AuditResult {
+                success: false,
+                error_code: None,
+                message: Some(err.to_string()),
+            },
+        };
+    }
+}
+
+/// Destination of audit events
+#[async_trait]
+pub trait AuditSink: Send + 'static {
+    async fn write(&mut self, event: &AuditEvent) -> Result<()>;
+}
+
+/// Handle to audit log. Disabled log drops all events.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    sender: Option<Sender<AuditEvent>>,
+}
+
+impl AuditLog {
+    /// start audit writer for configured sink
+    pub fn start(config: &AuditSinkConfig, public_endpoint: &str) -> Self {
+        match config {
+            AuditSinkConfig::File {
+                path,
+                max_bytes,
+                max_files,
+            } => {
+                info!(?path, "writing audit log to file");
+                Self::with_sink(FileAuditSink::new(path.clone(), *max_bytes, *max_files))
+            }
+            AuditSinkConfig::Topic(topic) => {
+                info!(%topic, "writing audit log to topic");
+                Self::with_sink(TopicAuditSink::new(topic.clone(), public_endpoint))
+            }
+        }
+    }
+
+    pub fn with_sink<S: AuditSink>(sink: S) -> Self {
+        let (sender, receiver) = async_channel::bounded(AUDIT_QUEUE_SIZE);
+        spawn(audit_loop(sink, receiver));
+        Self {
+            sender: Some(sender),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.sender.is_some()
+    }
+
+    /// queue event for writing, never blocks
+    pub fn record(&self, event: AuditEvent) {
+        let Some(sender) = &self.sender else {
+            return;
+        };
+
+        match sender.try_send(event) {
+            Ok(()) => {}
+            Err(TrySendError::Full(event)) => {
+                warn!(?event, "audit queue is full, event dropped");
+            }
+            Err(TrySendError::Closed(event)) => {
+                error!(?event, "audit writer terminated, event dropped");
+            }
+        }
+    }
+}
+
+async fn audit_loop<S: AuditSink>(mut sink: S, receiver: Receiver<AuditEvent>) {
+    debug!("starting audit loop");
+    while let Ok(event) = receiver.recv().await {
+        if let Err(err) = sink.write(&event).await {
+            error!(%err, ?event, "failed to write audit event");
+        }
+    }
+    debug!("audit loop terminated");
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_protocol::link::ErrorCode;
+
+    use super::*;
+
+    #[test]
+    fn test_event_result() {
+        let mut event = AuditEvent::new(AuditOperation::Delete, Some("alice"));
+        event.set_object("topic", "test", "force: false".to_owned());
+        // key is taken from request, status of failed request may not carry it
+        event.set_result(&Ok(Status::new(
+            String::new(),
+            ErrorCode::PermissionDenied,
+            Some("permission denied".to_owned()),
+        )));
+
+        assert_eq!(event.principal, "alice");
+        assert_eq!(event.key, "test");
+        assert!(!event.result.success);
+        assert_eq!(event.result.error_code.as_deref(), Some("PermissionDenied"));
+
+        let json = serde_json::to_value(&event).expect("serialize");
+        assert_eq!(json["operation"], "delete");
+        assert_eq!(json["object_type"], "topic");
+        assert_eq!(json["summary"], "force: false");
+
+        let mut event = AuditEvent::new(AuditOperation::Create, None);
+        event.set_object("topic", "test", "dry_run: true".to_owned());
+        event.set_result(&Ok(Status::new_ok("test".to_owned())));
+        assert_eq!(event.key, "test");
+        assert_eq!(event.summary, "dry_run: true");
+        assert_eq!(event.principal, ANONYMOUS_PRINCIPAL);
+        assert!(event.result.success);
+        assert!(event.result.error_code.is_none());
+    }
+}
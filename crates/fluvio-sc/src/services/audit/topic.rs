@@ -0,0 +1,79 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::{debug, info};
+
+use fluvio::{Fluvio, FluvioClusterConfig, RecordKey, TopicProducerPool};
+
+use super::{AuditEvent, AuditSink};
+
+/// Produces audit events as JSON records to system topic.
+/// Topic is created by `SystemTopicController`, producer connects to SC public endpoint
+/// lazily and reconnects after failure.
+/// Connection bypasses TLS proxy, so x509 based authorization should use file sink instead.
+pub struct TopicAuditSink {
+    topic: String,
+    endpoint: String,
+    producer: Option<(Fluvio, TopicProducerPool)>,
+}
+
+impl TopicAuditSink {
+    pub fn new(topic: String, public_endpoint: &str) -> Self {
+        Self {
+            topic,
+            endpoint: local_endpoint(public_endpoint),
+            producer: None,
+        }
+    }
+
+    async fn producer(&mut self) -> Result<&TopicProducerPool> {
+        if self.producer.is_none() {
+            debug!(endpoint = %self.endpoint, "connecting audit producer");
+            let fluvio =
+                Fluvio::connect_with_config(&FluvioClusterConfig::new(&self.endpoint)).await?;
+            let producer = fluvio.topic_producer(&self.topic).await?;
+            info!(topic = %self.topic, "audit producer connected");
+            self.producer = Some((fluvio, producer));
+        }
+        Ok(&self.producer.as_ref().expect("producer is connected").1)
+    }
+}
+
+/// wildcard bind address can't be used to connect
+fn local_endpoint(public_endpoint: &str) -> String {
+    match public_endpoint.rsplit_once(':') {
+        Some(("0.0.0.0", port)) | Some(("[::]", port)) => format!("127.0.0.1:{port}"),
+        _ => public_endpoint.to_owned(),
+    }
+}
+
+#[async_trait]
+impl AuditSink for TopicAuditSink {
+    async fn write(&mut self, event: &AuditEvent) -> Result<()> {
+        let record = serde_json::to_vec(event)?;
+        let result = async {
+            let producer = self.producer().await?;
+            producer.send(RecordKey::NULL, record).await?;
+            producer.flush().await
+        }
+        .await;
+
+        if result.is_err() {
+            // drop connection so next event reconnects
+            self.producer = None;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::local_endpoint;
+
+    #[test]
+    fn test_local_endpoint() {
+        assert_eq!(local_endpoint("0.0.0.0:9003"), "127.0.0.1:9003");
+        assert_eq!(local_endpoint("[::]:9003"), "127.0.0.1:9003");
+        assert_eq!(local_endpoint("10.0.0.1:9003"), "10.0.0.1:9003");
+    }
+}
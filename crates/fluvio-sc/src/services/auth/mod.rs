@@ -85,14 +85,17 @@ mod common {
             Ok(matches!(action, TypeAction::Read) || matches!(ty, ObjectType::CustomSpu))
         }
 
-        /// check if specific instance of spec can be deleted
+        /// check if specific instance of spec can be permitted
         async fn allow_instance_action(
             &self,
-            _ty: ObjectType,
-            _action: InstanceAction,
+            ty: ObjectType,
+            action: InstanceAction,
             _key: &str,
         ) -> Result<bool, AuthError> {
-            Ok(true)
+            Ok(
+                !matches!(action, InstanceAction::Create | InstanceAction::Produce)
+                    || matches!(ty, ObjectType::CustomSpu),
+            )
         }
     }
 
@@ -100,7 +103,7 @@ mod common {
     mod test {
         use fluvio_auth::{root::RootAuthContext, AuthContext};
 
-        use super::{ObjectType, ReadOnlyAuthContext, TypeAction};
+        use super::{InstanceAction, ObjectType, ReadOnlyAuthContext, TypeAction};
 
         /// test read only context
         /// read only context allows read on everything
@@ -132,6 +135,18 @@ mod common {
                     .await
                     .unwrap()
             );
+            assert!(
+                !auth_context
+                    .allow_instance_action(ObjectType::Topic, InstanceAction::Create, "test")
+                    .await
+                    .unwrap()
+            );
+            assert!(
+                auth_context
+                    .allow_instance_action(ObjectType::CustomSpu, InstanceAction::Create, "spu")
+                    .await
+                    .unwrap()
+            );
         }
 
         /// test root context
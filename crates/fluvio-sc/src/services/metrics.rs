@@ -0,0 +1,241 @@
+//!
+//! # SC metrics
+//!
+//! Cluster state known to SC exposed on OpenMetrics endpoint.
+//! Offsets of partitions are as last reported by leaders.
+//!
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_trait::async_trait;
+
+use fluvio_controlplane_metadata::partition::{PartitionStatus, ReplicaStatus};
+use fluvio_service::metrics::{MetricsCollector, start_metrics_server};
+use fluvio_stream_model::core::MetadataItem;
+use fluvio_types::openmetrics::{MetricType, MetricsEncoder};
+use fluvio_types::partition::decompose_partition_name;
+
+use crate::core::SharedContext;
+
+#[derive(Debug, Default)]
+pub struct ScMetrics {
+    connections: AtomicU64,
+}
+
+impl ScMetrics {
+    /// number of open public connections
+    pub fn connections(&self) -> u64 {
+        self.connections.load(Ordering::SeqCst)
+    }
+
+    /// count connection as open until guard is dropped
+    pub fn connection_guard(&self) -> ConnectionGuard<'_> {
+        self.connections.fetch_add(1, Ordering::SeqCst);
+        ConnectionGuard(self)
+    }
+}
+
+pub struct ConnectionGuard<'a>(&'a ScMetrics);
+
+impl Drop for ConnectionGuard<'_> {
+    fn drop(&mut self) {
+        self.0.connections.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// start OpenMetrics endpoint if address is configured
+pub fn start_metrics_endpoint<C>(ctx: SharedContext<C>)
+where
+    C: MetadataItem + 'static,
+{
+    if let Some(addr) = ctx.config().metrics_endpoint.clone() {
+        start_metrics_server(addr, ScMetricsCollector { ctx });
+    }
+}
+
+struct ScMetricsCollector<C: MetadataItem> {
+    ctx: SharedContext<C>,
+}
+
+/// partition state with labels
+struct PartitionMetrics {
+    topic: String,
+    partition: String,
+    leader: ReplicaStatus,
+    replicas: Vec<ReplicaStatus>,
+    isr: usize,
+    size: i64,
+}
+
+impl PartitionMetrics {
+    fn labels(&self) -> [(&str, &str); 2] {
+        [
+            ("topic", self.topic.as_str()),
+            ("partition", self.partition.as_str()),
+        ]
+    }
+}
+
+#[async_trait]
+impl<C> MetricsCollector for ScMetricsCollector<C>
+where
+    C: MetadataItem + 'static,
+{
+    async fn collect(&self, encoder: &mut MetricsEncoder) {
+        let ctx = &self.ctx;
+
+        encoder
+            .family(
+                "fluvio_sc_connections",
+                MetricType::Gauge,
+                "open public connections",
+            )
+            .sample(&[], ctx.metrics().connections());
+
+        encoder
+            .family("fluvio_sc_topics", MetricType::Gauge, "number of topics")
+            .sample(&[], ctx.topics().store().count().await);
+
+        let spus = ctx.spus().store().clone_values().await;
+        let online = spus.iter().filter(|spu| spu.status.is_online()).count();
+        encoder
+            .family("fluvio_sc_spus", MetricType::Gauge, "number of spus")
+            .sample(&[("status", "online")], online)
+            .sample(&[("status", "offline")], spus.len() - online);
+        encoder.family(
+            "fluvio_sc_spu_online",
+            MetricType::Gauge,
+            "1 if spu is online",
+        );
+        for spu in &spus {
+            let id = spu.spec.id.to_string();
+            encoder.sample(&[("spu", id.as_str())], u8::from(spu.status.is_online()));
+        }
+
+        let partitions: Vec<_> = ctx
+            .partitions()
+            .store()
+            .clone_values()
+            .await
+            .into_iter()
+            .filter_map(|partition| {
+                let (topic, partition_id) = decompose_partition_name(&partition.key).ok()?;
+                Some(PartitionMetrics {
+                    topic,
+                    partition: partition_id.to_string(),
+                    isr: in_sync_replicas(&partition.status),
+                    leader: partition.status.leader,
+                    replicas: partition.status.replicas,
+                    size: partition.status.size,
+                })
+            })
+            .collect();
+        encoder
+            .family(
+                "fluvio_sc_partitions",
+                MetricType::Gauge,
+                "number of partitions",
+            )
+            .sample(&[], partitions.len());
+
+        type Getter = fn(&PartitionMetrics) -> i64;
+        let families: [(&str, &str, Getter); 4] = [
+            (
+                "fluvio_partition_hw",
+                "high watermark of partition",
+                |partition| partition.leader.hw,
+            ),
+            (
+                "fluvio_partition_leo",
+                "log end offset of partition",
+                |partition| partition.leader.leo,
+            ),
+            (
+                "fluvio_partition_size_bytes",
+                "size of partition on leader",
+                |partition| partition.size,
+            ),
+            (
+                "fluvio_partition_in_sync_replicas",
+                "number of replicas in sync with leader",
+                |partition| partition.isr as i64,
+            ),
+        ];
+        for (name, help, value) in families {
+            encoder.family(name, MetricType::Gauge, help);
+            for partition in &partitions {
+                encoder.sample(&partition.labels(), value(partition));
+            }
+        }
+
+        encoder.family(
+            "fluvio_partition_follower_lag",
+            MetricType::Gauge,
+            "records follower is behind leader",
+        );
+        for partition in &partitions {
+            let [topic, partition_id] = partition.labels();
+            for replica in &partition.replicas {
+                let follower = replica.spu.to_string();
+                encoder.sample(
+                    &[topic, partition_id, ("follower", follower.as_str())],
+                    replica.leader_lag(&partition.leader).max(0),
+                );
+            }
+        }
+    }
+}
+
+/// SPUs with LRS v1 don't report isr, then leader and followers which have
+/// every committed record are counted
+fn in_sync_replicas(status: &PartitionStatus) -> usize {
+    if !status.isr.is_empty() {
+        return status.isr.len();
+    }
+    // leader has not reported yet
+    if status.leader.hw < 0 {
+        return 0;
+    }
+    1 + status
+        .replicas
+        .iter()
+        .filter(|replica| replica.leo >= status.leader.hw)
+        .count()
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_controlplane_metadata::partition::{PartitionStatus, ReplicaStatus};
+
+    use super::{ScMetrics, in_sync_replicas};
+
+    #[test]
+    fn test_connection_guard() {
+        let metrics = ScMetrics::default();
+        {
+            let _first = metrics.connection_guard();
+            let _second = metrics.connection_guard();
+            assert_eq!(metrics.connections(), 2);
+        }
+        assert_eq!(metrics.connections(), 0);
+    }
+
+    #[test]
+    fn test_in_sync_replicas_without_isr() {
+        let mut status = PartitionStatus::new(
+            ReplicaStatus::new(5000, 10, 12),
+            vec![
+                ReplicaStatus::new(5001, 10, 10),
+                ReplicaStatus::new(5002, 8, 9),
+            ],
+        );
+        assert_eq!(in_sync_replicas(&status), 2);
+
+        status.isr = vec![5000, 5001, 5002];
+        assert_eq!(in_sync_replicas(&status), 3);
+
+        assert_eq!(in_sync_replicas(&PartitionStatus::default()), 0);
+    }
+}
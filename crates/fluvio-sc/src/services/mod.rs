@@ -3,6 +3,8 @@ mod public_api;
 mod private_api;
 
 pub mod auth;
+pub mod audit;
+pub mod metrics;
 
-pub use public_api::start_public_server;
-pub use private_api::start_internal_server;
+pub use public_api::{start_public_server, bind_public_server};
+pub use private_api::{start_internal_server, bind_internal_server};
@@ -1,5 +1,8 @@
 mod private_server;
 
+use std::io::Error as IoError;
+use std::net::SocketAddr;
+
 use fluvio_stream_model::core::MetadataItem;
 use tracing::info;
 use tracing::instrument;
@@ -22,6 +25,18 @@ where
     info!("starting internal services");
 
     let addr = ctx.config().private_endpoint.clone();
+    let shutdown = ctx.shutdown().clone();
+    let server = FluvioApiServer::new(addr, ctx, ScInternalService::new());
+    server.run_until(shutdown);
+}
+
+/// bind internal server to private endpoint, returns bound address
+pub async fn bind_internal_server<C>(ctx: SharedContext<C>) -> Result<SocketAddr, IoError>
+where
+    C: MetadataItem + 'static,
+{
+    let addr = ctx.config().private_endpoint.clone();
+    let shutdown = ctx.shutdown().clone();
     let server = FluvioApiServer::new(addr, ctx, ScInternalService::new());
-    server.run();
+    server.bind_until(shutdown).await
 }
@@ -15,14 +15,20 @@ use fluvio_controlplane::sc_api::remove::ReplicaRemovedRequest;
 use fluvio_controlplane::sc_api::update_lrs::UpdateLrsRequest;
 use fluvio_controlplane::sc_api::update_mirror::UpdateMirrorStatRequest;
 use fluvio_controlplane::sc_api::update_partition::UpdatePartitionStatRequest;
+use fluvio_controlplane::sc_api::update_storage::UpdateStorageStatRequest;
+use fluvio_controlplane::spu_api::update_dictionary::DictionaryMsg;
+use fluvio_controlplane::spu_api::update_dictionary::UpdateDictionaryRequest;
 use fluvio_controlplane::spu_api::update_mirror::MirrorMsg;
 use fluvio_controlplane::spu_api::update_mirror::UpdateMirrorRequest;
+use fluvio_controlplane::spu_api::update_quota::UpdateQuotaRequest;
 use fluvio_controlplane::spu_api::update_replica::UpdateReplicaRequest;
 use fluvio_controlplane::spu_api::update_smartmodule::UpdateSmartModuleRequest;
 use fluvio_controlplane::spu_api::update_spu::UpdateSpuRequest;
 use fluvio_controlplane_metadata::message::Message;
+use fluvio_sc_schema::dictionary::DictionarySpec;
 use fluvio_sc_schema::mirror::MirrorSpec;
 use fluvio_stream_model::core::MetadataItem;
+use fluvio_stream_model::epoch::Epoch;
 use fluvio_stream_model::store::ChangeListener;
 use tracing::warn;
 use tracing::{debug, info, trace, instrument, error};
@@ -135,6 +141,9 @@ where
     let mut partition_spec_listener = context.partitions().change_listener();
     let mut sm_spec_listener = context.smartmodules().change_listener();
     let mut mirror_spec_listener = context.mirrors().change_listener();
+    let mut dictionary_spec_listener = context.dictionaries().change_listener();
+    let mut quota_listener = context.client_quotas().change_listener();
+    let mut quota_epoch = None;
 
     // send initial changes
 
@@ -148,6 +157,8 @@ where
         send_smartmodule_changes(&mut sm_spec_listener, &mut sink, spu_id).await?;
         send_replica_spec_changes(&mut partition_spec_listener, &mut sink, spu_id).await?;
         send_mirror_changes(&mut mirror_spec_listener, &mut sink, spu_id).await?;
+        send_dictionary_changes(&mut dictionary_spec_listener, &mut sink, spu_id).await?;
+        send_client_quotas(&context, &mut quota_epoch, &mut sink, spu_id).await?;
 
         trace!(spu_id, "waiting for SPU channel");
 
@@ -180,6 +191,9 @@ where
                             },
                             InternalScRequest::UpdatePartitionStatRequest(msg) => {
                                 receive_partition_status_update(&context, msg.request).await;
+                            },
+                            InternalScRequest::UpdateStorageStatRequest(msg) => {
+                                receive_storage_status_update(&context, spu_id, msg.request).await;
                             }
                         }
                         // reset timer
@@ -211,6 +225,14 @@ where
                 debug!("mirror lister changed");
             }
 
+            _ = dictionary_spec_listener.listen() => {
+                debug!("dictionary lister changed");
+            }
+
+            _ = quota_listener.listen() => {
+                debug!("client quotas changed");
+            }
+
         }
     }
 
@@ -236,13 +258,14 @@ where
         if let Some(partition) = read_guard.get(&lrs_req.id) {
             let mut current_status = partition.inner().status().clone();
             let key = lrs_req.id.clone();
-            let new_status = PartitionStatus::new2(
+            let mut new_status = PartitionStatus::new2(
                 lrs_req.leader,
                 lrs_req.replicas,
                 lrs_req.size,
                 PartitionResolution::Online,
                 lrs_req.base_offset,
             );
+            new_status.isr = lrs_req.isr;
             current_status.merge(new_status);
 
             actions.push(WSAction::<PartitionSpec, C>::UpdateStatus((
@@ -379,6 +402,70 @@ async fn receive_partition_status_update<C>(
     }
 }
 
+/// update storage status of SPU, partition controller performs election for offline replicas
+#[instrument(skip(ctx, request))]
+async fn receive_storage_status_update<C>(
+    ctx: &SharedContext<C>,
+    spu_id: SpuId,
+    request: UpdateStorageStatRequest,
+) where
+    C: MetadataItem,
+{
+    debug!(%request, "received storage status");
+    let Some(spu) = ctx.spus().store().get_by_id(spu_id).await else {
+        error!(spu_id, "trying to update storage of spu that doesn't exist");
+        return;
+    };
+
+    let mut status = spu.status.clone();
+    status.storage_dirs = request.dirs;
+    status.offline_replicas = request.offline_replicas;
+    if status == spu.status {
+        trace!(spu_id, "storage status unchanged");
+        return;
+    }
+
+    if !status.offline_replicas.is_empty() {
+        warn!(
+            spu_id,
+            offline_replicas = ?status.offline_replicas,
+            "spu reported offline replicas"
+        );
+    }
+
+    if let Err(err) = ctx.spus().update_status(spu.key, status).await {
+        error!(spu_id, %err, "error updating spu storage status");
+    }
+}
+
+/// send all client quotas if epoch has changed since last sent,
+/// SPU replaces its quotas even if there are none
+#[instrument(level = "trace", skip(ctx, sink))]
+async fn send_client_quotas<C: MetadataItem>(
+    ctx: &SharedContext<C>,
+    sent_epoch: &mut Option<Epoch>,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+) -> Result<(), SocketError> {
+    let (epoch, quotas) = ctx.client_quotas().all();
+    if *sent_epoch == Some(epoch) {
+        return Ok(());
+    }
+    debug!(
+        spu_id,
+        epoch,
+        quotas = quotas.len(),
+        "sending client quotas to spu"
+    );
+
+    let mut message = RequestMessage::new_request(UpdateQuotaRequest::with_all(epoch, quotas));
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    *sent_epoch = Some(epoch);
+    Ok(())
+}
+
 /// send spu spec changes only
 #[instrument(skip(sink))]
 async fn send_spu_spec_changes<C: MetadataItem>(
@@ -604,3 +691,62 @@ async fn send_mirror_changes<C: MetadataItem>(
     sink.send_request(&message).await?;
     Ok(())
 }
+
+#[instrument(level = "trace", skip(sink))]
+async fn send_dictionary_changes<C: MetadataItem>(
+    listener: &mut ChangeListener<DictionarySpec, C>,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+) -> Result<(), SocketError> {
+    use crate::stores::ChangeFlag;
+
+    if !listener.has_change() {
+        trace!("changes is empty, skipping");
+        return Ok(());
+    }
+
+    let changes = listener
+        .sync_changes_with_filter(&ChangeFlag {
+            spec: true,
+            status: false,
+            meta: true,
+        })
+        .await;
+    if changes.is_empty() {
+        trace!("spec changes is empty, skipping");
+        return Ok(());
+    }
+
+    let epoch = changes.epoch;
+
+    let is_sync_all = changes.is_sync_all();
+    let (updates, deletes) = changes.parts();
+
+    let request = if is_sync_all {
+        UpdateDictionaryRequest::with_all(epoch, updates.into_iter().map(|d| d.into()).collect())
+    } else {
+        let mut changes: Vec<DictionaryMsg> = updates
+            .into_iter()
+            .map(|d| Message::update(d.into()))
+            .collect();
+        let mut deletes = deletes
+            .into_iter()
+            .map(|d| Message::delete(d.into()))
+            .collect();
+        changes.append(&mut deletes);
+        UpdateDictionaryRequest::with_changes(epoch, changes)
+    };
+
+    debug!(
+        epoch,
+        all = request.all.len(),
+        changes = request.changes.len(),
+        "sending dictionaries to spu"
+    );
+
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    Ok(())
+}
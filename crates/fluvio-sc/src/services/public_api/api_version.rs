@@ -30,6 +30,13 @@ pub async fn handle_api_versions_request(
     let client_version = Version::parse(&request.request().client_version)?;
     debug!(client_version = %client_version, "client version");
 
+    // listed so clients know trace context is accepted
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::ApiVersion,
+        ApiVersionsRequest::MIN_API_VERSION,
+        ApiVersionsRequest::MAX_API_VERSION,
+    ));
+
     // topic versions
     response.api_keys.push(make_version_key(
         AdminPublicApiKey::Create,
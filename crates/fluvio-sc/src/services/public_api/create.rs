@@ -1,5 +1,6 @@
 use fluvio_protocol::link::ErrorCode;
 use fluvio_sc_schema::mirror::MirrorSpec;
+use fluvio_sc_schema::dictionary::DictionarySpec;
 use fluvio_stream_model::core::MetadataItem;
 use tracing::{instrument, debug, error};
 use anyhow::Result;
@@ -13,7 +14,9 @@ use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_sc_schema::{Status, TryEncodableFrom};
 use fluvio_sc_schema::objects::{ObjectApiCreateRequest, CreateRequest};
 use fluvio_auth::AuthContext;
+use fluvio_stream_model::core::Spec;
 
+use crate::services::audit::{AuditEvent, AuditOperation};
 use crate::services::auth::AuthServiceContext;
 
 /// Handler for create topic request
@@ -25,19 +28,41 @@ pub async fn handle_create_request<AC: AuthContext, C: MetadataItem>(
     let (header, req) = request.get_header_request();
 
     debug!(?req, "create request");
+    let mut audit = AuditEvent::new(AuditOperation::Create, auth_context.auth.principal());
+    let result = create_object(req, auth_context, &mut audit).await;
+    audit.set_result(&result);
+    auth_context.global_ctx.audit().record(audit);
+
+    Ok(ResponseMessage::from_header(&header, result?))
+}
+
+async fn create_object<AC: AuthContext, C: MetadataItem>(
+    req: ObjectApiCreateRequest,
+    auth_context: &AuthServiceContext<AC, C>,
+    audit: &mut AuditEvent,
+) -> Result<Status> {
     let status = if let Some(create) = req.downcast()? as Option<CreateRequest<TopicSpec>> {
+        audit_create(audit, TopicSpec::LABEL, &create);
         super::topic::handle_create_topics_request(create, auth_context).await?
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<SpuGroupSpec>> {
+        audit_create(audit, SpuGroupSpec::LABEL, &create);
         super::spg::handle_create_spu_group_request(create, auth_context).await?
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<CustomSpuSpec>> {
+        audit_create(audit, CustomSpuSpec::LABEL, &create);
         super::spu::RegisterCustomSpu::handle_register_custom_spu_request(create, auth_context)
             .await
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<SmartModuleSpec>> {
+        audit_create(audit, SmartModuleSpec::LABEL, &create);
         super::smartmodule::handle_create_smartmodule_request(create, auth_context).await?
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<TableFormatSpec>> {
+        audit_create(audit, TableFormatSpec::LABEL, &create);
         super::tableformat::handle_create_tableformat_request(create, auth_context).await?
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<MirrorSpec>> {
+        audit_create(audit, MirrorSpec::LABEL, &create);
         super::mirror::handle_register_mirror(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<DictionarySpec>> {
+        audit_create(audit, DictionarySpec::LABEL, &create);
+        super::dictionary::handle_create_dictionary_request(create, auth_context).await?
     } else {
         error!("unknown create request: {:#?}", req);
         Status::new(
@@ -47,7 +72,16 @@ pub async fn handle_create_request<AC: AuthContext, C: MetadataItem>(
         )
     };
 
-    Ok(ResponseMessage::from_header(&header, status))
+    Ok(status)
+}
+
+/// record name and options of create request, spec is left out
+fn audit_create<S: Spec>(audit: &mut AuditEvent, label: &str, create: &CreateRequest<S>) {
+    let summary = match create.common.timeout {
+        Some(timeout) => format!("dry_run: {}, timeout: {timeout}ms", create.common.dry_run),
+        None => format!("dry_run: {}", create.common.dry_run),
+    };
+    audit.set_object(label, &create.common.name, summary);
 }
 
 mod create_handler {
@@ -64,7 +98,7 @@ mod create_handler {
     use fluvio_sc_schema::{AdminSpec, Status};
     use fluvio_sc_schema::objects::CommonCreateRequest;
     use fluvio_controlplane_metadata::extended::SpecExt;
-    use fluvio_auth::{AuthContext, TypeAction};
+    use fluvio_auth::{AuthContext, InstanceAction};
 
     use crate::services::auth::AuthServiceContext;
 
@@ -87,7 +121,7 @@ mod create_handler {
 
         if let Ok(authorized) = auth_ctx
             .auth
-            .allow_type_action(S::OBJECT_TYPE, TypeAction::Create)
+            .allow_instance_action(S::OBJECT_TYPE, InstanceAction::Create, &name)
             .await
         {
             if !authorized {
@@ -5,8 +5,11 @@
 //! and send K8 a delete message.
 //!
 
+use std::fmt::Display;
+
 use fluvio_protocol::link::ErrorCode;
 use fluvio_sc_schema::mirror::MirrorSpec;
+use fluvio_sc_schema::dictionary::DictionarySpec;
 use fluvio_stream_model::core::MetadataItem;
 use tracing::{instrument, trace, debug, error};
 use anyhow::Result;
@@ -17,10 +20,12 @@ use fluvio_controlplane_metadata::spu::CustomSpuSpec;
 use fluvio_controlplane_metadata::tableformat::TableFormatSpec;
 use fluvio_controlplane_metadata::topic::TopicSpec;
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
-use fluvio_sc_schema::{Status, TryEncodableFrom};
+use fluvio_sc_schema::{DeletableAdminSpec, Status, TryEncodableFrom};
 use fluvio_sc_schema::objects::{ObjectApiDeleteRequest, DeleteRequest};
 use fluvio_auth::AuthContext;
+use fluvio_stream_model::core::Spec;
 
+use crate::services::audit::{AuditEvent, AuditOperation};
 use crate::services::auth::AuthServiceContext;
 
 /// Handler for delete topic request
@@ -33,19 +38,40 @@ pub async fn handle_delete_request<AC: AuthContext, C: MetadataItem>(
 
     debug!(?del_req, "del request");
 
+    let mut audit = AuditEvent::new(AuditOperation::Delete, auth_ctx.auth.principal());
+    let result = delete_object(del_req, auth_ctx, &mut audit).await;
+    audit.set_result(&result);
+    auth_ctx.global_ctx.audit().record(audit);
+
+    let status = result?;
+
+    trace!("flv delete topics resp {:#?}", status);
+
+    Ok(ResponseMessage::from_header(&header, status))
+}
+
+async fn delete_object<AC: AuthContext, C: MetadataItem>(
+    del_req: ObjectApiDeleteRequest,
+    auth_ctx: &AuthServiceContext<AC, C>,
+    audit: &mut AuditEvent,
+) -> Result<Status> {
     let status = if let Some(req) = del_req.downcast()? as Option<DeleteRequest<TopicSpec>> {
         let force = req.is_force();
-        super::topic::handle_delete_topic(req.key(), force, auth_ctx).await?
+        let key = audit_delete(audit, req);
+        super::topic::handle_delete_topic(key, force, auth_ctx).await?
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<CustomSpuSpec>> {
-        super::spu::handle_un_register_custom_spu_request(req.key(), auth_ctx).await?
+        super::spu::handle_un_register_custom_spu_request(audit_delete(audit, req), auth_ctx)
+            .await?
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<SpuGroupSpec>> {
-        super::spg::handle_delete_spu_group(req.key(), auth_ctx).await?
+        super::spg::handle_delete_spu_group(audit_delete(audit, req), auth_ctx).await?
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<SmartModuleSpec>> {
-        super::smartmodule::handle_delete_smartmodule(req.key(), auth_ctx).await?
+        super::smartmodule::handle_delete_smartmodule(audit_delete(audit, req), auth_ctx).await?
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<TableFormatSpec>> {
-        super::tableformat::handle_delete_tableformat(req.key(), auth_ctx).await?
+        super::tableformat::handle_delete_tableformat(audit_delete(audit, req), auth_ctx).await?
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<MirrorSpec>> {
-        super::mirror::handle_unregister_mirror(req.key(), auth_ctx).await?
+        super::mirror::handle_unregister_mirror(audit_delete(audit, req), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<DictionarySpec>> {
+        super::dictionary::handle_delete_dictionary(audit_delete(audit, req), auth_ctx).await?
     } else {
         error!("unknown create request: {:#?}", del_req);
         Status::new(
@@ -55,9 +81,19 @@ pub async fn handle_delete_request<AC: AuthContext, C: MetadataItem>(
         )
     };
 
-    trace!("flv delete topics resp {:#?}", status);
+    Ok(status)
+}
 
-    Ok(ResponseMessage::from_header(&header, status))
+/// record key and options of delete request, returns key to be deleted
+fn audit_delete<S>(audit: &mut AuditEvent, req: DeleteRequest<S>) -> S::DeleteKey
+where
+    S: DeletableAdminSpec,
+    S::DeleteKey: Display,
+{
+    let summary = format!("force: {}", req.is_force());
+    let key = req.key();
+    audit.set_object(S::LABEL, &key, summary);
+    key
 }
 
 mod delete_handler {
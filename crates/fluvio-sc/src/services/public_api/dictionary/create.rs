@@ -0,0 +1,114 @@
+//!
+//! # Create Dictionary Request
+//!
+//! Validates zstd dictionary and stores it in KV store.
+//!
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{debug, info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio::ZstdDictionary;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::dictionary::DictionarySpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::{AuthContext, InstanceAction};
+
+use crate::core::Context;
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for dictionary request
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_create_dictionary_request<AC: AuthContext, C: MetadataItem>(
+    req: CreateRequest<DictionarySpec>,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status> {
+    let (create, spec) = req.parts();
+    let name = create.name;
+
+    info!(%name, "creating dictionary");
+
+    if auth_ctx
+        .global_ctx
+        .dictionaries()
+        .store()
+        .contains_key(&name)
+        .await
+    {
+        debug!("dictionary already exists");
+        return Ok(Status::new(
+            name.to_string(),
+            ErrorCode::CompressionDictionaryAlreadyExists,
+            Some(format!("dictionary '{name}' already defined")),
+        ));
+    }
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(DictionarySpec::OBJECT_TYPE, InstanceAction::Create, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if let Some(error) = validate_dictionary(&auth_ctx.global_ctx, &spec).await {
+        let code = ErrorCode::CompressionDictionaryInvalid(error.clone());
+        return Ok(Status::new(name, code, Some(error)));
+    }
+
+    let status = process_dictionary_request(&auth_ctx.global_ctx, name, spec).await;
+    trace!("create dictionary response {:#?}", status);
+
+    Ok(status)
+}
+
+/// dictionary must be valid zstd dictionary, with id unique in the cluster
+async fn validate_dictionary<C: MetadataItem>(
+    ctx: &Context<C>,
+    spec: &DictionarySpec,
+) -> Option<String> {
+    let dictionary = match ZstdDictionary::new(spec.data.to_vec()) {
+        Ok(dictionary) => dictionary,
+        Err(err) => return Some(err.to_string()),
+    };
+    if dictionary.id() != spec.id {
+        return Some(format!(
+            "id {} doesn't match dictionary id {}",
+            spec.id,
+            dictionary.id()
+        ));
+    }
+    ctx.dictionaries()
+        .store()
+        .read()
+        .await
+        .values()
+        .find(|existing| existing.spec.id == spec.id)
+        .map(|existing| format!("id {} is used by dictionary '{}'", spec.id, existing.key))
+}
+
+#[instrument(skip(ctx, name, spec))]
+async fn process_dictionary_request<C: MetadataItem>(
+    ctx: &Context<C>,
+    name: String,
+    spec: DictionarySpec,
+) -> Status {
+    if let Err(err) = ctx.dictionaries().create_spec(name.clone(), spec).await {
+        let error = Some(err.to_string());
+        Status::new(name, ErrorCode::CompressionError, error)
+    } else {
+        info!(%name, "dictionary created");
+        Status::new_ok(name.clone())
+    }
+}
@@ -0,0 +1,93 @@
+use std::io::{Error, ErrorKind};
+
+use fluvio_stream_model::core::MetadataItem;
+use tracing::{info, trace, instrument};
+
+use fluvio_sc_schema::Status;
+use fluvio_auth::{AuthContext, InstanceAction};
+use fluvio_controlplane_metadata::dictionary::DictionarySpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for delete dictionary request
+#[instrument(skip(name, auth_ctx))]
+pub async fn handle_delete_dictionary<AC: AuthContext, C: MetadataItem>(
+    name: String,
+    auth_ctx: &AuthServiceContext<AC, C>,
+) -> Result<Status, Error> {
+    use fluvio_protocol::link::ErrorCode;
+
+    info!(%name, "deleting dictionary");
+
+    if let Ok(authorized) = auth_ctx
+        .auth
+        .allow_instance_action(DictionarySpec::OBJECT_TYPE, InstanceAction::Delete, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    // records compressed with dictionary can't be read without it
+    let used_by: Vec<String> = auth_ctx
+        .global_ctx
+        .topics()
+        .store()
+        .read()
+        .await
+        .values()
+        .filter(|topic| topic.spec.get_compression_dictionary() == Some(name.as_str()))
+        .map(|topic| topic.key.clone())
+        .collect();
+    if !used_by.is_empty() {
+        return Ok(Status::new(
+            name.clone(),
+            ErrorCode::CompressionDictionaryInUse(name),
+            Some(format!("used by topics: {}", used_by.join(", "))),
+        ));
+    }
+
+    let status = if auth_ctx
+        .global_ctx
+        .dictionaries()
+        .store()
+        .value(&name)
+        .await
+        .is_some()
+    {
+        if let Err(err) = auth_ctx
+            .global_ctx
+            .dictionaries()
+            .delete(name.clone())
+            .await
+        {
+            Status::new(
+                name.clone(),
+                ErrorCode::CompressionError,
+                Some(err.to_string()),
+            )
+        } else {
+            info!(%name, "dictionary deleted");
+            Status::new_ok(name)
+        }
+    } else {
+        Status::new(
+            name.clone(),
+            ErrorCode::CompressionDictionaryNotFound(name),
+            Some("not found".to_owned()),
+        )
+    };
+
+    trace!("flv delete dictionary resp {:#?}", status);
+
+    Ok(status)
+}
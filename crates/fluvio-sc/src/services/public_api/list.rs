@@ -14,6 +14,7 @@ use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_sc_schema::{
     objects::{ListRequest, ObjectApiListRequest, ObjectApiListResponse},
     mirror::MirrorSpec,
+    dictionary::DictionarySpec,
     TryEncodableFrom,
 };
 use fluvio_auth::AuthContext;
@@ -81,6 +82,16 @@ pub async fn handle_list_request<AC: AuthContext, C: MetadataItem>(
             handle_list_mirror(req.name_filters, auth_ctx).await?,
             header.api_version(),
         )?
+    } else if let Some(req) = req.downcast()? as Option<ListRequest<DictionarySpec>> {
+        ObjectApiListResponse::try_encode_from(
+            fetch::handle_fetch_request(
+                req.name_filters,
+                auth_ctx,
+                auth_ctx.global_ctx.dictionaries(),
+            )
+            .await?,
+            header.api_version(),
+        )?
     } else {
         return Err(anyhow::anyhow!("unsupported list request: {:#?}", req));
     };
@@ -52,7 +52,8 @@ impl<AC: AuthContext, C: MetadataItem> RemoteFetchingFromHomeController<AC, C> {
             auth_ctx,
         };
 
-        spawn(controller.dispatch_loop());
+        let shutdown = controller.auth_ctx.global_ctx.shutdown().clone();
+        spawn(shutdown.until_notified(controller.dispatch_loop()));
     }
 
     #[instrument(skip(self), name = "RemoteFetchingFromHomeControllerLoop")]
@@ -14,12 +14,15 @@ mod tableformat;
 mod derivedstream;
 mod mirror;
 mod mirroring;
+mod dictionary;
 
-pub use server::start_public_server;
+pub use server::{start_public_server, bind_public_server};
 
 mod server {
 
     use std::fmt::Debug;
+    use std::io::Error as IoError;
+    use std::net::SocketAddr;
 
     use fluvio_stream_model::core::MetadataItem;
     use tracing::debug;
@@ -40,8 +43,26 @@ mod server {
         <A as Authorization>::Context: Send + Sync,
     {
         let addr = ctx.global_ctx.config().public_endpoint.clone();
+        let shutdown = ctx.global_ctx.shutdown().clone();
         debug!("starting public api service");
         let server = FluvioApiServer::new(addr, ctx, PublicService::new());
-        server.run();
+        server.run_until(shutdown);
+    }
+
+    /// bind public server to public endpoint, returns bound address
+    pub async fn bind_public_server<A, C>(
+        ctx: AuthGlobalContext<A, C>,
+    ) -> Result<SocketAddr, IoError>
+    where
+        A: Authorization + Sync + Send + Debug + 'static,
+        C: MetadataItem + 'static,
+        C::UId: Send + Sync,
+        AuthGlobalContext<A, C>: Clone + Debug,
+        <A as Authorization>::Context: Send + Sync,
+    {
+        let addr = ctx.global_ctx.config().public_endpoint.clone();
+        let shutdown = ctx.global_ctx.shutdown().clone();
+        let server = FluvioApiServer::new(addr, ctx, PublicService::new());
+        server.bind_until(shutdown).await
     }
 }
@@ -20,7 +20,7 @@ use fluvio_sc_schema::objects::CreateRequest;
 use fluvio_sc_schema::shared::validate_resource_name;
 use fluvio_sc_schema::Status;
 use fluvio_sc_schema::topic::TopicSpec;
-use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_auth::{AuthContext, InstanceAction};
 use fluvio_controlplane_metadata::extended::SpecExt;
 use fluvio_controlplane_metadata::smartmodule::SmartModulePackageKey;
 use fluvio_stream_model::core::MetadataItem;
@@ -45,7 +45,7 @@ pub(crate) async fn handle_create_topics_request<AC: AuthContext, C: MetadataIte
 
     if let Ok(authorized) = auth_ctx
         .auth
-        .allow_type_action(TopicSpec::OBJECT_TYPE, TypeAction::Create)
+        .allow_instance_action(TopicSpec::OBJECT_TYPE, InstanceAction::Create, &name)
         .await
     {
         if !authorized {
@@ -137,6 +137,24 @@ async fn validate_topic_request<C: MetadataItem>(
         }
     }
 
+    if let Some(dictionary) = topic_spec.get_compression_dictionary() {
+        if !metadata
+            .dictionaries()
+            .store()
+            .contains_key(dictionary)
+            .await
+        {
+            return Status::new(
+                name.to_string(),
+                ErrorCode::CompressionDictionaryNotFound(dictionary.to_owned()),
+                Some(format!(
+                    "{}\nHint: create it with `fluvio dictionary create {dictionary}`",
+                    ErrorCode::CompressionDictionaryNotFound(dictionary.to_owned())
+                )),
+            );
+        }
+    }
+
     match topic_spec.replicas() {
         ReplicaSpec::Computed(param) => {
             let next_state = validate_computed_topic_parameters::<C>(param);
@@ -10,12 +10,14 @@ use tracing::{instrument, trace, debug, error};
 
 use fluvio_protocol::link::ErrorCode;
 use fluvio_stream_model::core::MetadataItem;
-use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_controlplane_metadata::topic::{TopicSpec, UpdateTopicAction};
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_sc_schema::{Status, TryEncodableFrom};
 use fluvio_sc_schema::objects::{ObjectApiUpdateRequest, UpdateRequest};
 use fluvio_auth::AuthContext;
+use fluvio_stream_model::core::Spec;
 
+use crate::services::audit::{AuditEvent, AuditOperation};
 use crate::services::auth::AuthServiceContext;
 
 /// Handler for update topic request
@@ -28,9 +30,32 @@ pub async fn handle_update_request<AC: AuthContext, C: MetadataItem>(
 
     debug!(?del_req, "del request");
 
+    let mut audit = AuditEvent::new(AuditOperation::Update, auth_ctx.auth.principal());
+    let result = update_object(del_req, auth_ctx, &mut audit).await;
+    audit.set_result(&result);
+    auth_ctx.global_ctx.audit().record(audit);
+
+    let status = result?;
+
+    trace!("flv update topics resp {:#?}", status);
+
+    Ok(ResponseMessage::from_header(&header, status))
+}
+
+async fn update_object<AC: AuthContext, C: MetadataItem>(
+    del_req: ObjectApiUpdateRequest,
+    auth_ctx: &AuthServiceContext<AC, C>,
+    audit: &mut AuditEvent,
+) -> Result<Status> {
     let status = if let Some(req) = del_req.downcast()? as Option<UpdateRequest<TopicSpec>> {
         let action = req.action.clone();
-        super::topic::update::handle_topic_update_request(req.key(), action, auth_ctx).await?
+        let summary = match &action {
+            UpdateTopicAction::AddPartition(add) => format!("add {} partitions", add.count),
+            UpdateTopicAction::AddMirror(add) => format!("add mirror {}", add.remote_cluster),
+        };
+        let key = req.key();
+        audit.set_object(TopicSpec::LABEL, &key, summary);
+        super::topic::update::handle_topic_update_request(key, action, auth_ctx).await?
     } else {
         error!("unknown update request: {:#?}", del_req);
         Status::new(
@@ -40,7 +65,5 @@ pub async fn handle_update_request<AC: AuthContext, C: MetadataItem>(
         )
     };
 
-    trace!("flv update topics resp {:#?}", status);
-
-    Ok(ResponseMessage::from_header(&header, status))
+    Ok(status)
 }
@@ -35,6 +35,7 @@ pub fn handle_watch_request<AC, C: MetadataItem + 'static>(
 ) -> Result<()> {
     let (header, req) = request.get_header_request();
     debug!("handling watch header: {:#?}, request: {:#?}", header, req);
+    let shutdown = auth_ctx.global_ctx.shutdown();
 
     if (req.downcast()? as Option<WatchRequest<TopicSpec>>).is_some() {
         WatchController::<TopicSpec, C>::update(
@@ -43,6 +44,7 @@ pub fn handle_watch_request<AC, C: MetadataItem + 'static>(
             auth_ctx.global_ctx.topics().clone(),
             header,
             false,
+            shutdown,
         )
     } else if (req.downcast()? as Option<WatchRequest<SpuSpec>>).is_some() {
         WatchController::<SpuSpec, C>::update(
@@ -51,6 +53,7 @@ pub fn handle_watch_request<AC, C: MetadataItem + 'static>(
             auth_ctx.global_ctx.spus().clone(),
             header,
             false,
+            shutdown,
         )
     } else if (req.downcast()? as Option<WatchRequest<SpuGroupSpec>>).is_some() {
         WatchController::<SpuGroupSpec, C>::update(
@@ -59,6 +62,7 @@ pub fn handle_watch_request<AC, C: MetadataItem + 'static>(
             auth_ctx.global_ctx.spgs().clone(),
             header,
             false,
+            shutdown,
         )
     } else if (req.downcast()? as Option<WatchRequest<PartitionSpec>>).is_some() {
         WatchController::<PartitionSpec, C>::update(
@@ -67,6 +71,7 @@ pub fn handle_watch_request<AC, C: MetadataItem + 'static>(
             auth_ctx.global_ctx.partitions().clone(),
             header,
             false,
+            shutdown,
         )
     } else if let Some(req) = req.downcast()? as Option<WatchRequest<SmartModuleSpec>> {
         WatchController::<SmartModuleSpec, C>::update(
@@ -75,6 +80,7 @@ pub fn handle_watch_request<AC, C: MetadataItem + 'static>(
             auth_ctx.global_ctx.smartmodules().clone(),
             header,
             req.summary,
+            shutdown,
         )
     } else if (req.downcast()? as Option<WatchRequest<TableFormatSpec>>).is_some() {
         WatchController::<TableFormatSpec, C>::update(
@@ -83,6 +89,7 @@ pub fn handle_watch_request<AC, C: MetadataItem + 'static>(
             auth_ctx.global_ctx.tableformats().clone(),
             header,
             false,
+            shutdown,
         )
     } else {
         debug!("Invalid Watch Req {:?}", req);
@@ -109,13 +116,14 @@ where
     S::Status: Encoder + Decoder + Send + Sync,
     S::IndexKey: ToString + Send + Sync,
 {
-    /// start watch controller
+    /// start watch controller, controller is stopped when `shutdown` is notified
     fn update(
         response_sink: ExclusiveFlvSink,
         end_event: Arc<StickyEvent>,
         store: StoreContext<S, C>,
         header: RequestHeader,
         summary: bool,
+        shutdown: &Arc<StickyEvent>,
     ) {
         use fluvio_future::task::spawn;
 
@@ -127,7 +135,7 @@ where
             summary,
         };
 
-        spawn(controller.dispatch_loop());
+        spawn(shutdown.until_notified(controller.dispatch_loop()));
     }
 
     #[instrument(
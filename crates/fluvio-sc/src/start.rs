@@ -1,5 +1,7 @@
 use std::{
-    sync::Arc,
+    io::Error as IoError,
+    net::SocketAddr,
+    sync::{Arc, Weak},
     path::{PathBuf, Path},
     time::Duration,
 };
@@ -10,11 +12,15 @@ use tracing::info;
 use fluvio_future::{task::run_block_on, timer::sleep};
 use fluvio_stream_dispatcher::metadata::{SharedClient, MetadataClient, local::LocalMetadataStorage};
 use fluvio_stream_model::{store::k8::K8MetaItem, core::MetadataItem};
+use fluvio_types::event::StickyEvent;
+use fluvio_auth::root::RootAuthorization;
 use k8_client::{K8Client, K8Config, memory::MemoryClient};
 
 use crate::{
     cli::{ScOpt, TlsConfig, RunMode},
     services::auth::basic::BasicRbacPolicy,
+    services::auth::AuthGlobalContext,
+    services::{bind_public_server, bind_internal_server},
     config::ScConfig,
     config::DEFAULT_NAMESPACE,
 };
@@ -140,6 +146,55 @@ fn local_main_loop<C, M>(
     });
 }
 
+/// SC services running in the current process
+#[derive(Debug)]
+pub struct LocalSc {
+    pub public_addr: SocketAddr,
+    pub private_addr: SocketAddr,
+    /// notify to stop servers, dispatchers and controllers
+    pub shutdown: Arc<StickyEvent>,
+    /// released by the dispatchers writing to it
+    storage: Weak<LocalMetadataStorage>,
+}
+
+impl LocalSc {
+    /// true once the dispatchers writing to the metadata directory ended, after shutdown is notified
+    pub fn is_stopped(&self) -> bool {
+        self.storage.strong_count() == 0
+    }
+}
+
+/// Start SC services in the current process, with metadata stored in a local directory.
+/// Endpoints with port 0 are bound to ephemeral ports, see [`LocalSc`] for bound addresses.
+pub async fn start_local(sc_config: ScConfig, metadata_path: &Path) -> Result<LocalSc, IoError> {
+    info!(?metadata_path, "starting local services");
+    let client = create_local_metadata_store(metadata_path);
+    let storage = Arc::downgrade(&client);
+    let ctx = crate::init::start_main_loop_with_servers((sc_config, None), client, false).await;
+    let shutdown = ctx.shutdown().clone();
+
+    let public_ctx = AuthGlobalContext::new(ctx.clone(), Arc::new(RootAuthorization::new()));
+    let addrs = async {
+        let public_addr = bind_public_server(public_ctx).await?;
+        let private_addr = bind_internal_server(ctx).await?;
+        Ok::<_, IoError>((public_addr, private_addr))
+    }
+    .await;
+
+    match addrs {
+        Ok((public_addr, private_addr)) => Ok(LocalSc {
+            public_addr,
+            private_addr,
+            shutdown,
+            storage,
+        }),
+        Err(err) => {
+            shutdown.notify();
+            Err(err)
+        }
+    }
+}
+
 mod proxy {
     use std::process;
     use tracing::info;
@@ -391,6 +391,7 @@ impl PartitonStatusExtension for PartitionStatus {
     fn merge(&mut self, other: Self) {
         self.resolution = other.resolution;
         self.size = other.size;
+        self.isr = other.isr;
         if let Some(old) = self.leader.merge(&other.leader) {
             self.replicas.push(old); // move old leader to replicas
         }
@@ -575,6 +576,18 @@ mod test {
         assert_eq!(target.replicas[0], (5001, 9, 11).into());
     }
 
+    #[test]
+    fn test_merge_isr() {
+        let mut target = PartitionStatus::new((5000, 10, 11), vec![(5001, 9, 11).into()]);
+        target.isr = vec![5000, 5001];
+
+        let mut source = PartitionStatus::new((5000, 12, 15), vec![(5001, 9, 11).into()]);
+        source.isr = vec![5000];
+        target.merge(source);
+
+        assert_eq!(target.isr(), &[5000]);
+    }
+
     #[test]
     fn test_merge_lrs_full() {
         let mut target = PartitionStatus::new(
@@ -11,6 +11,7 @@ use async_trait::async_trait;
 
 use fluvio_controlplane::message::SpuMsg;
 use fluvio_controlplane_metadata::spu::SpuSpec;
+use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_controlplane_metadata::spu::SpuStatus;
 use fluvio_controlplane_metadata::store::MetadataStoreObject;
 use fluvio_stream_model::core::MetadataItem;
@@ -50,6 +51,8 @@ where
 {
     async fn online_status(&self) -> HashSet<SpuId>;
 
+    async fn replica_online_status(&self, replica: &ReplicaKey) -> HashSet<SpuId>;
+
     async fn online_spu_count(&self) -> u32;
 
     async fn spu_used_for_replica(&self) -> usize;
@@ -99,6 +102,17 @@ where
         status
     }
 
+    // build hashmap of online spus which can serve replica
+    async fn replica_online_status(&self, replica: &ReplicaKey) -> HashSet<SpuId> {
+        let mut status = HashSet::new();
+        for (_, spu) in self.read().await.iter() {
+            if spu.status.is_online() && !spu.status.is_replica_offline(replica) {
+                status.insert(spu.spec.id);
+            }
+        }
+        status
+    }
+
     /// count online SPUs
     async fn online_spu_count(&self) -> u32 {
         self.read()
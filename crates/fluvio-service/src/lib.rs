@@ -1,5 +1,6 @@
 #[cfg(unix)]
 mod server;
+pub mod metrics;
 
 #[cfg(test)]
 pub mod test_request;
@@ -7,13 +8,24 @@ pub mod test_request;
 pub use self::server::*;
 pub use fluvio_protocol::codec::FluvioCodec;
 
+use fluvio_protocol::api::RequestHeader;
+use fluvio_socket::trace_context::set_remote_parent;
+
+/// span for handling request, child of client span if request carries trace context
+pub fn request_span(header: &RequestHeader, api: &str) -> tracing::Span {
+    let span = tracing::info_span!("request", api, client_id = %header.client_id());
+    set_remote_parent(&span, header.trace_context());
+    span
+}
+
 #[macro_export]
 macro_rules! call_service {
     ($req:expr,$handler:expr,$sink:expr,$msg:expr) => {{
         {
             let version = $req.header.api_version();
+            let span = $crate::request_span(&$req.header, $msg);
             tracing::debug!(api = $msg, "invoking handler");
-            let response = $handler.await?;
+            let response = tracing::Instrument::instrument($handler, span).await?;
             tracing::trace!("send back response: {:#?}", &response);
             // we do not fast return here because there could be incoming requests to read
             // even if the socket is closed to write.
@@ -0,0 +1,182 @@
+//!
+//! # Metrics endpoint
+//!
+//! Plain HTTP endpoint serving metrics in OpenMetrics text format on `GET /metrics`.
+//! Only what scrapers need is supported, connection is closed after each response.
+//!
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures_util::{AsyncReadExt, AsyncWriteExt, StreamExt};
+use tracing::{debug, error, info, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_future::net::{TcpListener, TcpStream};
+use fluvio_future::task::spawn;
+use fluvio_types::openmetrics::{MetricsEncoder, OPENMETRICS_CONTENT_TYPE};
+
+/// max size of request head
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+pub const METRICS_PATH: &str = "/metrics";
+
+/// Source of metrics exposed by endpoint
+#[async_trait]
+pub trait MetricsCollector: Send + Sync + 'static {
+    async fn collect(&self, encoder: &mut MetricsEncoder);
+}
+
+/// start metrics endpoint in background
+pub fn start_metrics_server<M: MetricsCollector>(addr: String, collector: M) {
+    spawn(async move {
+        if let Err(err) = metrics_server_loop(&addr, Arc::new(collector)).await {
+            error!(%addr, %err, "metrics endpoint terminated");
+        }
+    });
+}
+
+async fn metrics_server_loop<M: MetricsCollector>(addr: &str, collector: Arc<M>) -> Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    info!(%addr, "metrics endpoint started");
+
+    let mut incoming = listener.incoming();
+    while let Some(stream) = incoming.next().await {
+        match stream {
+            Ok(stream) => {
+                let collector = collector.clone();
+                spawn(async move {
+                    if let Err(err) = handle_connection(stream, collector).await {
+                        debug!(%err, "metrics request failed");
+                    }
+                });
+            }
+            Err(err) => {
+                error!(%err, "error accepting metrics connection");
+            }
+        }
+    }
+    Ok(())
+}
+
+#[instrument(skip(stream, collector))]
+async fn handle_connection<M: MetricsCollector>(
+    mut stream: TcpStream,
+    collector: Arc<M>,
+) -> Result<()> {
+    let head = read_request_head(&mut stream).await?;
+
+    let response = match parse_request_line(&head) {
+        Some(("GET", path)) if path == METRICS_PATH || path.starts_with("/metrics?") => {
+            let mut encoder = MetricsEncoder::new();
+            collector.collect(&mut encoder).await;
+            http_response("200 OK", OPENMETRICS_CONTENT_TYPE, &encoder.finish())
+        }
+        Some(("GET", _)) => http_response("404 Not Found", "text/plain", "not found\n"),
+        Some(_) => http_response(
+            "405 Method Not Allowed",
+            "text/plain",
+            "method not allowed\n",
+        ),
+        None => http_response("400 Bad Request", "text/plain", "bad request\n"),
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+/// read until end of request head, body is never expected
+async fn read_request_head(stream: &mut TcpStream) -> Result<String> {
+    let mut head = Vec::with_capacity(512);
+    let mut buf = [0u8; 512];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..read]);
+        if head.len() > MAX_REQUEST_HEAD {
+            return Err(anyhow!("request head too large"));
+        }
+    }
+    Ok(String::from_utf8_lossy(&head).into_owned())
+}
+
+/// method and path of request
+fn parse_request_line(head: &str) -> Option<(&str, &str)> {
+    let mut parts = head.lines().next()?.split_whitespace();
+    let method = parts.next()?;
+    let path = parts.next()?;
+    parts.next()?.starts_with("HTTP/").then_some((method, path))
+}
+
+fn http_response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use futures_util::{AsyncReadExt, AsyncWriteExt};
+
+    use fluvio_future::net::TcpStream;
+    use fluvio_future::timer::sleep;
+    use fluvio_types::openmetrics::{MetricType, MetricsEncoder};
+
+    use super::*;
+
+    struct TestCollector;
+
+    #[async_trait]
+    impl MetricsCollector for TestCollector {
+        async fn collect(&self, encoder: &mut MetricsEncoder) {
+            encoder
+                .family("test_requests", MetricType::Counter, "test requests")
+                .sample(&[], 1);
+        }
+    }
+
+    async fn get(addr: &str, path: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.expect("connect");
+        stream
+            .write_all(format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+            .await
+            .expect("write");
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.expect("read");
+        response
+    }
+
+    #[test]
+    fn test_parse_request_line() {
+        assert_eq!(
+            parse_request_line("GET /metrics HTTP/1.1\r\nHost: a\r\n\r\n"),
+            Some(("GET", "/metrics"))
+        );
+        assert_eq!(parse_request_line("GET /metrics\r\n"), None);
+        assert_eq!(parse_request_line(""), None);
+    }
+
+    #[fluvio_future::test]
+    async fn test_metrics_endpoint() {
+        let port = portpicker::pick_unused_port().expect("No free ports left");
+        let addr = format!("127.0.0.1:{port}");
+        start_metrics_server(addr.clone(), TestCollector);
+        sleep(Duration::from_millis(100)).await;
+
+        let response = get(&addr, "/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(OPENMETRICS_CONTENT_TYPE));
+        assert!(response.ends_with("test_requests_total 1\n# EOF\n"));
+
+        let response = get(&addr, "/other").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+}
@@ -1,6 +1,8 @@
 use std::fmt;
 use std::fmt::Debug;
+use std::io::Error as IoError;
 use std::marker::PhantomData;
+use std::net::SocketAddr;
 use std::process;
 use std::sync::Arc;
 use std::os::unix::io::AsRawFd;
@@ -83,21 +85,37 @@ where
 {
     pub fn run(self) -> Arc<StickyEvent> {
         let shutdown = StickyEvent::shared();
-        spawn(self.accept_incoming(shutdown.clone()));
+        self.run_until(shutdown.clone());
         shutdown
     }
 
-    #[instrument(skip(shutdown))]
-    async fn accept_incoming(self, shutdown: Arc<StickyEvent>) {
-        debug!("Binding TcpListener");
-        let listener = match TcpListener::bind(&self.addr).await {
-            Ok(listener) => listener,
-            Err(err) => {
-                error!("Error binding TcpListener: {}", err);
-                process::exit(-1);
+    /// accept connections until shutdown is notified, process exits if address can't be bound
+    pub fn run_until(self, shutdown: Arc<StickyEvent>) {
+        spawn(async move {
+            debug!("Binding TcpListener");
+            match TcpListener::bind(&self.addr).await {
+                Ok(listener) => self.accept_incoming(listener, shutdown).await,
+                Err(err) => {
+                    error!("Error binding TcpListener: {}", err);
+                    process::exit(-1);
+                }
             }
-        };
+        });
+    }
+
+    /// bind address and accept connections until shutdown is notified.
+    /// Returns bound address, so port 0 can be used to bind an ephemeral port.
+    pub async fn bind_until(mut self, shutdown: Arc<StickyEvent>) -> Result<SocketAddr, IoError> {
+        let listener = TcpListener::bind(&self.addr).await?;
+        let addr = listener.local_addr()?;
+        self.addr = addr.to_string();
+        spawn(self.accept_incoming(listener, shutdown));
+        Ok(addr)
+    }
 
+    /// connection handlers are dropped as well when shutdown is notified
+    #[instrument(skip(listener, shutdown))]
+    async fn accept_incoming(self, listener: TcpListener, shutdown: Arc<StickyEvent>) {
         info!("Opened TcpListener, waiting for connections");
         let mut incoming = listener.incoming().take_until(shutdown.listen_pinned());
 
@@ -109,7 +127,10 @@ where
                     let context = self.context.clone();
                     let service = self.service.clone();
                     let host = self.addr.clone();
-                    spawn(Self::handle_request(stream, context, service, host));
+                    spawn(
+                        shutdown
+                            .until_notified(Self::handle_request(stream, context, service, host)),
+                    );
                 }
                 Err(e) => {
                     error!("Error from TCP Stream: {:?}", e);
@@ -247,4 +268,25 @@ mod test {
         assert_eq!(service.processed_requests.load(Ordering::SeqCst), 4);
         shutdown.notify();
     }
+
+    #[fluvio_future::test(ignore)]
+    async fn test_server_bind_ephemeral_port() {
+        let shutdown = StickyEvent::shared();
+        let addr = create_server("127.0.0.1:0".to_owned())
+            .bind_until(shutdown.clone())
+            .await
+            .expect("bind");
+        assert_ne!(addr.port(), 0);
+
+        test_client_sync_requests(addr.to_string()).await;
+
+        // address in use is returned instead of exiting
+        assert!(
+            create_server(addr.to_string())
+                .bind_until(shutdown.clone())
+                .await
+                .is_err()
+        );
+        shutdown.notify();
+    }
 }
@@ -5,6 +5,7 @@ mod socket;
 mod stream;
 mod versioned;
 mod stream_socket;
+pub mod trace_context;
 
 #[cfg(test)]
 pub mod test_request;
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use fluvio_protocol::api::{Request, RequestMessage};
+use crate::trace_context::current_trace_context;
 use crate::{
     AsyncResponse, ClientConfig, SharedMultiplexerSocket, SocketError, VersionedSerialSocket,
     Versions,
@@ -51,6 +52,9 @@ impl StreamSocket {
         req_msg
             .header
             .set_client_id(self.config.client_id().to_owned());
+        if self.versions.accepts_trace_context() {
+            req_msg.header.set_trace_context(current_trace_context());
+        }
         self.socket
             .create_stream(req_msg, DEFAULT_STREAM_QUEUE_SIZE)
             .await
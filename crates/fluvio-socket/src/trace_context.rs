@@ -0,0 +1,106 @@
+//!
+//! # Trace context propagation
+//!
+//! Carries W3C trace context of current span in request header, so spans of client
+//! and servers handling request are part of same trace.
+//! Without `otel` feature, nothing is propagated.
+//! Exporting spans is left to binaries.
+//!
+
+use tracing::Span;
+
+use fluvio_protocol::api::TraceContext;
+
+/// trace context of current span, if span is recorded by OpenTelemetry
+pub fn current_trace_context() -> Option<TraceContext> {
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "otel")] {
+            otel::current_trace_context()
+        } else {
+            None
+        }
+    }
+}
+
+/// make span child of remote span which sent request
+#[allow(unused_variables)]
+pub fn set_remote_parent(span: &Span, trace_context: Option<&TraceContext>) {
+    #[cfg(feature = "otel")]
+    if let Some(trace_context) = trace_context {
+        otel::set_remote_parent(span, trace_context);
+    }
+}
+
+#[cfg(feature = "otel")]
+mod otel {
+
+    use std::collections::HashMap;
+
+    use opentelemetry::propagation::TextMapPropagator;
+    use opentelemetry_sdk::propagation::TraceContextPropagator;
+    use tracing::Span;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    use fluvio_protocol::api::TraceContext;
+
+    const TRACEPARENT: &str = "traceparent";
+    const TRACESTATE: &str = "tracestate";
+
+    pub(super) fn current_trace_context() -> Option<TraceContext> {
+        let mut carrier = HashMap::new();
+        TraceContextPropagator::new().inject_context(&Span::current().context(), &mut carrier);
+        let traceparent = carrier.remove(TRACEPARENT)?;
+        let tracestate = carrier.remove(TRACESTATE).unwrap_or_default();
+        Some(TraceContext::new(traceparent, tracestate))
+    }
+
+    pub(super) fn set_remote_parent(span: &Span, trace_context: &TraceContext) {
+        let mut carrier = HashMap::new();
+        carrier.insert(
+            TRACEPARENT.to_owned(),
+            trace_context.traceparent().to_owned(),
+        );
+        if !trace_context.tracestate().is_empty() {
+            carrier.insert(TRACESTATE.to_owned(), trace_context.tracestate().to_owned());
+        }
+        span.set_parent(TraceContextPropagator::new().extract(&carrier));
+    }
+}
+
+#[cfg(all(test, feature = "otel"))]
+mod test {
+
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use fluvio_protocol::api::TraceContext;
+
+    use super::{current_trace_context, set_remote_parent};
+
+    #[test]
+    fn test_propagate_trace_context() {
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let remote = TraceContext::new(
+                "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+                "",
+            );
+            let span = tracing::info_span!("request");
+            set_remote_parent(&span, Some(&remote));
+            let _enter = span.enter();
+
+            let propagated = current_trace_context().expect("trace context");
+            // same trace, but span of this process
+            assert!(
+                propagated
+                    .traceparent()
+                    .starts_with("00-0af7651916cd43dd8448eb211c80319c-")
+            );
+            assert_ne!(propagated.traceparent(), remote.traceparent());
+        });
+    }
+}
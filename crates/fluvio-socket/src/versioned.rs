@@ -10,11 +10,14 @@ use tracing::{debug, instrument, info};
 
 use fluvio_protocol::api::RequestMessage;
 use fluvio_protocol::api::Request;
-use fluvio_protocol::link::versions::{ApiVersions, ApiVersionsRequest, ApiVersionsResponse};
+use fluvio_protocol::link::versions::{
+    ApiVersions, ApiVersionsRequest, ApiVersionsResponse, TRACE_CONTEXT_VERSION, VERSIONS_API_KEY,
+};
 use fluvio_future::net::{DomainConnector, DefaultDomainConnector};
 use fluvio_future::retry::retry_if;
 
 use crate::{SocketError, FluvioSocket, SharedMultiplexerSocket, AsyncResponse};
+use crate::trace_context::current_trace_context;
 
 /// Frame with request and response
 pub trait SerialFrame: Display {
@@ -212,6 +215,14 @@ impl Versions {
         &self.platform_version
     }
 
+    /// Peer reads trace context following request, older peers may reject it
+    pub fn accepts_trace_context(&self) -> bool {
+        self.api_versions.iter().any(|version| {
+            version.api_key == VERSIONS_API_KEY as i16
+                && version.max_version >= TRACE_CONTEXT_VERSION
+        })
+    }
+
     /// Given an API key, it returns maximum compatible version. None if not found
     pub fn lookup_version<R: Request>(&self) -> Option<i16> {
         for version in &self.api_versions {
@@ -360,6 +371,11 @@ impl VersionedSerialSocket {
         if let Some(ver) = version {
             req_msg.get_mut_header().set_api_version(ver);
         }
+        if self.versions.accepts_trace_context() {
+            req_msg
+                .get_mut_header()
+                .set_trace_context(current_trace_context());
+        }
         req_msg
     }
 }
@@ -438,4 +454,24 @@ mod test {
         assert_eq!(versions.lookup_version::<T1>(), Some(9));
         assert_eq!(versions.lookup_version::<T2>(), None);
     }
+
+    #[test]
+    fn test_accepts_trace_context() {
+        use fluvio_protocol::link::versions::{TRACE_CONTEXT_VERSION, VERSIONS_API_KEY};
+
+        let versions_with = |max_version| {
+            let mut response = ApiVersionsResponse::default();
+            response.api_keys.push(ApiVersionKey {
+                api_key: VERSIONS_API_KEY as i16,
+                min_version: 0,
+                max_version,
+            });
+            Versions::new(response)
+        };
+
+        // peer which doesn't list api versions predates trace context
+        assert!(!Versions::new(ApiVersionsResponse::default()).accepts_trace_context());
+        assert!(!versions_with(TRACE_CONTEXT_VERSION - 1).accepts_trace_context());
+        assert!(versions_with(TRACE_CONTEXT_VERSION).accepts_trace_context());
+    }
 }
@@ -1,6 +1,8 @@
 use std::fmt;
+use std::fs::File;
 use std::io::Error as IoError;
 use std::io::ErrorKind;
+use std::sync::Arc;
 
 use tracing::trace;
 use bytes::BufMut;
@@ -19,8 +21,14 @@ use crate::fetch::FetchRequest;
 
 pub type FileFetchRequest = FetchRequest<FileRecordSet>;
 
+/// Slice of records in a file which is sent using zero copy.
+/// Records which are not backed by segment file, such as decrypted records,
+/// keep their file in `owner` until they are sent.
 #[derive(Default, Debug)]
-pub struct FileRecordSet(AsyncFileSlice);
+pub struct FileRecordSet {
+    slice: AsyncFileSlice,
+    owner: Option<Arc<File>>,
+}
 
 impl fmt::Display for FileRecordSet {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -30,22 +38,34 @@ impl fmt::Display for FileRecordSet {
 
 #[allow(clippy::len_without_is_empty)]
 impl FileRecordSet {
+    /// slice of file which must be kept open as long as slice is used
+    pub fn with_owner(slice: AsyncFileSlice, owner: Arc<File>) -> Self {
+        Self {
+            slice,
+            owner: Some(owner),
+        }
+    }
+
     pub fn position(&self) -> u64 {
-        self.0.position()
+        self.slice.position()
     }
 
     pub fn len(&self) -> usize {
-        self.0.len() as usize
+        self.slice.len() as usize
     }
 
     pub fn raw_slice(&self) -> AsyncFileSlice {
-        self.0.clone()
+        self.slice.clone()
+    }
+
+    pub fn owner(&self) -> Option<&Arc<File>> {
+        self.owner.as_ref()
     }
 }
 
 impl From<AsyncFileSlice> for FileRecordSet {
     fn from(slice: AsyncFileSlice) -> Self {
-        Self(slice)
+        Self { slice, owner: None }
     }
 }
 
@@ -8,7 +8,6 @@ use std::fmt;
 use tracing::trace;
 
 use fluvio_protocol::bytes::Buf;
-use fluvio_protocol::Decoder;
 use fluvio_protocol::api::ApiMessage;
 use fluvio_protocol::api::api_decode;
 use fluvio_protocol::api::RequestHeader;
@@ -25,6 +24,7 @@ use super::consumer_offset::{
     UpdateConsumerOffsetRequest, DeleteConsumerOffsetRequest, FetchConsumerOffsetsRequest,
 };
 use super::update_offset::UpdateOffsetsRequest;
+use super::delete_records::DeleteRecordsRequest;
 use super::mirror::StartMirrorRequest;
 
 #[allow(clippy::large_enum_variant)]
@@ -41,6 +41,7 @@ pub enum SpuServerRequest {
     UpdateConsumerOffsetRequest(RequestMessage<UpdateConsumerOffsetRequest>),
     DeleteConsumerOffsetRequest(RequestMessage<DeleteConsumerOffsetRequest>),
     FetchConsumerOffsetsRequest(RequestMessage<FetchConsumerOffsetsRequest>),
+    DeleteRecordsRequest(RequestMessage<DeleteRecordsRequest>),
     StartMirrorRequest(RequestMessage<StartMirrorRequest>),
 }
 
@@ -56,6 +57,7 @@ impl fmt::Display for SpuServerRequest {
             Self::UpdateConsumerOffsetRequest(_) => write!(f, "UpdateConsumerOffsetRequest"),
             Self::DeleteConsumerOffsetRequest(_) => write!(f, "DeleteConsumerOffsetRequest"),
             Self::FetchConsumerOffsetsRequest(_) => write!(f, "FetchConsumerOffsetsRequest"),
+            Self::DeleteRecordsRequest(_) => write!(f, "DeleteRecordsRequest"),
             Self::StartMirrorRequest(_) => write!(f, "StartMirrorRequest"),
         }
     }
@@ -82,8 +84,9 @@ impl ApiMessage for SpuServerRequest {
             SpuServerApiKey::ApiVersion => api_decode!(Self, ApiVersionsRequest, src, header),
 
             SpuServerApiKey::Produce => {
-                let request = DefaultProduceRequest::decode_from(src, header.api_version())?;
-                Ok(Self::ProduceRequest(RequestMessage::new(header, request)))
+                Ok(Self::ProduceRequest(
+                    RequestMessage::<DefaultProduceRequest>::decode_with_header(src, header)?,
+                ))
             }
             SpuServerApiKey::Fetch => api_decode!(Self, FileFetchRequest, src, header),
             SpuServerApiKey::FetchOffsets => api_decode!(Self, FetchOffsetsRequest, src, header),
@@ -98,6 +101,7 @@ impl ApiMessage for SpuServerRequest {
             SpuServerApiKey::FetchConsumerOffsets => {
                 api_decode!(Self, FetchConsumerOffsetsRequest, src, header)
             }
+            SpuServerApiKey::DeleteRecords => api_decode!(Self, DeleteRecordsRequest, src, header),
             SpuServerApiKey::StartMirror => api_decode!(Self, StartMirrorRequest, src, header),
         }
     }
@@ -0,0 +1,41 @@
+//!
+//! # Delete Records
+//!
+//! API that allows admin to delete records before an offset in a partition.
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_types::PartitionId;
+
+use crate::COMMON_VERSION;
+use crate::errors::ErrorCode;
+use super::SpuServerApiKey;
+
+/// Delete records before offset, offset can't be greater than high watermark
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct DeleteRecordsRequest {
+    pub replica_id: ReplicaKey,
+    pub offset: Offset,
+}
+
+impl DeleteRecordsRequest {
+    pub fn new(topic: impl Into<String>, partition: PartitionId, offset: Offset) -> Self {
+        Self {
+            replica_id: ReplicaKey::new(topic, partition),
+            offset,
+        }
+    }
+}
+
+impl Request for DeleteRecordsRequest {
+    const API_KEY: u16 = SpuServerApiKey::DeleteRecords as u16;
+    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    type Response = DeleteRecordsResponse;
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+pub struct DeleteRecordsResponse {
+    pub error_code: ErrorCode,
+    /// log start offset after deletion
+    pub log_start_offset: Offset,
+}
@@ -9,12 +9,15 @@ use fluvio_protocol::{Encoder, Decoder};
 use fluvio_protocol::record::PartitionOffset;
 use fluvio_protocol::record::ReplicaKey;
 
-use fluvio_types::PartitionId;
+use fluvio_types::{PartitionId, Timestamp};
 
 use crate::COMMON_VERSION;
 use crate::errors::ErrorCode;
 use super::SpuServerApiKey;
 
+// version with log end offset and batch timestamps for consumer lag
+pub const CONSUMER_LAG_API: i16 = 28;
+
 // -----------------------------------
 // FlvFetchOffsetsRequest
 // -----------------------------------
@@ -45,6 +48,7 @@ impl FetchOffsetsRequest {
                 name: topic,
                 partitions: vec![FetchOffsetPartition {
                     partition_index: partition,
+                    ..Default::default()
                 }],
             }],
             ..Default::default()
@@ -65,6 +69,10 @@ pub struct FetchOffsetTopic {
 pub struct FetchOffsetPartition {
     /// The partition index.
     pub partition_index: PartitionId,
+
+    /// Also fetch timestamp of the batch which contains this offset
+    #[fluvio(min_version = 28)]
+    pub timestamp_offset: Option<i64>,
 }
 
 // -----------------------------------
@@ -115,6 +123,18 @@ pub struct FetchOffsetPartitionResponse {
 
     /// Last readable offset
     pub last_stable_offset: i64,
+
+    /// Log end offset, including records not committed yet
+    #[fluvio(min_version = 28)]
+    pub log_end_offset: i64,
+
+    /// Max timestamp of the last committed batch, if `timestamp_offset` was requested
+    #[fluvio(min_version = 28)]
+    pub last_timestamp: Option<Timestamp>,
+
+    /// First timestamp of the batch containing `timestamp_offset`
+    #[fluvio(min_version = 28)]
+    pub offset_timestamp: Option<Timestamp>,
 }
 
 impl fmt::Display for FetchOffsetPartitionResponse {
@@ -7,6 +7,7 @@ pub mod fetch_offset;
 pub mod stream_fetch;
 pub mod update_offset;
 pub mod consumer_offset;
+pub mod delete_records;
 pub mod mirror;
 
 pub use self::api_key::*;
@@ -54,6 +54,12 @@ pub const SMARTMODULE_TIMESTAMP: i16 = 22;
 
 pub const OFFSET_MANAGEMENT_API: i16 = 23;
 
+// version for quota throttle time in stream response
+pub const THROTTLE_TIME_API: i16 = 26;
+
+// version for reading committed records from follower replica
+pub const FOLLOWER_FETCH_API: i16 = 27;
+
 /// Fetch records continuously
 /// Output will be send back as stream
 #[allow(deprecated)]
@@ -87,6 +93,10 @@ pub struct StreamFetchRequest<R> {
     #[builder(default)]
     #[fluvio(min_version = 23)]
     pub consumer_id: Option<String>,
+    /// allow follower replica to serve committed records
+    #[builder(default)]
+    #[fluvio(min_version = 27)]
+    pub read_from_follower: bool,
     #[builder(setter(skip))]
     data: PhantomData<R>,
 }
@@ -120,6 +130,10 @@ pub struct StreamFetchResponse<R> {
     pub topic: String,
     pub stream_id: u32,
     pub partition: FetchablePartitionResponse<R>,
+    /// The duration in milliseconds consumer should wait before acknowledging these records
+    /// due to a quota violation, or zero if the stream did not violate any quota.
+    #[fluvio(min_version = 26)]
+    pub throttle_time_ms: i32,
 }
 
 #[cfg(feature = "file")]
@@ -154,6 +168,9 @@ mod file {
             self.topic.encode(src, version)?;
             self.stream_id.encode(src, version)?;
             self.partition.file_encode(src, data, version)?;
+            if version >= THROTTLE_TIME_API {
+                self.throttle_time_ms.encode(src, version)?;
+            }
             Ok(())
         }
     }
@@ -4,6 +4,7 @@
 //! Command line interface to provision SPU id and configure various
 //! system parameters.
 //!
+use std::path::PathBuf;
 use std::process;
 
 use anyhow::{anyhow, Result};
@@ -16,6 +17,8 @@ use fluvio_types::print_cli_err;
 use fluvio_types::SpuId;
 use fluvio_future::openssl::TlsAcceptor;
 use fluvio_types::defaults::SPU_PEER_MAX_BYTES;
+use fluvio_storage::encryption::{FileKeyProvider, SharedKeyProvider};
+use fluvio_auth::rbac::BasicRbacPolicy;
 
 use super::SpuConfig;
 
@@ -42,6 +45,10 @@ pub struct SpuOpt {
     #[arg(long, value_name = "dir", env = "FLV_LOG_BASE_DIR")]
     pub log_base_dir: Option<String>,
 
+    /// more data directories, comma separated. New replicas are placed in least used directory
+    #[arg(long, value_name = "dirs", env = "FLV_LOG_DIRS", value_delimiter = ',')]
+    pub log_dirs: Vec<PathBuf>,
+
     #[arg(long, value_name = "log size", env = "FLV_LOG_SIZE")]
     pub log_size: Option<String>,
 
@@ -51,6 +58,11 @@ pub struct SpuOpt {
     #[arg(long, value_name = "integer", env = "FLV_LOG_INDEX_MAX_INTERVAL_BYTES")]
     pub index_max_interval_bytes: Option<u32>,
 
+    /// file with segment encryption keys, one `<key id>:<hex key>` per line.
+    /// last key is used for new segments
+    #[arg(long, value_name = "path", env = "FLV_ENCRYPTION_KEY_FILE")]
+    pub encryption_key_file: Option<PathBuf>,
+
     /// max bytes to transfer between leader and follower
     #[arg(
         long,
@@ -60,6 +72,14 @@ pub struct SpuOpt {
     )]
     pub peer_max_bytes: u32,
 
+    /// max time in ms follower can be behind leader before it's removed from in-sync replicas
+    #[arg(long, value_name = "integer", env = "FLV_REPLICA_LAG_TIME_MAX_MS")]
+    pub replica_lag_time_max_ms: Option<u64>,
+
+    /// max records follower can be behind leader before it's removed from in-sync replicas
+    #[arg(long, value_name = "integer", env = "FLV_REPLICA_LAG_MAX_RECORDS")]
+    pub replica_lag_max_records: Option<i64>,
+
     #[arg(
         long,
         value_name = "integer",
@@ -67,8 +87,31 @@ pub struct SpuOpt {
     )]
     pub smart_engine_max_memory: Option<usize>,
 
+    /// address of OpenMetrics endpoint, disabled if not set
+    #[arg(
+        long = "metrics-addr",
+        value_name = "host:port",
+        env = "FLV_METRICS_ADDR"
+    )]
+    pub bind_metrics: Option<String>,
+
     #[clap(flatten)]
     tls: TlsConfig,
+
+    #[arg(
+        long = "authorization-scopes",
+        value_name = "authorization scopes path",
+        env
+    )]
+    x509_auth_scopes: Option<PathBuf>,
+
+    /// same policy as the SC, produce and consume of topics are checked against it
+    #[arg(
+        long = "authorization-policy",
+        value_name = "authorization policy path",
+        env
+    )]
+    auth_policy: Option<PathBuf>,
 }
 
 impl SpuOpt {
@@ -82,8 +125,6 @@ impl SpuOpt {
 
     #[allow(clippy::wrong_self_convention)]
     fn as_spu_config(self) -> Result<(SpuConfig, Option<String>)> {
-        use std::path::PathBuf;
-
         let mut config = SpuConfig {
             id: match self.id {
                 Some(id) => id,
@@ -105,6 +146,11 @@ impl SpuOpt {
             config.log.base_dir = PathBuf::from(log_base);
         }
 
+        if !self.log_dirs.is_empty() {
+            info!("using additional log dirs: {:?}", self.log_dirs);
+            config.log.additional_dirs = self.log_dirs;
+        }
+
         if let Some(log_size) = self.log_size {
             info!("overriding log size {}", log_size);
             config.log.size = log_size;
@@ -120,6 +166,12 @@ impl SpuOpt {
             config.log.index_max_interval_bytes = index_max_interval_bytes;
         }
 
+        if let Some(key_file) = self.encryption_key_file {
+            info!("encrypting segments with keys from: {}", key_file.display());
+            let provider = FileKeyProvider::open(key_file)?;
+            config.log.key_provider = Some(SharedKeyProvider::new(provider));
+        }
+
         if let Some(public_addr) = self.bind_public {
             info!("overriding public addr: {}", public_addr);
             config.public_endpoint = public_addr;
@@ -144,6 +196,16 @@ impl SpuOpt {
 
         config.peer_max_bytes = self.peer_max_bytes;
 
+        if let Some(lag_time_max_ms) = self.replica_lag_time_max_ms {
+            info!("overriding replica lag time max ms: {}", lag_time_max_ms);
+            config.replication.lag_time_max_ms = lag_time_max_ms;
+        }
+
+        if let Some(lag_max_records) = self.replica_lag_max_records {
+            info!("overriding replica lag max records: {}", lag_max_records);
+            config.replication.lag_max_records = lag_max_records;
+        }
+
         if let Some(smart_engine_max_memory) = self.smart_engine_max_memory {
             info!(
                 "overriding smart engine max memory: {}",
@@ -152,6 +214,20 @@ impl SpuOpt {
             config.smart_engine.store_max_memory = smart_engine_max_memory;
         }
 
+        if let Some(metrics_addr) = self.bind_metrics {
+            info!("using metrics addr: {}", metrics_addr);
+            config.metrics_endpoint = Some(metrics_addr);
+        }
+
+        config.x509_auth_scopes = self.x509_auth_scopes;
+
+        if let Some(policy_path) = self.auth_policy {
+            // fail early on invalid policy, it's loaded again when services start
+            BasicRbacPolicy::try_from(policy_path.clone())?;
+            info!("using authorization policy: {}", policy_path.display());
+            config.auth_policy = Some(policy_path);
+        }
+
         Ok((config, tls_port))
     }
 
@@ -26,23 +26,32 @@ use fluvio_types::defaults::SPU_SMARTENGINE_STORE_MAX_BYTES;
 // environment variables
 
 use fluvio_types::defaults::SPU_MIN_IN_SYNC_REPLICAS;
+use fluvio_types::defaults::{SPU_REPLICA_LAG_MAX_RECORDS, SPU_REPLICA_LAG_TIME_MAX_MS};
 use fluvio_types::defaults::FLV_LOG_BASE_DIR;
 use fluvio_types::defaults::FLV_LOG_SIZE;
 use fluvio_types::SpuId;
 use fluvio_storage::config::ReplicaConfig;
+use fluvio_storage::encryption::SharedKeyProvider;
 use fluvio_types::defaults::{
     STORAGE_FLUSH_IDLE_MSEC, STORAGE_FLUSH_WRITE_COUNT, STORAGE_MAX_BATCH_SIZE,
 };
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct ReplicationConfig {
+    /// used when topic doesn't set its own minimum
     pub min_in_sync_replicas: u16,
+    /// follower is removed from in-sync replicas if it has not caught up to leader for this long
+    pub lag_time_max_ms: u64,
+    /// follower is removed from in-sync replicas if it is behind leader by more records than this
+    pub lag_max_records: i64,
 }
 
 impl Default for ReplicationConfig {
     fn default() -> Self {
         Self {
             min_in_sync_replicas: SPU_MIN_IN_SYNC_REPLICAS,
+            lag_time_max_ms: SPU_REPLICA_LAG_TIME_MAX_MS,
+            lag_max_records: SPU_REPLICA_LAG_MAX_RECORDS,
         }
     }
 }
@@ -50,6 +59,8 @@ impl Default for ReplicationConfig {
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Log {
     pub base_dir: PathBuf,
+    /// more data directories, replicas are spread over base and these directories
+    pub additional_dirs: Vec<PathBuf>,
     pub size: String,
     pub index_max_bytes: u32,
     pub index_max_interval_bytes: u32,
@@ -57,6 +68,8 @@ pub struct Log {
     pub flush_write_count: u32,
     pub flush_idle_msec: u32,
     pub max_batch_size: u32,
+    /// encrypt new segments with keys from this provider
+    pub key_provider: Option<SharedKeyProvider>,
 }
 
 impl Default for Log {
@@ -65,6 +78,7 @@ impl Default for Log {
             base_dir: PathBuf::from(
                 env::var(FLV_LOG_BASE_DIR).unwrap_or_else(|_| SPU_LOG_BASE_DIR.to_owned()),
             ),
+            additional_dirs: vec![],
             size: env::var(FLV_LOG_SIZE).unwrap_or_else(|_| SPU_LOG_SIZE.to_owned()),
             index_max_bytes: SPU_LOG_INDEX_MAX_BYTES,
             index_max_interval_bytes: SPU_LOG_INDEX_MAX_INTERVAL_BYTES,
@@ -72,6 +86,7 @@ impl Default for Log {
             flush_write_count: STORAGE_FLUSH_WRITE_COUNT,
             flush_idle_msec: STORAGE_FLUSH_IDLE_MSEC,
             max_batch_size: STORAGE_MAX_BATCH_SIZE,
+            key_provider: None,
         }
     }
 }
@@ -111,6 +126,14 @@ pub struct SpuConfig {
     pub peer_max_bytes: u32,
 
     pub smart_engine: SmartEngineConfig,
+
+    // openmetrics endpoint, disabled if not set
+    pub metrics_endpoint: Option<String>,
+
+    // scopes of x509 identities, forwarded by tls proxy
+    pub x509_auth_scopes: Option<PathBuf>,
+    // produce and consume are checked against this policy, root authorization if not set
+    pub auth_policy: Option<PathBuf>,
 }
 
 impl Default for SpuConfig {
@@ -126,6 +149,9 @@ impl Default for SpuConfig {
             log: Log::default(),
             peer_max_bytes: fluvio_storage::FileReplica::PREFER_MAX_LEN,
             smart_engine: SmartEngineConfig::default(),
+            metrics_endpoint: None,
+            x509_auth_scopes: None,
+            auth_policy: None,
         }
     }
 }
@@ -161,20 +187,32 @@ impl SpuConfig {
     pub fn storage(&self) -> &Log {
         &self.log
     }
+
+    /// directories where replicas of this SPU are stored, first one is default
+    pub fn data_dirs(&self) -> Vec<PathBuf> {
+        std::iter::once(&self.log.base_dir)
+            .chain(self.log.additional_dirs.iter())
+            .map(|dir| dir.join(format!("spu-logs-{}", self.id)))
+            .collect()
+    }
 }
 
 impl From<&SpuConfig> for ReplicaConfig {
     fn from(config: &SpuConfig) -> Self {
         let log = &config.log;
-        ReplicaConfig::builder()
+        let mut builder = ReplicaConfig::builder();
+        builder
             .base_dir(log.base_dir.join(format!("spu-logs-{}", config.id)))
             .index_max_bytes(log.index_max_bytes)
             .index_max_interval_bytes(log.index_max_interval_bytes)
             .segment_max_bytes(log.segment_max_bytes)
             .flush_write_count(log.flush_write_count)
             .flush_idle_msec(log.flush_idle_msec)
-            .max_batch_size(log.max_batch_size)
-            .build()
+            .max_batch_size(log.max_batch_size);
+        if let Some(key_provider) = &log.key_provider {
+            builder.key_provider(key_provider.clone());
+        }
+        builder.build()
     }
 }
 
@@ -22,12 +22,15 @@ use fluvio_socket::{FluvioSocket, FluvioSink};
 use fluvio_storage::FileReplica;
 use fluvio_controlplane::sc_api::update_mirror::UpdateMirrorStatRequest;
 use fluvio_controlplane::spu_api::update_mirror::UpdateMirrorRequest;
+use fluvio_controlplane::spu_api::update_quota::UpdateQuotaRequest;
+use fluvio_controlplane::spu_api::update_dictionary::UpdateDictionaryRequest;
 use fluvio_controlplane::sc_api::update_partition::UpdatePartitionStatRequest;
 
 use crate::core::SharedGlobalContext;
+use crate::core::dictionary::register_changes;
 
 use super::message_sink::SharedLrsStatusUpdate;
-use super::{SharedMirrorStatusUpdate, SharedPartitionStatusUpdate};
+use super::{SharedMirrorStatusUpdate, SharedPartitionStatusUpdate, SharedStorageStatusUpdate};
 
 // keep track of various internal state of dispatcher
 #[derive(Default)]
@@ -37,6 +40,8 @@ struct DispatcherCounter {
     pub reconnect: u64,       // number of reconnect to sc
     pub smartmodule: u64,     // number of sm updates from sc
     pub mirror: u64,          // number of mirror updates from sc
+    pub quota: u64,           // number of quota updates from sc
+    pub dictionary: u64,      // number of dictionary updates from sc
 }
 
 /// Controller for handling connection to SC
@@ -46,6 +51,7 @@ pub struct ScDispatcher<S> {
     lrs_status_update: SharedLrsStatusUpdate,
     mirror_status_update: SharedMirrorStatusUpdate,
     partition_status_update: SharedPartitionStatusUpdate,
+    storage_status_update: SharedStorageStatusUpdate,
     counter: DispatcherCounter,
 }
 
@@ -55,6 +61,7 @@ impl ScDispatcher<FileReplica> {
             lrs_status_update: ctx.status_update_owned(),
             mirror_status_update: ctx.mirror_status_update_owned(),
             partition_status_update: ctx.partition_status_update_owned(),
+            storage_status_update: ctx.storage_status_update_owned(),
             ctx,
             counter: DispatcherCounter::default(),
         }
@@ -62,7 +69,8 @@ impl ScDispatcher<FileReplica> {
 
     /// start the controller with ctx and receiver
     pub fn run(self) {
-        spawn(self.dispatch_loop());
+        let shutdown = self.ctx.shutdown().clone();
+        spawn(shutdown.until_notified(self.dispatch_loop()));
     }
 
     async fn dispatch_loop(mut self) {
@@ -144,6 +152,7 @@ impl ScDispatcher<FileReplica> {
                     self.send_lrs_status_back_to_sc(&mut sink).await?;
                     self.send_partition_status_back_to_sc(&mut sink).await?;
                     self.send_mirror_status_back_to_sc(&mut sink).await?;
+                    self.send_storage_status_back_to_sc(&mut sink).await?;
                 },
 
                 sc_request = api_stream.next() => {
@@ -174,6 +183,14 @@ impl ScDispatcher<FileReplica> {
                                 break;
                             }
                         },
+                        Some(Ok(InternalSpuRequest::UpdateQuotaRequest(request))) => {
+                            self.counter.quota += 1;
+                            self.handle_update_quota_request(request);
+                        },
+                        Some(Ok(InternalSpuRequest::UpdateDictionaryRequest(request))) => {
+                            self.counter.dictionary += 1;
+                            self.handle_update_dictionary_request(request);
+                        },
                         Some(Err(err)) => {
                             error!(%err, "Api error");
                             break;
@@ -226,6 +243,22 @@ impl ScDispatcher<FileReplica> {
         .await
     }
 
+    /// send storage status back to sc
+    #[instrument(skip(self))]
+    async fn send_storage_status_back_to_sc(&mut self, sc_sink: &mut FluvioSink) -> Result<()> {
+        let Some(status) = self.storage_status_update.take().await else {
+            return Ok(());
+        };
+
+        let message = RequestMessage::new_request(status);
+        if let Err(err) = sc_sink.send_request(&message).await {
+            // resend on next connection
+            self.storage_status_update.send(message.request).await;
+            return Err(anyhow!("error sending storage status back to sc: {}", err));
+        }
+        Ok(())
+    }
+
     /// send status back to sc, if there is error return false
     async fn send_unique_status<T, U>(
         requests: Vec<T>,
@@ -462,4 +495,43 @@ impl ScDispatcher<FileReplica> {
 
         Ok(())
     }
+
+    ///
+    /// Handle client quotas sent by SC, quotas are always full set
+    ///
+    #[instrument(skip(self, req_msg), name = "update_quota_request")]
+    fn handle_update_quota_request(&mut self, req_msg: RequestMessage<UpdateQuotaRequest>) {
+        let (_, request) = req_msg.get_header_request();
+
+        debug!(
+            epoch = request.epoch,
+            item_count = request.all.len(),
+            "received client quotas"
+        );
+        trace!("received client quotas: {:#?}", request.all);
+        self.ctx.quotas().sync_all(request.all);
+    }
+
+    ///
+    /// Handle zstd dictionaries sent by SC
+    ///
+    #[instrument(skip(self, req_msg), name = "update_dictionary_request")]
+    fn handle_update_dictionary_request(
+        &mut self,
+        req_msg: RequestMessage<UpdateDictionaryRequest>,
+    ) {
+        let (_, request) = req_msg.get_header_request();
+
+        debug!(
+            epoch = request.epoch,
+            all = request.all.len(),
+            changes = request.changes.len(),
+            "received dictionary update"
+        );
+        let actions = self
+            .ctx
+            .dictionaries_localstore()
+            .apply(request.all, request.changes);
+        register_changes(actions);
+    }
 }
@@ -7,11 +7,13 @@ use async_lock::Mutex;
 use fluvio_controlplane::sc_api::update_lrs::LrsRequest;
 use fluvio_controlplane::sc_api::update_partition::PartitionStatRequest;
 use fluvio_controlplane::sc_api::update_mirror::MirrorStatRequest;
+use fluvio_controlplane::sc_api::update_storage::UpdateStorageStatRequest;
 use fluvio_controlplane_metadata::mirror::{MirrorPairStatus, MirrorStatus};
 
 pub type SharedLrsStatusUpdate = Arc<StatusLrsMessageSink>;
 pub type SharedPartitionStatusUpdate = Arc<StatusPartitionMessageSink>;
 pub type SharedMirrorStatusUpdate = Arc<StatusMirrorMessageSink>;
+pub type SharedStorageStatusUpdate = Arc<StatusStorageMessageSink>;
 
 /// channel used to send message to sc
 #[derive(Debug)]
@@ -53,3 +55,22 @@ impl StatusMirrorMessageSink {
         Ok(())
     }
 }
+
+/// latest storage status to be sent to sc
+#[derive(Debug, Default)]
+pub struct StatusStorageMessageSink(Mutex<Option<UpdateStorageStatRequest>>);
+
+impl StatusStorageMessageSink {
+    pub fn shared() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// newer status overwrites previous if it has not been sent
+    pub async fn send(&self, status: UpdateStorageStatRequest) {
+        self.0.lock().await.replace(status);
+    }
+
+    pub async fn take(&self) -> Option<UpdateStorageStatRequest> {
+        self.0.lock().await.take()
+    }
+}
@@ -0,0 +1,188 @@
+//!
+//! # Data directories
+//!
+//! Tracks data directories of SPU and replicas which are offline because their directory failed.
+//!
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::select;
+use tracing::{debug, instrument, warn};
+
+use fluvio_controlplane::sc_api::update_storage::UpdateStorageStatRequest;
+use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_controlplane_metadata::spu::StorageDirStatus;
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_storage::dirs::DataDirs;
+use fluvio_types::event::offsets::OffsetPublisher;
+
+use super::DefaultSharedGlobalContext;
+
+/// how often directories are checked
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// how often change of usage alone is reported, every storage status update is
+/// written to SPU status, so reporting growing usage on every check would cause churn
+const USAGE_REPORT_INTERVAL: Duration = Duration::from_secs(300);
+
+#[derive(Debug)]
+pub struct SpuDataDirs {
+    dirs: DataDirs,
+    offline_replicas: Mutex<BTreeSet<ReplicaKey>>,
+    /// incremented whenever offline replicas change
+    changes: Arc<OffsetPublisher>,
+}
+
+impl SpuDataDirs {
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        Self {
+            dirs: DataDirs::new(paths),
+            offline_replicas: Mutex::new(BTreeSet::new()),
+            changes: OffsetPublisher::shared(0),
+        }
+    }
+
+    pub fn dirs(&self) -> &DataDirs {
+        &self.dirs
+    }
+
+    /// replica can't be served because its directory failed
+    pub fn set_replica_offline(&self, replica: ReplicaKey) {
+        warn!(%replica, "replica is offline");
+        if self.offline().insert(replica) {
+            self.changes.update_increment();
+        }
+    }
+
+    /// return true if replica was offline
+    pub fn set_replica_online(&self, replica: &ReplicaKey) -> bool {
+        let removed = self.offline().remove(replica);
+        if removed {
+            self.changes.update_increment();
+        }
+        removed
+    }
+
+    pub fn is_replica_offline(&self, replica: &ReplicaKey) -> bool {
+        self.offline().contains(replica)
+    }
+
+    /// forget replica which is removed from this SPU
+    pub fn remove_replica(&self, replica: &ReplicaKey) {
+        self.set_replica_online(replica);
+        self.dirs.remove(replica);
+    }
+
+    /// storage status to be reported to SC
+    pub async fn status(&self) -> UpdateStorageStatRequest {
+        let dirs = self
+            .dirs
+            .usage()
+            .await
+            .into_iter()
+            .map(|usage| StorageDirStatus {
+                path: usage.path.display().to_string(),
+                online: usage.online,
+                replicas: usage.replicas,
+                used_bytes: usage.used_bytes,
+            })
+            .collect();
+        let offline_replicas = self
+            .offline()
+            .iter()
+            .map(|replica| replica.to_string())
+            .collect();
+        UpdateStorageStatRequest::new(dirs, offline_replicas)
+    }
+
+    fn offline(&self) -> std::sync::MutexGuard<'_, BTreeSet<ReplicaKey>> {
+        self.offline_replicas
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Periodically checks data directories, takes replicas of failed directories offline
+/// and reports storage status to SC
+pub struct DataDirController {
+    ctx: DefaultSharedGlobalContext,
+}
+
+impl DataDirController {
+    pub fn run(ctx: DefaultSharedGlobalContext) {
+        let shutdown = ctx.shutdown().clone();
+        spawn(shutdown.until_notified(Self { ctx }.dispatch_loop()));
+    }
+
+    #[instrument(skip(self), name = "DataDirController")]
+    async fn dispatch_loop(self) {
+        let data_dirs = self.ctx.data_dirs();
+        debug!(dirs = ?data_dirs.dirs().paths(), "starting");
+        let mut changes = data_dirs.changes.change_listener();
+        let mut last_status: Option<UpdateStorageStatRequest> = None;
+        let mut last_report = Instant::now();
+        loop {
+            let failed = data_dirs.dirs().check().await;
+            if !failed.is_empty() {
+                self.ctx.take_data_dirs_offline(failed).await;
+            }
+
+            let status = data_dirs.status().await;
+            let report = match &last_status {
+                None => true,
+                Some(last) if !same_state(last, &status) => {
+                    debug!(%status, "storage status changed");
+                    true
+                }
+                Some(last) => *last != status && last_report.elapsed() >= USAGE_REPORT_INTERVAL,
+            };
+            if report {
+                self.ctx.storage_status_update().send(status.clone()).await;
+                last_status = Some(status);
+                last_report = Instant::now();
+            }
+
+            select! {
+                _ = sleep(CHECK_INTERVAL) => {},
+                _ = changes.listen() => {
+                    debug!("offline replicas changed");
+                }
+            }
+        }
+    }
+}
+
+/// status is same except for used bytes of directories
+fn same_state(last: &UpdateStorageStatRequest, status: &UpdateStorageStatRequest) -> bool {
+    last.offline_replicas == status.offline_replicas
+        && last.dirs.len() == status.dirs.len()
+        && last.dirs.iter().zip(&status.dirs).all(|(last, dir)| {
+            last.path == dir.path && last.online == dir.online && last.replicas == dir.replicas
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dir_status(online: bool, used_bytes: u64) -> UpdateStorageStatRequest {
+        UpdateStorageStatRequest::new(
+            vec![StorageDirStatus {
+                path: "/data/a".to_owned(),
+                online,
+                replicas: 2,
+                used_bytes,
+            }],
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_usage_is_not_state_change() {
+        assert!(same_state(&dir_status(true, 100), &dir_status(true, 200)));
+        assert!(!same_state(&dir_status(true, 100), &dir_status(false, 100)));
+    }
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use tracing::{debug, error};
+
+use fluvio_compression::{
+    CompressionError, DEFAULT_ZSTD_LEVEL, ZstdDictionary, lookup_dictionary, register_dictionary,
+    unregister_dictionary,
+};
+use fluvio_controlplane::spu_api::update_dictionary::Dictionary;
+use fluvio_controlplane_metadata::dictionary::DictionarySpec;
+use flv_util::actions::Actions;
+
+use crate::core::Spec;
+use crate::core::LocalStore;
+use crate::core::SpecChange;
+
+pub type DictionaryLocalStore = LocalStore<Dictionary>;
+
+pub type SharedDictionaryLocalStore = Arc<DictionaryLocalStore>;
+
+impl Spec for Dictionary {
+    const LABEL: &'static str = "Dictionary";
+
+    type Key = String;
+
+    fn key(&self) -> &Self::Key {
+        &self.name
+    }
+
+    fn key_owned(&self) -> Self::Key {
+        self.name.clone()
+    }
+}
+
+impl DictionaryLocalStore {
+    /// dictionary used by topic, if it is known to this SPU.
+    /// registered dictionary is preferred since it's already prepared
+    pub fn zstd_dictionary(&self, name: &str) -> Option<ZstdDictionary> {
+        let dictionary = self.spec(&name.to_owned())?;
+        lookup_dictionary(dictionary.spec.id).or_else(|| to_zstd_dictionary(&dictionary.spec).ok())
+    }
+}
+
+fn to_zstd_dictionary(spec: &DictionarySpec) -> Result<ZstdDictionary, CompressionError> {
+    Ok(ZstdDictionary::new(spec.data.to_vec())?
+        .with_level(spec.level.unwrap_or(DEFAULT_ZSTD_LEVEL)))
+}
+
+/// keep process wide dictionary registry in sync with store,
+/// so batches compressed with dictionaries can be decompressed by smartmodules and consumers
+pub fn register_changes(actions: Actions<SpecChange<Dictionary>>) {
+    for action in actions.into_iter() {
+        match action {
+            SpecChange::Add(dictionary) | SpecChange::Mod(dictionary, _) => {
+                match to_zstd_dictionary(&dictionary.spec) {
+                    Ok(zstd) => {
+                        debug!(name = %dictionary.name, id = zstd.id(), "register dictionary");
+                        register_dictionary(zstd);
+                    }
+                    Err(err) => {
+                        error!(name = %dictionary.name, %err, "invalid dictionary");
+                    }
+                }
+            }
+            SpecChange::Delete(dictionary) => {
+                debug!(name = %dictionary.name, "unregister dictionary");
+                unregister_dictionary(dictionary.spec.id);
+            }
+        }
+    }
+}
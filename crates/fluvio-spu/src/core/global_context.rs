@@ -9,6 +9,7 @@ use std::fmt::Debug;
 use tracing::{debug, error, instrument};
 
 use fluvio_types::SpuId;
+use fluvio_types::event::StickyEvent;
 use fluvio_storage::ReplicaStorage;
 
 use crate::config::SpuConfig;
@@ -16,6 +17,7 @@ use crate::control_plane::SharedMirrorStatusUpdate;
 use crate::control_plane::SharedPartitionStatusUpdate;
 use crate::control_plane::StatusMirrorMessageSink;
 use crate::control_plane::StatusPartitionMessageSink;
+use crate::control_plane::{SharedStorageStatusUpdate, StatusStorageMessageSink};
 use crate::kv::consumer::SharedConsumerOffsetStorages;
 use crate::replication::follower::FollowersState;
 use crate::replication::follower::SharedFollowersState;
@@ -24,9 +26,13 @@ use crate::replication::leader::{
 };
 use crate::control_plane::{StatusLrsMessageSink, SharedLrsStatusUpdate};
 use crate::core::metrics::SpuMetrics;
+use crate::core::quota::ClientQuotas;
 use crate::smartengine::SmartEngine;
 
+use super::data_dirs::SpuDataDirs;
 use super::leader_client::LeaderConnections;
+use super::dictionary::DictionaryLocalStore;
+use super::dictionary::SharedDictionaryLocalStore;
 use super::mirror::MirrorLocalStore;
 use super::mirror::SharedMirrorLocalStore;
 use super::smartmodule::SmartModuleLocalStore;
@@ -51,11 +57,16 @@ pub struct GlobalContext<S> {
     lrs_status_update: SharedLrsStatusUpdate,
     mirror_status_update: SharedMirrorStatusUpdate,
     partition_status_update: SharedPartitionStatusUpdate,
+    storage_status_update: SharedStorageStatusUpdate,
+    data_dirs: Arc<SpuDataDirs>,
     sm_engine: SmartEngine,
     leaders: Arc<LeaderConnections>,
     mirrors: SharedMirrorLocalStore,
+    dictionaries: SharedDictionaryLocalStore,
     metrics: Arc<SpuMetrics>,
+    quotas: Arc<ClientQuotas>,
     consumer_offset: SharedConsumerOffsetStorages,
+    shutdown: Arc<StickyEvent>,
 }
 
 // -----------------------------------
@@ -76,6 +87,7 @@ where
         let metrics = Arc::new(SpuMetrics::new());
 
         GlobalContext {
+            data_dirs: Arc::new(SpuDataDirs::new(spu_config.data_dirs())),
             spu_localstore: spus.clone(),
             replica_localstore: replicas.clone(),
             smartmodule_localstore: SmartModuleLocalStore::new_shared(),
@@ -86,11 +98,15 @@ where
             lrs_status_update: StatusLrsMessageSink::shared(),
             mirror_status_update: StatusMirrorMessageSink::shared(),
             partition_status_update: StatusPartitionMessageSink::shared(),
+            storage_status_update: StatusStorageMessageSink::shared(),
             sm_engine: SmartEngine::new(),
             leaders: LeaderConnections::shared(spus, replicas),
             mirrors: MirrorLocalStore::new_shared(),
+            dictionaries: DictionaryLocalStore::new_shared(),
+            quotas: Arc::new(ClientQuotas::new(metrics.clone())),
             metrics,
             consumer_offset: SharedConsumerOffsetStorages::default(),
+            shutdown: StickyEvent::shared(),
         }
     }
 
@@ -123,6 +139,10 @@ where
         self.mirrors.clone()
     }
 
+    pub fn dictionaries_localstore(&self) -> &DictionaryLocalStore {
+        &self.dictionaries
+    }
+
     pub fn leaders_state(&self) -> &ReplicaLeadersState<S> {
         &self.leaders_state
     }
@@ -139,6 +159,11 @@ where
         &self.config
     }
 
+    /// when notified, public and internal servers stop accepting connections
+    pub fn shutdown(&self) -> &Arc<StickyEvent> {
+        &self.shutdown
+    }
+
     pub fn config_owned(&self) -> SharedSpuConfig {
         self.config.clone()
     }
@@ -173,6 +198,18 @@ where
         self.partition_status_update.clone()
     }
 
+    pub fn storage_status_update(&self) -> &StatusStorageMessageSink {
+        &self.storage_status_update
+    }
+
+    pub fn storage_status_update_owned(&self) -> SharedStorageStatusUpdate {
+        self.storage_status_update.clone()
+    }
+
+    pub fn data_dirs(&self) -> &SpuDataDirs {
+        &self.data_dirs
+    }
+
     /// notify all follower handlers with SPU changes
     #[instrument(skip(self))]
     pub async fn sync_follower_update(&self) {
@@ -194,6 +231,10 @@ where
         self.metrics.clone()
     }
 
+    pub(crate) fn quotas(&self) -> Arc<ClientQuotas> {
+        self.quotas.clone()
+    }
+
     pub(crate) fn consumer_offset(&self) -> &SharedConsumerOffsetStorages {
         &self.consumer_offset
     }
@@ -201,13 +242,15 @@ where
 
 mod file_replica {
 
+    use std::path::PathBuf;
+
     use fluvio_controlplane::{
         sc_api::remove::ReplicaRemovedRequest, replica::Replica,
         spu_api::update_replica::UpdateReplicaRequest,
     };
     use tracing::{trace, warn};
 
-    use fluvio_storage::FileReplica;
+    use fluvio_storage::{FileReplica, StorageError};
     use flv_util::actions::Actions;
 
     use crate::core::SpecChange;
@@ -307,6 +350,7 @@ mod file_replica {
                             self.remove_replica(&mut outputs, new_replica).await;
                         } else if new_replica.leader == local_id {
                             // we are leader
+                            let replica_id = new_replica.id.clone();
                             if let Err(err) = self
                                 .leaders_state()
                                 .add_leader_replica(
@@ -316,6 +360,11 @@ mod file_replica {
                                 )
                                 .await
                             {
+                                if let Some(StorageError::DirOffline(_)) =
+                                    err.downcast_ref::<StorageError>()
+                                {
+                                    self.data_dirs().set_replica_offline(replica_id);
+                                }
                                 outputs.push(ReplicaChange::StorageError(err));
                             }
                         } else {
@@ -362,6 +411,8 @@ mod file_replica {
                                 }
                             } else if new_replica.leader == local_id {
                                 if self.leaders_state().get(&new_replica.id).await.is_some() {
+                                } else if self.data_dirs().is_replica_offline(&new_replica.id) {
+                                    debug!(replica = %new_replica.id, "leader replica is offline");
                                 } else {
                                     error!("leader controller was not found: {}", new_replica.id);
                                 }
@@ -377,6 +428,7 @@ mod file_replica {
         }
 
         async fn remove_replica(&self, outputs: &mut Vec<ReplicaChange>, replica: Replica) {
+            self.data_dirs().remove_replica(&replica.id);
             if replica.leader == self.local_spu_id() {
                 outputs.push(ReplicaChange::Remove(
                     self.remove_leader_replica(replica.clone()).await,
@@ -451,15 +503,50 @@ mod file_replica {
         pub async fn demote_replica(&self, replica: Replica) {
             if let Some(leader_replica_state) = self.leaders_state().remove(&replica.id).await {
                 drop(leader_replica_state);
-                if let Err(err) = self
-                    .followers_state_owned()
-                    .add_replica(self, replica)
-                    .await
-                {
-                    error!("demotion failed: {}", err);
-                }
+            } else if self.data_dirs().set_replica_online(&replica.id) {
+                // leader was elsewhere because our directory failed, start over as follower
+                debug!(replica = %replica.id, "offline replica becomes follower");
             } else {
-                error!("leader controller was not found: {}", replica.id)
+                error!("leader controller was not found: {}", replica.id);
+                return;
+            }
+            if let Err(err) = self
+                .followers_state_owned()
+                .add_replica(self, replica)
+                .await
+            {
+                error!("demotion failed: {}", err);
+            }
+        }
+
+        /// Take replicas in failed data directories offline.
+        /// Leader replicas are reported as offline so SC can elect other leader,
+        /// follower replicas start over in other directory and catch up from leader.
+        #[instrument(skip(self))]
+        pub async fn take_data_dirs_offline(&self, dirs: Vec<PathBuf>) {
+            for dir in dirs {
+                for replica_id in self.data_dirs().dirs().replicas_in(&dir) {
+                    if let Some(leader) = self.leaders_state().remove(&replica_id).await {
+                        drop(leader);
+                        self.data_dirs().set_replica_offline(replica_id);
+                    } else if let Some(replica) = self.replica_localstore().spec(&replica_id) {
+                        if let Some(follower) = self
+                            .followers_state()
+                            .remove_replica(replica.leader, &replica_id)
+                            .await
+                        {
+                            drop(follower);
+                        }
+                        if let Err(err) = self
+                            .followers_state_owned()
+                            .add_replica(self, replica)
+                            .await
+                        {
+                            error!(%replica_id, %err, "unable to relocate follower");
+                            self.data_dirs().set_replica_offline(replica_id);
+                        }
+                    }
+                }
             }
         }
 
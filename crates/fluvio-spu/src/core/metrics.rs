@@ -2,12 +2,14 @@ use std::{
     collections::HashMap,
     sync::{
         atomic::{AtomicU64, Ordering},
-        RwLock,
+        Arc, RwLock,
     },
     ops::AddAssign,
+    time::Duration,
 };
 
 use fluvio_protocol::record::Batch;
+use fluvio_types::openmetrics::Histogram;
 #[cfg(feature = "smartengine")]
 use fluvio_smartengine::metrics::SmartModuleChainMetrics;
 #[cfg(not(feature = "smartengine"))]
@@ -16,10 +18,18 @@ use crate::smartengine::SmartModuleChainMetrics;
 use fluvio_spu_schema::fetch::FilePartitionResponse;
 use serde::Serialize;
 
+use super::quota::QuotaKind;
+
 #[derive(Default, Debug, Serialize)]
 pub(crate) struct SpuMetrics {
     inbound: Activity,
     outbound: Activity,
+    quota: QuotaMetrics,
+    connections: AtomicU64,
+    #[serde(skip)]
+    produce_latency: Histogram,
+    #[serde(skip)]
+    fetch_latency: Histogram,
     #[serde(skip)] // Skip serializing the RwLock wrapper
     smartmodule_metrics: RwLock<HashMap<String, SmartModuleChainMetrics>>,
 }
@@ -29,6 +39,10 @@ impl SpuMetrics {
         Self {
             inbound: Activity::default(),
             outbound: Activity::default(),
+            quota: QuotaMetrics::default(),
+            connections: AtomicU64::new(0),
+            produce_latency: Histogram::latency(),
+            fetch_latency: Histogram::latency(),
             smartmodule_metrics: RwLock::new(HashMap::new()),
         }
     }
@@ -41,6 +55,31 @@ impl SpuMetrics {
         &self.outbound
     }
 
+    pub fn quota(&self) -> &QuotaMetrics {
+        &self.quota
+    }
+
+    /// number of open public connections
+    pub fn connections(&self) -> u64 {
+        self.connections.load(Ordering::SeqCst)
+    }
+
+    /// count connection as open until guard is dropped
+    pub(crate) fn connection_guard(self: &Arc<Self>) -> ConnectionGuard {
+        self.connections.fetch_add(1, Ordering::SeqCst);
+        ConnectionGuard(self.clone())
+    }
+
+    /// time to process produce request
+    pub fn produce_latency(&self) -> &Histogram {
+        &self.produce_latency
+    }
+
+    /// time to read and send back fetched records
+    pub fn fetch_latency(&self) -> &Histogram {
+        &self.fetch_latency
+    }
+
     pub fn smartmodule_metrics(&self) -> HashMap<String, SmartModuleChainMetrics> {
         // Return a copy of the metrics to avoid holding the lock
         self.smartmodule_metrics.read().unwrap().clone()
@@ -69,6 +108,14 @@ impl SpuMetrics {
     }
 }
 
+pub(crate) struct ConnectionGuard(Arc<SpuMetrics>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.connections.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 #[derive(Default, Debug, Serialize)]
 pub(crate) struct Record {
     records: AtomicU64,
@@ -88,6 +135,54 @@ pub struct Activity {
     client: Record,
 }
 
+/// number of times quota was exceeded and total time clients were throttled
+#[derive(Default, Debug, Serialize)]
+pub(crate) struct QuotaHits {
+    hits: AtomicU64,
+    throttle_time_ms: AtomicU64,
+}
+
+#[derive(Default, Debug, Serialize)]
+pub struct QuotaMetrics {
+    produce: QuotaHits,
+    fetch: QuotaHits,
+    requests: QuotaHits,
+}
+
+impl QuotaMetrics {
+    pub(crate) fn hit(&self, kind: QuotaKind, throttle: Duration) {
+        let hits = match kind {
+            QuotaKind::ProduceBytes => &self.produce,
+            QuotaKind::FetchBytes => &self.fetch,
+            QuotaKind::Requests => &self.requests,
+        };
+        hits.hits.fetch_add(1, Ordering::SeqCst);
+        hits.throttle_time_ms
+            .fetch_add(throttle.as_millis() as u64, Ordering::SeqCst);
+    }
+
+    // accessors below are read by metrics endpoint
+
+    pub fn produce_hits(&self) -> u64 {
+        self.produce.hits.load(Ordering::SeqCst)
+    }
+    pub fn fetch_hits(&self) -> u64 {
+        self.fetch.hits.load(Ordering::SeqCst)
+    }
+    pub fn requests_hits(&self) -> u64 {
+        self.requests.hits.load(Ordering::SeqCst)
+    }
+    pub fn produce_throttle_ms(&self) -> u64 {
+        self.produce.throttle_time_ms.load(Ordering::SeqCst)
+    }
+    pub fn fetch_throttle_ms(&self) -> u64 {
+        self.fetch.throttle_time_ms.load(Ordering::SeqCst)
+    }
+    pub fn requests_throttle_ms(&self) -> u64 {
+        self.requests.throttle_time_ms.load(Ordering::SeqCst)
+    }
+}
+
 #[derive(Default, Debug)]
 pub(crate) struct IncreaseValue {
     records: u64,
@@ -107,10 +202,9 @@ impl Activity {
         let IncreaseValue { records, bytes } = value;
         self.increase(connector, records, bytes)
     }
-}
 
-#[cfg(test)]
-impl Activity {
+    // accessors below are read by metrics endpoint
+
     pub fn connector_records(&self) -> u64 {
         self.connector.records.load(Ordering::SeqCst)
     }
@@ -129,6 +223,10 @@ impl IncreaseValue {
     pub(crate) fn new(records: u64, bytes: u64) -> Self {
         Self { records, bytes }
     }
+
+    pub(crate) fn bytes(&self) -> u64 {
+        self.bytes
+    }
 }
 
 // Measuring of serialized data. `bytes` is length of file slice, `records` is an offset's change
@@ -187,6 +285,20 @@ mod tests {
         assert_eq!(activity.connector.bytes.load(Ordering::SeqCst), 33); // 10 + 11 + 12
     }
 
+    #[test]
+    fn test_connection_guard() {
+        let metrics = Arc::new(SpuMetrics::new());
+
+        let first = metrics.connection_guard();
+        let second = metrics.connection_guard();
+        assert_eq!(metrics.connections(), 2);
+
+        drop(first);
+        assert_eq!(metrics.connections(), 1);
+        drop(second);
+        assert_eq!(metrics.connections(), 0);
+    }
+
     #[test]
     fn test_increase_from_file_partition_response() {
         //given
@@ -7,6 +7,9 @@ pub mod replica;
 pub mod smartmodule;
 pub mod metrics;
 pub mod mirror;
+pub mod dictionary;
+pub mod quota;
+pub mod data_dirs;
 
 pub use self::global_context::{GlobalContext, ReplicaChange};
 pub use self::store::Spec;
@@ -0,0 +1,290 @@
+//!
+//! # Client Quotas
+//!
+//! Rate limits per client pushed by SC. Usage of each client is tracked with token bucket
+//! which allows burst of one second worth of quota. Client which went over its quota
+//! is throttled for time needed to pay back the debt.
+//! Buckets refilled to full are same as new ones, so they are evicted to keep memory bounded
+//! when clients come and go.
+//!
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use tracing::{debug, info};
+
+use fluvio_controlplane::spu_api::update_quota::{ClientQuota, DEFAULT_QUOTA_CLIENT};
+
+use super::metrics::SpuMetrics;
+
+/// upper bound of throttle applied to single request
+pub(crate) const MAX_THROTTLE: Duration = Duration::from_secs(30);
+
+/// how often idle buckets are evicted
+const BUCKET_EVICT_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum QuotaKind {
+    ProduceBytes,
+    FetchBytes,
+    Requests,
+}
+
+impl QuotaKind {
+    /// limit of this kind, zero is same as unset
+    fn limit(&self, quota: &ClientQuota) -> Option<u64> {
+        let limit = match self {
+            Self::ProduceBytes => quota.produce_bytes_per_sec,
+            Self::FetchBytes => quota.fetch_bytes_per_sec,
+            Self::Requests => quota.requests_per_sec,
+        };
+        limit.filter(|limit| *limit > 0)
+    }
+}
+
+#[derive(Debug)]
+struct RateBucket {
+    rate: f64,
+    available: f64,
+    last_refill: Instant,
+}
+
+impl RateBucket {
+    fn new(rate: u64, now: Instant) -> Self {
+        Self {
+            rate: rate as f64,
+            available: rate as f64,
+            last_refill: now,
+        }
+    }
+
+    fn refilled(&self, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        (self.available + elapsed.as_secs_f64() * self.rate).min(self.rate)
+    }
+
+    /// bucket would be refilled to full, so it can be replaced by new one
+    fn is_full(&self, now: Instant) -> bool {
+        self.refilled(now) >= self.rate
+    }
+
+    /// take amount from bucket, return time until bucket is out of debt
+    fn take(&mut self, amount: u64, now: Instant) -> Duration {
+        self.available = self.refilled(now) - amount as f64;
+        self.last_refill = now;
+
+        if self.available >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.available / self.rate).min(MAX_THROTTLE)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RateBuckets {
+    buckets: HashMap<(String, QuotaKind), RateBucket>,
+    last_evict: Instant,
+}
+
+impl RateBuckets {
+    fn new(now: Instant) -> Self {
+        Self {
+            buckets: HashMap::new(),
+            last_evict: now,
+        }
+    }
+
+    /// drop buckets of clients which are within their quota for long enough
+    fn evict_idle(&mut self, now: Instant) {
+        if now.saturating_duration_since(self.last_evict) < BUCKET_EVICT_INTERVAL {
+            return;
+        }
+        self.last_evict = now;
+        self.buckets.retain(|_, bucket| !bucket.is_full(now));
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ClientQuotas {
+    quotas: RwLock<HashMap<String, ClientQuota>>,
+    buckets: Mutex<RateBuckets>,
+    metrics: Arc<SpuMetrics>,
+}
+
+impl ClientQuotas {
+    pub(crate) fn new(metrics: Arc<SpuMetrics>) -> Self {
+        Self {
+            quotas: RwLock::new(HashMap::new()),
+            buckets: Mutex::new(RateBuckets::new(Instant::now())),
+            metrics,
+        }
+    }
+
+    /// replace all quotas, usage tracked so far is reset
+    pub(crate) fn sync_all(&self, quotas: Vec<ClientQuota>) {
+        info!(quotas = quotas.len(), "updating client quotas");
+        let quotas = quotas
+            .into_iter()
+            .map(|quota| (quota.client.clone(), quota))
+            .collect();
+        *self.quotas.write().unwrap() = quotas;
+        self.buckets.lock().unwrap().buckets.clear();
+    }
+
+    /// record usage of client, return how long client should be throttled
+    pub(crate) fn record(&self, client: &str, kind: QuotaKind, amount: u64) -> Duration {
+        self.record_at(client, kind, amount, Instant::now())
+    }
+
+    fn record_at(&self, client: &str, kind: QuotaKind, amount: u64, now: Instant) -> Duration {
+        let limit = {
+            let quotas = self.quotas.read().unwrap();
+            quotas
+                .get(client)
+                .or_else(|| quotas.get(DEFAULT_QUOTA_CLIENT))
+                .and_then(|quota| kind.limit(quota))
+        };
+        let Some(limit) = limit else {
+            return Duration::ZERO;
+        };
+
+        let throttle = {
+            let mut buckets = self.buckets.lock().unwrap();
+            buckets.evict_idle(now);
+            buckets
+                .buckets
+                .entry((client.to_owned(), kind))
+                .or_insert_with(|| RateBucket::new(limit, now))
+                .take(amount, now)
+        };
+
+        if !throttle.is_zero() {
+            debug!(
+                client,
+                ?kind,
+                throttle_ms = throttle.as_millis(),
+                "quota exceeded"
+            );
+            self.metrics.quota().hit(kind, throttle);
+        }
+        throttle
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    fn quotas(quotas: Vec<ClientQuota>) -> ClientQuotas {
+        let client_quotas = ClientQuotas::new(Arc::new(SpuMetrics::new()));
+        client_quotas.sync_all(quotas);
+        client_quotas
+    }
+
+    #[test]
+    fn test_rate_bucket() {
+        let now = Instant::now();
+        let mut bucket = RateBucket::new(100, now);
+
+        // burst of one second is allowed
+        assert_eq!(bucket.take(100, now), Duration::ZERO);
+        assert_eq!(bucket.take(50, now), Duration::from_millis(500));
+
+        // refilled after debt is paid back
+        let later = now + Duration::from_secs(1);
+        assert_eq!(bucket.take(50, later), Duration::ZERO);
+
+        // refill never exceeds one second of quota
+        let much_later = later + Duration::from_secs(60);
+        assert_eq!(bucket.take(100, much_later), Duration::ZERO);
+        assert_eq!(bucket.take(5000, much_later), MAX_THROTTLE);
+    }
+
+    #[test]
+    fn test_idle_buckets_evicted() {
+        let client_quotas = quotas(vec![ClientQuota {
+            client: DEFAULT_QUOTA_CLIENT.to_owned(),
+            requests_per_sec: Some(10),
+            ..Default::default()
+        }]);
+        let bucket_count = || client_quotas.buckets.lock().unwrap().buckets.len();
+        let now = Instant::now();
+
+        for client in 0..100 {
+            client_quotas.record_at(&client.to_string(), QuotaKind::Requests, 1, now);
+        }
+        assert_eq!(bucket_count(), 100);
+
+        // client in debt keeps its bucket until debt is paid back
+        let later = now + BUCKET_EVICT_INTERVAL;
+        assert_eq!(
+            client_quotas.record_at("busy", QuotaKind::Requests, 200, later),
+            Duration::from_secs(19)
+        );
+        assert_eq!(bucket_count(), 1);
+
+        let much_later = later + BUCKET_EVICT_INTERVAL;
+        assert_eq!(
+            client_quotas.record_at("busy", QuotaKind::Requests, 10, much_later),
+            Duration::from_secs(10)
+        );
+        assert_eq!(bucket_count(), 1);
+    }
+
+    #[test]
+    fn test_client_quotas() {
+        let client_quotas = quotas(vec![
+            ClientQuota {
+                client: DEFAULT_QUOTA_CLIENT.to_owned(),
+                requests_per_sec: Some(1),
+                ..Default::default()
+            },
+            ClientQuota {
+                client: "ingest".to_owned(),
+                produce_bytes_per_sec: Some(1000),
+                ..Default::default()
+            },
+        ]);
+        let now = Instant::now();
+
+        // client with own quota doesn't fall back to default
+        assert!(
+            client_quotas
+                .record_at("ingest", QuotaKind::Requests, 10, now)
+                .is_zero()
+        );
+        assert_eq!(
+            client_quotas.record_at("ingest", QuotaKind::ProduceBytes, 2000, now),
+            Duration::from_secs(1)
+        );
+
+        // default quota is tracked for each client separately
+        assert!(
+            client_quotas
+                .record_at("a", QuotaKind::Requests, 1, now)
+                .is_zero()
+        );
+        assert!(
+            client_quotas
+                .record_at("b", QuotaKind::Requests, 1, now)
+                .is_zero()
+        );
+        assert_eq!(
+            client_quotas.record_at("a", QuotaKind::Requests, 1, now),
+            Duration::from_secs(1)
+        );
+        assert_eq!(client_quotas.metrics.quota().requests_hits(), 1);
+        assert_eq!(client_quotas.metrics.quota().produce_hits(), 1);
+
+        // removing quotas lifts throttling
+        client_quotas.sync_all(vec![]);
+        assert!(
+            client_quotas
+                .record_at("a", QuotaKind::Requests, 1, now)
+                .is_zero()
+        );
+    }
+}
@@ -67,6 +67,16 @@ impl SharedConsumerOffsetStorages {
             }
         }
     }
+
+    /// offsets of all consumers of replicas which storage was opened for
+    pub(crate) async fn list_all(&self) -> Result<Vec<(ConsumerOffsetKey, ConsumerOffset)>> {
+        let storages: Vec<_> = self.0.read().await.values().cloned().collect();
+        let mut offsets = Vec::new();
+        for storage in storages {
+            offsets.extend(storage.list().await?);
+        }
+        Ok(offsets)
+    }
 }
 
 impl ConsumerOffsetStorage {
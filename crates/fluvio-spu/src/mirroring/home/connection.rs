@@ -398,8 +398,8 @@ impl MirrorHomeHandler {
                         replica = %self.leader.id(),
                         "read records"
                     );
-                    if let Some(file_slice) = slice.file_slice {
-                        partition_response.records = file_slice.into();
+                    if let Some(records) = slice.records() {
+                        partition_response.records = records;
                     }
                     Ok(Some(partition_response.into()))
                 }
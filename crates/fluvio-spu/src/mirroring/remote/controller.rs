@@ -177,7 +177,7 @@ where
             status_update: ctx.mirror_status_update_owned(),
             follower_notifier: ctx.follower_notifier_owned(),
         };
-        spawn(controller.dispatch_loop());
+        spawn(ctx.shutdown().until_notified(controller.dispatch_loop()));
         state
     }
 
@@ -514,8 +514,8 @@ where
                         replica = %self.leader.id(),
                         "read records"
                     );
-                    if let Some(file_slice) = slice.file_slice {
-                        partition_response.records = file_slice.into();
+                    if let Some(records) = slice.records() {
+                        partition_response.records = records;
                     }
                     Ok(Some(partition_response))
                 }
@@ -625,6 +625,7 @@ where
             replica: self.leader.id().clone(),
             leo: self.leader.leo(),
             hw: self.leader.hw(),
+            ..Default::default()
         };
 
         debug!(?offset_request, "sending offset to home");
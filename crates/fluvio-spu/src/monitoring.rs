@@ -1,13 +1,25 @@
+use std::collections::{BTreeMap, HashMap};
 use std::io::Error as IoError;
 
+use async_trait::async_trait;
 use futures_util::{StreamExt, AsyncWriteExt};
 use fluvio_types::defaults::SPU_MONITORING_UNIX_SOCKET;
+use fluvio_types::SpuId;
+use fluvio_types::openmetrics::{MetricType, MetricsEncoder};
+use fluvio_protocol::record::{Offset, ReplicaKey};
+use fluvio_storage::OffsetInfo;
 use fluvio_future::task::spawn;
 use fluvio_future::net::unix::UnixListener;
+use fluvio_service::metrics::{MetricsCollector, start_metrics_server};
 use tracing::{error, info, debug};
 use serde_json::json;
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::core::metrics::Activity;
+#[cfg(feature = "smartengine")]
+use fluvio_smartengine::metrics::SmartModuleChainMetrics;
+#[cfg(not(feature = "smartengine"))]
+use crate::smartengine::SmartModuleChainMetrics;
 
 // Add SmartEngine to init_monitoring params
 pub(crate) fn init_monitoring(ctx: DefaultSharedGlobalContext) {
@@ -62,6 +74,7 @@ async fn start_monitoring(ctx: DefaultSharedGlobalContext) -> Result<(), IoError
                 "spu": {
                     "inbound": ctx.metrics().inbound(),
                     "outbound": ctx.metrics().outbound(),
+                    "quota": ctx.metrics().quota(),
                     "smartmodule": ctx.metrics().smartmodule_metrics(),
                 }
             });
@@ -74,3 +87,247 @@ async fn start_monitoring(ctx: DefaultSharedGlobalContext) -> Result<(), IoError
         fluvio_future::timer::sleep(std::time::Duration::from_secs(5)).await;
     }
 }
+
+/// start OpenMetrics endpoint if address is configured
+pub(crate) fn init_metrics_endpoint(ctx: DefaultSharedGlobalContext) {
+    if let Some(addr) = ctx.config().metrics_endpoint.clone() {
+        start_metrics_server(addr, SpuMetricsCollector { ctx });
+    }
+}
+
+struct SpuMetricsCollector {
+    ctx: DefaultSharedGlobalContext,
+}
+
+#[async_trait]
+impl MetricsCollector for SpuMetricsCollector {
+    async fn collect(&self, encoder: &mut MetricsEncoder) {
+        let metrics = self.ctx.metrics();
+
+        encoder
+            .family(
+                "fluvio_spu_connections",
+                MetricType::Gauge,
+                "open client connections",
+            )
+            .sample(&[], metrics.connections());
+
+        encode_activity(encoder, "fluvio_spu_inbound", "produced", metrics.inbound());
+        encode_activity(
+            encoder,
+            "fluvio_spu_outbound",
+            "fetched",
+            metrics.outbound(),
+        );
+
+        encoder
+            .family(
+                "fluvio_spu_request_latency_seconds",
+                MetricType::Histogram,
+                "time to process produce request or send back fetched records",
+            )
+            .histogram(&[("api", "produce")], metrics.produce_latency())
+            .histogram(&[("api", "fetch")], metrics.fetch_latency());
+
+        let quota = metrics.quota();
+        encoder
+            .family(
+                "fluvio_spu_quota_hits",
+                MetricType::Counter,
+                "number of times client went over quota",
+            )
+            .sample(&[("quota", "produce")], quota.produce_hits())
+            .sample(&[("quota", "fetch")], quota.fetch_hits())
+            .sample(&[("quota", "requests")], quota.requests_hits());
+        encoder
+            .family(
+                "fluvio_spu_quota_throttle_milliseconds",
+                MetricType::Counter,
+                "time clients were throttled",
+            )
+            .sample(&[("quota", "produce")], quota.produce_throttle_ms())
+            .sample(&[("quota", "fetch")], quota.fetch_throttle_ms())
+            .sample(&[("quota", "requests")], quota.requests_throttle_ms());
+
+        self.encode_partitions(encoder).await;
+        encode_smartmodules(encoder, &metrics.smartmodule_metrics());
+    }
+}
+
+/// offsets of partition this spu is leader of
+struct LeaderOffsets {
+    replica: ReplicaKey,
+    partition: String,
+    log_start: Offset,
+    hw: Offset,
+    leo: Offset,
+    followers: BTreeMap<SpuId, OffsetInfo>,
+}
+
+impl LeaderOffsets {
+    fn labels(&self) -> [(&str, &str); 2] {
+        [
+            ("topic", self.replica.topic.as_str()),
+            ("partition", self.partition.as_str()),
+        ]
+    }
+}
+
+impl SpuMetricsCollector {
+    async fn encode_partitions(&self, encoder: &mut MetricsEncoder) {
+        let leaders: Vec<_> = self
+            .ctx
+            .leaders_state()
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+
+        let mut partitions = Vec::with_capacity(leaders.len());
+        for leader in leaders {
+            let (log_start, hw) = leader.start_offset_info().await;
+            partitions.push(LeaderOffsets {
+                replica: leader.id().clone(),
+                partition: leader.id().partition.to_string(),
+                log_start,
+                hw,
+                leo: leader.leo(),
+                followers: leader.followers_info().await,
+            });
+        }
+
+        type Getter = fn(&LeaderOffsets) -> Offset;
+        let families: [(&str, &str, Getter); 3] = [
+            (
+                "fluvio_partition_log_start_offset",
+                "first offset available in partition",
+                |offsets| offsets.log_start,
+            ),
+            (
+                "fluvio_partition_hw",
+                "high watermark of partition",
+                |offsets| offsets.hw,
+            ),
+            (
+                "fluvio_partition_leo",
+                "log end offset of partition",
+                |offsets| offsets.leo,
+            ),
+        ];
+        for (name, help, value) in families {
+            encoder.family(name, MetricType::Gauge, help);
+            for offsets in &partitions {
+                encoder.sample(&offsets.labels(), value(offsets));
+            }
+        }
+
+        encoder.family(
+            "fluvio_partition_follower_lag",
+            MetricType::Gauge,
+            "records follower is behind leader",
+        );
+        for offsets in &partitions {
+            let [topic, partition] = offsets.labels();
+            for (follower, info) in &offsets.followers {
+                let follower = follower.to_string();
+                encoder.sample(
+                    &[topic, partition, ("follower", follower.as_str())],
+                    (offsets.leo - info.leo).max(0),
+                );
+            }
+        }
+
+        // offsets are stored only on spu leading consumer offsets partition,
+        // lag is known only for partitions led by same spu
+        let consumers = match self.ctx.consumer_offset().list_all().await {
+            Ok(consumers) => consumers,
+            Err(err) => {
+                debug!(%err, "unable to list consumer offsets");
+                Vec::new()
+            }
+        };
+        encoder.family(
+            "fluvio_consumer_lag",
+            MetricType::Gauge,
+            "records consumer is behind high watermark",
+        );
+        for (key, consumer) in consumers {
+            let Some(offsets) = partitions
+                .iter()
+                .find(|offsets| offsets.replica == key.replica_id)
+            else {
+                continue;
+            };
+            let [topic, partition] = offsets.labels();
+            encoder.sample(
+                &[topic, partition, ("consumer", key.consumer_id.as_str())],
+                (offsets.hw - consumer.offset - 1).max(0),
+            );
+        }
+    }
+}
+
+fn encode_activity(encoder: &mut MetricsEncoder, prefix: &str, verb: &str, activity: &Activity) {
+    encoder
+        .family(
+            &format!("{prefix}_records"),
+            MetricType::Counter,
+            &format!("records {verb}"),
+        )
+        .sample(&[("source", "client")], activity.client_records())
+        .sample(&[("source", "connector")], activity.connector_records());
+    encoder
+        .family(
+            &format!("{prefix}_bytes"),
+            MetricType::Counter,
+            &format!("bytes {verb}"),
+        )
+        .sample(&[("source", "client")], activity.client_bytes())
+        .sample(&[("source", "connector")], activity.connector_bytes());
+}
+
+fn encode_smartmodules(
+    encoder: &mut MetricsEncoder,
+    chains: &HashMap<String, SmartModuleChainMetrics>,
+) {
+    type Getter = fn(&SmartModuleChainMetrics) -> u64;
+    let families: [(&str, &str, Getter); 6] = [
+        (
+            "fluvio_smartmodule_invocations",
+            "smartmodule chain invocations",
+            SmartModuleChainMetrics::invocation_count,
+        ),
+        (
+            "fluvio_smartmodule_bytes_in",
+            "bytes processed by smartmodule chain",
+            SmartModuleChainMetrics::bytes_in,
+        ),
+        (
+            "fluvio_smartmodule_records_out",
+            "records produced by smartmodule chain",
+            SmartModuleChainMetrics::records_out,
+        ),
+        (
+            "fluvio_smartmodule_records_err",
+            "records failed in smartmodule chain",
+            SmartModuleChainMetrics::records_err,
+        ),
+        (
+            "fluvio_smartmodule_fuel_used",
+            "wasm fuel consumed by smartmodule chain",
+            SmartModuleChainMetrics::fuel_used,
+        ),
+        (
+            "fluvio_smartmodule_cpu_milliseconds",
+            "cpu time spent in smartmodule chain",
+            SmartModuleChainMetrics::cpu_ms,
+        ),
+    ];
+    for (name, help, value) in families {
+        encoder.family(name, MetricType::Counter, help);
+        for (chain, metrics) in chains {
+            encoder.sample(&[("smartmodule", chain.as_str())], value(metrics));
+        }
+    }
+}
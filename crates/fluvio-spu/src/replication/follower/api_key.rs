@@ -6,6 +6,7 @@ use fluvio_protocol::{Encoder, Decoder};
 pub enum FollowerPeerApiEnum {
     SyncRecords = 0,
     RejectedOffsetRequest = 1,
+    TruncateRequest = 2,
 }
 
 impl Default for FollowerPeerApiEnum {
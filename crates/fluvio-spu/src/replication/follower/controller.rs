@@ -5,6 +5,7 @@ use async_lock::RwLock;
 use adaptive_backoff::prelude::*;
 
 use fluvio_types::SpuId;
+use fluvio_types::event::StickyEvent;
 use fluvio_types::event::offsets::OffsetPublisher;
 use crate::core::FileGlobalContext;
 
@@ -13,6 +14,7 @@ use super::state::{SharedFollowersState, FollowerReplicaState};
 use super::api_key::FollowerPeerApiEnum;
 use super::sync::DefaultSyncRequest;
 use super::peer_api::FollowerPeerRequest;
+use super::truncate_request::TruncateRequest;
 
 /// time to resync follower offsets to leader
 const LEADER_RECONCILIATION_INTERVAL_SEC: u64 = 60; // 1 min
@@ -45,6 +47,7 @@ impl FollowerGroups {
                     ctx.followers_state_owned(),
                     notification,
                     ctx.config_owned(),
+                    ctx.shutdown(),
                 );
             }
         }
@@ -76,7 +79,7 @@ impl FollowerGroups {
 use inner::*;
 mod inner {
 
-    use tracing::info;
+    use tracing::{info, Instrument};
     use tokio::select;
     use futures_util::StreamExt;
     use once_cell::sync::Lazy;
@@ -86,6 +89,7 @@ mod inner {
     use fluvio_socket::FluvioSocket;
     use fluvio_socket::FluvioSink;
     use fluvio_socket::SocketError;
+    use fluvio_service::request_span;
     use fluvio_protocol::record::ReplicaKey;
     use fluvio_protocol::api::RequestMessage;
     use fluvio_types::SpuId;
@@ -120,6 +124,7 @@ mod inner {
             states: SharedFollowersState<FileReplica>,
             spu_ctx: Arc<GroupNotification>,
             config: SharedSpuConfig,
+            shutdown: &Arc<StickyEvent>,
         ) {
             let controller = Self {
                 leader,
@@ -128,7 +133,7 @@ mod inner {
                 group: spu_ctx,
                 config,
             };
-            spawn(controller.dispatch_loop());
+            spawn(shutdown.until_notified(controller.dispatch_loop()));
         }
 
         fn local_spu_id(&self) -> SpuId {
@@ -219,11 +224,18 @@ mod inner {
                             let req_msg = req_msg_res?;
 
                             match req_msg {
-                                FollowerPeerRequest::SyncRecords(sync_request)=> self.sync_from_leader(&mut sink,sync_request.request).await?,
+                                FollowerPeerRequest::SyncRecords(sync_request)=> {
+                                    let span = request_span(&sync_request.header, "SyncRecords");
+                                    self.sync_from_leader(&mut sink,sync_request.request).instrument(span).await?
+                                },
                                 FollowerPeerRequest::RejectedOffsetRequest(requests) => {
                                     debug!(fail_req = ?requests,"leader rejected these requests");
                                     timer= sleep(Duration::from_secs(*SHORT_RECONCILLATION));
                                 },
+                                FollowerPeerRequest::Truncate(truncate_request) => {
+                                    let span = request_span(&truncate_request.header, "Truncate");
+                                    self.truncate_from_leader(&mut sink,truncate_request.request).instrument(span).await?
+                                },
                              }
 
                         } else {
@@ -268,11 +280,15 @@ mod inner {
                     replica = %replica_key,
                     leader_hw=p.hw,
                     leader_leo=p.leo,
+                    leader_log_start=p.log_start_offset,
                     records = p.records.total_records(),
                     base_offset = p.records.base_offset(),
                     "update from leader");
                     if let Some(replica) = self.states.get(&replica_key).await {
-                        match replica.update_from_leader(&mut p.records, p.hw).await {
+                        match replica
+                            .update_from_leader(&mut p.records, p.hw, p.log_start_offset)
+                            .await
+                        {
                             Ok(changes) => {
                                 if changes {
                                     debug!("changes occur, need to send back offset");
@@ -301,6 +317,53 @@ mod inner {
             }
         }
 
+        /// truncate replicas which have diverged from leader then report new offsets.
+        /// if leader's epoch ended earlier in our log, truncate there instead
+        #[instrument(skip(self, req))]
+        async fn truncate_from_leader(
+            &self,
+            sink: &mut FluvioSink,
+            req: TruncateRequest,
+        ) -> Result<(), SocketError> {
+            let mut offsets = UpdateOffsetRequest::default();
+
+            for truncate in req.replicas {
+                let replica_key = truncate.replica;
+                if let Some(replica) = self.states.get(&replica_key).await {
+                    let offset = replica
+                        .epoch_end_offset(truncate.leader_epoch)
+                        .await
+                        .map(|(_, end_offset)| end_offset.min(truncate.offset))
+                        .unwrap_or(truncate.offset);
+                    match replica.truncate_to(offset).await {
+                        Ok(leo) => {
+                            info!(
+                                replica = %replica_key,
+                                leader_epoch = truncate.leader_epoch,
+                                leo,
+                                "truncated divergent log"
+                            );
+                            offsets.replicas.push(replica.as_offset_request());
+                        }
+                        Err(err) => {
+                            error!("problem truncating {}, error: {:#?}", replica_key, err)
+                        }
+                    }
+                } else {
+                    error!(
+                        "unable to find follower replica for truncation: {}",
+                        replica_key
+                    );
+                }
+            }
+
+            if !offsets.replicas.is_empty() {
+                self.send_offsets_to_leader(sink, offsets).await
+            } else {
+                Ok(())
+            }
+        }
+
         /// connect to leader, if can't connect try until we succeed
         /// or if we received termination message
         async fn create_socket_to_leader(
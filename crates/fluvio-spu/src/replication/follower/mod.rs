@@ -3,7 +3,9 @@ mod api_key;
 mod peer_api;
 mod controller;
 mod reject_request;
+mod truncate_request;
 pub mod sync;
 
 pub use self::state::{FollowersState, SharedFollowersState, FollowerReplicaState};
 pub use self::reject_request::RejectOffsetRequest;
+pub use self::truncate_request::{TruncateRequest, TruncateReplica};
@@ -10,11 +10,13 @@ use fluvio_protocol::api::{RequestMessage, ApiMessage, RequestHeader};
 use super::api_key::FollowerPeerApiEnum;
 use super::sync::DefaultSyncRequest;
 use super::reject_request::RejectOffsetRequest;
+use super::truncate_request::TruncateRequest;
 
 #[derive(Debug)]
 pub enum FollowerPeerRequest {
     SyncRecords(RequestMessage<DefaultSyncRequest>),
     RejectedOffsetRequest(RequestMessage<RejectOffsetRequest>),
+    Truncate(RequestMessage<TruncateRequest>),
 }
 
 impl Default for FollowerPeerRequest {
@@ -35,14 +37,21 @@ impl ApiMessage for FollowerPeerRequest {
         trace!("decoding with header: {:#?}", header);
         let version = header.api_version();
         match header.api_key().try_into()? {
-            FollowerPeerApiEnum::SyncRecords => Ok(FollowerPeerRequest::SyncRecords(
-                RequestMessage::new(header, DefaultSyncRequest::decode_from(src, version)?),
-            )),
+            FollowerPeerApiEnum::SyncRecords => {
+                Ok(FollowerPeerRequest::SyncRecords(RequestMessage::<
+                    DefaultSyncRequest,
+                >::decode_with_header(
+                    src, header
+                )?))
+            }
             FollowerPeerApiEnum::RejectedOffsetRequest => {
                 Ok(FollowerPeerRequest::RejectedOffsetRequest(
                     RequestMessage::new(header, RejectOffsetRequest::decode_from(src, version)?),
                 ))
             }
+            FollowerPeerApiEnum::TruncateRequest => Ok(FollowerPeerRequest::Truncate(
+                RequestMessage::new(header, TruncateRequest::decode_from(src, version)?),
+            )),
         }
     }
 }
@@ -5,17 +5,18 @@ use std::ops::{Deref, DerefMut};
 
 use fluvio_controlplane::replica::Replica;
 use tracing::{debug, warn, instrument};
-use async_lock::RwLock;
+use async_lock::{Mutex, RwLock};
 use anyhow::Result;
 
-use fluvio_protocol::record::{BatchRecords, ReplicaKey};
+use fluvio_protocol::record::{RawRecords, ReplicaKey};
 use fluvio_storage::config::ReplicaConfig;
 use fluvio_protocol::record::RecordSet;
 use fluvio_protocol::record::Offset;
 use fluvio_storage::{FileReplica, ReplicaStorage, ReplicaStorageConfig};
 use fluvio_types::SpuId;
+use fluvio_types::event::offsets::{SharedOffsetPublisher, WeakSharedOffsetPublisher};
 
-use crate::replication::leader::ReplicaOffsetRequest;
+use crate::replication::leader::{ReplicaOffsetRequest, CLEANUP_FREQUENCY};
 use crate::core::FileGlobalContext;
 use crate::storage::SharableReplicaStorage;
 
@@ -96,6 +97,8 @@ impl FollowersState<FileReplica> {
 
                 let mut replica_config: ReplicaConfig = ctx.config().into();
                 replica_config.update_from_replica(&replica);
+                // follower can start over in other directory and catch up from leader
+                replica_config.base_dir = ctx.data_dirs().dirs().select(&replica.id, true).await?;
 
                 let replica_state =
                     FollowerReplicaState::create(leader, replica.id, replica_config).await?;
@@ -148,6 +151,8 @@ impl FollowersState<FileReplica> {
 pub struct FollowerReplicaState<S> {
     leader: SpuId,
     inner: SharableReplicaStorage<S>,
+    /// consumers reading committed records from this follower
+    consumer_offset_publishers: Arc<Mutex<Vec<WeakSharedOffsetPublisher>>>,
 }
 
 impl<S> Clone for FollowerReplicaState<S> {
@@ -155,6 +160,7 @@ impl<S> Clone for FollowerReplicaState<S> {
         Self {
             leader: self.leader,
             inner: self.inner.clone(),
+            consumer_offset_publishers: self.consumer_offset_publishers.clone(),
         }
     }
 }
@@ -197,6 +203,7 @@ where
         Ok(Self {
             leader,
             inner: replica_storage,
+            consumer_offset_publishers: Arc::new(Mutex::new(Vec::new())),
         })
     }
 
@@ -205,15 +212,19 @@ where
     }
 
     /// update from leader with new record set
-    pub async fn update_from_leader<R: BatchRecords>(
+    /// records deleted in leader are deleted up to hw
+    /// corrupt batches are rejected and fetched again from leader
+    pub async fn update_from_leader(
         &self,
-        records: &mut RecordSet<R>,
+        records: &mut RecordSet<RawRecords>,
         leader_hw: Offset,
+        leader_log_start: Offset,
     ) -> Result<bool> {
         let mut changes = false;
 
         if records.total_records() > 0 {
             self.write_recordsets(records).await?;
+            // report offsets even if batches were rejected, so leader resends from follower leo
             changes = true;
         } else {
             debug!("no records");
@@ -240,12 +251,18 @@ where
             }
         }
 
+        let (log_start, _) = self.start_offset_info().await;
+        if leader_log_start > log_start {
+            debug!(log_start, leader_log_start, "deleting records");
+            self.delete_records(leader_log_start).await?;
+        }
+
         Ok(changes)
     }
 
     /// try to write records
     /// ensure records has correct baseoffset
-    async fn write_recordsets<R: BatchRecords>(&self, records: &mut RecordSet<R>) -> Result<bool> {
+    async fn write_recordsets(&self, records: &mut RecordSet<RawRecords>) -> Result<bool> {
         let storage_leo = self.leo();
         if records.base_offset() != storage_leo {
             // this could happened if records were sent from leader before hw was sync
@@ -256,6 +273,20 @@ where
             );
             Ok(false)
         } else {
+            // corrupt batch and batches after it are not written,
+            // leader resends them since leo has not moved past them
+            let corrupt = records
+                .batches
+                .iter()
+                .enumerate()
+                .find_map(|(index, batch)| batch.validate_crc().err().map(|err| (index, err)));
+            if let Some((index, err)) = corrupt {
+                warn!(%err, "rejecting corrupt batch from leader");
+                records.batches.truncate(index);
+                if records.batches.is_empty() {
+                    return Ok(false);
+                }
+            }
             self.write_record_set(records, false).await?;
             Ok(true)
         }
@@ -267,20 +298,39 @@ where
             replica: self.inner.id().to_owned(),
             leo: self.leo(),
             hw: self.hw(),
+            leader_epoch: self.leader_epoch(),
         }
     }
 
     pub fn inner_owned(self) -> SharableReplicaStorage<S> {
         self.inner
     }
+
+    pub fn consumer_offset_publishers(&self) -> Arc<Mutex<Vec<WeakSharedOffsetPublisher>>> {
+        self.consumer_offset_publishers.clone()
+    }
+
+    pub async fn register_offset_publisher(&self, offset_publisher: &SharedOffsetPublisher) {
+        let mut publishers = self.consumer_offset_publishers.lock().await;
+
+        // Filter out any dead weak pointers every so often
+        if publishers.len() % CLEANUP_FREQUENCY == 0 {
+            publishers.retain(|p| p.strong_count() > 0);
+        }
+
+        publishers.push(Arc::downgrade(offset_publisher));
+    }
 }
 
 #[cfg(test)]
 mod follower_tests {
 
+    use std::io::Cursor;
     use std::path::PathBuf;
 
     use flv_util::fixture::ensure_clean_dir;
+    use fluvio_protocol::{Decoder, Encoder};
+    use fluvio_protocol::record::{Batch, Record};
     use fluvio_types::{SpuId, PartitionId};
     use fluvio_storage::config::ReplicaConfig;
 
@@ -310,4 +360,50 @@ mod follower_tests {
         assert_eq!(follower_replica.hw(), 0);
         assert!(PathBuf::from(test_path).join("spu-5002").exists());
     }
+
+    fn raw_batch(base_offset: Offset) -> Batch<RawRecords> {
+        let mut batch = Batch::from(vec![Record::new("hello"), Record::new("world")]);
+        batch.set_base_offset(base_offset);
+        let bytes = batch.as_bytes(0).expect("encode");
+        Batch::decode_from(&mut Cursor::new(&bytes), 0).expect("decode")
+    }
+
+    #[fluvio_future::test]
+    async fn test_follower_rejects_corrupt_batch() {
+        let test_path = "/tmp/follower_corrupt_batch";
+        ensure_clean_dir(test_path);
+
+        let config = ReplicaConfig {
+            base_dir: PathBuf::from(test_path).join("spu-5002"),
+            ..Default::default()
+        };
+
+        let follower_replica: FollowerReplicaState<FileReplica> =
+            FollowerReplicaState::create(LEADER, TEST_REPLICA.into(), config)
+                .await
+                .expect("create");
+
+        let mut corrupt = raw_batch(2);
+        corrupt.header.crc ^= 0x01;
+        let mut records = RecordSet::default()
+            .add(raw_batch(0))
+            .add(corrupt)
+            .add(raw_batch(4));
+
+        // batches before corrupt one are written, rest are fetched again
+        let changes = follower_replica
+            .update_from_leader(&mut records, 0, 0)
+            .await
+            .expect("update");
+        assert!(changes);
+        assert_eq!(follower_replica.leo(), 2);
+
+        let mut records = RecordSet::default().add(raw_batch(2)).add(raw_batch(4));
+        follower_replica
+            .update_from_leader(&mut records, 6, 0)
+            .await
+            .expect("update");
+        assert_eq!(follower_replica.leo(), 6);
+        assert_eq!(follower_replica.hw(), 6);
+    }
 }
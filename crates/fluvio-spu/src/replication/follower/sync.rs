@@ -26,6 +26,9 @@ pub type DefaultSyncRequest = SyncRequest<RecordSet<RawRecords>>;
 pub type PeerFilePartitionResponse = PeerFetchablePartitionResponse<FileRecordSet>;
 pub type PeerFileTopicResponse = PeerFetchableTopicResponse<FileRecordSet>;
 
+/// sync version which carries leader's log start offset
+pub const LOG_START_VERSION: Version = 8;
+
 /// used for sending records and commits
 /// re purpose topic response since it has records and commit offsets
 #[derive(Default, Encoder, Decoder, Debug)]
@@ -50,14 +53,14 @@ where
 }
 
 // Request trait
-// Note that DEFAULT_API_VERSION is 7 which is required in order to map all fields for file encoding
+// Note that DEFAULT_API_VERSION must be at least 7 in order to map all fields for file encoding
 // TODO: come up with unify encoding
 impl<R> Request for SyncRequest<R>
 where
     R: Encoder + Decoder + Debug,
 {
     const API_KEY: u16 = FollowerPeerApiEnum::SyncRecords as u16;
-    const DEFAULT_API_VERSION: i16 = 7;
+    const DEFAULT_API_VERSION: i16 = LOG_START_VERSION;
     type Response = SyncResponse;
 }
 
@@ -110,6 +113,8 @@ where
     pub error: ErrorCode,
     pub hw: i64,
     pub leo: i64,
+    #[fluvio(min_version = 8)]
+    pub log_start_offset: i64,
     pub records: R,
 }
 
@@ -152,6 +157,9 @@ impl FileWrite for PeerFilePartitionResponse {
         self.error.encode(src, version)?;
         self.hw.encode(src, version)?;
         self.leo.encode(src, version)?;
+        if version >= LOG_START_VERSION {
+            self.log_start_offset.encode(src, version)?;
+        }
         self.records.file_encode(src, data, version)?;
         Ok(())
     }
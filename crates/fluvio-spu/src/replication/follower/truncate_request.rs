@@ -0,0 +1,33 @@
+#![allow(clippy::assign_op_pattern)]
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::Offset;
+use fluvio_controlplane_metadata::partition::ReplicaKey;
+
+use super::api_key::FollowerPeerApiEnum;
+
+/// Sent by leader when follower's log has diverged from leader
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct TruncateRequest {
+    pub replicas: Vec<TruncateReplica>,
+}
+
+impl Request for TruncateRequest {
+    const API_KEY: u16 = FollowerPeerApiEnum::TruncateRequest as u16;
+    const DEFAULT_API_VERSION: i16 = 0;
+    type Response = TruncateResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Clone, Debug)]
+pub struct TruncateReplica {
+    pub replica: ReplicaKey,
+    /// largest leader epoch which is not newer than follower's epoch, -1 if unknown
+    pub leader_epoch: i32,
+    /// end offset of leader epoch in leader's log
+    pub offset: Offset,
+}
+
+// no content, this is one way request
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct TruncateResponse {}
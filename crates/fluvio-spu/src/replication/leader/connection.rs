@@ -6,6 +6,7 @@ use tracing::instrument;
 
 use fluvio_storage::OffsetInfo;
 use fluvio_socket::{FluvioSink, SocketError, FluvioStream};
+use fluvio_socket::trace_context::current_trace_context;
 use fluvio_protocol::api::RequestMessage;
 use fluvio_types::SpuId;
 
@@ -13,9 +14,9 @@ use crate::{core::DefaultSharedGlobalContext, replication::follower::sync::FileS
 
 use super::LeaderPeerApiEnum;
 use super::LeaderPeerRequest;
-use super::UpdateOffsetRequest;
+use super::{UpdateOffsetRequest, LEADER_EPOCH_VERSION, TRACE_CONTEXT_VERSION};
 use super::spu::SharedSpuPendingUpdate;
-use super::super::follower::RejectOffsetRequest;
+use super::super::follower::{RejectOffsetRequest, TruncateRequest, TruncateReplica};
 
 /// Handle connection request from follower
 /// This follows similar arch as Consumer Stream Fetch Handler
@@ -25,6 +26,8 @@ pub struct FollowerHandler {
     follower_id: SpuId,
     max_bytes: u32,
     spu_update: SharedSpuPendingUpdate,
+    /// version of last offset update from follower, unknown until first one
+    follower_version: Option<i16>,
 }
 
 impl fmt::Debug for FollowerHandler {
@@ -47,6 +50,7 @@ impl FollowerHandler {
             max_bytes: ctx.config().peer_max_bytes,
             follower_id,
             spu_update,
+            follower_version: None,
         };
 
         connection.dispatch(sink, stream).await;
@@ -97,7 +101,9 @@ impl FollowerHandler {
                             match req_message {
 
                                 LeaderPeerRequest::UpdateOffsets(request) => {
-                                    self.update_from_follower(request.request,&mut sink).await?;
+                                    let version = request.header.api_version();
+                                    self.follower_version = Some(version);
+                                    self.update_from_follower(request.request, version, &mut sink).await?;
                                 }
                             }
                         } else {
@@ -154,8 +160,15 @@ impl FollowerHandler {
         if sync_request.topics.is_empty() {
             debug!("no topics found, skipping");
         } else {
-            let request = RequestMessage::new_request(sync_request)
+            let mut request = RequestMessage::new_request(sync_request)
                 .set_client_id(format!("leader: {}", self.ctx.local_spu_id()));
+            // older followers may reject trace context
+            if self
+                .follower_version
+                .is_some_and(|version| version >= TRACE_CONTEXT_VERSION)
+            {
+                request.header.set_trace_context(current_trace_context());
+            }
             sink.encode_file_slices(&request, request.header.api_version())
                 .await?;
         }
@@ -163,17 +176,33 @@ impl FollowerHandler {
     }
 
     /// process updates from followers
+    /// followers which can handle truncation are checked for divergent logs first
     #[instrument(skip(self, request))]
     async fn update_from_follower(
         &self,
         request: UpdateOffsetRequest,
+        version: i16,
         sink: &mut FluvioSink,
     ) -> Result<(), SocketError> {
         let mut rejects = vec![];
+        let mut truncates = vec![];
         for update in request.replicas.into_iter() {
             debug!(?update, "request");
             let replica_key = update.replica;
             if let Some(leader) = self.ctx.leaders_state().get(&replica_key).await {
+                if version >= LEADER_EPOCH_VERSION {
+                    if let Some((leader_epoch, offset)) = leader
+                        .follower_truncate_offset(self.follower_id, update.leo, update.leader_epoch)
+                        .await
+                    {
+                        truncates.push(TruncateReplica {
+                            replica: replica_key,
+                            leader_epoch,
+                            offset,
+                        });
+                        continue;
+                    }
+                }
                 let status = leader
                     .update_states_from_followers(
                         self.follower_id,
@@ -201,6 +230,16 @@ impl FollowerHandler {
             sink.send_request(&request).await?;
         }
 
+        if !truncates.is_empty() {
+            debug!(truncate_count = truncates.len());
+            let request = RequestMessage::new_request(TruncateRequest {
+                replicas: truncates,
+            })
+            .set_client_id(format!("leader: {}", self.ctx.local_spu_id()));
+
+            sink.send_request(&request).await?;
+        }
+
         Ok(())
     }
 }
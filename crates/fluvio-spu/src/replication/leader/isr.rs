@@ -0,0 +1,356 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use tracing::{debug, info, instrument};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::record::Offset;
+use fluvio_storage::OffsetInfo;
+use fluvio_types::SpuId;
+
+use crate::core::DefaultSharedGlobalContext;
+
+const MIN_CHECK_INTERVAL_MS: u64 = 100;
+
+/// In-sync replicas of leader.
+/// Follower is in sync until it has not caught up with leader's leo for longer than lag time
+/// or falls behind by more than max lag records.
+/// Out of sync follower rejoins once its leo reaches leader's hw.
+#[derive(Debug)]
+pub(crate) struct InSyncReplicas {
+    leader: SpuId,
+    followers: BTreeMap<SpuId, FollowerSync>,
+}
+
+#[derive(Debug)]
+struct FollowerSync {
+    in_sync: bool,
+    last_caught_up: Instant,
+    // leader's leo and time when follower last reported offsets
+    last_report_leader_leo: Offset,
+    last_report_time: Instant,
+}
+
+impl FollowerSync {
+    fn new(now: Instant) -> Self {
+        Self {
+            in_sync: true,
+            last_caught_up: now,
+            last_report_leader_leo: -1,
+            last_report_time: now,
+        }
+    }
+}
+
+impl InSyncReplicas {
+    /// all followers start in sync
+    pub fn new(leader: SpuId, followers: impl IntoIterator<Item = SpuId>, now: Instant) -> Self {
+        Self {
+            leader,
+            followers: followers
+                .into_iter()
+                .filter(|id| *id != leader)
+                .map(|id| (id, FollowerSync::new(now)))
+                .collect(),
+        }
+    }
+
+    /// in-sync replicas with leader first
+    pub fn ids(&self) -> Vec<SpuId> {
+        std::iter::once(self.leader)
+            .chain(
+                self.followers
+                    .iter()
+                    .filter(|(_, sync)| sync.in_sync)
+                    .map(|(id, _)| *id),
+            )
+            .collect()
+    }
+
+    /// number of in-sync replicas including leader
+    pub fn count(&self) -> u16 {
+        1 + self.followers.values().filter(|sync| sync.in_sync).count() as u16
+    }
+
+    pub fn is_in_sync(&self, follower_id: SpuId) -> bool {
+        self.followers
+            .get(&follower_id)
+            .map(|sync| sync.in_sync)
+            .unwrap_or(false)
+    }
+
+    /// record offsets reported by follower.
+    /// Follower has caught up if it reached leader's leo now
+    /// or leader's leo as of its previous report, in which case it was caught up at that time.
+    /// return true if follower rejoined in-sync replicas
+    pub fn update(
+        &mut self,
+        follower_id: SpuId,
+        follower_pos: &OffsetInfo,
+        leader_pos: &OffsetInfo,
+        now: Instant,
+    ) -> bool {
+        let Some(sync) = self.followers.get_mut(&follower_id) else {
+            return false;
+        };
+
+        if follower_pos.leo >= leader_pos.leo {
+            sync.last_caught_up = now;
+        } else if follower_pos.leo >= sync.last_report_leader_leo {
+            sync.last_caught_up = sync.last_caught_up.max(sync.last_report_time);
+        }
+        sync.last_report_leader_leo = leader_pos.leo;
+        sync.last_report_time = now;
+
+        if !sync.in_sync && follower_pos.is_valid() && follower_pos.leo >= leader_pos.hw {
+            info!(
+                follower_id,
+                leo = follower_pos.leo,
+                "follower rejoined in-sync replicas"
+            );
+            sync.in_sync = true;
+            sync.last_caught_up = now;
+            return true;
+        }
+        false
+    }
+
+    /// remove followers which are lagging behind leader.
+    /// return removed followers
+    pub fn shrink(
+        &mut self,
+        leader_pos: &OffsetInfo,
+        followers: &BTreeMap<SpuId, OffsetInfo>,
+        lag_time: Duration,
+        lag_max_records: i64,
+        now: Instant,
+    ) -> Vec<SpuId> {
+        let mut removed = vec![];
+        for (follower_id, sync) in self.followers.iter_mut().filter(|(_, sync)| sync.in_sync) {
+            let follower_pos = followers.get(follower_id).cloned().unwrap_or_default();
+            // idle leader doesn't wait for follower report
+            if follower_pos.is_valid() && follower_pos.leo >= leader_pos.leo {
+                sync.last_caught_up = now;
+                continue;
+            }
+
+            let lag = now.saturating_duration_since(sync.last_caught_up);
+            let behind = if follower_pos.is_valid() {
+                leader_pos.leo - follower_pos.leo
+            } else {
+                0
+            };
+            if lag > lag_time || behind > lag_max_records {
+                info!(
+                    follower_id,
+                    lag_ms = lag.as_millis() as u64,
+                    behind,
+                    "follower removed from in-sync replicas"
+                );
+                sync.in_sync = false;
+                removed.push(*follower_id);
+            } else {
+                debug!(
+                    follower_id,
+                    lag_ms = lag.as_millis() as u64,
+                    behind,
+                    "follower in sync"
+                );
+            }
+        }
+        removed
+    }
+}
+
+/// Periodically removes lagging followers from in-sync replicas of all leaders in this SPU.
+/// Followers which stopped reporting offsets can only be detected this way.
+pub struct IsrController {
+    ctx: DefaultSharedGlobalContext,
+}
+
+impl IsrController {
+    pub fn run(ctx: DefaultSharedGlobalContext) {
+        let shutdown = ctx.shutdown().clone();
+        spawn(shutdown.until_notified(Self { ctx }.dispatch_loop()));
+    }
+
+    #[instrument(skip(self), name = "IsrController")]
+    async fn dispatch_loop(self) {
+        let lag_time_max_ms = self.ctx.config().replication.lag_time_max_ms;
+        let interval = Duration::from_millis((lag_time_max_ms / 2).max(MIN_CHECK_INTERVAL_MS));
+        debug!(interval_ms = interval.as_millis() as u64, "starting");
+        loop {
+            sleep(interval).await;
+            let leaders: Vec<_> = self
+                .ctx
+                .leaders_state()
+                .read()
+                .await
+                .values()
+                .cloned()
+                .collect();
+            for leader in leaders {
+                leader
+                    .check_in_sync_replicas(self.ctx.follower_notifier())
+                    .await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    const LAG_TIME: Duration = Duration::from_secs(10);
+
+    fn followers(offsets: Vec<(SpuId, OffsetInfo)>) -> BTreeMap<SpuId, OffsetInfo> {
+        offsets.into_iter().collect()
+    }
+
+    #[test]
+    fn test_isr_shrink_on_lag_time() {
+        let start = Instant::now();
+        let mut isr = InSyncReplicas::new(5000, vec![5000, 5001, 5002], start);
+        assert_eq!(isr.count(), 3);
+        assert_eq!(isr.ids(), vec![5000, 5001, 5002]);
+
+        let leader = OffsetInfo { leo: 10, hw: 5 };
+        let offsets = followers(vec![
+            (5001, OffsetInfo { leo: 10, hw: 5 }),
+            (5002, OffsetInfo { leo: 5, hw: 5 }),
+        ]);
+
+        // within lag time nothing is removed
+        assert!(
+            isr.shrink(
+                &leader,
+                &offsets,
+                LAG_TIME,
+                100,
+                start + Duration::from_secs(5)
+            )
+            .is_empty()
+        );
+
+        // 5001 is caught up, 5002 has been behind for too long
+        let removed = isr.shrink(
+            &leader,
+            &offsets,
+            LAG_TIME,
+            100,
+            start + Duration::from_secs(11),
+        );
+        assert_eq!(removed, vec![5002]);
+        assert_eq!(isr.ids(), vec![5000, 5001]);
+        assert!(!isr.is_in_sync(5002));
+    }
+
+    #[test]
+    fn test_isr_shrink_on_lag_records() {
+        let start = Instant::now();
+        let mut isr = InSyncReplicas::new(5000, vec![5000, 5001], start);
+
+        let leader = OffsetInfo { leo: 200, hw: 50 };
+        let offsets = followers(vec![(5001, OffsetInfo { leo: 50, hw: 50 })]);
+        assert_eq!(
+            isr.shrink(&leader, &offsets, LAG_TIME, 100, start),
+            vec![5001]
+        );
+        assert_eq!(isr.count(), 1);
+    }
+
+    #[test]
+    fn test_isr_unknown_follower_only_time() {
+        let start = Instant::now();
+        let mut isr = InSyncReplicas::new(5000, vec![5000, 5001], start);
+
+        // follower which never reported is not checked for records lag
+        let leader = OffsetInfo {
+            leo: 1000,
+            hw: 1000,
+        };
+        let offsets = followers(vec![(5001, OffsetInfo::default())]);
+        assert!(
+            isr.shrink(&leader, &offsets, LAG_TIME, 100, start)
+                .is_empty()
+        );
+        assert_eq!(
+            isr.shrink(
+                &leader,
+                &offsets,
+                LAG_TIME,
+                100,
+                start + Duration::from_secs(11)
+            ),
+            vec![5001]
+        );
+    }
+
+    #[test]
+    fn test_isr_caught_up_to_previous_report() {
+        let start = Instant::now();
+        let mut isr = InSyncReplicas::new(5000, vec![5000, 5001], start);
+
+        // leader keeps writing so follower is never at leader's leo,
+        // but it always reaches leader's leo as of its previous report
+        isr.update(
+            5001,
+            &OffsetInfo { leo: 5, hw: 0 },
+            &OffsetInfo { leo: 10, hw: 0 },
+            start + Duration::from_secs(8),
+        );
+        isr.update(
+            5001,
+            &OffsetInfo { leo: 10, hw: 5 },
+            &OffsetInfo { leo: 15, hw: 5 },
+            start + Duration::from_secs(16),
+        );
+
+        let leader = OffsetInfo { leo: 15, hw: 10 };
+        let offsets = followers(vec![(5001, OffsetInfo { leo: 10, hw: 5 })]);
+        assert!(
+            isr.shrink(
+                &leader,
+                &offsets,
+                LAG_TIME,
+                100,
+                start + Duration::from_secs(17)
+            )
+            .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_isr_rejoin() {
+        let start = Instant::now();
+        let mut isr = InSyncReplicas::new(5000, vec![5000, 5001], start);
+
+        let leader = OffsetInfo { leo: 20, hw: 20 };
+        let offsets = followers(vec![(5001, OffsetInfo { leo: 5, hw: 5 })]);
+        assert_eq!(
+            isr.shrink(
+                &leader,
+                &offsets,
+                LAG_TIME,
+                100,
+                start + Duration::from_secs(11)
+            ),
+            vec![5001]
+        );
+
+        // still behind hw
+        assert!(!isr.update(5001, &OffsetInfo { leo: 15, hw: 5 }, &leader, start));
+        assert!(!isr.is_in_sync(5001));
+
+        // reached hw
+        assert!(isr.update(5001, &OffsetInfo { leo: 20, hw: 20 }, &leader, start));
+        assert!(isr.is_in_sync(5001));
+        assert_eq!(isr.count(), 2);
+
+        // unknown follower is ignored
+        assert!(!isr.update(6000, &OffsetInfo { leo: 20, hw: 20 }, &leader, start));
+    }
+}
@@ -8,7 +8,7 @@ use fluvio_protocol::{
 };
 use fluvio_storage::{
     ReplicaStorage,
-    iterators::{FileBatchIterator, FileRecordIterator, RecordItem},
+    iterators::{FileRecordIterator, RecordItem},
 };
 use futures_util::{Stream, ready, Future, FutureExt};
 use tracing::debug;
@@ -83,12 +83,9 @@ impl<S: ReplicaStorage + Send + Sync> ReplicaRevStream<S> {
         if next.is_negative() {
             return Ok(Vec::new());
         }
-        let slice = replica
-            .read_records(next, u32::MAX, Default::default())
-            .await?;
-        if let Some(file) = slice.file_slice {
-            let batch_it = FileBatchIterator::from_raw_slice(file).take(1);
-            let record_it = FileRecordIterator::new(batch_it, RECORDS_SERIALIZATION_VERSION);
+        if let Some(batch) = replica.read_batch(next, Default::default()).await? {
+            let record_it =
+                FileRecordIterator::new(std::iter::once(Ok(batch)), RECORDS_SERIALIZATION_VERSION);
             Ok(record_it.collect::<Result<Vec<_>, _>>()?)
         } else {
             Ok(Vec::new())
@@ -8,7 +8,8 @@ use tracing::{error, instrument};
 use anyhow::Result;
 
 use fluvio_controlplane_metadata::partition::{PartitionMirrorConfig, ReplicaKey};
-use fluvio_storage::{FileReplica, ReplicaStorage};
+use fluvio_storage::config::ReplicaConfig;
+use fluvio_storage::{FileReplica, ReplicaStorage, ReplicaStorageConfig};
 
 use crate::{control_plane::SharedLrsStatusUpdate, core::GlobalContext};
 use crate::config::ReplicationConfig;
@@ -119,8 +120,18 @@ impl ReplicaLeadersState<FileReplica> {
     ) -> Result<LeaderReplicaState<FileReplica>> {
         let replica_id = replica.id.clone();
 
-        let leader_replica =
-            LeaderReplicaState::create(replica, ctx.config(), status_update).await?;
+        let mut replica_config: ReplicaConfig = ctx.config().into();
+        replica_config.update_from_replica(&replica);
+        // leader must not start with empty log if its directory is offline
+        replica_config.base_dir = ctx.data_dirs().dirs().select(&replica_id, false).await?;
+
+        let leader_replica = LeaderReplicaState::create_with_storage_config(
+            replica,
+            ctx.config().into(),
+            replica_config,
+            status_update,
+        )
+        .await?;
         let leader_replica = leader_replica.init(ctx).await?;
         self.insert_leader(replica_id, leader_replica.clone()).await;
         Ok(leader_replica)
@@ -158,9 +169,16 @@ impl ReplicaLeadersState<FileReplica> {
         ctx: &GlobalContext<FileReplica>,
     ) -> Result<LeaderReplicaState<FileReplica>> {
         let replica_id = replica.id.clone();
+        let follower_consumers = follower.consumer_offset_publishers();
         let replica_storage = follower.inner_owned();
         let leader = LeaderReplicaState::new(replica, config, status_update, replica_storage);
         let leader = leader.init(ctx).await?;
+        // consumers reading from follower keep streaming from same storage
+        leader
+            .consumer_offset_publishers()
+            .lock()
+            .await
+            .append(&mut *follower_consumers.lock().await);
         self.insert_leader(replica_id, leader.clone()).await;
         Ok(leader)
     }
@@ -7,14 +7,19 @@ mod update_offsets;
 mod actions;
 mod spu;
 mod kv;
+mod isr;
 
 pub use self::leaders_state::{ReplicaLeadersState, SharedReplicaLeadersState};
-pub use self::replica_state::{SharedFileLeaderState, SharedLeaderState, LeaderReplicaState};
+pub use self::replica_state::{
+    SharedFileLeaderState, SharedLeaderState, LeaderReplicaState, CLEANUP_FREQUENCY,
+};
 pub use self::connection::FollowerHandler;
 pub use self::api_key::LeaderPeerApiEnum;
 pub use self::peer_api::LeaderPeerRequest;
 pub use self::update_offsets::UpdateOffsetRequest;
 pub use self::update_offsets::ReplicaOffsetRequest;
+pub use self::update_offsets::{LEADER_EPOCH_VERSION, TRACE_CONTEXT_VERSION};
 pub use self::kv::{LeaderKVStorage, LeaderReplicaLog};
+pub use self::isr::IsrController;
 
 pub use self::spu::*;
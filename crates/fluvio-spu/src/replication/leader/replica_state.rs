@@ -3,6 +3,7 @@ use std::{
     collections::{BTreeMap, HashSet, BinaryHeap},
     ops::{Deref, DerefMut},
     sync::Arc,
+    time::{Duration, Instant},
 };
 use std::iter::FromIterator;
 use std::fmt;
@@ -15,8 +16,9 @@ use async_lock::RwLock;
 use anyhow::{Result, Context};
 
 use fluvio_protocol::record::{RecordSet, Offset, ReplicaKey, RawRecords, Batch};
+use fluvio_protocol::link::ErrorCode;
 use fluvio_controlplane_metadata::partition::{PartitionMirrorConfig, PartitionStatus, ReplicaStatus};
-use fluvio_storage::{FileReplica, ReplicaStorage, OffsetInfo, ReplicaStorageConfig};
+use fluvio_storage::{FileReplica, ReplicaStorage, OffsetInfo};
 use fluvio_types::{
     event::offsets::{SharedOffsetPublisher, WeakSharedOffsetPublisher, TOPIC_DELETED},
     SpuId,
@@ -38,6 +40,7 @@ use crate::replication::follower::sync::{PeerFileTopicResponse, PeerFilePartitio
 use crate::storage::SharableReplicaStorage;
 
 use super::FollowerNotifier;
+use super::isr::InSyncReplicas;
 
 pub type SharedLeaderState<S> = LeaderReplicaState<S>;
 pub type SharedFileLeaderState = LeaderReplicaState<FileReplica>;
@@ -47,10 +50,12 @@ pub const CLEANUP_FREQUENCY: usize = 10;
 #[derive(Debug)]
 pub struct LeaderReplicaState<S> {
     replica: Replica,
-    in_sync_replica: u16,
+    isr: Arc<RwLock<InSyncReplicas>>,
     storage: SharableReplicaStorage<S>,
     config: ReplicationConfig,
     followers: Arc<RwLock<BTreeMap<SpuId, OffsetInfo>>>,
+    // followers which have not been sent latest log start offset
+    log_start_pending: Arc<Mutex<HashSet<SpuId>>>,
     status_update: SharedLrsStatusUpdate,
     sm_ctx: Option<SharedSmartModuleContext>,
     consumer_offset_publishers: Arc<Mutex<Vec<WeakSharedOffsetPublisher>>>,
@@ -64,7 +69,8 @@ impl<S> Clone for LeaderReplicaState<S> {
             storage: self.storage.clone(),
             config: self.config.clone(),
             followers: self.followers.clone(),
-            in_sync_replica: self.in_sync_replica,
+            log_start_pending: self.log_start_pending.clone(),
+            isr: self.isr.clone(),
             status_update: self.status_update.clone(),
             sm_ctx: self.sm_ctx.clone(),
             consumer_offset_publishers: self.consumer_offset_publishers.clone(),
@@ -110,7 +116,7 @@ where
     S: ReplicaStorage,
 {
     /// create new state from existing storage
-    /// calculate default in_sync_replica from followers
+    /// all replicas start as in-sync replicas
     pub fn new(
         replica: Replica,
         config: ReplicationConfig,
@@ -118,13 +124,13 @@ where
         inner: SharableReplicaStorage<S>,
     ) -> Uninit<Self> {
         debug!(?replica, "replica storage");
-        let in_sync_replica = replica.replicas.len() as u16;
         let follower_ids = HashSet::from_iter(replica.replicas.clone());
         let followers = ids_to_map(replica.leader, follower_ids);
         debug!(?followers, "leader followers");
+        let isr = InSyncReplicas::new(replica.leader, followers.keys().cloned(), Instant::now());
 
         debug!(
-            in_sync_replica,
+            in_sync_replica = isr.count(),
             replica = %replica.id,
             follower = ?replica.replicas,
             "creating leader"
@@ -135,7 +141,8 @@ where
             storage: inner,
             config,
             followers: Arc::new(RwLock::new(followers)),
-            in_sync_replica,
+            log_start_pending: Arc::new(Mutex::new(HashSet::new())),
+            isr: Arc::new(RwLock::new(isr)),
             status_update,
             sm_ctx: None,
             consumer_offset_publishers: Arc::new(Mutex::new(Vec::new())),
@@ -144,6 +151,7 @@ where
     }
 
     /// create new complete state and spawn controller
+    #[cfg(test)]
     pub async fn create<'a, C>(
         replica: Replica,
         config: &'a C,
@@ -153,10 +161,23 @@ where
         ReplicationConfig: From<&'a C>,
         S::ReplicaConfig: From<&'a C>,
     {
+        use fluvio_storage::ReplicaStorageConfig;
+
         let mut replica_config: S::ReplicaConfig = config.into();
         replica_config.update_from_replica(&replica);
+        Self::create_with_storage_config(replica, config.into(), replica_config, status_update)
+            .await
+    }
+
+    /// create new complete state with storage configuration already resolved
+    pub async fn create_with_storage_config(
+        replica: Replica,
+        config: ReplicationConfig,
+        replica_config: S::ReplicaConfig,
+        status_update: SharedLrsStatusUpdate,
+    ) -> Result<Uninit<LeaderReplicaState<S>>> {
         let inner = SharableReplicaStorage::create(replica.id.clone(), replica_config).await?;
-        let leader_replica = Self::new(replica, config.into(), status_update, inner);
+        let leader_replica = Self::new(replica, config, status_update, inner);
         leader_replica.0.update_status().await;
         Ok(leader_replica)
     }
@@ -176,10 +197,28 @@ where
         &self.replica
     }
 
-    /// override in sync replica
-    #[allow(unused)]
-    fn set_in_sync_replica(&mut self, replica_count: u16) {
-        self.in_sync_replica = replica_count;
+    /// minimum in-sync replicas for produce waiting for full acknowledgement,
+    /// topic setting overrides SPU setting
+    pub fn min_in_sync_replicas(&self) -> u16 {
+        self.replica
+            .min_in_sync_replicas
+            .unwrap_or(self.config.min_in_sync_replicas)
+    }
+
+    /// in-sync replicas with leader first
+    pub async fn in_sync_replicas(&self) -> Vec<SpuId> {
+        self.isr.read().await.ids()
+    }
+
+    /// check there are enough in-sync replicas to fully acknowledge records
+    pub async fn check_min_in_sync_replicas(&self) -> Result<(), ErrorCode> {
+        let in_sync = self.isr.read().await.count();
+        let min = self.min_in_sync_replicas();
+        if in_sync < min {
+            Err(ErrorCode::NotEnoughInSyncReplicas { in_sync, min })
+        } else {
+            Ok(())
+        }
     }
 
     /// update leader's state from follower's offset states
@@ -205,17 +244,17 @@ where
         // get follower info
         let mut followers = self.followers.write().await;
         let update = if let Some(current_follow_info) = followers.get_mut(&follower_id) {
-            if current_follow_info.update(&follower_pos) {
+            let changed = current_follow_info.update(&follower_pos);
+            let rejoined = self.isr.write().await.update(
+                follower_id,
+                &follower_pos,
+                &leader_pos,
+                Instant::now(),
+            );
+            if changed || rejoined {
                 // if our leo and hw is same there is no need to recompute hw
                 if !leader_pos.is_committed() {
-                    if let Some(hw) = compute_hw(&leader_pos, self.in_sync_replica, &followers) {
-                        debug!(hw, "updating hw");
-                        if let Err(err) = self.update_hw(hw).await {
-                            error!("error updating hw: {}", err);
-                        }
-                    } else {
-                        debug!("no hw change");
-                    }
+                    self.update_hw_from_isr(&leader_pos, &followers).await;
                 } else {
                     debug!("leader is committed");
                 }
@@ -239,6 +278,132 @@ where
         update
     }
 
+    /// remove followers lagging behind from in-sync replicas.
+    /// hw may advance since removed followers are no longer waited on
+    #[instrument(skip(self, notifier))]
+    pub async fn check_in_sync_replicas(&self, notifier: &FollowerNotifier) {
+        let leader_pos = self.as_offset();
+        let followers = self.followers.read().await;
+        let removed = self.isr.write().await.shrink(
+            &leader_pos,
+            &followers,
+            Duration::from_millis(self.config.lag_time_max_ms),
+            self.config.lag_max_records,
+            Instant::now(),
+        );
+        if removed.is_empty() {
+            return;
+        }
+
+        warn!(?removed, replica = %self.id(), "in-sync replicas shrunk");
+        if !leader_pos.is_committed() {
+            self.update_hw_from_isr(&leader_pos, &followers).await;
+        }
+        drop(followers);
+
+        self.notify_followers(notifier).await;
+        self.update_status().await;
+    }
+
+    /// compute hw from in-sync followers only
+    async fn update_hw_from_isr(
+        &self,
+        leader_pos: &OffsetInfo,
+        followers: &BTreeMap<SpuId, OffsetInfo>,
+    ) {
+        let isr = self.isr.read().await;
+        let hw = if isr.count() == 1 {
+            // leader is only in-sync replica
+            (leader_pos.leo > leader_pos.hw).then_some(leader_pos.leo)
+        } else {
+            let in_sync_followers: BTreeMap<SpuId, OffsetInfo> = followers
+                .iter()
+                .filter(|(follower_id, _)| isr.is_in_sync(**follower_id))
+                .map(|(follower_id, follower_pos)| (*follower_id, follower_pos.clone()))
+                .collect();
+            compute_hw(leader_pos, isr.count(), &in_sync_followers)
+        };
+        drop(isr);
+
+        if let Some(hw) = hw {
+            debug!(hw, "updating hw");
+            if let Err(err) = self.update_hw(hw).await {
+                error!("error updating hw: {}", err);
+            }
+        } else {
+            debug!("no hw change");
+        }
+    }
+
+    /// check follower's log against leader epochs.
+    /// Follower has diverged if it has records beyond end of its latest epoch in leader's log
+    /// or if its latest epoch was never seen by leader.
+    /// In this case, follower must be truncated to end of largest leader epoch which is not newer than follower's epoch.
+    /// Follower's offsets are reset so no records are sent until it reports offsets after truncation.
+    /// return leader epoch and its end offset
+    #[instrument(skip(self))]
+    pub async fn follower_truncate_offset(
+        &self,
+        follower_id: SpuId,
+        follower_leo: Offset,
+        follower_epoch: i32,
+    ) -> Option<(i32, Offset)> {
+        let leo = self.leo();
+        let (epoch, end_offset) = if follower_epoch < 0 {
+            (-1, leo)
+        } else {
+            self.epoch_end_offset(follower_epoch)
+                .await
+                .map(|(epoch, end_offset)| (epoch, min(end_offset, leo)))
+                .unwrap_or((-1, leo))
+        };
+
+        let diverged = follower_leo > end_offset || (epoch >= 0 && epoch != follower_epoch);
+        if !diverged {
+            return None;
+        }
+
+        warn!(
+            follower_id,
+            follower_leo, epoch, end_offset, "follower log has diverged, truncating"
+        );
+        if let Some(follower_info) = self.followers.write().await.get_mut(&follower_id) {
+            *follower_info = OffsetInfo::default();
+        }
+        Some((epoch, end_offset))
+    }
+
+    /// delete records before offset and replicate new log start offset to followers.
+    /// only committed records can be deleted
+    #[instrument(skip(self, notifier))]
+    pub async fn delete_records(
+        &self,
+        offset: Offset,
+        notifier: &FollowerNotifier,
+    ) -> Result<Offset, ErrorCode> {
+        let hw = self.hw();
+        if offset < 0 || offset > hw {
+            warn!(offset, hw, replica = %self.id(), "delete offset is out of range");
+            return Err(ErrorCode::OffsetOutOfRange);
+        }
+
+        let log_start = self.storage.delete_records(offset).await.map_err(|err| {
+            error!(%err, replica = %self.id(), "error deleting records");
+            ErrorCode::StorageError
+        })?;
+
+        let followers: Vec<SpuId> = self.followers.read().await.keys().cloned().collect();
+        self.log_start_pending
+            .lock()
+            .await
+            .extend(followers.iter().cloned());
+        for follower in &followers {
+            notifier.notify_follower(follower, self.id().clone()).await;
+        }
+        self.update_status().await;
+        Ok(log_start)
+    }
+
     /// compute follower that needs to be updated
     /// based on leader's state
     pub async fn follower_updates(
@@ -250,7 +415,13 @@ where
 
         let reader = self.followers.read().await;
         if let Some(follower_info) = reader.get(follower_id) {
-            if follower_info.is_valid() && !follower_info.is_same(&leader_offset) {
+            let mut log_start_pending = self.log_start_pending.lock().await;
+            if follower_info.is_valid()
+                && (!follower_info.is_same(&leader_offset)
+                    || log_start_pending.contains(follower_id))
+            {
+                log_start_pending.remove(follower_id);
+                drop(log_start_pending);
                 let mut topic_response = PeerFileTopicResponse {
                     name: self.id().topic.to_owned(),
                     ..Default::default()
@@ -275,8 +446,8 @@ where
                             );
                             partition_response.hw = slice.end.hw;
                             partition_response.leo = slice.end.leo;
-                            if let Some(file_slice) = slice.file_slice {
-                                partition_response.records = file_slice.into();
+                            if let Some(records) = slice.records() {
+                                partition_response.records = records;
                             }
                         }
                         Err(err) => {
@@ -296,6 +467,8 @@ where
                 // ensure leo and hw are set correctly. storage might have update last stable offset
                 partition_response.leo = leader_offset.leo;
                 partition_response.hw = leader_offset.hw;
+                partition_response.log_start_offset =
+                    self.storage.read().await.get_log_start_offset();
 
                 topic_response.partitions.push(partition_response);
                 Some(topic_response)
@@ -325,8 +498,10 @@ where
             .try_into()
             .unwrap_or(PartitionStatus::SIZE_ERROR);
         let base_offset = storage_reader.get_log_start_offset();
+        drop(storage_reader);
 
         LrsRequest::new(self.id().to_owned(), leader, replicas, size, base_offset)
+            .with_isr(self.in_sync_replicas().await)
     }
 
     #[instrument(skip(self))]
@@ -354,10 +529,13 @@ where
             return Ok((self.hw(), self.leo(), 0));
         }
 
-        let offsets = self
-            .storage
-            .write_record_set(records, self.in_sync_replica == 1)
-            .await?;
+        for batch in records.batches.iter_mut() {
+            batch.get_mut_header().partition_leader_epoch = self.replica.leader_epoch;
+        }
+
+        // without followers in sync, records are committed as soon as they are written
+        let hw_update = self.isr.read().await.count() == 1;
+        let offsets = self.storage.write_record_set(records, hw_update).await?;
 
         self.notify_followers(notifiers).await;
         self.update_status().await;
@@ -403,8 +581,7 @@ where
         self.followers.read().await.keys().cloned().collect()
     }
 
-    // get copy of followers_info for debugging
-    #[allow(unused)]
+    // get copy of followers_info
     pub async fn followers_info(&self) -> BTreeMap<SpuId, OffsetInfo> {
         self.followers.read().await.clone()
     }
@@ -477,6 +654,10 @@ where
 {
     pub async fn init(self, ctx: &GlobalContext<FileReplica>) -> Result<LeaderReplicaState<S>> {
         let mut state = self.0;
+        state
+            .storage
+            .assign_leader_epoch(state.replica.leader_epoch)
+            .await?;
         if let Some(dedup) = &state.replica.deduplication {
             debug!(?state.replica.deduplication, "init leader smartmodule context");
             let dedup_filter = dedup_to_invocation(dedup);
@@ -857,6 +1038,8 @@ mod test_leader {
     #[derive(Default)]
     struct MockStorage {
         pos: OffsetInfo,
+        epoch: i32,
+        log_start: Offset,
     }
 
     impl From<&SpuConfig> for MockConfig {
@@ -873,6 +1056,8 @@ mod test_leader {
         ) -> Result<Self> {
             Ok(MockStorage {
                 pos: OffsetInfo { leo: 0, hw: 0 },
+                epoch: -1,
+                log_start: 0,
             })
         }
 
@@ -926,7 +1111,43 @@ mod test_leader {
         type ReplicaConfig = MockConfig;
 
         fn get_log_start_offset(&self) -> Offset {
-            (self.pos.hw * 10) as Offset
+            self.log_start
+        }
+
+        fn get_leader_epoch(&self) -> i32 {
+            self.epoch
+        }
+
+        // single epoch which covers entire log
+        fn get_epoch_end_offset(&self, epoch: i32) -> Option<(i32, Offset)> {
+            if self.epoch >= 0 && epoch >= self.epoch {
+                Some((self.epoch, self.pos.leo))
+            } else {
+                None
+            }
+        }
+
+        async fn assign_leader_epoch(
+            &mut self,
+            epoch: i32,
+        ) -> Result<bool, fluvio_storage::StorageError> {
+            if epoch > self.epoch {
+                self.epoch = epoch;
+                Ok(true)
+            } else {
+                Ok(false)
+            }
+        }
+
+        async fn truncate_to(&mut self, offset: Offset) -> Result<Offset> {
+            self.pos.leo = min(self.pos.leo, offset);
+            self.pos.hw = min(self.pos.hw, self.pos.leo);
+            Ok(self.pos.leo)
+        }
+
+        async fn delete_records(&mut self, offset: Offset) -> Result<Offset> {
+            self.log_start = self.log_start.max(min(offset, self.pos.hw));
+            Ok(self.log_start)
         }
 
         async fn remove(&self) -> Result<(), fluvio_storage::StorageError> {
@@ -952,7 +1173,7 @@ mod test_leader {
         .expect("state")
         .0;
 
-        assert_eq!(state.in_sync_replica, 1);
+        assert_eq!(state.in_sync_replicas().await, vec![5000]);
     }
 
     #[fluvio_future::test]
@@ -1036,6 +1257,59 @@ mod test_leader {
         assert!(state.follower_updates(&5001, MAX_BYTES).await.is_some()); // 5001 is still need to besync
     }
 
+    #[fluvio_future::test]
+    async fn test_delete_records() {
+        let leader_config = SpuConfig {
+            id: 5000,
+            ..Default::default()
+        };
+
+        let notifier = FollowerNotifier::shared();
+
+        let replica: ReplicaKey = ("test", 1).into();
+        let state: LeaderReplicaState<MockStorage> = LeaderReplicaState::create(
+            Replica::new(replica, 5000, vec![5000, 5001]),
+            &leader_config,
+            StatusLrsMessageSink::shared(),
+        )
+        .await
+        .expect("state")
+        .0;
+
+        state
+            .write_record_set(&mut create_raw_recordset(10), &notifier)
+            .await
+            .expect("write");
+        state.update_hw(10).await.expect("hw");
+
+        // follower is fully caught up
+        state
+            .followers
+            .write()
+            .await
+            .get_mut(&5001)
+            .expect("map")
+            .update(&OffsetInfo { leo: 10, hw: 10 });
+        assert!(state.follower_updates(&5001, MAX_BYTES).await.is_none());
+
+        // uncommitted records can't be deleted
+        assert!(matches!(
+            state.delete_records(11, &notifier).await,
+            Err(ErrorCode::OffsetOutOfRange)
+        ));
+        assert_eq!(state.delete_records(4, &notifier).await.expect("delete"), 4);
+        assert_eq!(state.start_offset_info().await, (4, 10));
+
+        // new log start is sent to follower once
+        let updates = state
+            .follower_updates(&5001, MAX_BYTES)
+            .await
+            .expect("some");
+        assert_eq!(updates.partitions[0].log_start_offset, 4);
+        assert_eq!(updates.partitions[0].hw, 10);
+        assert!(state.follower_updates(&5001, MAX_BYTES).await.is_none());
+    }
+
     #[fluvio_future::test]
     async fn test_update_leader_from_followers() {
         use crate::core::GlobalContext;
@@ -1206,4 +1480,110 @@ mod test_leader {
         assert!(f1.drain_replicas().await.is_empty());
         assert!(f2.drain_replicas().await.is_empty());
     }
+
+    #[fluvio_future::test]
+    async fn test_isr_shrink_and_expand() {
+        let mut leader_config = SpuConfig {
+            id: 5000,
+            ..Default::default()
+        };
+        leader_config.replication.lag_time_max_ms = 0;
+
+        let notifier = FollowerNotifier::shared();
+
+        let mut replica = Replica::new(("test", 1), 5000, vec![5000, 5001, 5002]);
+        replica.min_in_sync_replicas = Some(3);
+        let state: LeaderReplicaState<MockStorage> =
+            LeaderReplicaState::create(replica, &leader_config, StatusLrsMessageSink::shared())
+                .await
+                .expect("state")
+                .0;
+        assert_eq!(state.in_sync_replicas().await, vec![5000, 5001, 5002]);
+        assert!(state.check_min_in_sync_replicas().await.is_ok());
+
+        state
+            .write_record_set(&mut create_raw_recordset(10), &notifier)
+            .await
+            .expect("write");
+        assert!(
+            state
+                .update_states_from_followers(5001, OffsetInfo { leo: 10, hw: 0 }, &notifier)
+                .await
+        );
+        // 5002 has not replicated yet
+        assert_eq!(state.hw(), 0);
+
+        // 5002 is lagging, removing it allows hw to advance
+        fluvio_future::timer::sleep(Duration::from_millis(1)).await;
+        state.check_in_sync_replicas(&notifier).await;
+        assert_eq!(state.in_sync_replicas().await, vec![5000, 5001]);
+        assert_eq!(state.hw(), 10);
+        assert_eq!(
+            state.check_min_in_sync_replicas().await,
+            Err(ErrorCode::NotEnoughInSyncReplicas { in_sync: 2, min: 3 })
+        );
+
+        // 5002 catches up to hw and rejoins
+        assert!(
+            state
+                .update_states_from_followers(5002, OffsetInfo { leo: 10, hw: 10 }, &notifier)
+                .await
+        );
+        assert_eq!(state.in_sync_replicas().await, vec![5000, 5001, 5002]);
+        assert!(state.check_min_in_sync_replicas().await.is_ok());
+    }
+
+    #[fluvio_future::test]
+    async fn test_follower_truncate_offset() {
+        let leader_config = SpuConfig {
+            id: 5000,
+            ..Default::default()
+        };
+
+        let notifier = FollowerNotifier::shared();
+
+        let mut replica = Replica::new(("test", 1), 5000, vec![5001]);
+        replica.leader_epoch = 2;
+        let state: LeaderReplicaState<MockStorage> =
+            LeaderReplicaState::create(replica, &leader_config, StatusLrsMessageSink::shared())
+                .await
+                .expect("state")
+                .0;
+        state
+            .assign_leader_epoch(state.get_replica().leader_epoch)
+            .await
+            .expect("epoch");
+        assert_eq!(state.leader_epoch(), 2);
+
+        state
+            .write_record_set(&mut create_raw_recordset(10), &notifier)
+            .await
+            .expect("write");
+        assert_eq!(state.leo(), 10);
+
+        let mut followers = state.followers.write().await;
+        followers
+            .get_mut(&5001)
+            .expect("map")
+            .update(&OffsetInfo { leo: 8, hw: 0 });
+        drop(followers);
+
+        // follower is behind leader
+        assert!(state.follower_truncate_offset(5001, 8, 2).await.is_none());
+        assert!(state.follower_truncate_offset(5001, 8, -1).await.is_none());
+
+        // follower has records which leader doesn't have
+        assert_eq!(
+            state.follower_truncate_offset(5001, 12, 2).await,
+            Some((2, 10))
+        );
+        assert!(!state.followers_info().await.get(&5001).unwrap().is_valid());
+        assert!(state.follower_updates(&5001, MAX_BYTES).await.is_none());
+
+        // follower has epoch which leader never had
+        assert_eq!(
+            state.follower_truncate_offset(5001, 8, 3).await,
+            Some((2, 10))
+        );
+    }
 }
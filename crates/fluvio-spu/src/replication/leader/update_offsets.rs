@@ -7,6 +7,12 @@ use fluvio_controlplane_metadata::partition::ReplicaKey;
 
 use super::LeaderPeerApiEnum;
 
+/// first version where follower sends leader epoch and can handle truncation
+pub const LEADER_EPOCH_VERSION: i16 = 2;
+
+/// first version where follower accepts trace context following sync request
+pub const TRACE_CONTEXT_VERSION: i16 = 3;
+
 #[derive(Decoder, Encoder, Default, Debug)]
 pub struct UpdateOffsetRequest {
     pub replicas: Vec<ReplicaOffsetRequest>,
@@ -14,15 +20,30 @@ pub struct UpdateOffsetRequest {
 
 impl Request for UpdateOffsetRequest {
     const API_KEY: u16 = LeaderPeerApiEnum::UpdateOffsets as u16;
-    const DEFAULT_API_VERSION: i16 = 0;
+    const DEFAULT_API_VERSION: i16 = TRACE_CONTEXT_VERSION;
     type Response = UpdateOffsetResponse;
 }
 
-#[derive(Decoder, Encoder, Default, Clone, Debug)]
+#[derive(Decoder, Encoder, Clone, Debug)]
 pub struct ReplicaOffsetRequest {
     pub replica: ReplicaKey,
     pub leo: Offset,
     pub hw: Offset,
+    /// leader epoch of follower's last record, -1 if unknown.
+    /// version 1 is used by mirroring which doesn't have epochs
+    #[fluvio(min_version = 2)]
+    pub leader_epoch: i32,
+}
+
+impl Default for ReplicaOffsetRequest {
+    fn default() -> Self {
+        Self {
+            replica: ReplicaKey::default(),
+            leo: 0,
+            hw: 0,
+            leader_epoch: -1,
+        }
+    }
 }
 
 // no content, this is one way request
@@ -9,6 +9,7 @@ use fluvio_spu_schema::server::SpuServerApiKey;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetsRequest;
 use fluvio_spu_schema::server::stream_fetch::DefaultStreamFetchRequest;
 use fluvio_spu_schema::server::update_offset::UpdateOffsetsRequest;
+use fluvio_spu_schema::server::delete_records::DeleteRecordsRequest;
 use fluvio_spu_schema::{ApiVersionsRequest, ApiVersionsResponse};
 
 #[instrument(skip(request))]
@@ -17,6 +18,12 @@ pub async fn handle_api_version_request(
 ) -> Result<ResponseMessage<ApiVersionsResponse>> {
     let client_version = &request.request.client_version;
     let mut response = ApiVersionsResponse::default();
+    // listed so clients know trace context is accepted
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::ApiVersion,
+        ApiVersionsRequest::MIN_API_VERSION,
+        ApiVersionsRequest::MAX_API_VERSION,
+    ));
     response.api_keys.push(make_version_key(
         SpuServerApiKey::Produce,
         DefaultProduceRequest::MIN_API_VERSION,
@@ -47,6 +54,11 @@ pub async fn handle_api_version_request(
         0,
         UpdateOffsetsRequest::DEFAULT_API_VERSION,
     ));
+    response.api_keys.push(make_version_key(
+        SpuServerApiKey::DeleteRecords,
+        DeleteRecordsRequest::DEFAULT_API_VERSION,
+        DeleteRecordsRequest::DEFAULT_API_VERSION,
+    ));
 
     trace!("Returning ApiVersionsResponse: {:#?}", &response);
     Ok(request.new_response(response))
@@ -1,14 +1,18 @@
+use fluvio_protocol::api::RequestHeader;
+
 use crate::services::public::StreamPublishers;
 
 #[derive(Debug)]
 pub(crate) struct ConnectionContext {
     stream_publishers: StreamPublishers,
+    principal: Option<String>,
 }
 
 impl ConnectionContext {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(principal: Option<String>) -> Self {
         Self {
             stream_publishers: StreamPublishers::new(),
+            principal,
         }
     }
 
@@ -19,4 +23,10 @@ impl ConnectionContext {
     pub(crate) fn stream_publishers_mut(&mut self) -> &mut StreamPublishers {
         &mut self.stream_publishers
     }
+
+    /// client which quotas are applied to, authenticated principal takes
+    /// precedence over client id of request
+    pub(crate) fn quota_client<'a>(&'a self, header: &'a RequestHeader) -> &'a str {
+        self.principal.as_deref().unwrap_or(header.client_id())
+    }
 }
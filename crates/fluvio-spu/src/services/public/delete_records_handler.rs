@@ -0,0 +1,51 @@
+use std::io::Error as IoError;
+
+use tracing::{debug, instrument};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_spu_schema::server::delete_records::DeleteRecordsRequest;
+use fluvio_spu_schema::server::delete_records::DeleteRecordsResponse;
+use fluvio_auth::{AuthContext, InstanceAction};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::services::public::allow_topic_action;
+
+#[instrument(skip(req_msg, ctx, auth))]
+pub(crate) async fn handle_delete_records_request<AC: AuthContext>(
+    req_msg: RequestMessage<DeleteRecordsRequest>,
+    ctx: DefaultSharedGlobalContext,
+    auth: &AC,
+) -> Result<ResponseMessage<DeleteRecordsResponse>, IoError> {
+    let DeleteRecordsRequest { replica_id, offset } = &req_msg.request;
+
+    if !allow_topic_action(auth, InstanceAction::Delete, &replica_id.topic).await? {
+        return Ok(req_msg.new_response(DeleteRecordsResponse {
+            error_code: ErrorCode::PermissionDenied,
+            ..Default::default()
+        }));
+    }
+
+    let response = match ctx.leaders_state().get(replica_id).await {
+        Some(leader) => match leader
+            .delete_records(*offset, ctx.follower_notifier())
+            .await
+        {
+            Ok(log_start_offset) => DeleteRecordsResponse {
+                error_code: ErrorCode::None,
+                log_start_offset,
+            },
+            Err(error_code) => DeleteRecordsResponse {
+                error_code,
+                log_start_offset: leader.start_offset_info().await.0,
+            },
+        },
+        None => DeleteRecordsResponse {
+            error_code: ErrorCode::PartitionNotLeader,
+            ..Default::default()
+        },
+    };
+
+    debug!(%replica_id, ?response, "delete records result");
+    Ok(req_msg.new_response(response))
+}
@@ -11,21 +11,25 @@ use fluvio_spu_schema::fetch::{
     FetchablePartitionResponse, FetchPartition, FetchableTopic, FetchableTopicResponse,
 };
 use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_auth::{AuthContext, InstanceAction};
 
 use crate::core::DefaultSharedGlobalContext;
 use crate::traffic::TrafficType;
 
+use super::allow_topic_action;
+
 /// perform log fetch request using zero copy write
 #[instrument(
-    skip(request, ctx, sink),
+    skip(request, ctx, sink, auth),
     fields(
         max_bytes = request.request.max_bytes,
     ),
 )]
-pub async fn handle_fetch_request(
+pub async fn handle_fetch_request<AC: AuthContext>(
     request: RequestMessage<FileFetchRequest>,
     ctx: DefaultSharedGlobalContext,
     sink: ExclusiveFlvSink,
+    auth: &AC,
 ) -> Result<()> {
     let (header, fetch_request) = request.get_header_request();
     trace!("Handling FileFetchRequest: {:#?}", fetch_request);
@@ -33,7 +37,12 @@ pub async fn handle_fetch_request(
 
     for topic_request in &fetch_request.topics {
         let topic_response =
-            handle_fetch_topic(&ctx, &fetch_request, topic_request, header.is_connector()).await?;
+            if allow_topic_action(auth, InstanceAction::Consume, &topic_request.name).await? {
+                handle_fetch_topic(&ctx, &fetch_request, topic_request, header.is_connector())
+                    .await?
+            } else {
+                denied_fetch_topic(topic_request)
+            };
         fetch_response.topics.push(topic_response);
     }
 
@@ -85,6 +94,23 @@ async fn handle_fetch_topic(
     Ok(topic_response)
 }
 
+/// every partition of topic is rejected, principal is not allowed to consume it
+fn denied_fetch_topic(topic_request: &FetchableTopic) -> FetchableTopicResponse<FileRecordSet> {
+    FileTopicResponse {
+        name: topic_request.name.clone(),
+        partitions: topic_request
+            .fetch_partitions
+            .iter()
+            .map(|partition_request| FilePartitionResponse {
+                partition_index: partition_request.partition_index,
+                error_code: ErrorCode::PermissionDenied,
+                ..Default::default()
+            })
+            .collect(),
+        ..Default::default()
+    }
+}
+
 #[instrument(
 skip(ctx, replica_id, partition_request),
     fields(%replica_id)
@@ -127,13 +153,13 @@ async fn handle_fetch_partition(
             partition_response.high_watermark = slice.end.hw;
             partition_response.log_start_offset = slice.start;
 
-            if let Some(file_slice) = slice.file_slice {
+            if let Some(records) = slice.records() {
                 metrics.outbound().increase(
                     is_connector,
                     (slice.end.hw - slice.start) as u64,
-                    file_slice.len(),
+                    records.len() as u64,
                 );
-                partition_response.records = file_slice.into();
+                partition_response.records = records;
             }
         }
         Err(err) => {
@@ -5,6 +5,7 @@ mod offset_request;
 mod offset_update;
 mod stream_fetch;
 mod consumer_handler;
+mod delete_records_handler;
 
 #[cfg(test)]
 mod tests;
@@ -12,7 +13,8 @@ mod conn_context;
 
 use std::sync::Arc;
 use async_trait::async_trait;
-use fluvio_auth::Authorization;
+use fluvio_auth::{AuthContext, Authorization, InstanceAction};
+use fluvio_controlplane_metadata::extended::ObjectType;
 use fluvio_protocol::api::Request;
 use fluvio_protocol::api::RequestMessage;
 use fluvio_protocol::link::ErrorCode;
@@ -40,6 +42,7 @@ use self::produce_handler::handle_produce_request;
 use self::fetch_handler::handle_fetch_request;
 use self::offset_request::handle_offset_request;
 use self::offset_update::handle_offset_update;
+use self::delete_records_handler::handle_delete_records_request;
 use self::stream_fetch::{StreamFetchHandler, publishers::StreamPublishers};
 use self::conn_context::ConnectionContext;
 use std::fmt::Debug;
@@ -103,6 +106,7 @@ where
                 io_error
             })?;
         let service_context = SpuAuthServiceContext::new(context.global_ctx.clone(), auth_context);
+        let _connection = context.global_ctx.metrics().connection_guard();
         let mut mirror_request: Option<RequestMessage<StartMirrorRequest>> = None;
         let shutdown = StickyEvent::shared();
         let (sink, mut stream) = socket.split();
@@ -111,9 +115,11 @@ where
         {
             let api_stream = stream.api_stream::<SpuServerRequest, SpuServerApiKey>();
             let mut event_stream = api_stream.take_until(shutdown.listen_pinned());
-            let mut conn_ctx = ConnectionContext::new();
+            let mut conn_ctx =
+                ConnectionContext::new(service_context.auth.principal().map(str::to_owned));
 
             let context = &context.global_ctx;
+            let auth = &service_context.auth;
 
             loop {
                 let event = event_stream.next().await;
@@ -135,17 +141,22 @@ where
                             ),
                             SpuServerRequest::ProduceRequest(request) => call_service!(
                                 request,
-                                handle_produce_request(request, context.clone()),
+                                handle_produce_request(request, context.clone(), &conn_ctx, auth),
                                 shared_sink,
                                 "ProduceRequest"
                             ),
                             SpuServerRequest::FileFetchRequest(request) => {
-                                handle_fetch_request(request, context.clone(), shared_sink.clone())
-                                    .await?
+                                handle_fetch_request(
+                                    request,
+                                    context.clone(),
+                                    shared_sink.clone(),
+                                    auth,
+                                )
+                                .await?
                             }
                             SpuServerRequest::FetchOffsetsRequest(request) => call_service!(
                                 request,
-                                handle_offset_request(request, context.clone()),
+                                handle_offset_request(request, context.clone(), auth),
                                 shared_sink,
                                 "FetchOffsetsRequest"
                             ),
@@ -154,6 +165,7 @@ where
                                     request,
                                     context.clone(),
                                     &mut conn_ctx,
+                                    auth,
                                     shared_sink.clone(),
                                     shutdown.clone(),
                                 )
@@ -193,6 +205,12 @@ where
                                     "FetchConsumersRequest"
                                 )
                             }
+                            SpuServerRequest::DeleteRecordsRequest(request) => call_service!(
+                                request,
+                                handle_delete_records_request(request, context.clone(), auth),
+                                shared_sink,
+                                "DeleteRecordsRequest"
+                            ),
                             SpuServerRequest::StartMirrorRequest(request) => {
                                 // send mirror mode, afer that mirror cycle will be started
                                 mirror_request = Some(request);
@@ -225,6 +243,21 @@ where
     }
 }
 
+/// check if principal of the connection may perform action on topic
+pub(crate) async fn allow_topic_action<AC: AuthContext>(
+    auth: &AC,
+    action: InstanceAction,
+    topic: &str,
+) -> Result<bool, std::io::Error> {
+    let allowed = auth
+        .allow_instance_action(ObjectType::Topic, action.clone(), topic)
+        .await?;
+    if !allowed {
+        debug!(principal = ?auth.principal(), topic, ?action, "permission denied");
+    }
+    Ok(allowed)
+}
+
 async fn send_private_request_to_leader<R: Request>(
     ctx: &DefaultSharedGlobalContext,
     replica_id: &ReplicaKey,
@@ -10,21 +10,27 @@ use fluvio_spu_schema::server::fetch_offset::FetchOffsetsRequest;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetTopicResponse;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetsResponse;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetPartitionResponse;
+use fluvio_spu_schema::server::fetch_offset::CONSUMER_LAG_API;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::Offset;
+use fluvio_auth::{AuthContext, InstanceAction};
 
 use crate::core::DefaultSharedGlobalContext;
 use crate::kv::consumer::ConsumerOffsetKey;
+use crate::replication::leader::SharedFileLeaderState;
 use crate::services::internal::FetchConsumerOffsetRequest;
-use crate::services::public::send_private_request_to_leader;
+use crate::services::public::{allow_topic_action, send_private_request_to_leader};
 
-#[instrument(skip(req_msg, ctx))]
-pub async fn handle_offset_request(
+#[instrument(skip(req_msg, ctx, auth))]
+pub async fn handle_offset_request<AC: AuthContext>(
     req_msg: RequestMessage<FetchOffsetsRequest>,
     ctx: DefaultSharedGlobalContext,
+    auth: &AC,
 ) -> Result<ResponseMessage<FetchOffsetsResponse>, IoError> {
     let request = req_msg.request();
     trace!("handling flv fetch request: {:#?}", request);
+    let with_timestamps = req_msg.header.api_version() >= CONSUMER_LAG_API;
 
     let mut response = FetchOffsetsResponse::default();
 
@@ -36,6 +42,20 @@ pub async fn handle_offset_request(
             ..Default::default()
         };
 
+        if !allow_topic_action(auth, InstanceAction::Read, topic).await? {
+            topic_response.partitions = topic_request
+                .partitions
+                .iter()
+                .map(|partition_req| FetchOffsetPartitionResponse {
+                    partition_index: partition_req.partition_index,
+                    error_code: ErrorCode::PermissionDenied,
+                    ..Default::default()
+                })
+                .collect();
+            response.topics.push(topic_response);
+            continue;
+        }
+
         for partition_req in &topic_request.partitions {
             let partition = &partition_req.partition_index;
             let mut partition_response = FetchOffsetPartitionResponse {
@@ -49,6 +69,21 @@ pub async fn handle_offset_request(
                 partition_response.error_code = ErrorCode::None;
                 partition_response.start_offset = start_offset;
                 partition_response.last_stable_offset = hw;
+                partition_response.log_end_offset = replica.leo();
+
+                if let Some(offset) = partition_req.timestamp_offset.filter(|_| with_timestamps) {
+                    if let Err(err) = batch_timestamps(
+                        replica,
+                        offset.max(start_offset),
+                        hw,
+                        &mut partition_response,
+                    )
+                    .await
+                    {
+                        error!(%rep_id, offset, "reading batch timestamps failed: {err:?}");
+                        partition_response.error_code = err;
+                    }
+                }
 
                 // This is only for compatibility with older clients
                 // now we're usign `FetchConsumerOffsetsRequest` to fetch consumer offset
@@ -83,6 +118,28 @@ pub async fn handle_offset_request(
     Ok(req_msg.new_response(response))
 }
 
+/// timestamps of the batch containing offset and of the last committed batch,
+/// left empty if there are no committed records at offset
+async fn batch_timestamps(
+    replica: &SharedFileLeaderState,
+    offset: Offset,
+    hw: Offset,
+    partition_response: &mut FetchOffsetPartitionResponse,
+) -> Result<(), ErrorCode> {
+    if offset >= hw {
+        return Ok(());
+    }
+    partition_response.offset_timestamp = replica
+        .batch_header(offset)
+        .await?
+        .map(|header| header.first_timestamp);
+    partition_response.last_timestamp = replica
+        .batch_header(hw - 1)
+        .await?
+        .map(|header| header.max_time_stamp);
+    Ok(())
+}
+
 async fn fetch_consumer_offset(
     ctx: &DefaultSharedGlobalContext,
     topic: &str,
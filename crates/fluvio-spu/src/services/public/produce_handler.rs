@@ -1,15 +1,18 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use fluvio_controlplane::sc_api::update_partition::PartitionStatRequest;
 use tokio::select;
-use tracing::{debug, trace, error};
+use tracing::{debug, trace, error, warn};
 use tracing::instrument;
 use anyhow::{anyhow, Result};
 
+use fluvio_protocol::Encoder;
 use fluvio_protocol::api::{RequestKind, RequestHeader};
 use fluvio_spu_schema::Isolation;
 use fluvio_protocol::record::{BatchRecords, Offset, Batch, RawRecords};
 use fluvio::Compression;
+use fluvio_compression::ZstdDictionary;
+use fluvio_controlplane::replica::Replica;
 use fluvio_controlplane_metadata::topic::CompressionAlgorithm;
 use fluvio_storage::StorageError;
 use fluvio_spu_schema::produce::{
@@ -21,10 +24,12 @@ use fluvio_protocol::{api::RequestMessage, link::ErrorCode};
 use fluvio_protocol::api::ResponseMessage;
 use fluvio_protocol::record::RecordSet;
 use fluvio_controlplane_metadata::partition::{PartitionResolution, ReplicaKey};
+use fluvio_auth::{AuthContext, InstanceAction};
 
 use fluvio_future::timer::sleep;
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::core::quota::QuotaKind;
 use crate::replication::leader::SharedFileLeaderState;
 use crate::smartengine::batch::process_batch;
 use crate::smartengine::context::SmartModuleContext;
@@ -34,6 +39,9 @@ use crate::smartengine::produce_batch::ProduceBatchIterator;
 
 use crate::traffic::TrafficType;
 
+use super::allow_topic_action;
+use super::conn_context::ConnectionContext;
+
 struct TopicWriteResult {
     topic: String,
     partitions: Vec<PartitionWriteResult>,
@@ -48,25 +56,42 @@ struct PartitionWriteResult {
 }
 
 #[instrument(
-    skip(request,ctx),
+    skip(request,ctx,conn_ctx,auth),
     fields(
         id = request.header.correlation_id(),
         client = %request.header.client_id()
     )
 )]
-pub async fn handle_produce_request(
+pub(crate) async fn handle_produce_request<AC: AuthContext>(
     request: RequestMessage<DefaultProduceRequest>,
     ctx: DefaultSharedGlobalContext,
+    conn_ctx: &ConnectionContext,
+    auth: &AC,
 ) -> Result<ResponseMessage<ProduceResponse>> {
+    let started = Instant::now();
     let (header, produce_request) = request.get_header_request();
     trace!("Handling ProduceRequest: {:#?}", produce_request);
 
+    let throttle = record_produce_quota(
+        &ctx,
+        conn_ctx.quota_client(&header),
+        &produce_request,
+        &header,
+    );
+
     let smartmodules = produce_request.smartmodules;
 
     let mut topic_results = Vec::with_capacity(produce_request.topics.len());
     for topic_request in produce_request.topics.into_iter() {
-        let topic_result =
-            handle_produce_topic(&ctx, topic_request, &smartmodules, &header).await?;
+        let topic_result = handle_produce_topic(
+            &ctx,
+            auth,
+            topic_request,
+            &smartmodules,
+            &header,
+            &produce_request.isolation,
+        )
+        .await?;
         topic_results.push(topic_result);
     }
     wait_for_acks(
@@ -76,20 +101,50 @@ pub async fn handle_produce_request(
         &ctx,
     )
     .await;
-    let response = into_response(topic_results);
+    let mut response = into_response(topic_results);
+    ctx.metrics()
+        .produce_latency()
+        .observe_duration(started.elapsed());
+    if !throttle.is_zero() {
+        // request is not delayed, client backs off for throttle time before sending more
+        debug!(throttle_ms = throttle.as_millis(), "produce over quota");
+        response.throttle_time_ms = throttle.as_millis() as i32;
+    }
     trace!("Returning ProduceResponse: {:#?}", &response);
     Ok(RequestMessage::<DefaultProduceRequest>::response_with_header(&header, response))
 }
 
+/// record request and produced bytes against client quotas, return longest throttle
+fn record_produce_quota(
+    ctx: &DefaultSharedGlobalContext,
+    client: &str,
+    produce_request: &DefaultProduceRequest,
+    header: &RequestHeader,
+) -> Duration {
+    let bytes: usize = produce_request
+        .topics
+        .iter()
+        .flat_map(|topic| topic.partitions.iter())
+        .map(|partition| partition.records.write_size(header.api_version()))
+        .sum();
+
+    let quotas = ctx.quotas();
+    quotas
+        .record(client, QuotaKind::Requests, 1)
+        .max(quotas.record(client, QuotaKind::ProduceBytes, bytes as u64))
+}
+
 #[instrument(
-    skip(ctx, topic_request, smartmodules, header),
+    skip(ctx, auth, topic_request, smartmodules, header, isolation),
     fields(topic = %topic_request.name),
 )]
-async fn handle_produce_topic(
+async fn handle_produce_topic<AC: AuthContext>(
     ctx: &DefaultSharedGlobalContext,
+    auth: &AC,
     topic_request: DefaultTopicRequest,
     smartmodules: &[SmartModuleInvocation],
     header: &RequestHeader,
+    isolation: &Isolation,
 ) -> Result<TopicWriteResult> {
     let topic = &topic_request.name;
 
@@ -100,6 +155,20 @@ async fn handle_produce_topic(
         partitions: vec![],
     };
 
+    if !allow_topic_action(auth, InstanceAction::Produce, topic).await? {
+        topic_result.partitions = topic_request
+            .partitions
+            .iter()
+            .map(|partition_request| {
+                PartitionWriteResult::error(
+                    ReplicaKey::new(topic.clone(), partition_request.partition_index),
+                    ErrorCode::PermissionDenied,
+                )
+            })
+            .collect();
+        return Ok(topic_result);
+    }
+
     for mut partition_request in topic_request.partitions.into_iter() {
         let replica_id = ReplicaKey::new(topic.clone(), partition_request.partition_index);
         let leader_state = match ctx.leaders_state().get(&replica_id).await {
@@ -125,6 +194,26 @@ async fn handle_produce_topic(
             }
         }
 
+        // records can't be fully acknowledged without enough in-sync replicas
+        if matches!(isolation, Isolation::ReadCommitted) {
+            if let Err(err) = leader_state.check_min_in_sync_replicas().await {
+                warn!(%replica_id, %err, "rejecting produce");
+                topic_result
+                    .partitions
+                    .push(PartitionWriteResult::error(replica_id, err));
+                continue;
+            }
+        }
+
+        if let Err(err) = validate_crc(&partition_request.records) {
+            error!(%replica_id, %err, "rejecting corrupt batch");
+            topic_result.partitions.push(PartitionWriteResult::error(
+                replica_id,
+                ErrorCode::CorruptRecordBatch,
+            ));
+            continue;
+        }
+
         if let Err(err) = apply_smartmodules(
             &mut partition_request,
             smartmodules,
@@ -186,8 +275,12 @@ async fn handle_produce_partition(
 
     let mut records = partition_request.records;
 
-    if validate_records(&records, replica_metadata.compression_type).is_err() {
-        error!(%replica_key, "Compression in batch not supported by this topic");
+    let dictionary = replica_metadata
+        .compression_dictionary
+        .as_deref()
+        .and_then(|name| ctx.dictionaries_localstore().zstd_dictionary(name));
+    if let Err(err) = conform_records(&mut records, &replica_metadata, dictionary.as_ref()) {
+        error!(%replica_key, %err, "Compression in batch not supported by this topic");
         return PartitionWriteResult::error(replica_key, ErrorCode::CompressionError);
     }
 
@@ -326,6 +419,52 @@ fn validate_records<R: BatchRecords>(
         Err(anyhow!("Compression not supported by topic"))
     }
 }
+
+/// Make batches match compression of the topic.
+/// Batches are recompressed only if topic opted in, otherwise mismatched batches are rejected.
+fn conform_records(
+    records: &mut RecordSet<RawRecords>,
+    replica: &Replica,
+    dictionary: Option<&ZstdDictionary>,
+) -> Result<()> {
+    if !replica.recompress {
+        return validate_records(records, replica.compression_type);
+    }
+    let Some(compression) = topic_compression(replica.compression_type) else {
+        return Ok(());
+    };
+    let dictionary = dictionary.filter(|_| compression == Compression::Zstd);
+    for batch in records.batches.iter_mut() {
+        let matches = batch.get_compression()? == compression
+            && dictionary.is_none_or(|dictionary| batch.dictionary_id() == Some(dictionary.id()));
+        if !matches {
+            trace!(base_offset = batch.base_offset, %compression, "recompressing batch");
+            *batch = batch.recompress(compression, dictionary)?;
+        }
+    }
+    Ok(())
+}
+
+/// compression required by topic, none if topic accepts any
+fn topic_compression(algorithm: CompressionAlgorithm) -> Option<Compression> {
+    match algorithm {
+        CompressionAlgorithm::Any => None,
+        CompressionAlgorithm::None => Some(Compression::None),
+        CompressionAlgorithm::Gzip => Some(Compression::Gzip),
+        CompressionAlgorithm::Snappy => Some(Compression::Snappy),
+        CompressionAlgorithm::Lz4 => Some(Compression::Lz4),
+        CompressionAlgorithm::Zstd => Some(Compression::Zstd),
+    }
+}
+
+/// check crc of batches as sent by producer, before smartmodules or storage rewrite them
+fn validate_crc(records: &RecordSet<RawRecords>) -> Result<()> {
+    for batch in &records.batches {
+        batch.validate_crc()?;
+    }
+    Ok(())
+}
+
 /// For isolation = ReadCommitted wait until the replica's `hw` includes written records offsets or
 /// until `timeout` passes. In case of timeout, the partition response returns `RequestTimedOut`
 /// error code. The timeout is not shared between partitions.
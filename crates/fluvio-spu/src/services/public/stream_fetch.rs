@@ -1,9 +1,10 @@
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use tracing::{debug, error, instrument, trace, warn};
+use tracing::{debug, error, instrument, trace, warn, Instrument};
 use tokio::select;
 
+use fluvio_auth::{AuthContext, InstanceAction};
 use fluvio_compression::CompressionError;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_types::event::{
@@ -18,6 +19,8 @@ use fluvio_protocol::{
 use fluvio_protocol::link::{ErrorCode, smartmodule::SmartModuleTransformRuntimeError};
 use fluvio_protocol::record::Batch;
 use fluvio_socket::{ExclusiveFlvSink, SocketError};
+use fluvio_service::request_span;
+use fluvio_storage::FileReplica;
 use fluvio_storage::iterators::FileBatchIterator;
 use fluvio_spu_schema::{
     server::stream_fetch::{
@@ -27,14 +30,18 @@ use fluvio_spu_schema::{
     Isolation,
     file::FileRecordSet,
 };
-use fluvio_types::event::offsets::OffsetChangeListener;
+use fluvio_types::event::offsets::{OffsetChangeListener, SharedOffsetPublisher};
 
 use crate::core::{metrics::IncreaseValue, DefaultSharedGlobalContext};
 use crate::replication::leader::SharedFileLeaderState;
+use crate::replication::follower::FollowerReplicaState;
+use crate::storage::SharableReplicaStorage;
+use crate::services::public::allow_topic_action;
 use crate::services::public::conn_context::ConnectionContext;
 use crate::smartengine::context::SmartModuleContext;
 use crate::smartengine::batch::process_batch;
 use crate::core::metrics::SpuMetrics;
+use crate::core::quota::{ClientQuotas, QuotaKind};
 use crate::traffic::TrafficType;
 
 /// Fetch records as stream
@@ -47,52 +54,75 @@ pub struct StreamFetchHandler {
     sink: ExclusiveFlvSink,
     end_event: Arc<StickyEvent>,
     consumer_offset_listener: OffsetChangeListener,
-    leader_state: SharedFileLeaderState,
+    storage: SharableReplicaStorage<FileReplica>,
     stream_id: u32,
     metrics: Arc<SpuMetrics>,
+    quotas: Arc<ClientQuotas>,
+    quota_client: String,
+    /// throttle not reported to consumer yet
+    pending_throttle: Duration,
 }
 
 impl StreamFetchHandler {
     /// handle fluvio continuous fetch request
-    pub(crate) async fn start(
+    pub(crate) async fn start<AC: AuthContext>(
         request: RequestMessage<FileStreamFetchRequest>,
         ctx: DefaultSharedGlobalContext,
         conn_ctx: &mut ConnectionContext,
+        auth: &AC,
         sink: ExclusiveFlvSink,
         end_event: Arc<StickyEvent>,
     ) -> Result<(), SocketError> {
         let (header, msg) = request.get_header_request();
         let replica = ReplicaKey::new(msg.topic.clone(), msg.partition);
 
-        if let Some(leader_state) = ctx.leaders_state().get(&replica).await {
+        if !allow_topic_action(auth, InstanceAction::Consume, &replica.topic).await? {
+            return send_back_error(&sink, &replica, &header, 0, ErrorCode::PermissionDenied).await;
+        }
+
+        // stream has no response yet, throttle time is carried by first one
+        let quota_client = conn_ctx.quota_client(&header).to_owned();
+        let start_throttle = ctx.quotas().record(&quota_client, QuotaKind::Requests, 1);
+
+        if let Some(source) = ReplicaSource::find(&ctx, &replica, &msg).await {
             let (stream_id, offset_publisher) = conn_ctx
                 .stream_publishers_mut()
                 .create_new_publisher(msg.topic.clone(), msg.partition, msg.consumer_id.clone())
                 .await;
             let consumer_offset_listener = offset_publisher.offset_publisher.change_listener();
 
-            leader_state
+            source
                 .register_offset_publisher(&offset_publisher.offset_publisher)
                 .await;
-
-            spawn(async move {
-                if let Err(err) = StreamFetchHandler::fetch(
-                    ctx,
-                    sink,
-                    end_event.clone(),
-                    leader_state,
-                    stream_id,
-                    header,
-                    replica,
-                    consumer_offset_listener,
-                    msg,
-                )
-                .await
-                {
-                    error!("error starting stream fetch handler: {:#?}", err);
-                    end_event.notify();
-                }
-            });
+            let storage = source.storage();
+
+            let span = request_span(&header, "StreamFetch");
+            let shutdown = ctx.shutdown().clone();
+            spawn(
+                shutdown.until_notified(
+                    async move {
+                        if let Err(err) = StreamFetchHandler::fetch(
+                            ctx,
+                            sink,
+                            end_event.clone(),
+                            storage,
+                            stream_id,
+                            header,
+                            replica,
+                            consumer_offset_listener,
+                            msg,
+                            quota_client,
+                            start_throttle,
+                        )
+                        .await
+                        {
+                            error!("error starting stream fetch handler: {:#?}", err);
+                            end_event.notify();
+                        }
+                    }
+                    .instrument(span),
+                ),
+            );
         } else {
             debug!(topic = %replica.topic," no leader found, returning");
             let response = StreamFetchResponse {
@@ -103,6 +133,7 @@ impl StreamFetchHandler {
                     error_code: ErrorCode::NotLeaderForPartition,
                     ..Default::default()
                 },
+                ..Default::default()
             };
 
             let response_msg =
@@ -121,7 +152,7 @@ impl StreamFetchHandler {
 
     #[allow(clippy::too_many_arguments)]
     #[instrument(
-        skip(ctx,replica,end_event,leader_state,header,msg,consumer_offset_listener),
+        skip(ctx,replica,end_event,storage,header,msg,consumer_offset_listener,quota_client,start_throttle),
         fields(
             replica = %replica,
             sink = sink.id()
@@ -131,19 +162,21 @@ impl StreamFetchHandler {
         ctx: DefaultSharedGlobalContext,
         sink: ExclusiveFlvSink,
         end_event: Arc<StickyEvent>,
-        leader_state: SharedFileLeaderState,
+        storage: SharableReplicaStorage<FileReplica>,
         stream_id: u32,
         header: RequestHeader,
         replica: ReplicaKey,
         consumer_offset_listener: OffsetChangeListener,
         msg: StreamFetchRequest<FileRecordSet>,
+        quota_client: String,
+        start_throttle: Duration,
     ) -> Result<(), SocketError> {
         debug!("request: {:#?}", msg);
         let version = header.api_version();
 
         let sm_ctx = match SmartModuleContext::try_from(msg.smartmodules, version, &ctx).await {
             Ok(Some(mut ctx)) => {
-                if let Err(error_code) = ctx.look_back(&leader_state).await {
+                if let Err(error_code) = ctx.look_back(&storage).await {
                     warn!("smartmodule look_back failed: {:?}", error_code);
                     send_back_error(&sink, &replica, &header, stream_id, error_code).await?;
                     return Ok(());
@@ -187,9 +220,12 @@ impl StreamFetchHandler {
             header: header.clone(),
             consumer_offset_listener,
             stream_id,
-            leader_state,
+            storage,
             max_fetch_bytes,
             metrics: ctx.metrics(),
+            quotas: ctx.quotas(),
+            quota_client,
+            pending_throttle: start_throttle,
         };
 
         if let Err(err) = handler.process(starting_offset, sm_ctx).await {
@@ -226,7 +262,7 @@ impl StreamFetchHandler {
             .send_back_records(starting_offset, sm_ctx.as_mut())
             .await?;
 
-        let mut leader_offset_receiver = self.leader_state.offset_listener(&self.isolation);
+        let mut leader_offset_receiver = self.storage.offset_listener(&self.isolation);
         let mut counter: i32 = 0;
         // since we don't need to wait for consumer, can move consumer to same offset as last read
         let mut last_known_consumer_offset: Option<Offset> =
@@ -354,6 +390,12 @@ impl StreamFetchHandler {
     ) -> Result<(Offset, bool), StreamFetchError> {
         let now = Instant::now();
 
+        // follower may not have committed records consumer asked for yet
+        if self.isolation == Isolation::ReadCommitted && starting_offset >= self.storage.hw() {
+            debug!(starting_offset, "no committed records yet, skipping");
+            return Ok((starting_offset, false));
+        }
+
         let mut file_partition_response = FilePartitionResponse {
             partition_index: self.replica.partition,
             ..Default::default()
@@ -363,7 +405,7 @@ impl StreamFetchHandler {
         // Returns with the HW/LEO of the latest records available in the leader
         // This describes the range of records that can be read in this request
         let read_end_offset = match self
-            .leader_state
+            .storage
             .read_records(starting_offset, self.max_fetch_bytes, self.isolation)
             .await
         {
@@ -371,8 +413,8 @@ impl StreamFetchHandler {
                 file_partition_response.high_watermark = slice.end.hw;
                 file_partition_response.log_start_offset = slice.start;
 
-                if let Some(file_slice) = slice.file_slice {
-                    file_partition_response.records = file_slice.into();
+                if let Some(records) = slice.records() {
+                    file_partition_response.records = records;
                 }
                 slice.end
             }
@@ -400,7 +442,7 @@ impl StreamFetchHandler {
             return Ok((starting_offset, false));
         }
 
-        let (offset, wait, metrics_update) = match sm_ctx {
+        let (offset, wait, metrics_update, throttle) = match sm_ctx {
             Some(sm_ctx) => {
                 // If a SmartModule is provided, we need to read records from file to memory
                 // In-memory records are then processed by SmartModule and returned to consumer
@@ -418,6 +460,7 @@ impl StreamFetchHandler {
                     StreamFetchError::Fetch(ErrorCode::Other(format!("SmartModule err {err}")))
                 })?;
                 let metrics_update = IncreaseValue::from(&batch);
+                let throttle = self.record_fetch_quota(&metrics_update);
 
                 sm_ctx.update_global_metrics();
 
@@ -427,19 +470,22 @@ impl StreamFetchHandler {
                         next_offset,
                         batch,
                         smartmodule_error,
+                        throttle,
                     )
                     .await?;
-                (offset, wait, metrics_update)
+                (offset, wait, metrics_update, throttle)
             }
             None => {
                 // If no SmartModule is provided, respond using raw file records
                 debug!("No SmartModule, sending back entire log");
                 let metrics_update = IncreaseValue::from(&file_partition_response);
+                let throttle = self.record_fetch_quota(&metrics_update);
 
                 let response = StreamFetchResponse {
                     topic: self.replica.topic.clone(),
                     stream_id: self.stream_id,
                     partition: file_partition_response,
+                    throttle_time_ms: throttle.as_millis() as i32,
                 };
 
                 let response_msg = RequestMessage::<FileStreamFetchRequest>::response_with_header(
@@ -462,15 +508,33 @@ impl StreamFetchHandler {
                     read_end_offset.isolation(&self.isolation),
                     true,
                     metrics_update,
+                    throttle,
                 )
             }
         };
         self.metrics
             .outbound()
             .increase_by_value(self.header.is_connector(), metrics_update);
+        self.metrics.fetch_latency().observe_duration(now.elapsed());
+
+        if !throttle.is_zero() {
+            // consumer holds back acknowledgement of these records for throttle time,
+            // next records are sent only after that
+            debug!(
+                throttle_ms = throttle.as_millis(),
+                "stream fetch over quota"
+            );
+        }
         Ok((offset, wait))
     }
 
+    fn record_fetch_quota(&mut self, sent: &IncreaseValue) -> Duration {
+        let throttle = self
+            .quotas
+            .record(&self.quota_client, QuotaKind::FetchBytes, sent.bytes());
+        throttle.max(std::mem::take(&mut self.pending_throttle))
+    }
+
     #[instrument(skip(self, file_partition_response, batch, smartmodule_error))]
     async fn send_processed_response(
         &self,
@@ -478,6 +542,7 @@ impl StreamFetchHandler {
         next_offset: Offset,
         batch: Batch,
         smartmodule_error: Option<SmartModuleTransformRuntimeError>,
+        throttle: Duration,
     ) -> Result<(Offset, bool), StreamFetchError> {
         type DefaultPartitionResponse = FetchablePartitionResponse<RecordSet<RawRecords>>;
 
@@ -526,6 +591,7 @@ impl StreamFetchHandler {
             topic: self.replica.topic.clone(),
             stream_id: self.stream_id,
             partition: partition_response,
+            throttle_time_ms: throttle.as_millis() as i32,
         };
 
         let response_msg = RequestMessage::<DefaultStreamFetchRequest>::response_with_header(
@@ -544,6 +610,55 @@ impl StreamFetchHandler {
     }
 }
 
+/// replica which can serve stream fetch
+enum ReplicaSource {
+    Leader(SharedFileLeaderState),
+    /// follower serves committed records it has already replicated
+    Follower(FollowerReplicaState<FileReplica>),
+}
+
+impl ReplicaSource {
+    async fn find(
+        ctx: &DefaultSharedGlobalContext,
+        replica: &ReplicaKey,
+        msg: &FileStreamFetchRequest,
+    ) -> Option<Self> {
+        if let Some(leader_state) = ctx.leaders_state().get(replica).await {
+            return Some(Self::Leader(leader_state));
+        }
+
+        if !msg.read_from_follower || msg.isolation != Isolation::ReadCommitted {
+            return None;
+        }
+
+        let follower_state = ctx.followers_state().get(replica).await?;
+        debug!(%replica, hw = follower_state.hw(), "serving stream fetch from follower");
+        Some(Self::Follower(follower_state))
+    }
+
+    async fn register_offset_publisher(&self, offset_publisher: &SharedOffsetPublisher) {
+        match self {
+            Self::Leader(leader_state) => {
+                leader_state
+                    .register_offset_publisher(offset_publisher)
+                    .await
+            }
+            Self::Follower(follower_state) => {
+                follower_state
+                    .register_offset_publisher(offset_publisher)
+                    .await
+            }
+        }
+    }
+
+    fn storage(&self) -> SharableReplicaStorage<FileReplica> {
+        match self {
+            Self::Leader(leader_state) => (**leader_state).clone(),
+            Self::Follower(follower_state) => (**follower_state).clone(),
+        }
+    }
+}
+
 async fn send_back_error(
     sink: &ExclusiveFlvSink,
     replica: &ReplicaKey,
@@ -562,6 +677,7 @@ async fn send_back_error(
         topic: replica.topic.clone(),
         stream_id,
         partition: partition_response,
+        ..Default::default()
     };
 
     let response_msg =
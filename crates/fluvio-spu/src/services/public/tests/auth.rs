@@ -0,0 +1,217 @@
+use std::{env::temp_dir, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+
+use fluvio_auth::{AuthContext, AuthError, Authorization, InstanceAction, TypeAction};
+use fluvio_controlplane::replica::Replica;
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::{api::RequestMessage, link::ErrorCode};
+use fluvio_socket::{FluvioSocket, MultiplexerSocket};
+use fluvio_spu_schema::produce::{DefaultProduceRequest, PartitionProduceData, TopicProduceData};
+use fluvio_spu_schema::server::delete_records::DeleteRecordsRequest;
+use fluvio_spu_schema::server::stream_fetch::DefaultStreamFetchRequest;
+use fluvio_storage::FileReplica;
+use flv_util::fixture::ensure_clean_dir;
+
+use crate::{
+    config::SpuConfig,
+    core::GlobalContext,
+    replication::leader::{LeaderReplicaState, SharedFileLeaderState},
+    services::{
+        auth::SpuAuthGlobalContext,
+        public::{create_public_server, SpuPublicServer},
+    },
+};
+
+use super::create_filter_raw_records;
+
+/// authorization which denies every action
+#[derive(Debug, Default)]
+struct DenyAllAuthorization;
+
+#[async_trait]
+impl Authorization for DenyAllAuthorization {
+    type Context = DenyAllAuthorization;
+
+    async fn create_auth_context(
+        &self,
+        _socket: &mut FluvioSocket,
+    ) -> Result<Self::Context, AuthError> {
+        Ok(DenyAllAuthorization)
+    }
+}
+
+#[async_trait]
+impl AuthContext for DenyAllAuthorization {
+    async fn allow_type_action(
+        &self,
+        _ty: ObjectType,
+        _action: TypeAction,
+    ) -> Result<bool, AuthError> {
+        Ok(false)
+    }
+
+    async fn allow_instance_action(
+        &self,
+        _ty: ObjectType,
+        _action: InstanceAction,
+        _key: &str,
+    ) -> Result<bool, AuthError> {
+        Ok(false)
+    }
+}
+
+fn create_public_server_with_deny_auth(
+    addr: String,
+    ctx: Arc<GlobalContext<FileReplica>>,
+) -> SpuPublicServer<DenyAllAuthorization> {
+    let auth_global_ctx = SpuAuthGlobalContext::new(ctx, Arc::new(DenyAllAuthorization));
+    create_public_server(addr, auth_global_ctx)
+}
+
+async fn create_leader(
+    ctx: &Arc<GlobalContext<FileReplica>>,
+    topic: &str,
+) -> SharedFileLeaderState {
+    let test = Replica::new((topic, 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    ctx.replica_localstore().sync_all(vec![test.clone()]);
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(ctx)
+        .await
+        .expect("init succeeded");
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+    replica
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_produce_permission_denied() {
+    let test_path = temp_dir().join("auth_produce_permission_denied");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_deny_auth(addr.clone(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+    let topic = "auth_produce";
+    let replica = create_leader(&ctx, topic).await;
+
+    let mut produce_request = DefaultProduceRequest::default();
+    produce_request.topics.push(TopicProduceData {
+        name: topic.to_owned(),
+        partitions: vec![PartitionProduceData {
+            partition_index: 0,
+            records: create_filter_raw_records(2),
+        }],
+        ..Default::default()
+    });
+
+    let produce_response = client_socket
+        .send_and_receive(RequestMessage::new_request(produce_request))
+        .await
+        .expect("send produce");
+
+    assert_eq!(produce_response.responses.len(), 1);
+    assert_eq!(produce_response.responses[0].partitions.len(), 1);
+    assert_eq!(
+        produce_response.responses[0].partitions[0].error_code,
+        ErrorCode::PermissionDenied
+    );
+    assert_eq!(replica.leo(), 0);
+
+    server_end_event.notify();
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_stream_fetch_permission_denied() {
+    let test_path = temp_dir().join("auth_stream_fetch_permission_denied");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_deny_auth(addr.clone(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+    let topic = "auth_stream_fetch";
+    let replica = create_leader(&ctx, topic).await;
+    replica
+        .write_record_set(&mut create_filter_raw_records(2), ctx.follower_notifier())
+        .await
+        .expect("write");
+
+    let stream_request = DefaultStreamFetchRequest::builder()
+        .topic(topic.to_owned())
+        .max_bytes(10000)
+        .build()
+        .expect("build");
+
+    let mut stream = client_socket
+        .create_stream(RequestMessage::new_request(stream_request), 11)
+        .await
+        .expect("create stream");
+
+    let response = stream.next().await.expect("first").expect("response");
+    assert_eq!(response.partition.error_code, ErrorCode::PermissionDenied);
+    assert!(response.partition.records.batches.is_empty());
+
+    server_end_event.notify();
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_delete_records_permission_denied() {
+    let test_path = temp_dir().join("auth_delete_records_permission_denied");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_deny_auth(addr.clone(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+    let topic = "auth_delete_records";
+    let replica = create_leader(&ctx, topic).await;
+    replica
+        .write_record_set(&mut create_filter_raw_records(2), ctx.follower_notifier())
+        .await
+        .expect("write");
+
+    let response = client_socket
+        .send_and_receive(RequestMessage::new_request(DeleteRecordsRequest::new(
+            topic, 0, 2,
+        )))
+        .await
+        .expect("send delete records");
+
+    assert_eq!(response.error_code, ErrorCode::PermissionDenied);
+    assert_eq!(replica.start_offset_info().await.0, 0);
+
+    server_end_event.notify();
+}
@@ -28,6 +28,8 @@ use super::{create_public_server, SpuPublicServer};
 mod stream_fetch;
 mod produce;
 mod consumer_offset;
+mod offset_request;
+mod auth;
 
 /// create records that can be filtered
 fn create_filter_records(records: u16) -> RecordSet {
@@ -0,0 +1,101 @@
+use std::{env::temp_dir, time::Duration};
+
+use fluvio_controlplane::replica::Replica;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::api::RequestMessage;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Batch, Record, RecordSet, RawRecords};
+use fluvio_socket::{FluvioSocket, MultiplexerSocket};
+use fluvio_spu_schema::server::fetch_offset::{FetchOffsetPartition, FetchOffsetsRequest};
+use flv_util::fixture::ensure_clean_dir;
+
+use crate::config::SpuConfig;
+use crate::core::GlobalContext;
+use crate::replication::leader::LeaderReplicaState;
+use crate::services::public::tests::create_public_server_with_root_auth;
+
+fn batch_with_timestamps(records: usize, first: i64, max: i64) -> RecordSet<RawRecords> {
+    let mut batch = Batch::default();
+    for _ in 0..records {
+        batch.add_record(Record::new("record"));
+    }
+    let header = batch.get_mut_header();
+    header.first_timestamp = first;
+    header.max_time_stamp = max;
+    RecordSet::default().add(batch).try_into().expect("raw")
+}
+
+#[fluvio_future::test(ignore)]
+async fn test_fetch_offsets_with_batch_timestamps() {
+    let test_path = temp_dir().join("test_fetch_offsets_with_batch_timestamps");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+    let topic = "lag";
+    let test = Replica::new((topic.to_owned(), 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    replica
+        .write_record_set(
+            &mut batch_with_timestamps(2, 1_000, 1_500),
+            ctx.follower_notifier(),
+        )
+        .await
+        .expect("write");
+    replica
+        .write_record_set(
+            &mut batch_with_timestamps(3, 4_000, 6_000),
+            ctx.follower_notifier(),
+        )
+        .await
+        .expect("write");
+
+    let mut request = FetchOffsetsRequest::new(topic.to_owned(), 0);
+    request.topics[0].partitions[0] = FetchOffsetPartition {
+        partition_index: 0,
+        timestamp_offset: Some(1),
+    };
+    let response = client_socket
+        .send_and_receive(RequestMessage::new_request(request))
+        .await
+        .expect("send");
+    let partition = &response.topics[0].partitions[0];
+    assert_eq!(partition.error_code, ErrorCode::None);
+    assert_eq!(partition.last_stable_offset, 5);
+    assert_eq!(partition.log_end_offset, 5);
+    assert_eq!(partition.offset_timestamp, Some(1_000));
+    assert_eq!(partition.last_timestamp, Some(6_000));
+
+    // nothing to read at high watermark, no timestamps
+    let mut request = FetchOffsetsRequest::new(topic.to_owned(), 0);
+    request.topics[0].partitions[0].timestamp_offset = Some(5);
+    let response = client_socket
+        .send_and_receive(RequestMessage::new_request(request))
+        .await
+        .expect("send");
+    let partition = &response.topics[0].partitions[0];
+    assert_eq!(partition.offset_timestamp, None);
+    assert_eq!(partition.last_timestamp, None);
+
+    server_end_event.notify();
+}
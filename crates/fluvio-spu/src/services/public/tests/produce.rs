@@ -218,6 +218,89 @@ async fn test_produce_invalid_compression() {
     server_end_event.notify();
     debug!("terminated controller");
 }
+#[fluvio_future::test(ignore)]
+async fn test_produce_recompress() {
+    let test_path = temp_dir().join("produce_recompress");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+    let topic = "test_produce";
+    let mut test = Replica::new((topic, 0), 5001, vec![5001]);
+    test.compression_type = CompressionAlgorithm::Gzip;
+    test.recompress = true;
+    let test_id = test.id.clone();
+    ctx.replica_localstore().sync_all(vec![test.clone()]);
+
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica")
+        .init(&ctx)
+        .await
+        .expect("init succeeded");
+
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    let records_per_request = 9;
+    let records = create_filter_records(records_per_request)
+        .try_into()
+        .expect("filter records");
+
+    let mut produce_request = DefaultProduceRequest {
+        ..Default::default()
+    };
+
+    let partition_produce = DefaultPartitionRequest {
+        partition_index: 0,
+        records,
+    };
+    let topic_produce_request = TopicProduceData {
+        name: topic.to_owned(),
+        partitions: vec![partition_produce],
+        ..Default::default()
+    };
+
+    produce_request.topics.push(topic_produce_request);
+
+    let produce_response = client_socket
+        .send_and_receive(RequestMessage::new_request(produce_request))
+        .await
+        .expect("send offset");
+
+    // batch is accepted and stored with topic compression
+    assert_eq!(produce_response.responses.len(), 1);
+    assert_eq!(produce_response.responses[0].partitions.len(), 1);
+    assert_eq!(
+        produce_response.responses[0].partitions[0].error_code,
+        ErrorCode::None
+    );
+
+    let stored = replica
+        .read_records(0, u32::MAX, Isolation::ReadUncommitted)
+        .await
+        .expect("read records");
+    let mut batches = FileBatchIterator::from_raw_slice(stored.file_slice.expect("slice"));
+    let batch = batches.next().expect("batch").expect("valid batch");
+    assert_eq!(
+        batch.batch.get_compression().expect("compression"),
+        fluvio::Compression::Gzip
+    );
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
+
 use crate::replication::test::TestConfig;
 use crate::services::create_internal_server;
 
@@ -415,6 +498,65 @@ async fn test_produce_waiting_replication() {
     debug!("terminated controller");
 }
 
+#[fluvio_future::test(ignore)]
+async fn test_produce_not_enough_in_sync_replicas() {
+    let config = TestConfig::builder()
+        .followers(1_u16)
+        .base_port(14040_u16)
+        .generate("produce_not_enough_in_sync_replicas");
+
+    let mut replica = Replica::new(("test", 0), 5001, vec![5001, 5002]);
+    replica.min_in_sync_replicas = Some(3);
+    let (leader_ctx, leader_replica) = config.leader_replica_inner(replica).await;
+    let public_addr = config.leader_public_addr();
+
+    let server_end_event =
+        create_public_server_with_root_auth(public_addr.to_owned(), leader_ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&public_addr).await.expect("connect"));
+
+    for (isolation, expected) in [
+        (
+            Isolation::ReadCommitted,
+            ErrorCode::NotEnoughInSyncReplicas { in_sync: 2, min: 3 },
+        ),
+        (Isolation::ReadUncommitted, ErrorCode::None),
+    ] {
+        let mut produce_request = DefaultProduceRequest {
+            isolation,
+            timeout: Duration::from_millis(300),
+            ..Default::default()
+        };
+        produce_request.topics.push(TopicProduceData {
+            name: "test".to_owned(),
+            partitions: vec![DefaultPartitionRequest {
+                partition_index: 0,
+                records: create_filter_records(5).try_into().expect("filter records"),
+            }],
+            ..Default::default()
+        });
+
+        let produce_response = client_socket
+            .send_and_receive(RequestMessage::new_request(produce_request))
+            .await
+            .expect("send offset");
+        assert_eq!(
+            produce_response.responses[0].partitions[0].error_code,
+            expected
+        );
+    }
+
+    // rejected records are not written
+    assert_eq!(leader_replica.leo(), 5);
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
+
 #[fluvio_future::test(ignore)]
 async fn test_produce_metrics() {
     let test_path = temp_dir().join("produce_basic_metrics");
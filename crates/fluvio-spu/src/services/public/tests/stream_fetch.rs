@@ -21,7 +21,7 @@ use fluvio_spu_schema::server::smartmodule::{
 };
 use fluvio_protocol::{
     fixture::BatchProducer,
-    record::{RecordData, Record, Batch},
+    record::{RecordData, Record, Batch, ReplicaKey},
     link::{smartmodule::SmartModuleKind as SmartModuleKindError, ErrorCode},
     ByteBuf,
 };
@@ -30,7 +30,8 @@ use fluvio_spu_schema::{
     server::update_offset::{UpdateOffsetsRequest, OffsetUpdate},
     fetch::DefaultFetchRequest,
 };
-use fluvio_spu_schema::server::stream_fetch::DefaultStreamFetchRequest;
+use fluvio_spu_schema::server::stream_fetch::{DefaultStreamFetchRequest, FOLLOWER_FETCH_API};
+use fluvio_spu_schema::Isolation;
 use crate::services::public::tests::{
     create_filter_raw_records, create_public_server_with_root_auth, read_records, vec_to_batch,
 };
@@ -40,6 +41,7 @@ use crate::{
 };
 use crate::config::SpuConfig;
 use crate::replication::leader::LeaderReplicaState;
+use crate::replication::follower::FollowerReplicaState;
 
 use fluvio_protocol::{api::RequestMessage, record::RecordSet};
 
@@ -3078,3 +3080,104 @@ async fn test_stream_fetch_sends_topic_delete_error_on_topic_delete() {
     server_end_event.notify();
     debug!("terminated controller");
 }
+
+#[fluvio_future::test(ignore)]
+async fn test_stream_fetch_from_follower() {
+    let test_path = temp_dir().join("test_stream_fetch_from_follower");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server_with_root_auth(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+
+    let topic = "test_follower";
+    let replica_key: ReplicaKey = (topic, 0).into();
+    let follower: FollowerReplicaState<FileReplica> =
+        FollowerReplicaState::create(5002, replica_key.clone(), ctx.config().into())
+            .await
+            .expect("follower");
+    ctx.followers_state()
+        .write()
+        .await
+        .insert(replica_key, follower.clone());
+
+    // records replicated from leader are committed
+    follower
+        .update_from_leader(&mut create_raw_recordset(2), 2, 0)
+        .await
+        .expect("update");
+
+    let request = |fetch_offset: i64, isolation: Isolation, read_from_follower: bool| {
+        DefaultStreamFetchRequest::builder()
+            .topic(topic)
+            .fetch_offset(fetch_offset)
+            .isolation(isolation)
+            .read_from_follower(read_from_follower)
+            .max_bytes(1000)
+            .build()
+            .expect("request")
+    };
+
+    // follower doesn't serve consumers which didn't ask for it or uncommitted records
+    for request in [
+        request(0, Isolation::ReadCommitted, false),
+        request(0, Isolation::ReadUncommitted, true),
+    ] {
+        let mut stream = client_socket
+            .create_stream(RequestMessage::new_request(request), FOLLOWER_FETCH_API)
+            .await
+            .expect("create stream");
+        let response = stream.next().await.expect("first").expect("response");
+        assert_eq!(
+            response.partition.error_code,
+            ErrorCode::NotLeaderForPartition
+        );
+    }
+
+    let mut stream = client_socket
+        .create_stream(
+            RequestMessage::new_request(request(0, Isolation::ReadCommitted, true)),
+            FOLLOWER_FETCH_API,
+        )
+        .await
+        .expect("create stream");
+    let response = stream.next().await.expect("first").expect("response");
+    let partition = &response.partition;
+    assert_eq!(partition.error_code, ErrorCode::None);
+    assert_eq!(partition.high_watermark, 2);
+    assert_eq!(partition.records.batches.len(), 1);
+    assert_eq!(partition.next_offset_for_fetch(), Some(2));
+
+    // consumer ahead of follower waits until records are replicated
+    let mut stream = client_socket
+        .create_stream(
+            RequestMessage::new_request(request(3, Isolation::ReadCommitted, true)),
+            FOLLOWER_FETCH_API,
+        )
+        .await
+        .expect("create stream");
+    let mut records = create_raw_recordset(2);
+    records.batches[0].base_offset = 2;
+    follower
+        .update_from_leader(&mut records, 4, 0)
+        .await
+        .expect("update");
+    let response = stream.next().await.expect("first").expect("response");
+    let partition = &response.partition;
+    assert_eq!(partition.error_code, ErrorCode::None);
+    assert_eq!(partition.high_watermark, 4);
+    assert_eq!(partition.next_offset_for_fetch(), Some(4));
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
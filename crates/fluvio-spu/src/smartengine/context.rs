@@ -7,13 +7,13 @@ use fluvio_protocol::link::ErrorCode;
 use fluvio_smartmodule::Record;
 use fluvio_spu_schema::server::smartmodule::{SmartModuleInvocation, SmartModuleInvocationWasm};
 use fluvio_storage::ReplicaStorage;
-use fluvio_storage::iterators::{FileBatch, FileBatchIterator, FileRecordIterator, RecordItem};
+use fluvio_storage::iterators::{FileBatch, FileRecordIterator, RecordItem};
 use fluvio_types::Timestamp;
 use tracing::{debug, trace, error};
 
 use crate::core::GlobalContext;
 use crate::core::metrics::SpuMetrics;
-use crate::replication::leader::LeaderReplicaState;
+use crate::storage::SharableReplicaStorage;
 
 use crate::smartengine::chain;
 use crate::smartengine::Lookback;
@@ -45,7 +45,7 @@ impl SmartModuleContext {
 
     pub async fn look_back<R: ReplicaStorage>(
         &mut self,
-        replica: &LeaderReplicaState<R>,
+        replica: &SharableReplicaStorage<R>,
     ) -> Result<(), ErrorCode> {
         self.chain
             .look_back(|lookback| read_records(replica, lookback, self.version))
@@ -120,7 +120,7 @@ fn resolve_invocation<R: ReplicaStorage>(
 }
 
 async fn read_records<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     lookback: Lookback,
     version: Version,
 ) -> anyhow::Result<Vec<Record>> {
@@ -133,7 +133,7 @@ async fn read_records<R: ReplicaStorage>(
 }
 
 async fn lookback_iterator<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     lookback: Lookback,
     version: Version,
 ) -> anyhow::Result<Box<dyn Iterator<Item = Result<Record, std::io::Error>>>> {
@@ -146,7 +146,7 @@ async fn lookback_iterator<R: ReplicaStorage>(
 }
 
 async fn lookback_last_iterator<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     last: u64,
     version: Version,
 ) -> anyhow::Result<Box<dyn Iterator<Item = Result<RecordItem, std::io::Error>>>> {
@@ -157,17 +157,22 @@ async fn lookback_last_iterator<R: ReplicaStorage>(
     let offset = offset.clamp(start_offset, hw);
     debug!(offset, "reading last {last} records for look_back");
 
-    let slice = replica
-        .read_records(offset, u32::MAX, fluvio::Isolation::ReadCommitted)
-        .await?;
-
-    let Some(file_slice) = slice.file_slice else {
-        trace!(?slice);
-        return Ok(Box::new(std::iter::empty()));
-    };
+    // read batch by batch, so decrypted reads are limited to requested records
+    let mut batches = Vec::new();
+    let mut next_offset = offset;
+    while next_offset < hw {
+        let Some(batch) = replica
+            .read_batch(next_offset, fluvio::Isolation::ReadCommitted)
+            .await?
+        else {
+            trace!(next_offset, "no more batches");
+            break;
+        };
+        next_offset = batch.batch.get_last_offset() + 1;
+        batches.push(batch);
+    }
 
-    let batch_iter = FileBatchIterator::from_raw_slice(file_slice);
-    let records_iter = FileRecordIterator::new(batch_iter, version);
+    let records_iter = FileRecordIterator::new(batches.into_iter().map(Ok), version);
 
     Ok(Box::new(records_iter.filter(move |r| match r {
         Ok(item) => item.offset >= offset,
@@ -176,7 +181,7 @@ async fn lookback_last_iterator<R: ReplicaStorage>(
 }
 
 async fn lookback_age_iterator<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     age: Duration,
     last: u64,
     version: Version,
@@ -204,7 +209,7 @@ async fn lookback_age_iterator<R: ReplicaStorage>(
 }
 
 async fn read_batches_by_age<R: ReplicaStorage>(
-    replica: &LeaderReplicaState<R>,
+    replica: &SharableReplicaStorage<R>,
     min_timestamp: Timestamp,
 ) -> anyhow::Result<Vec<FileBatch>> {
     let mut result = Vec::new();
@@ -214,18 +219,12 @@ async fn read_batches_by_age<R: ReplicaStorage>(
             break;
         }
         trace!(offset, "reading next batch");
-        let slice = replica
-            .read_records(offset - 1, u32::MAX, fluvio::Isolation::ReadCommitted)
-            .await?;
-        let Some(file_slice) = slice.file_slice else {
-            trace!(?slice);
-            break;
-        };
-        let mut batch_iter = FileBatchIterator::from_raw_slice(file_slice);
-        let Some(batch) = batch_iter.next() else {
+        let Some(batch) = replica
+            .read_batch(offset - 1, fluvio::Isolation::ReadCommitted)
+            .await?
+        else {
             break;
         };
-        let batch = batch?;
         trace!(?batch.batch, "next file batch");
 
         if batch.batch.header.max_time_stamp < min_timestamp {
@@ -80,6 +80,14 @@ mod null_smartengine {
             self.invocation_count.load(Ordering::SeqCst)
         }
 
+        pub fn cpu_ms(&self) -> u64 {
+            self.cpu_ms.load(Ordering::SeqCst)
+        }
+
+        pub fn records_err(&self) -> u64 {
+            self.records_err.load(Ordering::SeqCst)
+        }
+
         // Added append method
         pub fn append(&self, other: &Self) {
             self.bytes_in.fetch_add(other.bytes_in(), Ordering::SeqCst);
@@ -1,7 +1,18 @@
-use std::sync::Arc;
-
+use std::fmt::Debug;
+use std::io::Error as IoError;
+use std::net::SocketAddr;
+use std::process;
+use std::sync::{Arc, Weak};
+
+use tracing::info;
+
+use flv_util::print_cli_err;
+use fluvio_auth::Authorization;
+use fluvio_auth::basic::BasicAuthorization;
+use fluvio_auth::rbac::BasicRbacPolicy;
 use fluvio_auth::root::RootAuthorization;
 use fluvio_storage::FileReplica;
+use fluvio_types::event::StickyEvent;
 
 use crate::config::{SpuConfig, SpuOpt};
 use crate::services::auth::SpuAuthGlobalContext;
@@ -9,7 +20,9 @@ use crate::services::create_internal_server;
 use crate::services::public::create_public_server;
 use crate::core::DefaultSharedGlobalContext;
 use crate::core::GlobalContext;
+use crate::core::data_dirs::DataDirController;
 use crate::control_plane::ScDispatcher;
+use crate::replication::leader::IsrController;
 
 type FileReplicaContext = GlobalContext<FileReplica>;
 
@@ -19,12 +32,11 @@ pub fn main_loop(opt: SpuOpt) {
     use std::time::Duration;
 
     use sysinfo::System;
-    use tracing::info;
 
     use fluvio_future::task::run_block_on;
     use fluvio_future::timer::sleep;
 
-    use crate::monitoring::init_monitoring;
+    use crate::monitoring::{init_monitoring, init_metrics_endpoint};
 
     // parse configuration (program exits on error)
     let (spu_config, tls_acceptor_option) = opt.process_spu_cli_or_exit();
@@ -47,6 +59,7 @@ pub fn main_loop(opt: SpuOpt) {
     run_block_on(async move {
         let ctx = create_services(spu_config.clone(), true, true);
 
+        init_metrics_endpoint(ctx.clone());
         init_monitoring(ctx);
 
         if let Some(tls_config) = tls_acceptor_option {
@@ -74,21 +87,134 @@ pub fn create_services(
     let private_ep_addr = ctx.config().private_socket_addr().to_owned();
 
     if public {
-        let authorization = Arc::new(RootAuthorization::new());
-        let auth_global_ctx = SpuAuthGlobalContext::new(ctx.clone(), authorization);
-        let pub_server = create_public_server(public_ep_addr, auth_global_ctx);
-        pub_server.run();
+        if let Some(policy_path) = ctx.config().auth_policy.clone() {
+            let policy = match BasicRbacPolicy::try_from(policy_path.clone()) {
+                Ok(policy) => policy,
+                Err(err) => {
+                    print_cli_err!(err);
+                    process::exit(-1);
+                }
+            };
+            info!("using basic authorization");
+            let authorization = BasicAuthorization::new(policy);
+            authorization.watch(policy_path);
+            start_public_server(&ctx, public_ep_addr, authorization);
+        } else {
+            start_public_server(&ctx, public_ep_addr, RootAuthorization::new());
+        }
     };
 
     if internal {
         let priv_server = create_internal_server(private_ep_addr, ctx.clone());
-        priv_server.run();
+        priv_server.run_until(ctx.shutdown().clone());
     };
 
+    start_controllers(&ctx);
+
+    ctx
+}
+
+/// start controllers, they are stopped when context shutdown is notified
+fn start_controllers(ctx: &DefaultSharedGlobalContext) {
     let sc_dispatcher = ScDispatcher::new(ctx.clone());
     sc_dispatcher.run();
 
-    ctx
+    IsrController::run(ctx.clone());
+
+    DataDirController::run(ctx.clone());
+}
+
+fn start_public_server<A>(ctx: &DefaultSharedGlobalContext, addr: String, authorization: A)
+where
+    A: Authorization + Sync + Send + Debug + 'static,
+    <A as Authorization>::Context: Send + Sync,
+{
+    let auth_global_ctx = SpuAuthGlobalContext::new(ctx.clone(), Arc::new(authorization));
+    let pub_server = create_public_server(addr, auth_global_ctx);
+    pub_server.run_until(ctx.shutdown().clone());
+}
+
+/// SPU running in the current process, without monitoring and TLS proxy.
+/// Servers are bound first, so endpoints with port 0 are bound to ephemeral ports
+/// which can be registered with SC before controllers are started.
+#[derive(Debug)]
+pub struct LocalSpu {
+    ctx: DefaultSharedGlobalContext,
+    public_addr: SocketAddr,
+    private_addr: SocketAddr,
+}
+
+impl LocalSpu {
+    /// bind public and internal servers, SPU is stopped if binding fails
+    pub async fn bind(local_spu: SpuConfig) -> Result<Self, IoError> {
+        let ctx = FileReplicaContext::new_shared_context(local_spu);
+        let shutdown = ctx.shutdown().clone();
+
+        let addrs = async {
+            let public_ep_addr = ctx.config().public_socket_addr().to_owned();
+            let auth_global_ctx =
+                SpuAuthGlobalContext::new(ctx.clone(), Arc::new(RootAuthorization::new()));
+            let public_addr = create_public_server(public_ep_addr, auth_global_ctx)
+                .bind_until(shutdown.clone())
+                .await?;
+
+            let private_ep_addr = ctx.config().private_socket_addr().to_owned();
+            let private_addr = create_internal_server(private_ep_addr, ctx.clone())
+                .bind_until(shutdown.clone())
+                .await?;
+            Ok::<_, IoError>((public_addr, private_addr))
+        }
+        .await;
+
+        match addrs {
+            Ok((public_addr, private_addr)) => Ok(Self {
+                ctx,
+                public_addr,
+                private_addr,
+            }),
+            Err(err) => {
+                shutdown.notify();
+                Err(err)
+            }
+        }
+    }
+
+    pub fn public_addr(&self) -> SocketAddr {
+        self.public_addr
+    }
+
+    pub fn private_addr(&self) -> SocketAddr {
+        self.private_addr
+    }
+
+    /// start controllers, notify shutdown of returned SPU to stop servers and controllers
+    pub fn start(self) -> StartedSpu {
+        start_controllers(&self.ctx);
+        StartedSpu {
+            shutdown: self.ctx.shutdown().clone(),
+            ctx: Arc::downgrade(&self.ctx),
+        }
+    }
+
+    /// stop servers without starting controllers
+    pub fn stop(self) {
+        self.ctx.shutdown().notify();
+    }
+}
+
+/// SPU running in the current process
+pub struct StartedSpu {
+    /// notify to stop servers and controllers
+    pub shutdown: Arc<StickyEvent>,
+    /// released by the tasks holding replicas
+    ctx: Weak<FileReplicaContext>,
+}
+
+impl StartedSpu {
+    /// true once the tasks holding replicas ended, after shutdown is notified
+    pub fn is_stopped(&self) -> bool {
+        self.ctx.strong_count() == 0
+    }
 }
 
 mod proxy {
@@ -97,8 +223,11 @@ mod proxy {
     use tracing::info;
 
     use flv_util::print_cli_err;
+    use fluvio_auth::x509::X509Authenticator;
     use fluvio_future::openssl::TlsAcceptor;
-    use flv_tls_proxy::start as proxy_start;
+    use flv_tls_proxy::{
+        start as proxy_start, start_with_authenticator as proxy_start_with_authenticator,
+    };
 
     use crate::config::SpuConfig;
 
@@ -107,8 +236,14 @@ mod proxy {
         let target = config.public_endpoint;
         info!("starting TLS proxy: {}", proxy_addr);
 
-        //TODO: add X509Authenticator
-        if let Err(err) = proxy_start(&proxy_addr, tls_acceptor, target).await {
+        let result = if let Some(x509_auth_scopes) = config.x509_auth_scopes {
+            let authenticator = Box::new(X509Authenticator::new(&x509_auth_scopes));
+            proxy_start_with_authenticator(&proxy_addr, tls_acceptor, target, authenticator).await
+        } else {
+            proxy_start(&proxy_addr, tls_acceptor, target).await
+        };
+
+        if let Err(err) = result {
             print_cli_err!(err);
             process::exit(-1);
         } else {
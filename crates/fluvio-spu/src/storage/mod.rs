@@ -1,4 +1,5 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicI32, Ordering};
 use std::fmt::Debug;
 use std::time::Instant;
 
@@ -10,9 +11,10 @@ use fluvio_protocol::record::BatchRecords;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_spu_schema::Isolation;
 use fluvio_protocol::Encoder;
-use fluvio_protocol::record::{Offset, RecordSet};
+use fluvio_protocol::record::{BatchHeader, Offset, RecordSet, BATCH_FILE_HEADER_SIZE};
 use fluvio_protocol::link::ErrorCode;
 use fluvio_storage::{ReplicaStorage, StorageError, OffsetInfo, ReplicaSlice};
+use fluvio_storage::iterators::{FileBatch, FileBatchIterator};
 use fluvio_types::event::offsets::OffsetChangeListener;
 use fluvio_types::event::offsets::OffsetPublisher;
 
@@ -26,6 +28,7 @@ pub struct SharableReplicaStorage<S> {
     inner: Arc<RwLock<S>>,
     leo: Arc<OffsetPublisher>,
     hw: Arc<OffsetPublisher>,
+    leader_epoch: Arc<AtomicI32>,
 }
 
 impl<S> Clone for SharableReplicaStorage<S> {
@@ -35,6 +38,7 @@ impl<S> Clone for SharableReplicaStorage<S> {
             inner: self.inner.clone(),
             leo: self.leo.clone(),
             hw: self.hw.clone(),
+            leader_epoch: self.leader_epoch.clone(),
         }
     }
 }
@@ -49,11 +53,13 @@ where
 
         let leo = Arc::new(OffsetPublisher::new(storage.get_leo()));
         let hw = Arc::new(OffsetPublisher::new(storage.get_hw()));
+        let leader_epoch = Arc::new(AtomicI32::new(storage.get_leader_epoch()));
         Ok(Self {
             id,
             inner: Arc::new(RwLock::new(storage)),
             leo,
             hw,
+            leader_epoch,
         })
     }
 
@@ -71,6 +77,11 @@ where
         self.hw.current_value()
     }
 
+    /// latest leader epoch in the log
+    pub fn leader_epoch(&self) -> i32 {
+        self.leader_epoch.load(Ordering::Acquire)
+    }
+
     pub fn as_offset(&self) -> OffsetInfo {
         OffsetInfo {
             hw: self.hw(),
@@ -118,6 +129,32 @@ where
             .await
     }
 
+    /// batch which contains offset, nothing past the batch is read
+    pub async fn read_batch(
+        &self,
+        offset: Offset,
+        isolation: Isolation,
+    ) -> Result<Option<FileBatch>, ErrorCode> {
+        // slice only needs to cover batch header, iterator reads rest of the batch from file
+        let slice = self
+            .read_records(offset, BATCH_FILE_HEADER_SIZE as u32, isolation)
+            .await?;
+        let Some(records) = slice.records() else {
+            return Ok(None);
+        };
+        match FileBatchIterator::from_records(records).next() {
+            Some(Ok(file_batch)) => Ok(Some(file_batch)),
+            Some(Err(err)) => Err(ErrorCode::Other(err.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// header of the committed batch which contains offset
+    pub async fn batch_header(&self, offset: Offset) -> Result<Option<BatchHeader>, ErrorCode> {
+        let file_batch = self.read_batch(offset, Isolation::ReadCommitted).await?;
+        Ok(file_batch.map(|file_batch| file_batch.batch.header))
+    }
+
     pub async fn update_hw(&self, hw: Offset) -> Result<bool, StorageError> {
         let mut writer = self.write().await;
         if writer.update_high_watermark(hw).await? {
@@ -154,6 +191,8 @@ where
         let leo = writer.get_leo();
         debug!(leo, "updated leo");
         self.leo.update(leo);
+        self.leader_epoch
+            .store(writer.get_leader_epoch(), Ordering::Release);
         if hw_update {
             let hw = writer.get_hw();
             debug!(hw, "updated hw");
@@ -163,6 +202,41 @@ where
         Ok((base_offset, leo, bytes_written))
     }
 
+    /// record start of new leader epoch at log end offset
+    pub async fn assign_leader_epoch(&self, epoch: i32) -> Result<bool, StorageError> {
+        let mut writer = self.write().await;
+        let assigned = writer.assign_leader_epoch(epoch).await?;
+        self.leader_epoch
+            .store(writer.get_leader_epoch(), Ordering::Release);
+        Ok(assigned)
+    }
+
+    /// end offset of largest leader epoch which is less than or equal to epoch
+    pub async fn epoch_end_offset(&self, epoch: i32) -> Option<(i32, Offset)> {
+        self.read().await.get_epoch_end_offset(epoch)
+    }
+
+    /// remove records at or beyond offset, return new leo
+    #[instrument(skip(self))]
+    pub async fn truncate_to(&self, offset: Offset) -> Result<Offset> {
+        let mut writer = self.write().await;
+        let leo = writer.truncate_to(offset).await?;
+        self.leo.update(leo);
+        self.hw.update(writer.get_hw());
+        self.leader_epoch
+            .store(writer.get_leader_epoch(), Ordering::Release);
+        debug!(leo, replica = %self.id, "truncated");
+        Ok(leo)
+    }
+
+    /// remove records before offset, return new log start offset
+    #[instrument(skip(self))]
+    pub async fn delete_records(&self, offset: Offset) -> Result<Offset> {
+        let log_start = self.write().await.delete_records(offset).await?;
+        debug!(log_start, replica = %self.id, "records deleted");
+        Ok(log_start)
+    }
+
     /// perform permanent remove
     pub async fn remove(&self) -> Result<(), StorageError> {
         self.leo.update(REMOVAL_START);
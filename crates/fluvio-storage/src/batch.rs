@@ -41,6 +41,17 @@ pub enum BatchHeaderError {
     },
 }
 
+#[derive(Debug, thiserror::Error, Clone)]
+#[error(
+    "Batch crc mismatch at pos: {pos} base offset: {base_offset} crc: {crc:#010x} computed: {computed:#010x}"
+)]
+pub struct BatchCrcError {
+    pub pos: u32,
+    pub base_offset: Offset,
+    pub crc: u32,
+    pub computed: u32,
+}
+
 /// hold information about position of batch in the file
 pub struct FileBatchPos<R>
 where
@@ -60,6 +71,7 @@ where
     #[instrument(skip(file))]
     pub(crate) async fn read_from<S: StorageBytesIterator>(
         file: &mut S,
+        verify_crc: bool,
     ) -> Result<Option<FileBatchPos<R>>> {
         let pos = file.get_pos();
         trace!(pos, "reading from pos");
@@ -142,6 +154,19 @@ where
             .into());
         }
 
+        if verify_crc {
+            let computed = batch.header.compute_crc(&bytes)?;
+            if computed != batch.header.crc {
+                return Err(BatchCrcError {
+                    pos,
+                    base_offset: batch.get_base_offset(),
+                    crc: batch.header.crc,
+                    computed,
+                }
+                .into());
+            }
+        }
+
         let mut cursor = Cursor::new(bytes);
         batch.mut_records().decode(&mut cursor, 0)?;
 
@@ -166,6 +191,7 @@ where
 // Stream to iterate over batches in a file
 pub struct FileBatchStream<R = MemoryRecords, S = FileBytesIterator> {
     invalid: bool,
+    verify_crc: bool,
     byte_iterator: S,
     data: PhantomData<R>,
 }
@@ -180,6 +206,11 @@ where
         self.invalid
     }
 
+    /// verify crc of each batch read, mismatch is returned as [`BatchCrcError`]
+    pub fn set_verify_crc(&mut self, verify_crc: bool) {
+        self.verify_crc = verify_crc;
+    }
+
     #[inline(always)]
     pub fn get_pos(&self) -> Size {
         self.byte_iterator.get_pos()
@@ -210,6 +241,7 @@ where
         Ok(Self {
             byte_iterator,
             invalid: false,
+            verify_crc: false,
             data: PhantomData,
         })
     }
@@ -220,6 +252,7 @@ where
         Ok(Self {
             byte_iterator,
             invalid: false,
+            verify_crc: false,
             data: PhantomData,
         })
     }
@@ -231,8 +264,10 @@ where
         if self.invalid {
             return Err(anyhow!("stream has been invalidated"));
         }
-        match FileBatchPos::read_from(&mut self.byte_iterator).await {
+        match FileBatchPos::read_from(&mut self.byte_iterator, self.verify_crc).await {
             Ok(batch_res) => Ok(batch_res),
+            // whole batch has been read, so stream can continue with next batch
+            Err(err) if err.is::<BatchCrcError>() => Err(err),
             Err(err) => {
                 error!("error getting batch: {}, invalidating", err);
                 self.invalid = true;
@@ -1,4 +1,6 @@
-use std::path::PathBuf;
+use std::io::Cursor;
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use clap::Parser;
@@ -6,14 +8,18 @@ use anyhow::{Result, anyhow};
 
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_future::timer::sleep;
-use fluvio_protocol::record::Offset;
+use fluvio_protocol::Decoder;
+use fluvio_protocol::record::{Batch, MemoryRecords, Offset, BATCH_PREAMBLE_SIZE};
 use fluvio_future::task::run_block_on;
 use fluvio_storage::checkpoint::{CheckPoint, HW_CHECKPOINT_FILE_NAME};
 use fluvio_storage::{
     LogIndex, OffsetPosition, batch_header::BatchHeaderStream, segment::MutableSegment,
-    config::ReplicaConfig, FileReplica, ReplicaStorage,
+    config::ReplicaConfig, FileReplica, ReplicaStorage, FileRecordsSlice,
 };
+use fluvio_storage::batch::BatchCrcError;
 use fluvio_storage::records::FileRecords;
+use fluvio_storage::encryption::{FileKeyProvider, KeyProvider, SegmentCipher, SharedKeyProvider};
+use fluvio_storage::repair;
 
 ///
 /// Bunch of storage utilities:
@@ -37,6 +43,18 @@ enum Main {
     Replica(ReplicaOpt),
 
     Hw(Hw),
+
+    /// rebuild index of segments from their logs
+    #[clap(name = "rebuild-index")]
+    RebuildIndex(RebuildIndexOpt),
+
+    /// truncate segment after last valid batch
+    #[clap(name = "truncate")]
+    Truncate(TruncateOpt),
+
+    /// rewrite segments of replica with different segment size
+    #[clap(name = "resegment")]
+    Resegment(ResegmentOpt),
 }
 
 fn main() {
@@ -51,6 +69,9 @@ fn main() {
             Main::ValidateSegment(opt) => validate_segment(opt).await,
             Main::Replica(opt) => replica_info(opt).await,
             Main::Hw(hw) => hw.process().await,
+            Main::RebuildIndex(opt) => rebuild_index(opt).await,
+            Main::Truncate(opt) => truncate(opt).await,
+            Main::Resegment(opt) => resegment(opt).await,
         }
     });
     if let Err(err) = result {
@@ -76,6 +97,14 @@ pub(crate) struct LogOpt {
     /// set position
     #[clap(long, default_value = "0")]
     position: u32,
+
+    /// verify crc of batches
+    #[clap(long)]
+    verify_crc: bool,
+
+    /// decrypt batches of encrypted segment with keys from file
+    #[clap(long)]
+    key_file: Option<PathBuf>,
 }
 
 async fn dump_log(opt: LogOpt) -> Result<()> {
@@ -89,12 +118,28 @@ async fn dump_log(opt: LogOpt) -> Result<()> {
 
     println!("opening: {:#?} position: {}", opt.file_name, opt.position);
 
+    let cipher = match &opt.key_file {
+        Some(key_file) => {
+            let provider = FileKeyProvider::open(key_file)?;
+            let cipher = segment_cipher(&opt.file_name, &provider)?;
+            match &cipher {
+                Some(cipher) => println!("segment is encrypted with key: {}", cipher.key_id()),
+                None => println!("segment is not encrypted"),
+            }
+            cipher
+        }
+        None => None,
+    };
+    let log_file = std::fs::File::open(&opt.file_name)?;
+
     let mut header_stream = BatchHeaderStream::open(opt.file_name).await?;
     header_stream.set_absolute(opt.position).await?;
+    header_stream.set_verify_crc(opt.verify_crc);
 
     //  println!("base offset: {}",batch_stream.get_base_offset());
 
     let mut count: usize = 0;
+    let mut corrupt: usize = 0;
     let time = std::time::Instant::now();
     let mut last_batch_offset = 0;
     loop {
@@ -123,12 +168,36 @@ async fn dump_log(opt: LogOpt) -> Result<()> {
                     );
                 }
 
+                if let Some(cipher) = &cipher {
+                    let mut stored = vec![0u8; BATCH_PREAMBLE_SIZE + batch.batch_len as usize];
+                    log_file.read_exact_at(&mut stored, pos as u64)?;
+                    match cipher.decrypt_batch(&stored).and_then(|bytes| {
+                        Ok(Batch::<MemoryRecords>::decode_from(
+                            &mut Cursor::new(bytes),
+                            0,
+                        )?)
+                    }) {
+                        Ok(decrypted) if opt.print => {
+                            println!("  decrypted records: {}", decrypted.records().len());
+                        }
+                        Ok(_) => {}
+                        Err(err) => {
+                            println!("batch offset: {base_offset} can't be decrypted: {err}");
+                            corrupt += 1;
+                        }
+                    }
+                }
+
                 count += 1;
                 last_batch_offset = base_offset;
             }
             Ok(None) => {
                 break;
             }
+            Err(err) if err.is::<BatchCrcError>() => {
+                println!("corrupt batch: {err}");
+                corrupt += 1;
+            }
             Err(err) => {
                 println!("encountered error: {err:#?}");
                 println!("last batch offset: {last_batch_offset}");
@@ -141,10 +210,33 @@ async fn dump_log(opt: LogOpt) -> Result<()> {
         "{count} records checked in {} millsecs",
         time.elapsed().as_millis()
     );
+    if opt.verify_crc || cipher.is_some() {
+        println!("{corrupt} corrupt batches found");
+    }
 
     Ok(())
 }
 
+/// cipher of segment, none if segment is not encrypted
+fn segment_cipher(
+    log_path: &Path,
+    provider: &dyn KeyProvider,
+) -> Result<Option<Arc<SegmentCipher>>> {
+    let base_offset: Offset = log_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .and_then(|stem| stem.parse().ok())
+        .ok_or_else(|| anyhow!("invalid log file name: {}", log_path.display()))?;
+    let base_dir = log_path.parent().unwrap_or_else(|| Path::new("."));
+    SegmentCipher::open_with(base_offset, base_dir, Some(provider))
+}
+
+fn key_provider(key_file: Option<PathBuf>) -> Result<Option<SharedKeyProvider>> {
+    key_file
+        .map(|path| Ok(SharedKeyProvider::new(FileKeyProvider::open(path)?)))
+        .transpose()
+}
+
 #[derive(Debug, Parser)]
 pub(crate) struct IndexOpt {
     #[clap(value_parser)]
@@ -194,15 +286,42 @@ pub(crate) struct SegmentValidateOpt {
 
     #[clap(long, default_value = "0")]
     base_offset: Offset,
+
+    /// verify crc of batches without repairing segment
+    #[clap(long)]
+    verify_crc: bool,
+
+    /// key file, required for encrypted segment
+    #[clap(long)]
+    key_file: Option<PathBuf>,
 }
 
 pub(crate) async fn validate_segment(opt: SegmentValidateOpt) -> Result<()> {
     let file_path = opt.file_name;
 
-    let option = ReplicaConfig::builder()
-        .base_dir(file_path.clone())
-        .build()
-        .shared();
+    let mut builder = ReplicaConfig::builder();
+    builder.base_dir(file_path.clone());
+    if let Some(provider) = key_provider(opt.key_file)? {
+        builder.key_provider(provider);
+    }
+    let option = builder.build().shared();
+
+    if opt.verify_crc {
+        let records = FileRecordsSlice::open(opt.base_offset, option.clone()).await?;
+        let index = LogIndex::open_from_offset(opt.base_offset, option).await?;
+        let validation = records.validate_with_crc(&index).await?;
+        println!(
+            "verified {} batches, valid up to pos = {}, took: {} seconds",
+            validation.batches,
+            validation.last_valid_file_pos,
+            validation.duration.as_secs_f32()
+        );
+        if let Some(err) = validation.error {
+            println!("error: {err}");
+        }
+        return Ok(());
+    }
+
     let mut active_segment = MutableSegment::open_for_write(opt.base_offset, option)
         .await
         .expect("failed to open segment");
@@ -233,15 +352,22 @@ pub(crate) struct ReplicaOpt {
 
     #[clap(long, default_value = "0")]
     partition: u32,
+
+    /// key file, required for encrypted replica
+    #[clap(long)]
+    key_file: Option<PathBuf>,
 }
 
 pub(crate) async fn replica_info(opt: ReplicaOpt) -> Result<()> {
     let replica_dir = opt.replica_dir;
 
     println!("opening replica dir: {replica_dir:#?}");
-    let option = ReplicaConfig::builder()
-        .base_dir(replica_dir.clone())
-        .build();
+    let mut builder = ReplicaConfig::builder();
+    builder.base_dir(replica_dir.clone());
+    if let Some(provider) = key_provider(opt.key_file)? {
+        builder.key_provider(provider);
+    }
+    let option = builder.build();
 
     let replica = ReplicaKey::new(opt.topic, opt.partition);
     let replica = FileReplica::create_or_load(&replica, option).await?;
@@ -260,6 +386,10 @@ pub(crate) struct Hw {
 
     #[arg(long)]
     offset: Option<Offset>,
+
+    /// set hw to log end offset of replica
+    #[arg(long, conflicts_with = "offset")]
+    recompute: bool,
 }
 
 impl Hw {
@@ -270,7 +400,15 @@ impl Hw {
             ..Default::default()
         };
 
-        if let Some(offset) = self.offset {
+        let offset = if self.recompute {
+            let leo = repair::replica_leo(&config).await?;
+            println!("leo: {leo}");
+            Some(leo)
+        } else {
+            self.offset
+        };
+
+        if let Some(offset) = offset {
             println!("writing hw: {offset} to checkpoint");
             let mut commit_checkpoint =
                 CheckPoint::create(Arc::new(config.into()), HW_CHECKPOINT_FILE_NAME, offset)
@@ -295,3 +433,105 @@ impl Hw {
         Ok(())
     }
 }
+
+#[derive(Debug, Parser)]
+pub(crate) struct RebuildIndexOpt {
+    #[clap(value_parser)]
+    replica_dir: PathBuf,
+
+    /// base offset of segment, all segments if not set
+    #[clap(long)]
+    base_offset: Option<Offset>,
+}
+
+async fn rebuild_index(opt: RebuildIndexOpt) -> Result<()> {
+    let config = ReplicaConfig {
+        base_dir: opt.replica_dir,
+        ..Default::default()
+    };
+    let offsets = match opt.base_offset {
+        Some(base_offset) => vec![base_offset],
+        None => repair::segment_offsets(&config.base_dir)?,
+    };
+    for base_offset in offsets {
+        let rebuild = repair::rebuild_index(&config, base_offset).await?;
+        println!(
+            "segment: {base_offset}, indexed {} batches with {} entries up to pos = {}",
+            rebuild.batches, rebuild.entries, rebuild.indexed_len
+        );
+        if rebuild.invalid_tail {
+            println!(
+                "segment: {base_offset} has invalid batches after pos = {}, truncate it",
+                rebuild.indexed_len
+            );
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct TruncateOpt {
+    #[clap(value_parser)]
+    replica_dir: PathBuf,
+
+    /// base offset of segment, last segment if not set
+    #[clap(long)]
+    base_offset: Option<Offset>,
+
+    /// also truncate at first batch with crc mismatch
+    #[clap(long)]
+    verify_crc: bool,
+}
+
+async fn truncate(opt: TruncateOpt) -> Result<()> {
+    let config = ReplicaConfig {
+        base_dir: opt.replica_dir,
+        ..Default::default()
+    };
+    let base_offset = match opt.base_offset {
+        Some(base_offset) => base_offset,
+        None => repair::segment_offsets(&config.base_dir)?
+            .last()
+            .copied()
+            .ok_or_else(|| anyhow!("no segment found in: {}", config.base_dir.display()))?,
+    };
+    let truncation = repair::truncate_segment(&config, base_offset, opt.verify_crc).await?;
+    println!(
+        "segment: {base_offset}, {} valid batches, leo: {}, valid up to pos = {}",
+        truncation.batches, truncation.leo, truncation.valid_len
+    );
+    if truncation.removed_bytes > 0 {
+        println!("removed {} bytes", truncation.removed_bytes);
+    } else {
+        println!("segment is valid, nothing removed");
+    }
+    Ok(())
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct ResegmentOpt {
+    #[clap(value_parser)]
+    replica_dir: PathBuf,
+
+    /// max bytes of new segments
+    #[clap(long)]
+    segment_max_bytes: u32,
+}
+
+async fn resegment(opt: ResegmentOpt) -> Result<()> {
+    let config = ReplicaConfig {
+        base_dir: opt.replica_dir,
+        segment_max_bytes: opt.segment_max_bytes,
+        ..Default::default()
+    };
+    let resegment = repair::resegment(&config).await?;
+    println!(
+        "{} batches copied from {} into {} segments",
+        resegment.batches, resegment.segments_before, resegment.segments_after
+    );
+    println!(
+        "original segments moved to: {}",
+        resegment.backup_dir.display()
+    );
+    Ok(())
+}
@@ -27,6 +27,7 @@ use tracing::instrument;
 use crate::config::SharedReplicaConfig;
 
 pub const HW_CHECKPOINT_FILE_NAME: &str = "replication.chk";
+pub const LOG_START_CHECKPOINT_FILE_NAME: &str = "log-start.chk";
 
 pub trait ReadToBuf: Sized {
     fn read_from<B>(buf: &mut B) -> Self
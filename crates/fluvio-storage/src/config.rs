@@ -19,6 +19,7 @@ use fluvio_types::defaults::SPU_LOG_SEGMENT_MAX_BYTES;
 use fluvio_protocol::record::{Size, Size64};
 
 use crate::ReplicaStorageConfig;
+use crate::encryption::SharedKeyProvider;
 
 // Replica specific config
 #[derive(Builder, Clone, Debug, Eq, PartialEq, Deserialize)]
@@ -57,6 +58,10 @@ pub struct ReplicaConfig {
     #[builder(default = "default_max_partition_size()")]
     #[serde(default = "default_max_partition_size")]
     pub max_partition_size: Size64,
+    /// if set, new segments are encrypted with current key of the provider
+    #[builder(default, setter(strip_option))]
+    #[serde(skip)]
+    pub key_provider: Option<SharedKeyProvider>,
 }
 
 impl fmt::Display for ReplicaConfig {
@@ -161,6 +166,7 @@ impl Default for ReplicaConfig {
             retention_seconds: default_retention_seconds(),
             max_partition_size: default_max_partition_size(),
             update_hw: true,
+            key_provider: None,
         }
     }
 }
@@ -238,6 +244,7 @@ pub struct SharedReplicaConfig {
     pub update_hw: bool, // if true, enable hw update
     pub retention_seconds: SharedConfigU32Value,
     pub max_partition_size: SharedConfigU64Value,
+    pub key_provider: Option<SharedKeyProvider>,
 }
 
 impl From<ReplicaConfig> for SharedReplicaConfig {
@@ -254,6 +261,7 @@ impl From<ReplicaConfig> for SharedReplicaConfig {
             update_hw: config.update_hw,
             retention_seconds: SharedConfigU32Value::new(config.retention_seconds),
             max_partition_size: SharedConfigU64Value::new(config.max_partition_size),
+            key_provider: config.key_provider,
         }
     }
 }
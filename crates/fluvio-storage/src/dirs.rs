@@ -0,0 +1,375 @@
+//!
+//! # Data directories
+//!
+//! Replicas can be spread over several data directories, typically one per disk.
+//! New replica is placed in the directory whose filesystem is least used.  When directory fails,
+//! it is marked offline and replicas stored in it become unavailable
+//! while other directories continue to serve their replicas.
+//!
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::fs;
+use std::io::{Error as IoError, ErrorKind};
+use std::mem::MaybeUninit;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use blocking::unblock;
+use tracing::{debug, info, warn};
+
+use fluvio_protocol::record::ReplicaKey;
+
+use crate::StorageError;
+use crate::replica::replica_dir_name;
+
+/// file written to check directory is still writable
+const PROBE_FILE_NAME: &str = ".probe";
+
+#[derive(Debug)]
+struct DataDir {
+    path: PathBuf,
+    online: AtomicBool,
+}
+
+/// usage of data directory
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct DirUsage {
+    pub path: PathBuf,
+    pub online: bool,
+    pub replicas: u32,
+    /// bytes used in filesystem which holds directory
+    pub used_bytes: u64,
+}
+
+#[derive(Debug)]
+pub struct DataDirs {
+    dirs: Vec<DataDir>,
+    /// directory of each replica placed by this
+    placements: Mutex<HashMap<ReplicaKey, PathBuf>>,
+}
+
+impl DataDirs {
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let mut dirs: Vec<DataDir> = vec![];
+        for path in paths {
+            if dirs.iter().any(|dir| dir.path == path) {
+                continue;
+            }
+            dirs.push(DataDir {
+                path,
+                online: AtomicBool::new(true),
+            });
+        }
+        Self {
+            dirs,
+            placements: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn paths(&self) -> Vec<PathBuf> {
+        self.dirs.iter().map(|dir| dir.path.clone()).collect()
+    }
+
+    pub fn is_online(&self, path: &Path) -> bool {
+        self.dirs
+            .iter()
+            .any(|dir| dir.path == path && dir.online.load(Ordering::Acquire))
+    }
+
+    pub fn has_offline(&self) -> bool {
+        self.dirs
+            .iter()
+            .any(|dir| !dir.online.load(Ordering::Acquire))
+    }
+
+    /// mark directory as offline, return true if it was online before
+    pub fn set_offline(&self, path: &Path) -> bool {
+        let Some(dir) = self.dirs.iter().find(|dir| dir.path == path) else {
+            return false;
+        };
+        let was_online = dir.online.swap(false, Ordering::AcqRel);
+        if was_online {
+            warn!(dir = %path.display(), "data directory is offline");
+        }
+        was_online
+    }
+
+    /// write probe file in each online directory,
+    /// directories which can't be written are marked offline and returned
+    pub async fn check(&self) -> Vec<PathBuf> {
+        let mut failed = vec![];
+        for dir in self.dirs.iter() {
+            if !dir.online.load(Ordering::Acquire) {
+                continue;
+            }
+            let path = dir.path.clone();
+            if let Err(err) = unblock(move || probe(&path)).await {
+                warn!(dir = %dir.path.display(), %err, "data directory check failed");
+                if self.set_offline(&dir.path) {
+                    failed.push(dir.path.clone());
+                }
+            }
+        }
+        failed
+    }
+
+    /// directory which holds replica
+    pub fn locate(&self, replica: &ReplicaKey) -> Option<PathBuf> {
+        if let Some(path) = self.placements().get(replica) {
+            return Some(path.clone());
+        }
+        let name = replica_dir_name(&replica.topic, replica.partition);
+        let path = self
+            .dirs
+            .iter()
+            .filter(|dir| dir.online.load(Ordering::Acquire))
+            .find(|dir| dir.path.join(&name).is_dir())
+            .map(|dir| dir.path.clone())?;
+        self.placements().insert(replica.clone(), path.clone());
+        Some(path)
+    }
+
+    /// select directory for replica.
+    /// Existing replica stays in its directory, new replica goes to the least used online directory.
+    /// If any directory is offline, replica which is not found could be stored in it,
+    /// so this fails instead of creating empty replica unless `relocate` is set.
+    pub async fn select(
+        &self,
+        replica: &ReplicaKey,
+        relocate: bool,
+    ) -> Result<PathBuf, StorageError> {
+        if let Some(path) = self.locate(replica) {
+            if self.is_online(&path) {
+                return Ok(path);
+            }
+            if !relocate {
+                return Err(StorageError::DirOffline(path.display().to_string()));
+            }
+        } else if !relocate && self.has_offline() {
+            return Err(StorageError::DirOffline(self.offline_paths()));
+        }
+
+        let path = least_used(self.usage().await)
+            .ok_or_else(|| StorageError::DirOffline(self.offline_paths()))?;
+
+        info!(%replica, dir = %path.display(), "placing replica");
+        self.placements().insert(replica.clone(), path.clone());
+        Ok(path)
+    }
+
+    /// forget replica placement, this should be called when replica is removed
+    pub fn remove(&self, replica: &ReplicaKey) {
+        self.placements().remove(replica);
+    }
+
+    /// replicas placed in directory
+    pub fn replicas_in(&self, path: &Path) -> Vec<ReplicaKey> {
+        self.placements()
+            .iter()
+            .filter(|(_, dir)| dir.as_path() == path)
+            .map(|(replica, _)| replica.clone())
+            .collect()
+    }
+
+    /// compute usage of all directories, offline directories are not queried.
+    /// Used bytes come from filesystem stats so files are not walked.
+    pub async fn usage(&self) -> Vec<DirUsage> {
+        let mut usages = vec![];
+        for dir in self.dirs.iter() {
+            let online = dir.online.load(Ordering::Acquire);
+            let usage = if online {
+                let path = dir.path.clone();
+                match unblock(move || dir_usage(&path)).await {
+                    Ok((replicas, used_bytes)) => DirUsage {
+                        path: dir.path.clone(),
+                        online,
+                        replicas,
+                        used_bytes,
+                    },
+                    Err(err) => {
+                        debug!(dir = %dir.path.display(), %err, "unable to compute usage");
+                        DirUsage {
+                            path: dir.path.clone(),
+                            online,
+                            ..Default::default()
+                        }
+                    }
+                }
+            } else {
+                DirUsage {
+                    path: dir.path.clone(),
+                    online,
+                    replicas: self.replicas_in(&dir.path).len() as u32,
+                    used_bytes: 0,
+                }
+            };
+            usages.push(usage);
+        }
+        usages
+    }
+
+    fn offline_paths(&self) -> String {
+        self.dirs
+            .iter()
+            .filter(|dir| !dir.online.load(Ordering::Acquire))
+            .map(|dir| dir.path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn placements(&self) -> std::sync::MutexGuard<'_, HashMap<ReplicaKey, PathBuf>> {
+        self.placements
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn probe(path: &Path) -> Result<(), IoError> {
+    fs::create_dir_all(path)?;
+    let probe = path.join(PROBE_FILE_NAME);
+    fs::write(&probe, b"probe")?;
+    fs::remove_file(&probe)
+}
+
+/// online directory with least used bytes, ties go to directory with fewer replicas
+fn least_used(usages: Vec<DirUsage>) -> Option<PathBuf> {
+    usages
+        .into_iter()
+        .filter(|usage| usage.online)
+        .min_by_key(|usage| (usage.used_bytes, usage.replicas))
+        .map(|usage| usage.path)
+}
+
+/// number of replica directories and used bytes of filesystem
+fn dir_usage(path: &Path) -> Result<(u32, u64), IoError> {
+    let mut replicas = 0;
+    for entry in fs::read_dir(path)? {
+        if entry?.file_type()?.is_dir() {
+            replicas += 1;
+        }
+    }
+    Ok((replicas, fs_used_bytes(path)?))
+}
+
+// statvfs field types differ between platforms
+#[allow(clippy::unnecessary_cast)]
+fn fs_used_bytes(path: &Path) -> Result<u64, IoError> {
+    let path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|err| IoError::new(ErrorKind::InvalidInput, err))?;
+    let mut stat = MaybeUninit::<libc::statvfs>::uninit();
+    if unsafe { libc::statvfs(path.as_ptr(), stat.as_mut_ptr()) } != 0 {
+        return Err(IoError::last_os_error());
+    }
+    let stat = unsafe { stat.assume_init() };
+    let used_blocks = (stat.f_blocks as u64).saturating_sub(stat.f_bfree as u64);
+    Ok(used_blocks * stat.f_frsize as u64)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+    use std::fs;
+
+    use flv_util::fixture::ensure_clean_dir;
+    use fluvio_protocol::record::ReplicaKey;
+
+    use super::{DataDirs, DirUsage, least_used};
+
+    fn dir_usage(path: &str, online: bool, replicas: u32, used_bytes: u64) -> DirUsage {
+        DirUsage {
+            path: path.into(),
+            online,
+            replicas,
+            used_bytes,
+        }
+    }
+
+    #[test]
+    fn test_least_used() {
+        assert_eq!(
+            least_used(vec![
+                dir_usage("disk1", true, 1, 200),
+                dir_usage("disk2", true, 5, 100),
+                dir_usage("disk3", false, 0, 0),
+            ]),
+            Some("disk2".into())
+        );
+        // same filesystem, fewer replicas wins
+        assert_eq!(
+            least_used(vec![
+                dir_usage("disk1", true, 2, 100),
+                dir_usage("disk2", true, 1, 100),
+            ]),
+            Some("disk2".into())
+        );
+        assert_eq!(least_used(vec![dir_usage("disk1", false, 0, 0)]), None);
+    }
+
+    #[fluvio_future::test]
+    async fn test_select_dir() {
+        let base_dir = temp_dir().join("test_select_dir");
+        ensure_clean_dir(&base_dir);
+        let dir1 = base_dir.join("disk1");
+        let dir2 = base_dir.join("disk2");
+        fs::create_dir_all(dir1.join("existing-0")).expect("create");
+        fs::create_dir_all(&dir2).expect("create");
+
+        let dirs = DataDirs::new(vec![dir1.clone(), dir2.clone()]);
+        assert!(dirs.check().await.is_empty());
+
+        // existing replica stays in its directory
+        let existing = ReplicaKey::new("existing", 0u32);
+        assert_eq!(dirs.select(&existing, false).await.expect("select"), dir1);
+
+        // new replica is placed in one of directories
+        let new = ReplicaKey::new("new", 0u32);
+        let placed = dirs.select(&new, false).await.expect("select");
+        assert!(placed == dir1 || placed == dir2);
+        assert!(dirs.replicas_in(&placed).contains(&new));
+
+        let usage = dirs.usage().await;
+        assert_eq!(usage[0].replicas, 1);
+        assert!(usage[0].used_bytes > 0);
+        assert_eq!(usage[1].replicas, 0);
+    }
+
+    #[fluvio_future::test]
+    async fn test_offline_dir() {
+        let base_dir = temp_dir().join("test_offline_dir");
+        ensure_clean_dir(&base_dir);
+        let dir1 = base_dir.join("disk1");
+        let dir2 = base_dir.join("disk2");
+        fs::create_dir_all(dir1.join("topic-0")).expect("create");
+
+        // dir2 can't be created since file is in the way
+        fs::create_dir_all(&base_dir).expect("create");
+        fs::write(&dir2, b"not a dir").expect("write");
+
+        let dirs = DataDirs::new(vec![dir1.clone(), dir2.clone()]);
+        assert_eq!(dirs.check().await, vec![dir2.clone()]);
+        assert!(dirs.is_online(&dir1));
+        assert!(!dirs.is_online(&dir2));
+
+        // replica in online dir is still served
+        let existing = ReplicaKey::new("topic", 0u32);
+        assert_eq!(dirs.select(&existing, false).await.expect("select"), dir1);
+
+        // unknown replica may be stored in offline dir
+        let unknown = ReplicaKey::new("topic", 1u32);
+        assert!(dirs.select(&unknown, false).await.is_err());
+        assert_eq!(dirs.select(&unknown, true).await.expect("select"), dir1);
+
+        // replica placed in dir which goes offline is relocated only if allowed
+        assert!(dirs.set_offline(&dir1));
+        assert!(!dirs.set_offline(&dir1));
+        let offline_replicas = dirs.replicas_in(&dir1);
+        assert_eq!(offline_replicas.len(), 2);
+        assert!(offline_replicas.contains(&existing));
+        assert!(offline_replicas.contains(&unknown));
+        assert!(dirs.select(&unknown, false).await.is_err());
+        assert!(dirs.select(&unknown, true).await.is_err());
+    }
+}
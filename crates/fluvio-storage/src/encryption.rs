@@ -0,0 +1,521 @@
+//! Optional at-rest encryption of segment files.
+//!
+//! When [`ReplicaConfig::key_provider`](crate::config::ReplicaConfig) is set, each new segment is
+//! encrypted with the provider's current key.  The key id is recorded in a small metadata file
+//! next to the segment (`<base_offset>.key`), so rotating keys only affects segments created
+//! afterwards while existing segments keep using the key they were written with.
+//!
+//! Every batch is sealed separately with AES-256-GCM.  Batch preamble and header are kept in
+//! plaintext so offsets, index and validation work without the key, while the content is replaced
+//! by `nonce | ciphertext(original crc | content) | tag`.  Batch length and crc of the header are
+//! rewritten to describe the on-disk content.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{Error as IoError, ErrorKind, Write};
+use std::ops::Deref;
+use std::os::fd::{BorrowedFd, FromRawFd};
+use std::os::unix::fs::FileExt;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{Result, anyhow};
+use blocking::unblock;
+use tracing::{debug, info};
+
+use fluvio_future::file_slice::AsyncFileSlice;
+use fluvio_future::fs::remove_file;
+use fluvio_protocol::Decoder;
+use fluvio_protocol::record::{BatchHeader, Offset, BATCH_FILE_HEADER_SIZE, BATCH_PREAMBLE_SIZE};
+
+use crate::StorageError;
+use crate::config::SharedReplicaConfig;
+use crate::util::generate_file_name;
+
+pub const KEY_ID_EXTENSION: &str = "key";
+
+pub const ENCRYPTION_KEY_LEN: usize = 32;
+
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+const CRC_LEN: usize = 4;
+
+/// bytes added to content of each encrypted batch
+pub const ENCRYPTED_BATCH_OVERHEAD: usize = NONCE_LEN + CRC_LEN + TAG_LEN;
+
+// position of batch_len and crc in the encoded batch
+const BATCH_LEN_POS: usize = BATCH_PREAMBLE_SIZE - 4;
+const CRC_POS: usize = BATCH_PREAMBLE_SIZE + 4 + 1;
+const CRC_FIELDS_POS: usize = CRC_POS + CRC_LEN;
+
+pub type EncryptionKey = [u8; ENCRYPTION_KEY_LEN];
+
+/// Source of encryption keys
+pub trait KeyProvider: fmt::Debug + Send + Sync {
+    /// key id and key used to encrypt new segments
+    fn current_key(&self) -> Result<(String, EncryptionKey)>;
+
+    /// look up key for existing segment
+    fn key(&self, key_id: &str) -> Result<EncryptionKey>;
+}
+
+/// Key provider that can be shared by replicas
+#[derive(Debug, Clone)]
+pub struct SharedKeyProvider(Arc<dyn KeyProvider>);
+
+impl SharedKeyProvider {
+    pub fn new<P: KeyProvider + 'static>(provider: P) -> Self {
+        Self(Arc::new(provider))
+    }
+}
+
+impl Deref for SharedKeyProvider {
+    type Target = dyn KeyProvider;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl PartialEq for SharedKeyProvider {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SharedKeyProvider {}
+
+/// Keys stored in local file, one key per line as `<key id>:<hex encoded 256 bit key>`.
+/// Last key in the file is used for new segments.  File is read on every lookup,
+/// so keys can be rotated by appending new key without restarting.
+#[derive(Debug, Clone)]
+pub struct FileKeyProvider {
+    path: PathBuf,
+}
+
+impl FileKeyProvider {
+    /// create provider, file must contain at least one valid key
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let provider = Self { path: path.into() };
+        provider.current_key()?;
+        Ok(provider)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn read_keys(&self) -> Result<Vec<(String, EncryptionKey)>> {
+        let content = std::fs::read_to_string(&self.path)
+            .map_err(|err| anyhow!("unable to read key file {}: {err}", self.path.display()))?;
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(parse_key_line)
+            .collect()
+    }
+}
+
+impl KeyProvider for FileKeyProvider {
+    fn current_key(&self) -> Result<(String, EncryptionKey)> {
+        self.read_keys()?
+            .pop()
+            .ok_or_else(|| anyhow!("no keys found in key file {}", self.path.display()))
+    }
+
+    fn key(&self, key_id: &str) -> Result<EncryptionKey> {
+        self.read_keys()?
+            .into_iter()
+            .find(|(id, _)| id == key_id)
+            .map(|(_, key)| key)
+            .ok_or_else(|| anyhow!("key: {key_id} not found in {}", self.path.display()))
+    }
+}
+
+fn parse_key_line(line: &str) -> Result<(String, EncryptionKey)> {
+    let (id, hex) = line
+        .split_once(':')
+        .ok_or_else(|| anyhow!("invalid key entry, expected <id>:<hex key>"))?;
+    let id = id.trim();
+    let hex = hex.trim();
+    if id.is_empty() {
+        return Err(anyhow!("key id is empty"));
+    }
+    if hex.len() != ENCRYPTION_KEY_LEN * 2 || !hex.is_ascii() {
+        return Err(anyhow!(
+            "key: {id} must be {} hex characters",
+            ENCRYPTION_KEY_LEN * 2
+        ));
+    }
+    let mut key = [0u8; ENCRYPTION_KEY_LEN];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+            .map_err(|err| anyhow!("key: {id} is not valid hex: {err}"))?;
+    }
+    Ok((id.to_owned(), key))
+}
+
+/// Cipher used to encrypt and decrypt batches of a single segment
+pub struct SegmentCipher {
+    key_id: String,
+    cipher: Aes256Gcm,
+}
+
+impl fmt::Debug for SegmentCipher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SegmentCipher({})", self.key_id)
+    }
+}
+
+impl SegmentCipher {
+    pub fn new(key_id: impl Into<String>, key: &EncryptionKey) -> Self {
+        Self {
+            key_id: key_id.into(),
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+        }
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    /// set up encryption for new segment using current key of the provider.
+    /// key id is recorded next to the segment
+    pub(crate) fn create(
+        base_offset: Offset,
+        option: &SharedReplicaConfig,
+    ) -> Result<Option<Arc<Self>>> {
+        let Some(provider) = &option.key_provider else {
+            return Ok(None);
+        };
+        let (key_id, key) = provider.current_key()?;
+        let path = generate_file_name(&option.base_dir, base_offset, KEY_ID_EXTENSION);
+        std::fs::write(&path, &key_id)?;
+        info!(base_offset, key_id, "encrypting new segment");
+        Ok(Some(Arc::new(Self::new(key_id, &key))))
+    }
+
+    /// cipher of existing segment, none if segment is not encrypted
+    pub(crate) fn open(
+        base_offset: Offset,
+        option: &SharedReplicaConfig,
+    ) -> Result<Option<Arc<Self>>> {
+        Self::open_with(
+            base_offset,
+            &option.base_dir,
+            option.key_provider.as_deref(),
+        )
+    }
+
+    /// cipher of existing segment in the directory
+    pub fn open_with(
+        base_offset: Offset,
+        base_dir: &Path,
+        provider: Option<&dyn KeyProvider>,
+    ) -> Result<Option<Arc<Self>>> {
+        let path = generate_file_name(base_dir, base_offset, KEY_ID_EXTENSION);
+        let key_id = match std::fs::read_to_string(&path) {
+            Ok(key_id) => key_id.trim().to_owned(),
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let Some(provider) = provider else {
+            return Err(anyhow!(
+                "segment: {base_offset} is encrypted with key: {key_id}, but no key provider is configured"
+            ));
+        };
+        let key = provider.key(&key_id)?;
+        debug!(base_offset, key_id, "opened encrypted segment");
+        Ok(Some(Arc::new(Self::new(key_id, &key))))
+    }
+
+    /// remove key id of the segment if exists
+    pub(crate) async fn remove(
+        base_offset: Offset,
+        option: &SharedReplicaConfig,
+    ) -> Result<(), IoError> {
+        let path = generate_file_name(&option.base_dir, base_offset, KEY_ID_EXTENSION);
+        match remove_file(&path).await {
+            Ok(_) => {
+                info!(key_path = %path.display(), "removed segment key id");
+                Ok(())
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// encrypt encoded batch in place
+    pub fn encrypt_batch(&self, buffer: &mut Vec<u8>) -> Result<()> {
+        let header = decode_header(buffer)?;
+        let mut plain = Vec::with_capacity(CRC_LEN + buffer.len() - BATCH_FILE_HEADER_SIZE);
+        plain.extend_from_slice(&buffer[CRC_POS..CRC_FIELDS_POS]);
+        plain.extend_from_slice(&buffer[BATCH_FILE_HEADER_SIZE..]);
+
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let sealed = self
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: &plain,
+                    aad: &associated_data(buffer),
+                },
+            )
+            .map_err(|_| anyhow!("batch encryption failed"))?;
+
+        buffer.truncate(BATCH_FILE_HEADER_SIZE);
+        buffer.extend_from_slice(&nonce);
+        buffer.extend_from_slice(&sealed);
+
+        let batch_len = (buffer.len() - BATCH_PREAMBLE_SIZE) as i32;
+        let crc = header.compute_crc(&buffer[BATCH_FILE_HEADER_SIZE..])?;
+        buffer[BATCH_LEN_POS..BATCH_PREAMBLE_SIZE].copy_from_slice(&batch_len.to_be_bytes());
+        buffer[CRC_POS..CRC_FIELDS_POS].copy_from_slice(&crc.to_be_bytes());
+        Ok(())
+    }
+
+    /// decrypt batch as stored on disk, return batch as originally encoded
+    pub fn decrypt_batch(&self, stored: &[u8]) -> Result<Vec<u8>> {
+        if stored.len() < BATCH_FILE_HEADER_SIZE + ENCRYPTED_BATCH_OVERHEAD - CRC_LEN {
+            return Err(anyhow!("encrypted batch is too short: {}", stored.len()));
+        }
+        let content = &stored[BATCH_FILE_HEADER_SIZE..];
+        let (nonce, sealed) = content.split_at(NONCE_LEN);
+        let plain = self
+            .cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: sealed,
+                    aad: &associated_data(stored),
+                },
+            )
+            .map_err(|_| anyhow!("batch decryption failed with key: {}", self.key_id))?;
+        if plain.len() < CRC_LEN {
+            return Err(anyhow!("decrypted batch is missing crc"));
+        }
+
+        let mut batch = Vec::with_capacity(BATCH_FILE_HEADER_SIZE + plain.len() - CRC_LEN);
+        batch.extend_from_slice(&stored[..BATCH_FILE_HEADER_SIZE]);
+        batch.extend_from_slice(&plain[CRC_LEN..]);
+        let batch_len = (batch.len() - BATCH_PREAMBLE_SIZE) as i32;
+        batch[BATCH_LEN_POS..BATCH_PREAMBLE_SIZE].copy_from_slice(&batch_len.to_be_bytes());
+        batch[CRC_POS..CRC_FIELDS_POS].copy_from_slice(&plain[..CRC_LEN]);
+        Ok(batch)
+    }
+
+    /// decrypt batches in the slice of segment file into anonymous file.
+    /// batches are decrypted until `max_len` bytes are available, result is limited to `max_len`.
+    /// file is read and decrypted in blocking thread pool
+    pub async fn decrypt_slice(
+        self: &Arc<Self>,
+        slice: &AsyncFileSlice,
+        max_len: u32,
+    ) -> Result<(AsyncFileSlice, Arc<File>)> {
+        // SAFETY: fd of the slice belongs to segment file, which caller keeps open during this call.
+        // it's duplicated, so the blocking task doesn't depend on segment staying open
+        let segment_file =
+            File::from(unsafe { BorrowedFd::borrow_raw(slice.fd()) }.try_clone_to_owned()?);
+        let cipher = self.clone();
+        let start = slice.position();
+        let end = start + slice.len();
+        unblock(move || cipher.decrypt_range(&segment_file, start, end, max_len)).await
+    }
+
+    fn decrypt_range(
+        &self,
+        segment_file: &File,
+        start: u64,
+        end: u64,
+        max_len: u32,
+    ) -> Result<(AsyncFileSlice, Arc<File>)> {
+        let mut pos = start;
+        let mut records: Vec<u8> = Vec::new();
+
+        while pos < end && records.len() < max_len as usize {
+            let mut preamble = [0u8; BATCH_PREAMBLE_SIZE];
+            segment_file.read_exact_at(&mut preamble, pos)?;
+            let mut batch_len_bytes = [0u8; 4];
+            batch_len_bytes.copy_from_slice(&preamble[BATCH_LEN_POS..]);
+            let batch_len = i32::from_be_bytes(batch_len_bytes);
+            // corrupted length must not turn into huge read or allocation
+            let stored_len = usize::try_from(batch_len)
+                .map(|len| BATCH_PREAMBLE_SIZE + len)
+                .ok()
+                .filter(|stored_len| pos + *stored_len as u64 <= end)
+                .ok_or(StorageError::InvalidBatchLength {
+                    pos,
+                    len: batch_len,
+                })?;
+
+            let mut stored = vec![0u8; stored_len];
+            segment_file.read_exact_at(&mut stored, pos)?;
+            records.extend_from_slice(&self.decrypt_batch(&stored)?);
+            pos += stored_len as u64;
+        }
+
+        let mut file = anonymous_file()?;
+        file.write_all(&records)?;
+        let len = std::cmp::min(records.len() as u64, max_len as u64);
+        let decrypted = AsyncFileSlice::new(file.as_raw_fd(), 0, len);
+        Ok((decrypted, Arc::new(file)))
+    }
+}
+
+fn decode_header(batch: &[u8]) -> Result<BatchHeader> {
+    if batch.len() < BATCH_FILE_HEADER_SIZE {
+        return Err(anyhow!("batch is too short: {}", batch.len()));
+    }
+    let mut header = BatchHeader::default();
+    header.decode(
+        &mut std::io::Cursor::new(&batch[BATCH_PREAMBLE_SIZE..BATCH_FILE_HEADER_SIZE]),
+        0,
+    )?;
+    Ok(header)
+}
+
+/// base offset and header fields except crc are authenticated along with content
+fn associated_data(batch: &[u8]) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(BATCH_FILE_HEADER_SIZE);
+    aad.extend_from_slice(&batch[..BATCH_LEN_POS]);
+    aad.extend_from_slice(&batch[BATCH_PREAMBLE_SIZE..CRC_POS]);
+    aad.extend_from_slice(&batch[CRC_FIELDS_POS..BATCH_FILE_HEADER_SIZE]);
+    aad
+}
+
+/// file in memory which holds decrypted records, so they can be sent by zero copy
+#[cfg(target_os = "linux")]
+fn anonymous_file() -> Result<File, IoError> {
+    // SAFETY: name is a valid nul terminated string
+    let fd = unsafe { libc::memfd_create(c"fluvio-records".as_ptr(), libc::MFD_CLOEXEC) };
+    if fd < 0 {
+        return Err(IoError::last_os_error());
+    }
+    // SAFETY: fd was just created by memfd_create and is not owned by anything else
+    Ok(unsafe { File::from_raw_fd(fd) })
+}
+
+/// sendfile requires regular file on other platforms, so use unlinked temporary file
+#[cfg(not(target_os = "linux"))]
+fn anonymous_file() -> Result<File, IoError> {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let path = std::env::temp_dir().join(format!(
+        "fluvio-records-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create_new(true)
+        .open(&path)?;
+    std::fs::remove_file(&path)?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use flv_util::fixture::ensure_new_dir;
+    use fluvio_protocol::Encoder;
+    use fluvio_protocol::record::{Batch, MemoryRecords, Record};
+
+    use super::*;
+
+    const KEY: EncryptionKey = [7u8; ENCRYPTION_KEY_LEN];
+
+    fn encoded_batch() -> Vec<u8> {
+        let mut batch = Batch::<MemoryRecords>::default();
+        batch.base_offset = 10;
+        batch.add_record(Record::new("hello"));
+        batch.add_record(Record::new("world"));
+        let mut buffer = Vec::new();
+        batch.encode(&mut buffer, 0).expect("encode");
+        buffer
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_batch() {
+        let cipher = SegmentCipher::new("k1", &KEY);
+        let plain = encoded_batch();
+
+        let mut stored = plain.clone();
+        cipher.encrypt_batch(&mut stored).expect("encrypt");
+        assert_eq!(stored.len(), plain.len() + ENCRYPTED_BATCH_OVERHEAD);
+        assert_eq!(&stored[..BATCH_LEN_POS], &plain[..BATCH_LEN_POS]);
+
+        // header crc describes encrypted content
+        let header = decode_header(&stored).expect("header");
+        header
+            .validate_crc(10, &stored[BATCH_FILE_HEADER_SIZE..])
+            .expect("crc");
+
+        assert_eq!(cipher.decrypt_batch(&stored).expect("decrypt"), plain);
+
+        // header is authenticated
+        let mut tampered = stored.clone();
+        tampered[BATCH_FILE_HEADER_SIZE - 1] ^= 1;
+        assert!(cipher.decrypt_batch(&tampered).is_err());
+
+        let other = SegmentCipher::new("k2", &[8u8; ENCRYPTION_KEY_LEN]);
+        assert!(other.decrypt_batch(&stored).is_err());
+    }
+
+    #[test]
+    fn test_decrypt_invalid_batch_len() {
+        let cipher = SegmentCipher::new("k1", &KEY);
+        let dir = temp_dir().join("encryption-invalid-batch-len");
+        ensure_new_dir(&dir).expect("new");
+
+        for batch_len in [-1, 1000] {
+            let mut preamble = 10i64.to_be_bytes().to_vec();
+            preamble.extend_from_slice(&batch_len.to_be_bytes());
+            preamble.resize(100, 0);
+            let path = dir.join(format!("{batch_len}.log"));
+            std::fs::write(&path, &preamble).expect("write");
+            let file = File::open(&path).expect("open");
+
+            let err = cipher
+                .decrypt_range(&file, 0, preamble.len() as u64, 1000)
+                .expect_err("invalid length");
+            assert!(matches!(
+                err.downcast_ref::<StorageError>(),
+                Some(StorageError::InvalidBatchLength { pos: 0, len }) if *len == batch_len
+            ));
+        }
+    }
+
+    #[test]
+    fn test_file_key_provider() {
+        let dir = temp_dir().join("encryption-key-provider");
+        ensure_new_dir(&dir).expect("new");
+        let path = dir.join("keys");
+        std::fs::write(&path, format!("# keys\nk1:{}\n", "01".repeat(32))).expect("write");
+
+        let provider = FileKeyProvider::open(&path).expect("open");
+        assert_eq!(provider.current_key().expect("current").0, "k1");
+
+        // rotation is picked up without reopening
+        let mut content = std::fs::read_to_string(&path).expect("read");
+        content.push_str(&format!("k2:{}\n", "ab".repeat(32)));
+        std::fs::write(&path, content).expect("write");
+
+        let (id, key) = provider.current_key().expect("current");
+        assert_eq!(id, "k2");
+        assert_eq!(key, [0xab; ENCRYPTION_KEY_LEN]);
+        assert_eq!(provider.key("k1").expect("k1"), [1; ENCRYPTION_KEY_LEN]);
+        assert!(provider.key("k3").is_err());
+
+        std::fs::write(&path, "k1:1234\n").expect("write");
+        assert!(FileKeyProvider::open(&path).is_err());
+    }
+}
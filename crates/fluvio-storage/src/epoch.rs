@@ -0,0 +1,174 @@
+use std::fs;
+use std::io::{Error as IoError, ErrorKind};
+use std::path::{Path, PathBuf};
+
+use tracing::{debug, info};
+
+use fluvio_protocol::record::Offset;
+
+pub const LEADER_EPOCH_FILE_NAME: &str = "leader-epoch.checkpoint";
+
+/// Start offset of leader epoch
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct EpochEntry {
+    pub epoch: i32,
+    pub start_offset: Offset,
+}
+
+/// Sequence of leader epochs with offset where each epoch started.
+/// Epochs and start offsets are always increasing.
+/// This is persisted in replica directory as text file, one line per epoch.
+/// File is small and only written when epoch changes, so it's written synchronously.
+#[derive(Debug)]
+pub struct LeaderEpochCache {
+    path: PathBuf,
+    entries: Vec<EpochEntry>,
+}
+
+impl LeaderEpochCache {
+    /// load from replica directory, if file doesn't exist start empty
+    pub fn open(base_dir: &Path) -> Result<Self, IoError> {
+        let path = base_dir.join(LEADER_EPOCH_FILE_NAME);
+        let entries = if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            parse_entries(&content)?
+        } else {
+            vec![]
+        };
+        debug!(path = %path.display(), entries = entries.len(), "loaded leader epochs");
+        Ok(Self { path, entries })
+    }
+
+    pub fn entries(&self) -> &[EpochEntry] {
+        &self.entries
+    }
+
+    /// latest epoch, -1 if there is no epoch
+    pub fn latest_epoch(&self) -> i32 {
+        self.entries.last().map(|entry| entry.epoch).unwrap_or(-1)
+    }
+
+    /// record that epoch starts at offset.
+    /// epoch which is not newer than latest is ignored.
+    /// return true if new epoch was recorded
+    pub fn assign(&mut self, epoch: i32, start_offset: Offset) -> Result<bool, IoError> {
+        if epoch < 0 || epoch <= self.latest_epoch() {
+            return Ok(false);
+        }
+
+        // previous epoch can't start after new one
+        self.entries
+            .retain(|entry| entry.start_offset < start_offset);
+        info!(epoch, start_offset, "new leader epoch");
+        self.entries.push(EpochEntry {
+            epoch,
+            start_offset,
+        });
+        self.flush()?;
+        Ok(true)
+    }
+
+    /// find largest epoch which is less than or equal to requested epoch
+    /// and offset where it ended (exclusive), which is start of next epoch or leo.
+    /// return none if requested epoch is older than any known epoch
+    pub fn end_offset_for(&self, epoch: i32, leo: Offset) -> Option<(i32, Offset)> {
+        let index = self
+            .entries
+            .iter()
+            .rposition(|entry| entry.epoch <= epoch)?;
+        let end_offset = self
+            .entries
+            .get(index + 1)
+            .map(|next| next.start_offset)
+            .unwrap_or(leo);
+        Some((self.entries[index].epoch, end_offset))
+    }
+
+    /// remove epochs which started at or after offset
+    pub fn truncate_from_end(&mut self, offset: Offset) -> Result<(), IoError> {
+        let len = self.entries.len();
+        self.entries.retain(|entry| entry.start_offset < offset);
+        if self.entries.len() != len {
+            debug!(
+                offset,
+                removed = len - self.entries.len(),
+                "truncated epochs"
+            );
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// write to temporary file then rename so file is never partially written
+    fn flush(&self) -> Result<(), IoError> {
+        let content: String = self
+            .entries
+            .iter()
+            .map(|entry| format!("{} {}\n", entry.epoch, entry.start_offset))
+            .collect();
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, content)?;
+        fs::rename(&tmp_path, &self.path)
+    }
+}
+
+fn parse_entries(content: &str) -> Result<Vec<EpochEntry>, IoError> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut parts = line.split_whitespace();
+            match (
+                parts.next().and_then(|epoch| epoch.parse().ok()),
+                parts.next().and_then(|offset| offset.parse().ok()),
+            ) {
+                (Some(epoch), Some(start_offset)) => Ok(EpochEntry {
+                    epoch,
+                    start_offset,
+                }),
+                _ => Err(IoError::new(
+                    ErrorKind::InvalidData,
+                    format!("invalid leader epoch entry: {line}"),
+                )),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use flv_util::fixture::ensure_new_dir;
+
+    use super::*;
+
+    #[test]
+    fn test_epoch_end_offset() {
+        let dir = std::env::temp_dir().join("epoch_end_offset");
+        ensure_new_dir(&dir).expect("dir");
+
+        let mut cache = LeaderEpochCache::open(&dir).expect("open");
+        assert_eq!(cache.latest_epoch(), -1);
+        assert_eq!(cache.end_offset_for(0, 10), None);
+
+        assert!(cache.assign(1, 0).expect("assign"));
+        assert!(cache.assign(2, 5).expect("assign"));
+        assert!(!cache.assign(2, 7).expect("assign"));
+        assert!(cache.assign(4, 8).expect("assign"));
+        assert_eq!(cache.latest_epoch(), 4);
+
+        assert_eq!(cache.end_offset_for(0, 10), None);
+        assert_eq!(cache.end_offset_for(1, 10), Some((1, 5)));
+        assert_eq!(cache.end_offset_for(3, 10), Some((2, 8)));
+        assert_eq!(cache.end_offset_for(4, 10), Some((4, 10)));
+
+        // reload from file
+        let mut cache = LeaderEpochCache::open(&dir).expect("open");
+        assert_eq!(cache.entries().len(), 3);
+
+        cache.truncate_from_end(6).expect("truncate");
+        assert_eq!(cache.latest_epoch(), 2);
+        let cache = LeaderEpochCache::open(&dir).expect("open");
+        assert_eq!(cache.latest_epoch(), 2);
+    }
+}
@@ -30,6 +30,10 @@ pub enum StorageError {
     EmptyBatch,
     #[error("Storage is short-circuited")]
     ShortCircuited,
+    #[error("Data directory is offline: {0}")]
+    DirOffline(String),
+    #[error("Invalid batch length {len} at position {pos}")]
+    InvalidBatchLength { pos: u64, len: i32 },
 }
 
 impl From<BoundedFileSinkError> for StorageError {
@@ -1,7 +1,9 @@
 use std::collections::VecDeque;
+use std::fs::File;
 use std::os::fd::BorrowedFd;
 use std::os::unix::io::RawFd;
 use std::io::{Error as IoError, ErrorKind, Cursor};
+use std::sync::Arc;
 
 use nix::sys::uio::pread;
 
@@ -10,6 +12,7 @@ use fluvio_protocol::{Decoder, Version};
 
 use fluvio_protocol::record::{Batch, Offset, BATCH_FILE_HEADER_SIZE, BATCH_HEADER_SIZE, Record};
 use fluvio_future::file_slice::AsyncFileSlice;
+use fluvio_spu_schema::file::FileRecordSet;
 
 // only encode information necessary to decode batches efficiently
 pub struct FileBatch {
@@ -29,6 +32,7 @@ pub struct FileBatchIterator {
     fd: RawFd,
     offset: Offset,
     end: i64,
+    _owner: Option<Arc<File>>,
 }
 
 impl FileBatchIterator {
@@ -38,6 +42,7 @@ impl FileBatchIterator {
             fd,
             offset,
             end: offset + len,
+            _owner: None,
         }
     }
 
@@ -48,6 +53,15 @@ impl FileBatchIterator {
             fd: slice.as_raw_fd(),
             offset,
             end: offset + slice.len() as i64,
+            _owner: None,
+        }
+    }
+
+    /// iterate over records, keeps file of decrypted records open while iterating
+    pub fn from_records(records: FileRecordSet) -> Self {
+        Self {
+            _owner: records.owner().cloned(),
+            ..Self::from_raw_slice(records.raw_slice())
         }
     }
 }
@@ -275,11 +289,11 @@ mod test {
             u32::MAX,
             fluvio_spu_schema::Isolation::ReadUncommitted,
         ))?;
-        let file_slice = slice
-            .file_slice
+        let records = slice
+            .records()
             .ok_or_else(|| anyhow::anyhow!("expected file slice"))?;
 
-        let record_iter = FileRecordIterator::new(FileBatchIterator::from_raw_slice(file_slice), 0);
+        let record_iter = FileRecordIterator::new(FileBatchIterator::from_records(records), 0);
         let records: Vec<RecordItem> =
             record_iter.collect::<Result<Vec<RecordItem>, std::io::Error>>()?;
 
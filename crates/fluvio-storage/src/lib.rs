@@ -1,6 +1,7 @@
 pub mod batch;
 pub mod batch_header;
 pub mod checkpoint;
+pub mod epoch;
 mod error;
 pub mod records;
 mod index;
@@ -13,6 +14,9 @@ mod util;
 mod validator;
 mod file;
 pub mod config;
+pub mod dirs;
+pub mod encryption;
+pub mod repair;
 #[cfg(feature = "iterators")]
 pub mod iterators;
 
@@ -29,6 +33,9 @@ pub use crate::replica::FileReplica;
 pub use inner::*;
 mod inner {
 
+    use std::fs::File;
+    use std::sync::Arc;
+
     use async_trait::async_trait;
     use anyhow::Result;
 
@@ -39,6 +46,7 @@ mod inner {
     use fluvio_protocol::record::RecordSet;
     use fluvio_future::file_slice::AsyncFileSlice;
     use fluvio_controlplane::replica::Replica;
+    use fluvio_spu_schema::file::FileRecordSet;
 
     #[derive(Debug, Clone, Eq, PartialEq)]
     pub struct OffsetInfo {
@@ -118,6 +126,19 @@ mod inner {
         pub start: Offset,   // start offset
         pub end: OffsetInfo, // end offset
         pub file_slice: Option<AsyncFileSlice>,
+        /// file backing `file_slice` when it is not segment file, i.e. decrypted records
+        pub records_file: Option<Arc<File>>,
+    }
+
+    impl ReplicaSlice {
+        /// records to be sent, keeps backing file open
+        pub fn records(&self) -> Option<FileRecordSet> {
+            let slice = self.file_slice.clone()?;
+            Some(match &self.records_file {
+                Some(file) => FileRecordSet::with_owner(slice, file.clone()),
+                None => slice.into(),
+            })
+        }
     }
 
     /// some storage configuration
@@ -165,6 +186,24 @@ mod inner {
 
         async fn update_high_watermark(&mut self, offset: Offset) -> Result<bool, StorageError>;
 
+        /// latest leader epoch recorded in this replica, -1 if there is none
+        fn get_leader_epoch(&self) -> i32;
+
+        /// find largest leader epoch which is less than or equal to requested epoch
+        /// and end offset (exclusive) of that epoch
+        fn get_epoch_end_offset(&self, epoch: i32) -> Option<(i32, Offset)>;
+
+        /// record that leader epoch starts at log end offset
+        /// return true if epoch is newer than latest epoch
+        async fn assign_leader_epoch(&mut self, epoch: i32) -> Result<bool, StorageError>;
+
+        /// remove records at or beyond offset, return new log end offset
+        async fn truncate_to(&mut self, offset: Offset) -> Result<Offset>;
+
+        /// remove records before offset, offset is limited to high watermark.
+        /// return new log start offset
+        async fn delete_records(&mut self, offset: Offset) -> Result<Offset>;
+
         /// permanently remove
         async fn remove(&self) -> Result<(), StorageError>;
     }
@@ -227,6 +227,22 @@ impl MutLogIndex {
         Ok(())
     }
 
+    /// remove entries at or beyond file position, this is used when segment is truncated
+    /// offset_delta: relative offset of first removed batch
+    /// file_position: file position of first removed batch
+    #[instrument(skip(self))]
+    pub fn truncate(&mut self, offset_delta: Size, file_position: Size) {
+        let mut slot = self.first_empty_slot;
+        while slot > 0 && self[slot as usize - 1].to_be().position() >= file_position {
+            slot -= 1;
+            self[slot as usize] = (0, 0);
+        }
+        debug!(slot, "truncated index");
+        self.first_empty_slot = slot;
+        self.last_offset_delta = offset_delta.saturating_sub(1);
+        self.accumulated_batch_len = 0;
+    }
+
     /// entries capacity in the index
     fn entries(&self) -> Size {
         (self.capacity() / INDEX_ENTRY_SIZE) as u32
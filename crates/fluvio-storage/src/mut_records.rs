@@ -31,6 +31,7 @@ use crate::util::generate_file_name;
 use crate::validator::LogValidationError;
 use crate::records::FileRecords;
 use crate::validator::LogValidator;
+use crate::encryption::{SegmentCipher, ENCRYPTED_BATCH_OVERHEAD};
 
 pub const MESSAGE_LOG_EXTENSION: &str = "log";
 
@@ -52,6 +53,7 @@ pub struct MutFileRecords {
     flush_count: Arc<AtomicU32>,
     path: PathBuf,
     _flush_time_tx: Option<Sender<Instant>>,
+    cipher: Option<Arc<SegmentCipher>>,
 }
 
 impl fmt::Debug for MutFileRecords {
@@ -102,6 +104,7 @@ impl MutFileRecords {
             flush_count: Arc::new(AtomicU32::new(0)),
             path: log_path.to_owned(),
             _flush_time_tx: None,
+            cipher: None,
         })
     }
 
@@ -109,6 +112,11 @@ impl MutFileRecords {
         self.base_offset
     }
 
+    /// encrypt batches written from now on
+    pub(crate) fn set_cipher(&mut self, cipher: Option<Arc<SegmentCipher>>) {
+        self.cipher = cipher;
+    }
+
     /// readjust to new length
     pub(crate) async fn set_len(&mut self, len: u32) -> Result<()> {
         self.file.set_len(len as u64).await?;
@@ -141,7 +149,13 @@ impl MutFileRecords {
             .into());
         }
 
-        let batch_len = batch.write_size(0);
+        let encoded_len = batch.write_size(0);
+        // encrypted batch is stored with nonce, crc and tag
+        let batch_len = if self.cipher.is_some() {
+            encoded_len + ENCRYPTED_BATCH_OVERHEAD
+        } else {
+            encoded_len
+        };
         debug!(batch_len, "writing batch of size",);
 
         if (batch_len as u32 + self.len) > self.max_len {
@@ -151,6 +165,10 @@ impl MutFileRecords {
 
         let mut buffer: Vec<u8> = Vec::with_capacity(batch_len);
         batch.encode(&mut buffer, 0)?;
+        assert_eq!(buffer.len(), encoded_len);
+        if let Some(cipher) = &self.cipher {
+            cipher.encrypt_batch(&mut buffer)?;
+        }
         assert_eq!(buffer.len(), batch_len);
 
         let raw_fd = self.file.as_raw_fd();
@@ -93,6 +93,11 @@ impl FileRecordsSlice {
         LogValidator::default_validate(&self.path, Some(index)).await
     }
 
+    /// validate and verify crc of every batch
+    pub async fn validate_with_crc(&self, index: &LogIndex) -> Result<LogValidator> {
+        LogValidator::validate_with_crc(&self.path, Some(index)).await
+    }
+
     pub fn modified_time_elapsed(&self) -> Result<Duration, SystemTimeError> {
         self.last_modified_time.elapsed()
     }
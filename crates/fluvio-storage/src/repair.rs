@@ -0,0 +1,425 @@
+//!
+//! # Offline repair
+//!
+//! Recovers replica directory after a crash without deleting data.
+//! These work directly on segment files of a replica directory, so replica must not be
+//! opened by SPU at the same time.  High watermark beyond log end and leader epochs of
+//! truncated records are reset when replica is loaded next time.
+//!
+use std::ffi::OsStr;
+use std::fs::{self, File};
+use std::io::Write;
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow, bail};
+use tracing::{debug, info, warn};
+
+use fluvio_protocol::record::{Offset, Size};
+
+use crate::LogIndex;
+use crate::batch_header::BatchHeaderStream;
+use crate::config::{ReplicaConfig, SharedReplicaConfig};
+use crate::encryption::KEY_ID_EXTENSION;
+use crate::index::{INDEX_ENTRY_SIZE, Index};
+use crate::mut_index::{EXTENSION as INDEX_EXTENSION, MutLogIndex};
+use crate::util::{generate_file_name, log_path_get_offset};
+use crate::validator::LogValidator;
+
+const LOG_EXTENSION: &str = "log";
+
+#[derive(Debug, Default)]
+pub struct IndexRebuild {
+    pub batches: u32,
+    pub entries: u64,
+    /// end position of last indexed batch
+    pub indexed_len: Size,
+    /// log has invalid batch after indexed batches
+    pub invalid_tail: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct SegmentTruncation {
+    pub batches: u32,
+    pub leo: Offset,
+    pub valid_len: u64,
+    pub removed_bytes: u64,
+    pub index: IndexRebuild,
+}
+
+#[derive(Debug)]
+pub struct Resegment {
+    pub batches: u64,
+    pub segments_before: usize,
+    pub segments_after: usize,
+    /// directory where original segment files are moved
+    pub backup_dir: PathBuf,
+}
+
+/// base offsets of segments in replica directory, in ascending order
+pub fn segment_offsets(replica_dir: &Path) -> Result<Vec<Offset>> {
+    let mut offsets = vec![];
+    for entry in fs::read_dir(replica_dir)? {
+        let path = entry?.path();
+        if path.extension() == Some(OsStr::new(LOG_EXTENSION)) {
+            offsets.push(log_path_get_offset(&path)?);
+        }
+    }
+    offsets.sort_unstable();
+    Ok(offsets)
+}
+
+/// Replace index of segment with index built from its log.
+/// Indexing stops at first batch which can't be decoded.
+pub async fn rebuild_index(config: &ReplicaConfig, base_offset: Offset) -> Result<IndexRebuild> {
+    let option: Arc<SharedReplicaConfig> = Arc::new(config.clone().into());
+    let log_path = generate_file_name(&option.base_dir, base_offset, LOG_EXTENSION);
+    if !log_path.exists() {
+        bail!("log file not found: {}", log_path.display());
+    }
+    let index_path = generate_file_name(&option.base_dir, base_offset, INDEX_EXTENSION);
+    if index_path.exists() {
+        debug!(index = %index_path.display(), "removing old index");
+        fs::remove_file(&index_path)?;
+    }
+
+    let mut index = MutLogIndex::create(base_offset, option).await?;
+    let mut rebuild = IndexRebuild::default();
+    let mut stream = BatchHeaderStream::open(&log_path).await?;
+    let mut last_delta: Option<Size> = None;
+    loop {
+        let batch_pos = match stream.try_next().await {
+            Ok(Some(batch_pos)) => batch_pos,
+            Ok(None) => break,
+            Err(err) => {
+                warn!(%err, pos = rebuild.indexed_len, "invalid batch, indexing stopped");
+                rebuild.invalid_tail = true;
+                break;
+            }
+        };
+        let pos = batch_pos.get_pos();
+        let delta = batch_pos.get_batch().get_base_offset() - base_offset;
+        if delta < 0 || last_delta.is_some_and(|last| delta as Size <= last) {
+            warn!(delta, pos, "batch offset out of order, indexing stopped");
+            rebuild.invalid_tail = true;
+            break;
+        }
+        index
+            .write_index(delta as Size, pos, stream.get_pos() - pos)
+            .await?;
+        last_delta = Some(delta as Size);
+        rebuild.batches += 1;
+        rebuild.indexed_len = stream.get_pos();
+    }
+    index.shrink().await?;
+    rebuild.entries = index.len() / INDEX_ENTRY_SIZE;
+    info!(base_offset, ?rebuild, "index rebuilt");
+    Ok(rebuild)
+}
+
+/// Truncate segment after last valid batch found by [`LogValidator`] and rebuild its index
+pub async fn truncate_segment(
+    config: &ReplicaConfig,
+    base_offset: Offset,
+    verify_crc: bool,
+) -> Result<SegmentTruncation> {
+    let log_path = generate_file_name(&config.base_dir, base_offset, LOG_EXTENSION);
+    let len = fs::metadata(&log_path)?.len();
+
+    let mut truncation = SegmentTruncation {
+        leo: base_offset,
+        ..Default::default()
+    };
+    if len > 0 {
+        let validation = if verify_crc {
+            LogValidator::validate_with_crc::<LogIndex>(&log_path, None).await?
+        } else {
+            LogValidator::default_validate::<LogIndex>(&log_path, None).await?
+        };
+        if let Some(err) = &validation.error {
+            warn!(%err, pos = validation.last_valid_file_pos, "invalid batch found");
+        }
+        truncation.batches = validation.batches;
+        truncation.leo = validation.leo();
+        truncation.valid_len = validation.last_valid_file_pos as u64;
+    }
+
+    if truncation.valid_len < len {
+        info!(
+            log = %log_path.display(),
+            len,
+            valid_len = truncation.valid_len,
+            "truncating segment"
+        );
+        File::options()
+            .write(true)
+            .open(&log_path)?
+            .set_len(truncation.valid_len)?;
+        truncation.removed_bytes = len - truncation.valid_len;
+    }
+    truncation.index = rebuild_index(config, base_offset).await?;
+    Ok(truncation)
+}
+
+/// log end offset of replica, computed from its last segment
+pub async fn replica_leo(config: &ReplicaConfig) -> Result<Offset> {
+    let Some(base_offset) = segment_offsets(&config.base_dir)?.last().copied() else {
+        bail!("no segment found in: {}", config.base_dir.display());
+    };
+    let log_path = generate_file_name(&config.base_dir, base_offset, LOG_EXTENSION);
+    if fs::metadata(&log_path)?.len() == 0 {
+        return Ok(base_offset);
+    }
+    let validation = LogValidator::default_validate::<LogIndex>(&log_path, None).await?;
+    Ok(validation.leo())
+}
+
+/// Rewrite segments of replica so each segment is at most `segment_max_bytes` of the config.
+/// Batches are copied as they are into a work directory first.  Then original segment files
+/// are moved to a backup directory next to replica directory and replaced with new ones,
+/// other files such as checkpoints are kept.
+pub async fn resegment(config: &ReplicaConfig) -> Result<Resegment> {
+    let replica_dir = &config.base_dir;
+    let name = replica_dir
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow!("invalid replica directory: {}", replica_dir.display()))?;
+    let offsets = segment_offsets(replica_dir)?;
+    let Some(last_offset) = offsets.last().copied() else {
+        bail!("no segment found in: {}", replica_dir.display());
+    };
+    if has_extension(replica_dir, KEY_ID_EXTENSION)? {
+        bail!("encrypted replica can't be re-segmented");
+    }
+    let backup_dir = replica_dir.with_file_name(format!("{name}.backup"));
+    if backup_dir.exists() {
+        bail!("backup directory already exists: {}", backup_dir.display());
+    }
+    let work_dir = replica_dir.with_file_name(format!("{name}.resegment"));
+    if work_dir.exists() {
+        fs::remove_dir_all(&work_dir)?;
+    }
+    fs::create_dir_all(&work_dir)?;
+
+    let max_bytes = config.segment_max_bytes as u64;
+    let mut new_offsets = vec![];
+    let mut current: Option<(File, u64)> = None;
+    let mut batches = 0;
+    for base_offset in offsets.iter().copied() {
+        let log_path = generate_file_name(replica_dir, base_offset, LOG_EXTENSION);
+        if fs::metadata(&log_path)?.len() == 0 {
+            continue;
+        }
+        let source = File::open(&log_path)?;
+        let mut stream = BatchHeaderStream::open(&log_path).await?;
+        while let Some(batch_pos) = stream.try_next().await.map_err(|err| {
+            anyhow!("segment {base_offset} has invalid batch, truncate it first: {err}")
+        })? {
+            let pos = batch_pos.get_pos();
+            let len = (stream.get_pos() - pos) as u64;
+            let mut bytes = vec![0; len as usize];
+            source.read_exact_at(&mut bytes, pos as u64)?;
+
+            let full = current
+                .as_ref()
+                .is_none_or(|(_, written)| *written > 0 && written + len > max_bytes);
+            if full {
+                if let Some((file, _)) = current.take() {
+                    file.sync_all()?;
+                }
+                let batch_offset = batch_pos.get_batch().get_base_offset();
+                let file =
+                    File::create(generate_file_name(&work_dir, batch_offset, LOG_EXTENSION))?;
+                debug!(batch_offset, "new segment");
+                new_offsets.push(batch_offset);
+                current = Some((file, 0));
+            }
+            if let Some((file, written)) = current.as_mut() {
+                file.write_all(&bytes)?;
+                *written += len;
+            }
+            batches += 1;
+        }
+    }
+    if let Some((file, _)) = current.take() {
+        file.sync_all()?;
+    }
+    // replica without records keeps its last segment
+    if new_offsets.is_empty() {
+        File::create(generate_file_name(&work_dir, last_offset, LOG_EXTENSION))?;
+        new_offsets.push(last_offset);
+    }
+
+    let mut work_config = config.clone();
+    work_config.base_dir = work_dir.clone();
+    for base_offset in new_offsets.iter().copied() {
+        rebuild_index(&work_config, base_offset).await?;
+    }
+
+    fs::create_dir_all(&backup_dir)?;
+    move_segments(replica_dir, &backup_dir, &offsets)?;
+    move_segments(&work_dir, replica_dir, &new_offsets)?;
+    fs::remove_dir(&work_dir)?;
+
+    let resegment = Resegment {
+        batches,
+        segments_before: offsets.len(),
+        segments_after: new_offsets.len(),
+        backup_dir,
+    };
+    info!(?resegment, "replica re-segmented");
+    Ok(resegment)
+}
+
+fn move_segments(from: &Path, to: &Path, offsets: &[Offset]) -> Result<()> {
+    for base_offset in offsets.iter().copied() {
+        for extension in [LOG_EXTENSION, INDEX_EXTENSION] {
+            let path = generate_file_name(from, base_offset, extension);
+            if path.exists() {
+                fs::rename(&path, generate_file_name(to, base_offset, extension))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn has_extension(dir: &Path, extension: &str) -> Result<bool> {
+    for entry in fs::read_dir(dir)? {
+        if entry?.path().extension() == Some(OsStr::new(extension)) {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(test)]
+#[cfg(feature = "fixture")]
+mod tests {
+
+    use std::env::temp_dir;
+    use std::fs;
+    use std::io::Write;
+
+    use flv_util::fixture::ensure_clean_dir;
+    use fluvio_protocol::fixture::create_batch;
+    use fluvio_protocol::record::Offset;
+
+    use crate::FileReplica;
+    use crate::ReplicaStorage;
+    use crate::config::ReplicaConfig;
+    use crate::fixture::storage_config;
+    use crate::util::generate_file_name;
+
+    use super::*;
+
+    const START_OFFSET: Offset = 20;
+
+    /// replica with 5 batches of 2 records, returns config of replica directory
+    async fn create_replica(dir: &str) -> ReplicaConfig {
+        let base_dir = temp_dir().join(dir);
+        ensure_clean_dir(&base_dir);
+        let config = ReplicaConfig {
+            segment_max_bytes: 10000,
+            base_dir: base_dir.clone(),
+            index_max_interval_bytes: 0,
+            index_max_bytes: 1000,
+            ..Default::default()
+        };
+        let mut replica = FileReplica::create_or_load_inner(
+            "test",
+            0,
+            START_OFFSET,
+            config.clone(),
+            storage_config(),
+        )
+        .await
+        .expect("replica");
+        for _ in 0..5 {
+            replica
+                .write_batch(&mut create_batch())
+                .await
+                .expect("write");
+        }
+        assert_eq!(replica.get_leo(), START_OFFSET + 10);
+        drop(replica);
+
+        ReplicaConfig {
+            base_dir: base_dir.join("test-0"),
+            ..config
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_rebuild_index() {
+        let config = create_replica("repair_rebuild_index").await;
+        let index_path = generate_file_name(&config.base_dir, START_OFFSET, INDEX_EXTENSION);
+        let original = fs::read(&index_path).expect("read");
+
+        fs::remove_file(&index_path).expect("remove");
+        let rebuild = rebuild_index(&config, START_OFFSET).await.expect("rebuild");
+        assert_eq!(rebuild.batches, 5);
+        assert!(!rebuild.invalid_tail);
+
+        let rebuilt = fs::read(&index_path).expect("read");
+        assert_eq!(rebuilt.len() as u64, rebuild.entries * INDEX_ENTRY_SIZE);
+        assert_eq!(&original[..rebuilt.len()], rebuilt.as_slice());
+        assert!(original[rebuilt.len()..].iter().all(|byte| *byte == 0));
+    }
+
+    #[fluvio_future::test]
+    async fn test_truncate_segment() {
+        let config = create_replica("repair_truncate_segment").await;
+        let log_path = generate_file_name(&config.base_dir, START_OFFSET, LOG_EXTENSION);
+        let len = fs::metadata(&log_path).expect("metadata").len();
+
+        let mut file = File::options().append(true).open(&log_path).expect("open");
+        file.write_all(&[0x01, 0x02, 0x03]).expect("write junk");
+        drop(file);
+
+        let truncation = truncate_segment(&config, START_OFFSET, true)
+            .await
+            .expect("truncate");
+        assert_eq!(truncation.batches, 5);
+        assert_eq!(truncation.leo, START_OFFSET + 10);
+        assert_eq!(truncation.valid_len, len);
+        assert_eq!(truncation.removed_bytes, 3);
+        assert_eq!(fs::metadata(&log_path).expect("metadata").len(), len);
+        assert_eq!(replica_leo(&config).await.expect("leo"), START_OFFSET + 10);
+    }
+
+    #[fluvio_future::test]
+    async fn test_resegment() {
+        let config = create_replica("repair_resegment").await;
+        let log_len = fs::metadata(generate_file_name(
+            &config.base_dir,
+            START_OFFSET,
+            LOG_EXTENSION,
+        ))
+        .expect("metadata")
+        .len();
+
+        // each segment holds 2 batches
+        let batch_len = log_len / 5;
+        let config = ReplicaConfig {
+            segment_max_bytes: (batch_len * 2) as u32,
+            ..config
+        };
+        let resegment = resegment(&config).await.expect("resegment");
+        assert_eq!(resegment.batches, 5);
+        assert_eq!(resegment.segments_before, 1);
+        assert_eq!(resegment.segments_after, 3);
+        assert_eq!(
+            segment_offsets(&config.base_dir).expect("offsets"),
+            vec![START_OFFSET, START_OFFSET + 4, START_OFFSET + 8]
+        );
+        assert_eq!(
+            segment_offsets(&resegment.backup_dir).expect("offsets"),
+            vec![START_OFFSET]
+        );
+        assert_eq!(replica_leo(&config).await.expect("leo"), START_OFFSET + 10);
+
+        // backup must be removed before replica can be re-segmented again
+        assert!(super::resegment(&config).await.is_err());
+    }
+}
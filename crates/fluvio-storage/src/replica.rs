@@ -1,4 +1,4 @@
-use std::cmp::min;
+use std::cmp::{max, min};
 use std::{fmt, mem};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
@@ -7,7 +7,6 @@ use tracing::{debug, trace, warn, instrument, info};
 use async_trait::async_trait;
 use anyhow::Result;
 
-use fluvio_future::file_slice::AsyncFileSlice;
 use fluvio_protocol::Encoder;
 use fluvio_future::fs::{create_dir_all, remove_dir_all};
 use fluvio_protocol::link::ErrorCode;
@@ -16,7 +15,8 @@ use fluvio_protocol::record::{Offset, ReplicaKey, Size, Size64};
 use fluvio_protocol::record::{Batch, BatchRecords};
 use fluvio_protocol::record::RecordSet;
 
-use crate::checkpoint::HW_CHECKPOINT_FILE_NAME;
+use crate::checkpoint::{HW_CHECKPOINT_FILE_NAME, LOG_START_CHECKPOINT_FILE_NAME};
+use crate::epoch::LeaderEpochCache;
 use crate::{OffsetInfo, checkpoint::CheckPoint};
 use crate::segments::SharedSegments;
 use crate::segment::MutableSegment;
@@ -38,6 +38,8 @@ pub struct FileReplica {
     active_segment: MutableSegment,
     prev_segments: Arc<SharedSegments>,
     commit_checkpoint: CheckPoint,
+    log_start_checkpoint: CheckPoint,
+    epochs: LeaderEpochCache,
     cleaner: Arc<Cleaner>,
     size: Arc<ReplicaSize>,
     short_circuit: bool, // if this is true, last append failed, should not append again
@@ -86,14 +88,15 @@ impl ReplicaStorage for FileReplica {
         self.active_segment.get_end_offset()
     }
 
-    /// earliest offset
+    /// earliest offset, records before deleted offset are masked even if their segment still exists
     fn get_log_start_offset(&self) -> Offset {
         let min_base_offset = self.prev_segments.min_offset();
-        if min_base_offset < 0 {
+        let segment_start = if min_base_offset < 0 {
             self.active_segment.get_base_offset()
         } else {
             min_base_offset
-        }
+        };
+        max(segment_start, self.log_start_checkpoint.get_offset())
     }
 
     /// read partition slice
@@ -154,7 +157,13 @@ impl ReplicaStorage for FileReplica {
         }
 
         for batch in &mut records.batches {
+            let base_offset = self.get_leo();
             self.write_batch(batch).await?;
+            // batch from newer leader starts new epoch
+            let epoch = batch.get_header().partition_leader_epoch;
+            if epoch > self.epochs.latest_epoch() {
+                self.epochs.assign(epoch, base_offset)?;
+            }
         }
 
         if update_highwatermark {
@@ -184,6 +193,82 @@ impl ReplicaStorage for FileReplica {
         }
     }
 
+    fn get_leader_epoch(&self) -> i32 {
+        self.epochs.latest_epoch()
+    }
+
+    fn get_epoch_end_offset(&self, epoch: i32) -> Option<(i32, Offset)> {
+        self.epochs.end_offset_for(epoch, self.get_leo())
+    }
+
+    #[instrument(skip(self))]
+    async fn assign_leader_epoch(&mut self, epoch: i32) -> Result<bool, StorageError> {
+        let leo = self.get_leo();
+        Ok(self.epochs.assign(epoch, leo)?)
+    }
+
+    /// truncate log to offset, this is used by follower when its log has diverged from leader.
+    /// previous segments are reopened as active segment if offset is before active segment
+    #[instrument(skip(self))]
+    async fn truncate_to(&mut self, offset: Offset) -> Result<Offset> {
+        if offset >= self.get_leo() {
+            // epoch may have been assigned without any records
+            self.epochs.truncate_from_end(offset)?;
+            return Ok(self.get_leo());
+        }
+
+        while offset < self.active_segment.get_base_offset() {
+            let Some(prev_segment) = self.prev_segments.pop_last_segment().await else {
+                break;
+            };
+            let prev_base_offset = prev_segment.get_base_offset();
+            drop(prev_segment);
+
+            info!(
+                prev_base_offset,
+                "reopening previous segment for truncation"
+            );
+            let mut segment =
+                MutableSegment::open_for_write(prev_base_offset, self.option.clone()).await?;
+            segment.validate_and_repair().await?;
+            let old_segment = mem::replace(&mut self.active_segment, segment);
+            old_segment.as_segment().await?.remove().await?;
+        }
+
+        let leo = self.active_segment.truncate(offset).await?;
+        self.short_circuit = false;
+        self.size
+            .store_prev(self.prev_segments.read().await.occupied_memory());
+        self.size
+            .store_active(self.active_segment.occupied_memory());
+        if self.get_hw() > leo {
+            self.commit_checkpoint.write(leo);
+        }
+        self.epochs.truncate_from_end(leo)?;
+        info!(offset, leo, "replica truncated");
+        Ok(leo)
+    }
+
+    /// advance log start offset up to high watermark.
+    /// segments which only contain deleted records are purged
+    #[instrument(skip(self))]
+    async fn delete_records(&mut self, offset: Offset) -> Result<Offset> {
+        let offset = min(offset, self.get_hw());
+        if offset <= self.get_log_start_offset() {
+            return Ok(self.get_log_start_offset());
+        }
+
+        let purged = self.prev_segments.read().await.find_below(offset);
+        if !purged.is_empty() {
+            self.prev_segments.remove_segments(&purged).await;
+            self.size
+                .store_prev(self.prev_segments.read().await.occupied_memory());
+        }
+        self.log_start_checkpoint.write(offset);
+        info!(offset, purged = purged.len(), "records deleted");
+        Ok(self.get_log_start_offset())
+    }
+
     #[instrument(skip(self))]
     async fn remove(&self) -> Result<(), StorageError> {
         remove_dir_all(&self.option.base_dir)
@@ -272,6 +357,13 @@ impl FileReplica {
             commit_checkpoint.write(leo);
         }
 
+        let log_start_checkpoint =
+            CheckPoint::create(shared_config.clone(), LOG_START_CHECKPOINT_FILE_NAME, 0).await?;
+
+        // epochs beyond leo are not valid if log was repaired
+        let mut epochs = LeaderEpochCache::open(&shared_config.base_dir)?;
+        epochs.truncate_from_end(leo + 1)?;
+
         let size = Arc::new(ReplicaSize::default());
         size.store_active(active_segment.occupied_memory());
 
@@ -292,6 +384,8 @@ impl FileReplica {
             active_segment,
             prev_segments: segments,
             commit_checkpoint,
+            log_start_checkpoint,
+            epochs,
             cleaner,
             size,
             short_circuit: false,
@@ -346,14 +440,22 @@ impl FileReplica {
         let leo = self.get_leo();
         debug!(hw, leo, "starting read records",);
 
+        let log_start = self.get_log_start_offset();
+        if start_offset < log_start {
+            return Err(ErrorCode::OffsetEvicted {
+                offset: start_offset,
+                next_available: log_start,
+            });
+        }
+
         let mut slice = ReplicaSlice {
             end: OffsetInfo { hw, leo },
-            start: self.get_log_start_offset(),
+            start: log_start,
             ..Default::default()
         };
 
         let active_base_offset = self.active_segment.get_base_offset();
-        let file_slice = if start_offset >= active_base_offset {
+        let segment_slice = if start_offset >= active_base_offset {
             debug!(start_offset, active_base_offset, "is in active segment");
             if start_offset == leo {
                 trace!("start offset is same as end offset, skipping");
@@ -364,7 +466,7 @@ impl FileReplica {
                 )));
             } else if let Some(slice) = self
                 .active_segment
-                .records_slice(start_offset, max_offset)
+                .read_slice(start_offset, max_offset, max_len)
                 .await?
             {
                 slice
@@ -377,7 +479,7 @@ impl FileReplica {
         } else {
             debug!(start_offset, active_base_offset, "not in active sgments");
             self.prev_segments
-                .find_slice(start_offset, max_offset)
+                .find_slice(start_offset, max_offset, max_len)
                 .await?
                 .ok_or_else(|| ErrorCode::OffsetEvicted {
                     offset: start_offset,
@@ -385,20 +487,16 @@ impl FileReplica {
                 })?
         };
 
-        let limited_slice = AsyncFileSlice::new(
-            file_slice.fd(),
-            file_slice.position(),
-            min(file_slice.len(), max_len as u64),
-        );
-
         debug!(
-            fd = limited_slice.fd(),
-            pos = limited_slice.position(),
-            len = limited_slice.len(),
+            fd = segment_slice.slice.fd(),
+            pos = segment_slice.slice.position(),
+            len = segment_slice.slice.len(),
+            decrypted = segment_slice.file.is_some(),
             "retrieved slice",
         );
 
-        slice.file_slice = Some(limited_slice);
+        slice.file_slice = Some(segment_slice.slice);
+        slice.records_file = segment_slice.file;
         Ok(slice)
     }
 
@@ -502,7 +600,7 @@ impl ReplicaSize {
 }
 
 // generate replication folder name
-fn replica_dir_name<S: AsRef<str>>(topic_name: S, partition_index: Size) -> String {
+pub(crate) fn replica_dir_name<S: AsRef<str>>(topic_name: S, partition_index: Size) -> String {
     format!("{}-{}", topic_name.as_ref(), partition_index)
 }
 
@@ -535,6 +633,7 @@ mod tests {
     use flv_util::fixture::ensure_clean_dir;
 
     use crate::config::{ReplicaConfig, StorageConfig};
+    use crate::encryption::{FileKeyProvider, SharedKeyProvider, ENCRYPTED_BATCH_OVERHEAD};
     use crate::StorageError;
     use crate::ReplicaStorage;
     use crate::fixture::storage_config;
@@ -1020,6 +1119,126 @@ mod tests {
         assert_eq!(segment.get_end_offset(), 4);
     }
 
+    /// truncate replica across segments and check leader epochs are truncated
+    #[fluvio_future::test]
+    async fn test_replica_truncate() {
+        let mut option = base_option("test_replica_truncate");
+        // enough for 2 batch (2 records per batch)
+        option.segment_max_bytes = 160;
+        option.index_max_interval_bytes = 50;
+
+        let producer = BatchProducer::builder()
+            .records(2u16)
+            .record_generator(Arc::new(|_, _| Record::new("1")))
+            .build()
+            .expect("batch");
+
+        let mut replica = create_replica("test", 0, option.clone()).await;
+        for epoch in [1, 1, 2, 2, 2] {
+            let mut batch = producer.generate_batch();
+            batch.get_mut_header().partition_leader_epoch = epoch;
+            replica
+                .write_recordset(&mut RecordSet::default().add(batch), false)
+                .await
+                .expect("write");
+        }
+        assert_eq!(replica.get_leo(), 10);
+        assert_eq!(replica.prev_segments.read().await.len(), 2);
+        assert_eq!(replica.get_leader_epoch(), 2);
+        assert_eq!(replica.get_epoch_end_offset(1), Some((1, 4)));
+        assert_eq!(replica.get_epoch_end_offset(2), Some((2, 10)));
+        replica.update_high_watermark(10).await.expect("hw");
+
+        // truncate into first segment
+        assert_eq!(replica.truncate_to(2).await.expect("truncate"), 2);
+        assert_eq!(replica.get_leo(), 2);
+        assert_eq!(replica.get_hw(), 2);
+        assert_eq!(replica.get_leader_epoch(), 1);
+        assert_eq!(replica.prev_segments.read().await.len(), 0);
+        assert_eq!(replica.active_segment.get_base_offset(), 0);
+        let replica_dir = option.base_dir.join("test-0");
+        assert!(!replica_dir.join("00000000000000000004.log").exists());
+        assert!(!replica_dir.join("00000000000000000008.log").exists());
+
+        // write after truncation
+        let mut batch = producer.generate_batch();
+        batch.get_mut_header().partition_leader_epoch = 3;
+        replica
+            .write_recordset(&mut RecordSet::default().add(batch), false)
+            .await
+            .expect("write");
+        assert_eq!(replica.get_leo(), 4);
+        assert_eq!(replica.get_epoch_end_offset(2), Some((1, 2)));
+        drop(replica);
+
+        // reload replica
+        let replica = create_replica("test", 0, option).await;
+        assert_eq!(replica.get_leo(), 4);
+        assert_eq!(replica.get_leader_epoch(), 3);
+    }
+
+    /// delete records across segments and check deleted records are masked
+    #[fluvio_future::test]
+    async fn test_replica_delete_records() {
+        let mut option = base_option("test_replica_delete_records");
+        // enough for 2 batch (2 records per batch)
+        option.segment_max_bytes = 160;
+        option.index_max_interval_bytes = 50;
+
+        let producer = BatchProducer::builder()
+            .records(2u16)
+            .record_generator(Arc::new(|_, _| Record::new("1")))
+            .build()
+            .expect("batch");
+
+        let mut replica = create_replica("test", 0, option.clone()).await;
+        for _ in 0..5 {
+            replica
+                .write_recordset(
+                    &mut RecordSet::default().add(producer.generate_batch()),
+                    false,
+                )
+                .await
+                .expect("write");
+        }
+        replica.update_high_watermark(8).await.expect("hw");
+        assert_eq!(replica.get_leo(), 10);
+        assert_eq!(replica.get_log_start_offset(), 0);
+        assert_eq!(replica.prev_segments.read().await.len(), 2);
+
+        // first segment is purged, second one is masked
+        assert_eq!(replica.delete_records(6).await.expect("delete"), 6);
+        assert_eq!(replica.prev_segments.read().await.len(), 1);
+        assert!(matches!(
+            replica
+                .read_partition_slice(4, 1000, Isolation::ReadUncommitted)
+                .await,
+            Err(ErrorCode::OffsetEvicted {
+                offset: 4,
+                next_available: 6
+            })
+        ));
+        let slice = replica
+            .read_partition_slice(6, 1000, Isolation::ReadUncommitted)
+            .await
+            .expect("read");
+        assert_eq!(slice.start, 6);
+        assert!(slice.file_slice.is_some());
+
+        // offset is limited to hw
+        assert_eq!(replica.delete_records(20).await.expect("delete"), 8);
+        assert_eq!(replica.prev_segments.read().await.len(), 0);
+
+        // log start never goes back
+        assert_eq!(replica.delete_records(2).await.expect("delete"), 8);
+        drop(replica);
+
+        // reload replica
+        let replica = create_replica("test", 0, option).await;
+        assert_eq!(replica.get_log_start_offset(), 8);
+        assert_eq!(replica.get_leo(), 10);
+    }
+
     /// test replica with purging segments
     #[fluvio_future::test]
     async fn test_replica_segment_purge() {
@@ -1247,4 +1466,97 @@ mod tests {
             }
         ));
     }
+
+    #[fluvio_future::test]
+    async fn test_replica_encrypted() {
+        let mut option = base_option("test_replica_encrypted");
+        // one encrypted batch per segment
+        option.segment_max_bytes = 150;
+        let key_file = temp_dir().join("test_replica_encrypted.keys");
+        fs::write(&key_file, format!("k1:{}\n", "01".repeat(32))).expect("key file");
+        option.key_provider = Some(SharedKeyProvider::new(
+            FileKeyProvider::open(&key_file).expect("provider"),
+        ));
+
+        let mut replica = create_replica("test", START_OFFSET, option.clone()).await;
+        replica
+            .write_batch(&mut create_batch())
+            .await
+            .expect("write");
+
+        // rotated key is used for next segment only
+        fs::write(
+            &key_file,
+            format!("k1:{}\nk2:{}\n", "01".repeat(32), "02".repeat(32)),
+        )
+        .expect("key file");
+        replica
+            .write_batch(&mut create_batch())
+            .await
+            .expect("write");
+        assert_eq!(replica.prev_segments.read().await.len(), 1);
+
+        let replica_dir = option.base_dir.join("test-0");
+        let key_id = |base_offset: Offset| {
+            fs::read_to_string(replica_dir.join(format!("{base_offset:020}.key"))).ok()
+        };
+        assert_eq!(key_id(20).as_deref(), Some("k1"));
+        assert_eq!(key_id(22).as_deref(), Some("k2"));
+
+        // batch is stored with encryption overhead
+        let bytes = read_bytes_from_file(replica_dir.join(TEST_SEG_NAME)).expect("read");
+        assert_eq!(
+            bytes.len(),
+            create_batch().write_size(0) + ENCRYPTED_BATCH_OVERHEAD
+        );
+
+        let read_batch = |slice: crate::ReplicaSlice| {
+            use std::os::unix::fs::FileExt;
+
+            let file_slice = slice.file_slice.expect("slice");
+            let file = slice.records_file.expect("decrypted file");
+            let mut bytes = vec![0u8; file_slice.len() as usize];
+            file.read_exact_at(&mut bytes, file_slice.position())
+                .expect("read");
+            let batch =
+                Batch::<MemoryRecords>::decode_from(&mut Cursor::new(bytes), 0).expect("decode");
+            assert_eq!(batch.records().len(), 2);
+            assert_eq!(batch.records()[0].value().as_ref(), TEST_RECORD);
+            batch
+        };
+
+        for offset in [20, 22] {
+            let slice = replica
+                .read_partition_slice(offset, 1000, Isolation::ReadUncommitted)
+                .await
+                .expect("read");
+            let batch = read_batch(slice);
+            assert_eq!(batch.get_base_offset(), offset);
+            assert_eq!(batch.compute_crc().expect("crc"), batch.header.crc);
+        }
+        drop(replica);
+
+        // key is needed to open encrypted segments
+        let mut no_key = option.clone();
+        no_key.key_provider = None;
+        assert!(
+            FileReplica::create_or_load_inner("test", 0, START_OFFSET, no_key, storage_config())
+                .await
+                .is_err()
+        );
+
+        let mut replica = create_replica("test", START_OFFSET, option).await;
+        assert_eq!(replica.get_leo(), 24);
+        let slice = replica
+            .read_partition_slice(20, 1000, Isolation::ReadUncommitted)
+            .await
+            .expect("read");
+        read_batch(slice);
+
+        // key id is removed with segment
+        replica.update_high_watermark(24).await.expect("hw");
+        replica.delete_records(22).await.expect("delete");
+        assert!(key_id(20).is_none());
+        assert_eq!(key_id(22).as_deref(), Some("k2"));
+    }
 }
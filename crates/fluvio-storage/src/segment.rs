@@ -1,4 +1,5 @@
 use std::fmt;
+use std::fs::File;
 use std::io::Error as IoError;
 use std::ops::Deref;
 use std::sync::Arc;
@@ -25,6 +26,7 @@ use crate::StorageError;
 use crate::batch::FileBatchStream;
 use crate::index::OffsetPosition;
 use crate::validator::LogValidationError;
+use crate::encryption::SegmentCipher;
 
 pub type MutableSegment = Segment<MutLogIndex, MutFileRecords>;
 pub type ReadSegment = Segment<LogIndex, FileRecordsSlice>;
@@ -41,6 +43,14 @@ pub struct Segment<I, L> {
     index: I,
     base_offset: Offset,
     end_offset: Offset,
+    cipher: Option<Arc<SegmentCipher>>,
+}
+
+/// Records read from segment, limited to max length.
+/// Decrypted records are held in separate file.
+pub struct SegmentSlice {
+    pub slice: AsyncFileSlice,
+    pub file: Option<Arc<File>>,
 }
 
 impl<I, L> fmt::Debug for Segment<I, L> {
@@ -69,6 +79,11 @@ impl<I, L> Segment<I, L> {
     pub fn get_base_offset(&self) -> Offset {
         self.base_offset
     }
+
+    /// cipher if segment is encrypted
+    pub fn get_cipher(&self) -> Option<&Arc<SegmentCipher>> {
+        self.cipher.as_ref()
+    }
 }
 
 impl<I, L> Segment<I, L>
@@ -174,6 +189,41 @@ where
         }
     }
 
+    /// get records from offset limited to max_len.
+    /// batches of encrypted segment are decrypted
+    #[instrument(skip(self))]
+    pub async fn read_slice(
+        &self,
+        start_offset: Offset,
+        max_offset_opt: Option<Offset>,
+        max_len: u32,
+    ) -> Result<Option<SegmentSlice>, ErrorCode> {
+        let Some(file_slice) = self.records_slice(start_offset, max_offset_opt).await? else {
+            return Ok(None);
+        };
+
+        match &self.cipher {
+            Some(cipher) => {
+                let (slice, file) = cipher
+                    .decrypt_slice(&file_slice, max_len)
+                    .await
+                    .map_err(|err| ErrorCode::Other(format!("decrypt error: {err:#?}")))?;
+                Ok(Some(SegmentSlice {
+                    slice,
+                    file: Some(file),
+                }))
+            }
+            None => Ok(Some(SegmentSlice {
+                slice: AsyncFileSlice::new(
+                    file_slice.fd(),
+                    file_slice.position(),
+                    std::cmp::min(file_slice.len(), max_len as u64),
+                ),
+                file: None,
+            })),
+        }
+    }
+
     /// find position of the offset
     #[instrument(skip(self))]
     pub(crate) async fn find_offset_position(
@@ -243,6 +293,7 @@ impl Segment<LogIndex, FileRecordsSlice> {
         let base_offset = msg_log.get_base_offset();
         debug!(base_offset, end_offset, "offset from msg log");
         let index = LogIndex::open_from_offset(base_offset, option.clone()).await?;
+        let cipher = SegmentCipher::open(base_offset, &option)?;
 
         Ok(Segment {
             msg_log,
@@ -250,6 +301,7 @@ impl Segment<LogIndex, FileRecordsSlice> {
             option,
             base_offset,
             end_offset,
+            cipher,
         })
     }
 
@@ -262,6 +314,7 @@ impl Segment<LogIndex, FileRecordsSlice> {
         let msg_log = FileRecordsSlice::open(base_offset, option.clone()).await?;
         let index = LogIndex::open_from_offset(base_offset, option.clone()).await?;
         let base_offset = msg_log.get_base_offset();
+        let cipher = SegmentCipher::open(base_offset, &option)?;
         match msg_log.validate(&index).await {
             Ok(val) => {
                 // check if validation is successful
@@ -277,6 +330,7 @@ impl Segment<LogIndex, FileRecordsSlice> {
                     option,
                     base_offset,
                     end_offset: val.leo(),
+                    cipher,
                 })
             }
             Err(err) => {
@@ -295,6 +349,7 @@ impl Segment<LogIndex, FileRecordsSlice> {
         let index_file_path = self.index.clean();
         info!(index_path = %index_file_path.display(),"removing index file");
         remove_file(&index_file_path).await?;
+        SegmentCipher::remove(self.base_offset, &self.option).await?;
         Ok(())
     }
 }
@@ -309,7 +364,10 @@ impl Segment<MutLogIndex, MutFileRecords> {
         option: Arc<SharedReplicaConfig>,
     ) -> Result<MutableSegment, StorageError> {
         info!(base_offset, "creating new active segment");
-        let msg_log = MutFileRecords::create(base_offset, option.clone()).await?;
+        let mut msg_log = MutFileRecords::create(base_offset, option.clone()).await?;
+        let cipher = SegmentCipher::create(base_offset, &option)
+            .map_err(|err| StorageError::Other(format!("segment encryption: {err}")))?;
+        msg_log.set_cipher(cipher.clone());
 
         let index = MutLogIndex::create(base_offset, option.clone()).await?;
 
@@ -319,6 +377,7 @@ impl Segment<MutLogIndex, MutFileRecords> {
             index,
             base_offset,
             end_offset: base_offset,
+            cipher,
         })
     }
 
@@ -331,9 +390,13 @@ impl Segment<MutLogIndex, MutFileRecords> {
             base_dir = ?option.base_dir,
             "opening active segment for write"
         );
-        let msg_log = MutFileRecords::create(base_offset, option.clone()).await?;
+        let mut msg_log = MutFileRecords::create(base_offset, option.clone()).await?;
         let base_offset = msg_log.get_base_offset();
         let index = MutLogIndex::open(base_offset, option.clone()).await?;
+        // existing segment keeps its key, unencrypted segment stays unencrypted
+        let cipher = SegmentCipher::open(base_offset, &option)
+            .map_err(|err| StorageError::Other(format!("segment encryption: {err}")))?;
+        msg_log.set_cipher(cipher.clone());
 
         let base_offset = msg_log.get_base_offset();
         Ok(MutableSegment {
@@ -342,6 +405,7 @@ impl Segment<MutLogIndex, MutFileRecords> {
             index,
             base_offset,
             end_offset: base_offset,
+            cipher,
         })
     }
 
@@ -454,6 +518,37 @@ impl Segment<MutLogIndex, MutFileRecords> {
         }
     }
 
+    /// Remove batches at or beyond offset.
+    /// If offset is inside a batch, whole batch is removed.
+    /// Return new end offset
+    #[instrument(skip(self))]
+    pub async fn truncate(&mut self, offset: Offset) -> Result<Offset> {
+        if offset >= self.end_offset {
+            return Ok(self.end_offset);
+        }
+
+        let (end_offset, file_pos) = if offset <= self.base_offset {
+            (self.base_offset, 0)
+        } else {
+            match self.find_offset_position(offset).await? {
+                Some(batch_pos) => (batch_pos.batch.base_offset, batch_pos.pos),
+                None => {
+                    return Err(StorageError::Other(format!(
+                        "truncate offset: {offset} not found in segment: {self:?}"
+                    ))
+                    .into());
+                }
+            }
+        };
+
+        info!(offset, end_offset, file_pos, "truncating active segment");
+        self.msg_log.set_len(file_pos).await?;
+        self.index
+            .truncate((end_offset - self.base_offset) as Size, file_pos);
+        self.end_offset = end_offset;
+        Ok(end_offset)
+    }
+
     #[allow(unused)]
     pub async fn flush(&mut self) -> Result<(), StorageError> {
         self.msg_log.flush().await.map_err(|err| err.into())
@@ -683,4 +778,55 @@ mod tests {
             )
             .expect("failed to get records");
     }
+
+    const TEST3_FILE_NAME: &str = "00000000000000000060.log";
+
+    #[fluvio_future::test]
+    async fn test_segment_truncate() {
+        let test_dir = temp_dir().join("truncate-segment");
+        ensure_new_dir(&test_dir).expect("new");
+
+        let option = default_option(test_dir.clone(), 50).shared();
+
+        let mut seg_sink = MutableSegment::create(60, option).await.expect("create");
+        for _ in 0..3 {
+            seg_sink
+                .append_batch(&mut create_batch())
+                .await
+                .expect("write");
+        }
+        assert_eq!(seg_sink.get_end_offset(), 66);
+        assert_eq!(seg_sink.get_index()[0].to_be(), (2, 79));
+
+        // truncate beyond end is no op
+        assert_eq!(seg_sink.truncate(70).await.expect("truncate"), 66);
+
+        // offset inside batch removes whole batch
+        assert_eq!(seg_sink.truncate(63).await.expect("truncate"), 62);
+        assert_eq!(seg_sink.get_end_offset(), 62);
+        assert_eq!(seg_sink.get_log_pos(), 79);
+        assert_eq!(seg_sink.get_index()[0].to_be(), (0, 0));
+        assert_eq!(
+            metadata(test_dir.join(TEST3_FILE_NAME))
+                .expect("metadata")
+                .len(),
+            79
+        );
+
+        // can append after truncation
+        seg_sink
+            .append_batch(&mut create_batch())
+            .await
+            .expect("write");
+        assert_eq!(seg_sink.get_end_offset(), 64);
+        let offset_pos = seg_sink
+            .find_offset_position(62)
+            .await
+            .expect("pos")
+            .expect("batch");
+        assert_eq!(offset_pos.pos, 79);
+
+        assert_eq!(seg_sink.truncate(0).await.expect("truncate"), 60);
+        assert_eq!(seg_sink.get_log_pos(), 0);
+    }
 }
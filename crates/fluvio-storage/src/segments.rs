@@ -12,10 +12,9 @@ use anyhow::Result;
 use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::record::Size64;
 use fluvio_protocol::record::Offset;
-use fluvio_future::file_slice::AsyncFileSlice;
 
 use crate::config::SharedReplicaConfig;
-use crate::segment::ReadSegment;
+use crate::segment::{ReadSegment, SegmentSlice};
 use crate::util::log_path_get_offset;
 
 const MEM_ORDER: std::sync::atomic::Ordering = std::sync::atomic::Ordering::SeqCst;
@@ -118,16 +117,28 @@ impl SharedSegments {
         }
     }
 
+    /// remove segment with highest base offset without deleting its files
+    pub(crate) async fn pop_last_segment(&self) -> Option<ReadSegment> {
+        let mut write = self.write().await;
+        let (segment, min_offset) = write.pop_last_segment()?;
+        self.min_offset.store(min_offset, MEM_ORDER);
+        Some(segment)
+    }
+
     /// find slice in the segments
     /// if not found, return OutOfRange error
     pub async fn find_slice(
         &self,
         start_offset: Offset,
         max_offset: Option<Offset>,
-    ) -> Result<Option<AsyncFileSlice>, ErrorCode> {
+        max_len: u32,
+    ) -> Result<Option<SegmentSlice>, ErrorCode> {
         let reader = self.read().await;
         if let Some((_offset, segment)) = reader.find_segment(start_offset) {
-            if let Some(slice) = segment.records_slice(start_offset, max_offset).await? {
+            if let Some(slice) = segment
+                .read_slice(start_offset, max_offset, max_len)
+                .await?
+            {
                 Ok(Some(slice))
             } else {
                 Err(ErrorCode::Other(format!(
@@ -220,6 +231,13 @@ impl SegmentList {
         }
     }
 
+    /// remove last segment and return min offset
+    fn pop_last_segment(&mut self) -> Option<(ReadSegment, Offset)> {
+        let (_, segment) = self.segments.pop_last()?;
+        self.update_min_max();
+        Some((segment, self.min_offset))
+    }
+
     #[cfg(test)]
     #[cfg(feature = "fixture")]
     pub fn get_segment(&self, offset: Offset) -> Option<&ReadSegment> {
@@ -262,6 +280,15 @@ impl SegmentList {
     pub(crate) fn find_first(&self, count: usize) -> Vec<Offset> {
         self.segments.keys().take(count).copied().collect()
     }
+
+    /// segments which only contain records before offset
+    pub(crate) fn find_below(&self, offset: Offset) -> Vec<Offset> {
+        self.segments
+            .iter()
+            .take_while(|(_, segment)| segment.get_end_offset() <= offset)
+            .map(|(base_offset, _)| *base_offset)
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -484,6 +511,12 @@ mod tests {
         assert_eq!(list.find_first(2), vec![100, 600]);
         assert_eq!(list.find_first(10), vec![100, 600, 4000]);
 
+        assert!(list.find_below(100).is_empty());
+        assert!(list.find_below(599).is_empty());
+        assert_eq!(list.find_below(600), vec![100]);
+        assert_eq!(list.find_below(8999), vec![100, 600]);
+        assert_eq!(list.find_below(10000), vec![100, 600, 4000]);
+
         //when
         let offsets = list.find_first(10);
         drop(list);
@@ -13,6 +13,7 @@ use anyhow::{Result, anyhow};
 
 use fluvio_protocol::record::Offset;
 
+use crate::batch::BatchCrcError;
 use crate::batch::BatchHeaderError;
 use crate::batch::FileBatchStream;
 use crate::batch::StorageBytesIterator;
@@ -29,6 +30,8 @@ pub enum LogValidationError {
     BatchDecoding(#[from] BatchHeaderError),
     #[error("batch offset is less than base offset: {invalid_batch_offset}")]
     InvalidBaseOffsetMinimum { invalid_batch_offset: Offset },
+    #[error("Batch is corrupt: {0}")]
+    Crc(#[from] BatchCrcError),
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -55,7 +58,11 @@ pub struct LogValidator {
 }
 
 impl LogValidator {
-    async fn validate_core<I, S, R>(path: impl AsRef<Path>, index: Option<&I>) -> Result<Self>
+    async fn validate_core<I, S, R>(
+        path: impl AsRef<Path>,
+        index: Option<&I>,
+        verify_crc: bool,
+    ) -> Result<Self>
     where
         I: Index,
         S: StorageBytesIterator,
@@ -76,16 +83,18 @@ impl LogValidator {
         );
 
         let start_time = std::time::Instant::now();
-        let batch_stream: FileBatchStream<R, S> = match FileBatchStream::open(&val.file_path).await
-        {
-            Ok(batch_stream) => batch_stream,
-            Err(err) => match err.kind() {
-                ErrorKind::UnexpectedEof => {
-                    return Err(anyhow!("empty file with base offset: {}", val.base_offset));
-                }
-                _ => return Err(err.into()),
-            },
-        };
+        let mut batch_stream: FileBatchStream<R, S> =
+            match FileBatchStream::open(&val.file_path).await {
+                Ok(batch_stream) => batch_stream,
+                Err(err) => match err.kind() {
+                    ErrorKind::UnexpectedEof => {
+                        return Err(anyhow!("empty file with base offset: {}", val.base_offset));
+                    }
+                    _ => return Err(err.into()),
+                },
+            };
+
+        batch_stream.set_verify_crc(verify_crc);
 
         // find recoverable error
 
@@ -93,13 +102,16 @@ impl LogValidator {
             error!(%err, "found error from stream");
             error!("{:#?} debug", err);
             val.duration = start_time.elapsed();
-            match err.downcast_ref::<BatchHeaderError>() {
-                Some(header_error) => {
-                    error!(%header_error, "found batch header error, turn into recoverable error");
-                    val.error = Some(LogValidationError::BatchDecoding(header_error.clone()));
-                    Ok(val)
-                }
-                _ => Err(err),
+            if let Some(header_error) = err.downcast_ref::<BatchHeaderError>() {
+                error!(%header_error, "found batch header error, turn into recoverable error");
+                val.error = Some(LogValidationError::BatchDecoding(header_error.clone()));
+                Ok(val)
+            } else if let Some(crc_error) = err.downcast_ref::<BatchCrcError>() {
+                error!(%crc_error, "found corrupt batch");
+                val.error = Some(LogValidationError::Crc(crc_error.clone()));
+                Ok(val)
+            } else {
+                Err(err)
             }
         } else {
             val.duration = start_time.elapsed();
@@ -245,7 +257,7 @@ impl LogValidator {
         I: Index,
         S: StorageBytesIterator,
     {
-        Self::validate_core::<I, S, FileEmptyRecords>(path, index).await
+        Self::validate_core::<I, S, FileEmptyRecords>(path, index, false).await
     }
 
     #[instrument(skip(index, path))]
@@ -258,6 +270,19 @@ impl LogValidator {
     {
         Self::validate::<I, FileBytesIterator>(path, index).await
     }
+
+    /// validate log file and verify crc of every batch.
+    /// validation stops at first corrupt batch
+    #[instrument(skip(index, path))]
+    pub(crate) async fn validate_with_crc<I>(
+        path: impl AsRef<Path>,
+        index: Option<&I>,
+    ) -> Result<Self>
+    where
+        I: Index,
+    {
+        Self::validate_core::<I, FileBytesIterator, FileEmptyRecords>(path, index, true).await
+    }
 }
 
 #[cfg(test)]
@@ -430,6 +455,71 @@ mod tests {
         let err = validator.error.expect("error");
         assert!(matches!(err, LogValidationError::BatchDecoding(_)));
     }
+
+    #[fluvio_future::test]
+    async fn test_validate_crc() {
+        const OFFSET: i64 = 701;
+
+        let test_dir = temp_dir().join("validate_crc");
+        ensure_new_dir(&test_dir).expect("new");
+
+        let options = ReplicaConfig {
+            base_dir: test_dir,
+            segment_max_bytes: 1000,
+            ..Default::default()
+        }
+        .shared();
+
+        let mut msg_sink = MutFileRecords::create(OFFSET, options)
+            .await
+            .expect("record created");
+
+        let mut builder = BatchProducer::builder()
+            .base_offset(OFFSET)
+            .build()
+            .expect("build");
+
+        msg_sink
+            .write_batch(&builder.batch_records(3))
+            .await
+            .expect("write");
+        msg_sink
+            .write_batch(&builder.batch_records(3))
+            .await
+            .expect("write");
+        msg_sink.flush().await.expect("flush");
+        let test_fs_path = msg_sink.get_path().to_owned();
+        drop(msg_sink);
+
+        let validator = LogValidator::validate_with_crc::<LogIndex>(&test_fs_path, None)
+            .await
+            .expect("validate");
+        assert!(validator.error.is_none());
+        assert_eq!(validator.batches, 2);
+        let first_batch_len = validator.last_valid_batch_pos as usize;
+
+        // flip a byte in the records of the second batch
+        let mut contents = std::fs::read(&test_fs_path).expect("read");
+        let last = contents.len() - 2;
+        contents[last] ^= 0xff;
+        std::fs::write(&test_fs_path, contents).expect("write");
+
+        // without crc, corruption is not detected
+        let validator = LogValidator::default_validate::<LogIndex>(&test_fs_path, None)
+            .await
+            .expect("validate");
+        assert!(validator.error.is_none());
+        assert_eq!(validator.leo(), OFFSET + 6);
+
+        let validator = LogValidator::validate_with_crc::<LogIndex>(&test_fs_path, None)
+            .await
+            .expect("validate");
+        assert_eq!(validator.batches, 1);
+        assert_eq!(validator.leo(), OFFSET + 3);
+        assert_eq!(validator.last_valid_file_pos as usize, first_batch_len);
+        let err = validator.error.expect("error");
+        assert!(matches!(err, LogValidationError::Crc(_)));
+    }
 }
 
 #[cfg(test)]
@@ -108,8 +108,8 @@ async fn handle_response(socket: &mut FluvioSocket, replica: &FileReplica) {
     debug!("response: {:#?}", slice);
 
     part_response.partition_index = 0;
-    if let Some(file_slice) = slice.file_slice {
-        part_response.records = file_slice.into();
+    if let Some(records) = slice.records() {
+        part_response.records = records;
     }
 
     // assert_eq!(part)
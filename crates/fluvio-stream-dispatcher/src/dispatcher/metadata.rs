@@ -3,8 +3,10 @@ use std::fmt;
 use std::fmt::Debug;
 use std::io::Error as IoError;
 use std::io::ErrorKind;
+use std::sync::Arc;
 
 use fluvio_future::task::spawn;
+use fluvio_types::event::StickyEvent;
 use fluvio_stream_model::core::MetadataItem;
 use fluvio_stream_model::store::NameSpace;
 use futures_util::stream::StreamExt;
@@ -78,6 +80,22 @@ where
         spawn(dispatcher.outer_loop())
     }
 
+    /// start dispatcher, which stops when shutdown is notified
+    pub fn start_until(
+        namespace: impl Into<NameSpace>,
+        client: SharedClient<C>,
+        ctx: StoreContext<S, M>,
+        shutdown: &Arc<StickyEvent>,
+    ) {
+        let dispatcher = Self {
+            namespace: namespace.into(),
+            client,
+            ctx,
+        };
+
+        spawn(shutdown.until_notified(dispatcher.outer_loop()));
+    }
+
     #[instrument(
         name = "MetadataDispatcher",
         skip(self),
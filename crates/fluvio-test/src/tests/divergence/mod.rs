@@ -0,0 +1,137 @@
+use std::time::Duration;
+
+use futures_lite::stream::StreamExt;
+
+use fluvio::{Offset, RecordKey};
+use fluvio_controlplane_metadata::partition::PartitionSpec;
+use fluvio_future::timer::sleep;
+use clap::Parser;
+
+use fluvio_test_derive::fluvio_test;
+use fluvio_test_case_derive::MyTestCase;
+
+// time to wait for replication and election
+const ACK_WAIT: u64 = 20;
+
+#[derive(Debug, Clone, Parser, Default, Eq, PartialEq, MyTestCase)]
+#[command(name = "Fluvio DIVERGENCE Test")]
+pub struct DivergenceTestOption {}
+
+/// Old leader writes uncommitted record while follower is down.
+/// Follower is then elected with new leader epoch and writes different record at same offset.
+/// When old leader comes back as follower, it must truncate its divergent record and converge.
+#[fluvio_test(topic = "test", async)]
+pub async fn divergence(mut test_driver: TestDriver, mut test_case: TestCase) {
+    println!("Starting divergence test");
+
+    let topic_name = test_case.environment.base_topic_name();
+    let producer = test_driver.create_producer(&topic_name).await;
+
+    producer
+        .send(RecordKey::NULL, "msg1")
+        .await
+        .expect("sending");
+    producer.flush().await.expect("flushing");
+
+    sleep(Duration::from_secs(ACK_WAIT)).await;
+
+    let admin = test_driver.client().admin().await;
+
+    let partitions = admin.all::<PartitionSpec>().await.expect("partitions");
+    assert_eq!(partitions.len(), 1);
+    let status = &partitions[0].status;
+    assert_eq!(status.leader.leo, 1);
+    assert_eq!(status.leader.hw, 1);
+    let follower_id = status.replicas[0].spu;
+    let leader = partitions[0].spec.leader;
+    let first_epoch = partitions[0].spec.leader_epoch;
+    println!("leader: {leader}, follower: {follower_id}, epoch: {first_epoch}");
+
+    let cluster_manager = test_driver
+        .get_cluster()
+        .expect("cluster")
+        .env_driver()
+        .create_cluster_manager();
+
+    println!("terminating follower and writing uncommitted record to leader");
+    cluster_manager
+        .terminate_spu(follower_id)
+        .expect("terminate");
+    sleep(Duration::from_secs(ACK_WAIT)).await;
+
+    producer
+        .send(RecordKey::NULL, "diverged")
+        .await
+        .expect("sending");
+    producer.flush().await.expect("flushing");
+    sleep(Duration::from_secs(ACK_WAIT)).await;
+
+    {
+        let partitions = admin.all::<PartitionSpec>().await.expect("partitions");
+        let leader_status = &partitions[0].status.leader;
+        assert_eq!(leader_status.leo, 2);
+        assert_eq!(leader_status.hw, 1);
+    }
+
+    println!("terminating leader and starting follower, follower should be elected");
+    cluster_manager.terminate_spu(leader).expect("terminate");
+    sleep(Duration::from_secs(ACK_WAIT)).await;
+    let follower_spu = cluster_manager.create_spu_absolute(follower_id as u16);
+    follower_spu.start().expect("start");
+    sleep(Duration::from_secs(ACK_WAIT)).await;
+
+    {
+        let partitions = admin.all::<PartitionSpec>().await.expect("partitions");
+        assert_eq!(partitions[0].spec.leader, follower_id);
+        assert!(partitions[0].spec.leader_epoch > first_epoch);
+    }
+
+    let producer2 = test_driver.create_producer(&topic_name).await;
+    producer2
+        .send(RecordKey::NULL, "msg2")
+        .await
+        .expect("sending");
+    producer2.flush().await.expect("flushing");
+    sleep(Duration::from_secs(ACK_WAIT)).await;
+
+    println!("starting old leader, it should truncate divergent record");
+    let leader_spu = cluster_manager.create_spu_absolute(leader as u16);
+    leader_spu.start().expect("start");
+    sleep(Duration::from_secs(ACK_WAIT)).await;
+
+    {
+        let partitions = admin.all::<PartitionSpec>().await.expect("partitions");
+        let status = &partitions[0].status;
+        assert_eq!(status.leader.leo, 2);
+        assert_eq!(status.leader.hw, 2);
+        let old_leader = status
+            .replicas
+            .iter()
+            .find(|replica| replica.spu == leader)
+            .expect("old leader");
+        assert_eq!(old_leader.leo, 2);
+    }
+
+    println!("terminating current leader, old leader should serve converged log");
+    cluster_manager
+        .terminate_spu(follower_id)
+        .expect("terminate");
+    sleep(Duration::from_secs(ACK_WAIT)).await;
+
+    {
+        let partitions = admin.all::<PartitionSpec>().await.expect("partitions");
+        assert_eq!(partitions[0].spec.leader, leader);
+    }
+
+    let mut stream = test_driver
+        .get_consumer_with_start(&topic_name, 0, Offset::absolute(0).expect("offset"))
+        .await;
+
+    println!("checking msg1");
+    let record = stream.next().await.expect("get next").expect("next");
+    assert_eq!(record.value(), "msg1".as_bytes());
+
+    println!("checking msg2");
+    let record = stream.next().await.expect("get next").expect("next");
+    assert_eq!(record.value(), "msg2".as_bytes());
+}
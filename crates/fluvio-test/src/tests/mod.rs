@@ -6,6 +6,7 @@ pub mod longevity;
 pub mod producer;
 pub mod consumer;
 pub mod election;
+pub mod divergence;
 pub mod producer_fail;
 pub mod reconnection;
 pub mod batching;
@@ -1,5 +1,8 @@
+use std::future::{Future, poll_fn};
+use std::pin::pin;
 use std::sync::atomic::{Ordering, AtomicBool};
 use std::sync::Arc;
+use std::task::Poll;
 
 use tracing::trace;
 use event_listener::Event;
@@ -49,6 +52,26 @@ impl StickyEvent {
         self.flag.store(true, DEFAULT_EVENT_ORDERING);
         self.event.notify(usize::MAX);
     }
+
+    /// run future until it completes or event is notified, in which case future is dropped
+    /// and `None` is returned. Used to stop spawned tasks on shutdown.
+    pub fn until_notified<F: Future>(
+        self: &Arc<Self>,
+        future: F,
+    ) -> impl Future<Output = Option<F::Output>> + use<F> {
+        let event = self.clone();
+        async move {
+            let mut future = pin!(future);
+            let mut notified = pin!(event.listen());
+            poll_fn(|cx| {
+                if let Poll::Ready(output) = future.as_mut().poll(cx) {
+                    return Poll::Ready(Some(output));
+                }
+                notified.as_mut().poll(cx).map(|_| None)
+            })
+            .await
+        }
+    }
 }
 
 pub mod offsets {
@@ -198,10 +221,11 @@ mod test {
 
     use tracing::debug;
 
-    use fluvio_future::task::spawn;
+    use fluvio_future::task::{spawn, spawn_task};
     use fluvio_future::timer::sleep;
 
     use super::offsets::{OffsetChangeListener, OffsetPublisher};
+    use super::StickyEvent;
 
     const ITER: u16 = 10;
 
@@ -303,4 +327,20 @@ mod test {
             assert!(status.load(Ordering::SeqCst), "status should be set");
         }
     }
+
+    #[fluvio_future::test]
+    async fn test_until_notified() {
+        let event = StickyEvent::shared();
+        assert_eq!(event.until_notified(async { 1 }).await, Some(1));
+
+        let pending = spawn_task(event.until_notified(std::future::pending::<()>()));
+        event.notify();
+        assert_eq!(pending.await, None);
+
+        // already notified
+        assert_eq!(
+            event.until_notified(std::future::pending::<()>()).await,
+            None
+        );
+    }
 }
@@ -5,6 +5,7 @@ pub mod defaults;
 pub mod macros;
 pub mod partition;
 pub mod config_file;
+pub mod openmetrics;
 
 #[cfg(feature = "events")]
 pub mod event;
@@ -0,0 +1,273 @@
+//!
+//! # OpenMetrics
+//!
+//! Minimal encoder of OpenMetrics text exposition format and histogram
+//! which can be shared by metrics of servers and clients.
+//!
+
+use std::fmt::{Display, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// content type of encoded metrics
+pub const OPENMETRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// default buckets for latencies in seconds
+pub const LATENCY_BUCKETS: &[f64] = &[
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricType {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl MetricType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+            Self::Histogram => "histogram",
+        }
+    }
+}
+
+/// Writes metric families in OpenMetrics text format.
+/// Samples are written to family started last.
+#[derive(Debug, Default)]
+pub struct MetricsEncoder {
+    out: String,
+    family: String,
+    metric_type: Option<MetricType>,
+}
+
+impl MetricsEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// start new metric family
+    pub fn family(&mut self, name: &str, metric_type: MetricType, help: &str) -> &mut Self {
+        self.family = name.to_owned();
+        self.metric_type = Some(metric_type);
+        let _ = writeln!(self.out, "# TYPE {name} {}", metric_type.as_str());
+        let _ = writeln!(self.out, "# HELP {name} {}", escape(help, false));
+        self
+    }
+
+    /// write sample of counter or gauge family
+    pub fn sample(&mut self, labels: &[(&str, &str)], value: impl Display) -> &mut Self {
+        let suffix = match self.metric_type {
+            Some(MetricType::Counter) => "_total",
+            _ => "",
+        };
+        let name = format!("{}{suffix}", self.family);
+        self.write_sample(&name, labels, None, value);
+        self
+    }
+
+    /// write buckets, sum and count of histogram family
+    pub fn histogram(&mut self, labels: &[(&str, &str)], histogram: &Histogram) -> &mut Self {
+        let bucket_name = format!("{}_bucket", self.family);
+        let mut cumulative = 0;
+        for (bound, count) in histogram.bounds.iter().zip(histogram.buckets.iter()) {
+            cumulative += count.load(Ordering::Relaxed);
+            self.write_sample(&bucket_name, labels, Some(&canonical(*bound)), cumulative);
+        }
+        let count = histogram.count();
+        self.write_sample(&bucket_name, labels, Some("+Inf"), count);
+        let sum_name = format!("{}_sum", self.family);
+        self.write_sample(&sum_name, labels, None, canonical(histogram.sum()));
+        let count_name = format!("{}_count", self.family);
+        self.write_sample(&count_name, labels, None, count);
+        self
+    }
+
+    fn write_sample(
+        &mut self,
+        name: &str,
+        labels: &[(&str, &str)],
+        le: Option<&str>,
+        value: impl Display,
+    ) {
+        self.out.push_str(name);
+        if !labels.is_empty() || le.is_some() {
+            self.out.push('{');
+            let le = le.map(|le| ("le", le));
+            for (index, (key, value)) in labels.iter().copied().chain(le).enumerate() {
+                if index > 0 {
+                    self.out.push(',');
+                }
+                let _ = write!(self.out, "{key}=\"{}\"", escape(value, true));
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {value}");
+    }
+
+    /// finish exposition
+    pub fn finish(mut self) -> String {
+        self.out.push_str("# EOF\n");
+        self.out
+    }
+}
+
+/// float in canonical form, integral values keep fraction, so bound `1.0` is `le="1.0"`
+fn canonical(value: f64) -> String {
+    format!("{value:?}")
+}
+
+fn escape(value: &str, quote: bool) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '"' if quote => escaped.push_str("\\\""),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Histogram with fixed buckets, can be updated concurrently
+#[derive(Debug)]
+pub struct Histogram {
+    bounds: &'static [f64],
+    buckets: Vec<AtomicU64>,
+    count: AtomicU64,
+    // f64 bits
+    sum: AtomicU64,
+}
+
+impl Histogram {
+    /// bounds must be sorted in ascending order
+    pub fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            buckets: bounds.iter().map(|_| AtomicU64::new(0)).collect(),
+            count: AtomicU64::new(0),
+            sum: AtomicU64::new(0f64.to_bits()),
+        }
+    }
+
+    pub fn latency() -> Self {
+        Self::new(LATENCY_BUCKETS)
+    }
+
+    pub fn observe(&self, value: f64) {
+        if let Some(index) = self.bounds.iter().position(|bound| value <= *bound) {
+            self.buckets[index].fetch_add(1, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        let _ = self
+            .sum
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |sum| {
+                Some((f64::from_bits(sum) + value).to_bits())
+            });
+    }
+
+    /// observe duration in seconds
+    pub fn observe_duration(&self, duration: Duration) {
+        self.observe(duration.as_secs_f64());
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    pub fn sum(&self) -> f64 {
+        f64::from_bits(self.sum.load(Ordering::Relaxed))
+    }
+
+    pub fn bounds(&self) -> &'static [f64] {
+        self.bounds
+    }
+
+    /// number of observations in each bucket, not cumulative
+    pub fn bucket_counts(&self) -> Vec<u64> {
+        self.buckets
+            .iter()
+            .map(|count| count.load(Ordering::Relaxed))
+            .collect()
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self::latency()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    #[test]
+    fn test_encode_families() {
+        let mut encoder = MetricsEncoder::new();
+        encoder
+            .family(
+                "fluvio_spu_inbound_bytes",
+                MetricType::Counter,
+                "bytes produced",
+            )
+            .sample(&[("source", "client")], 10)
+            .sample(&[("source", "connector")], 2);
+        encoder
+            .family(
+                "fluvio_spu_connections",
+                MetricType::Gauge,
+                "open connections",
+            )
+            .sample(&[], 3);
+        encoder
+            .family("fluvio_partition_hw", MetricType::Gauge, "high watermark")
+            .sample(&[("topic", "a\"b"), ("partition", "0")], 5);
+
+        let text = encoder.finish();
+        assert_eq!(
+            text,
+            "# TYPE fluvio_spu_inbound_bytes counter\n\
+             # HELP fluvio_spu_inbound_bytes bytes produced\n\
+             fluvio_spu_inbound_bytes_total{source=\"client\"} 10\n\
+             fluvio_spu_inbound_bytes_total{source=\"connector\"} 2\n\
+             # TYPE fluvio_spu_connections gauge\n\
+             # HELP fluvio_spu_connections open connections\n\
+             fluvio_spu_connections 3\n\
+             # TYPE fluvio_partition_hw gauge\n\
+             # HELP fluvio_partition_hw high watermark\n\
+             fluvio_partition_hw{topic=\"a\\\"b\",partition=\"0\"} 5\n\
+             # EOF\n"
+        );
+    }
+
+    #[test]
+    fn test_histogram() {
+        static BOUNDS: &[f64] = &[0.125, 1.0];
+        let histogram = Histogram::new(BOUNDS);
+        histogram.observe(0.0625);
+        histogram.observe(0.5);
+        histogram.observe(0.5);
+        histogram.observe(3.0);
+
+        assert_eq!(histogram.count(), 4);
+        assert_eq!(histogram.sum(), 4.0625);
+        assert_eq!(histogram.bucket_counts(), vec![1, 2]);
+
+        let mut encoder = MetricsEncoder::new();
+        encoder
+            .family("latency_seconds", MetricType::Histogram, "latency")
+            .histogram(&[("api", "produce")], &histogram);
+        let text = encoder.finish();
+        assert!(text.contains("latency_seconds_bucket{api=\"produce\",le=\"0.125\"} 1\n"));
+        assert!(text.contains("latency_seconds_bucket{api=\"produce\",le=\"1.0\"} 3\n"));
+        assert!(text.contains("latency_seconds_bucket{api=\"produce\",le=\"+Inf\"} 4\n"));
+        assert!(text.contains("latency_seconds_sum{api=\"produce\"} 4.0625\n"));
+        assert!(text.contains("latency_seconds_count{api=\"produce\"} 4\n"));
+    }
+}
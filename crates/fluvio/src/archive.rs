@@ -0,0 +1,703 @@
+//!
+//! # Topic archive
+//!
+//! Portable snapshot of a topic, used for backups, moving a topic between clusters
+//! without mirroring or as a reproducible test fixture.
+//!
+//! Archive starts with magic bytes, the archive version and the version used to encode metadata.
+//! The length prefixed [`ArchiveHeader`] follows, with the topic spec, offsets of every partition
+//! and consumer offsets.  The rest of the archive are raw batches of all partitions,
+//! each prefixed with its partition and length.  Batches are stored as they are in the cluster,
+//! records are neither decompressed nor re-encoded.
+//!
+use std::collections::HashMap;
+use std::io::{Cursor, Error as IoError, ErrorKind};
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow, bail};
+use derive_builder::Builder;
+use futures_util::StreamExt;
+use futures_util::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use tracing::{debug, info, instrument, warn};
+
+use fluvio_future::timer::sleep;
+use fluvio_protocol::api::Request;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Batch, Offset, RawRecords, Record, ReplicaKey};
+use fluvio_protocol::{Decoder, Encoder, Version};
+use fluvio_sc_schema::objects::ObjectApiListRequest;
+use fluvio_sc_schema::topic::{ReplicaSpec, TopicSpec};
+use fluvio_spu_schema::Isolation;
+use fluvio_spu_schema::produce::{DefaultPartitionRequest, DefaultProduceRequest, DefaultTopicRequest};
+use fluvio_types::PartitionId;
+
+use crate::consumer::{ConsumerConfig, ConsumerConfigExt, ConsumerStream, OffsetManagementStrategy};
+use crate::offset::fetch_offsets;
+use crate::spu::{SpuDirectory, SpuPool, SpuSocketPool};
+use crate::{Fluvio, FluvioError, PartitionConsumer};
+
+/// identifies topic archive
+pub const ARCHIVE_MAGIC: [u8; 8] = *b"FLVTOPIC";
+
+/// version of the archive layout
+pub const ARCHIVE_VERSION: i16 = 0;
+
+/// max size of batches sent in a single produce request on import
+const IMPORT_REQUEST_BYTES: usize = 1024 * 1024;
+
+const IMPORT_PRODUCE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// how many times import checks that partitions of created topic are online
+const PARTITION_ONLINE_RETRIES: u32 = 300;
+const PARTITION_ONLINE_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+/// Topic and offsets at the time of export
+#[derive(Debug, Default, Clone, PartialEq, Encoder, Decoder)]
+pub struct ArchiveHeader {
+    pub topic: String,
+    pub spec: TopicSpec,
+    pub partitions: Vec<ArchivePartition>,
+    pub consumers: Vec<ArchiveConsumerOffset>,
+}
+
+impl ArchiveHeader {
+    /// number of records in the archive
+    pub fn records(&self) -> u64 {
+        self.partitions
+            .iter()
+            .map(|partition| (partition.end_offset - partition.start_offset).max(0) as u64)
+            .sum()
+    }
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Encoder, Decoder)]
+pub struct ArchivePartition {
+    pub partition: PartitionId,
+    /// log start offset
+    pub start_offset: Offset,
+    /// high watermark, archive has all records before it
+    pub end_offset: Offset,
+}
+
+#[derive(Debug, Default, Clone, Eq, PartialEq, Encoder, Decoder)]
+pub struct ArchiveConsumerOffset {
+    pub consumer_id: String,
+    pub partition: PartitionId,
+    /// last offset seen by consumer
+    pub offset: Offset,
+}
+
+/// Writes archive to underlying writer, header is written first
+pub struct ArchiveWriter<W> {
+    writer: W,
+}
+
+impl<W: AsyncWrite + Unpin> ArchiveWriter<W> {
+    pub async fn new(mut writer: W, header: &ArchiveHeader) -> Result<Self> {
+        let version = metadata_version();
+        let mut buf = vec![];
+        header.encode(&mut buf, version)?;
+
+        writer.write_all(&ARCHIVE_MAGIC).await?;
+        writer.write_all(&ARCHIVE_VERSION.to_be_bytes()).await?;
+        writer.write_all(&version.to_be_bytes()).await?;
+        write_frame(&mut writer, &buf).await?;
+        Ok(Self { writer })
+    }
+
+    pub async fn write_batch(
+        &mut self,
+        partition: PartitionId,
+        batch: &Batch<RawRecords>,
+    ) -> Result<()> {
+        let mut buf = vec![];
+        batch.encode(&mut buf, 0)?;
+        self.writer.write_all(&partition.to_be_bytes()).await?;
+        write_frame(&mut self.writer, &buf).await?;
+        Ok(())
+    }
+
+    /// flush and return underlying writer
+    pub async fn finish(mut self) -> Result<W> {
+        self.writer.flush().await?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads archive from underlying reader
+pub struct ArchiveReader<R> {
+    reader: R,
+    header: ArchiveHeader,
+}
+
+impl<R: AsyncRead + Unpin> ArchiveReader<R> {
+    pub async fn new(mut reader: R) -> Result<Self> {
+        let mut magic = [0; 8];
+        reader
+            .read_exact(&mut magic)
+            .await
+            .context("unable to read archive")?;
+        if magic != ARCHIVE_MAGIC {
+            bail!("not a topic archive");
+        }
+        let archive_version = read_i16(&mut reader).await?;
+        if archive_version > ARCHIVE_VERSION {
+            bail!("unsupported archive version: {archive_version}");
+        }
+        let version = read_i16(&mut reader).await?;
+        let buf = read_frame(&mut reader).await?;
+        let header = ArchiveHeader::decode_from(&mut Cursor::new(buf), version)
+            .context("unable to decode archive header")?;
+        Ok(Self { reader, header })
+    }
+
+    pub fn header(&self) -> &ArchiveHeader {
+        &self.header
+    }
+
+    /// next batch, None at the end of the archive
+    pub async fn next_batch(&mut self) -> Result<Option<(PartitionId, Batch<RawRecords>)>> {
+        let mut partition = [0; 4];
+        if !read_or_eof(&mut self.reader, &mut partition).await? {
+            return Ok(None);
+        }
+        let partition = PartitionId::from_be_bytes(partition);
+        let buf = read_frame(&mut self.reader).await?;
+        let batch = Batch::<RawRecords>::decode_from(&mut Cursor::new(buf), 0)?;
+        batch.validate_crc()?;
+        Ok(Some((partition, batch)))
+    }
+}
+
+/// Configures topic import
+#[derive(Debug, Builder, Clone)]
+#[builder(build_fn(private, name = "build_impl"))]
+pub struct ImportConfig {
+    /// name of created topic, topic of the archive if not set
+    #[builder(default, setter(strip_option, into))]
+    pub topic: Option<String>,
+    /// records keep offsets they had in the exported topic and consumer offsets are restored
+    #[builder(default)]
+    pub preserve_offsets: bool,
+}
+
+impl ImportConfig {
+    pub fn builder() -> ImportConfigBuilder {
+        ImportConfigBuilder::default()
+    }
+}
+
+impl ImportConfigBuilder {
+    pub fn build(&self) -> Result<ImportConfig> {
+        self.build_impl()
+            .map_err(|e| anyhow!("Missing required config option: {e}"))
+    }
+}
+
+/// Export topic into archive.
+/// Every partition is exported up to its high watermark at the time export starts.
+#[instrument(skip(fluvio, writer))]
+pub async fn export_topic<W: AsyncWrite + Unpin>(
+    fluvio: &Fluvio,
+    topic: &str,
+    writer: W,
+) -> Result<ArchiveHeader> {
+    let admin = fluvio.admin().await;
+    let spec = admin
+        .list::<TopicSpec, _>(vec![topic.to_owned()])
+        .await?
+        .into_iter()
+        .find(|metadata| metadata.name == topic)
+        .ok_or_else(|| FluvioError::TopicNotFound(topic.to_owned()))?
+        .spec;
+
+    let spu_pool = fluvio.spu_pool().await?;
+    let mut partitions = vec![];
+    for partition in 0..spec.partitions() {
+        let replica = ReplicaKey::new(topic, partition);
+        let mut socket = spu_pool.create_serial_socket(&replica).await?;
+        let offsets = fetch_offsets(&mut socket, &replica).await?;
+        if offsets.error_code != ErrorCode::None {
+            bail!(
+                "fetch offsets of {replica} failed with: {}",
+                offsets.error_code
+            );
+        }
+        partitions.push(ArchivePartition {
+            partition,
+            start_offset: offsets.start_offset,
+            end_offset: offsets.last_stable_offset,
+        });
+    }
+
+    let consumers = fluvio
+        .consumer_offsets()
+        .await?
+        .into_iter()
+        .filter(|consumer| consumer.topic == topic)
+        .map(|consumer| ArchiveConsumerOffset {
+            consumer_id: consumer.consumer_id,
+            partition: consumer.partition,
+            offset: consumer.offset,
+        })
+        .collect();
+
+    let header = ArchiveHeader {
+        topic: topic.to_owned(),
+        spec,
+        partitions,
+        consumers,
+    };
+    let mut writer = ArchiveWriter::new(writer, &header).await?;
+
+    for partition in header.partitions.iter() {
+        if partition.end_offset <= partition.start_offset {
+            continue;
+        }
+        debug!(?partition, "exporting partition");
+        let consumer = PartitionConsumer::new(
+            topic.to_owned(),
+            partition.partition,
+            spu_pool.clone(),
+            fluvio.metrics(),
+        );
+        let config = ConsumerConfig::builder()
+            .isolation(Isolation::ReadCommitted)
+            .build()?;
+        let mut stream = consumer
+            .stream_raw_batches_with_config(
+                crate::Offset::absolute(partition.start_offset)?,
+                config,
+            )
+            .await?;
+        while let Some(batch) = stream.next().await {
+            let batch = batch?;
+            if batch.get_base_offset() >= partition.end_offset {
+                break;
+            }
+            writer.write_batch(partition.partition, &batch).await?;
+            if batch.get_last_offset() + 1 >= partition.end_offset {
+                break;
+            }
+        }
+    }
+    writer.finish().await?;
+    info!(topic, records = header.records(), "topic exported");
+    Ok(header)
+}
+
+/// Create topic from archive and write batches of the archive into it.
+/// Partition assignment and mirroring refer to the exported cluster, so the topic is
+/// created with computed replicas with the same partitions and replication factor.
+#[instrument(skip(fluvio, reader))]
+pub async fn import_topic<R: AsyncRead + Unpin>(
+    fluvio: &Fluvio,
+    reader: R,
+    config: ImportConfig,
+) -> Result<ArchiveHeader> {
+    let mut archive = ArchiveReader::new(reader).await?;
+    let mut header = archive.header().clone();
+    if let Some(topic) = config.topic {
+        header.topic = topic;
+    }
+    let topic = header.topic.clone();
+
+    let mut spec = header.spec.clone();
+    if !spec.replicas().is_computed() {
+        let replicas = spec.replicas();
+        spec.set_replicas(ReplicaSpec::new_computed(
+            replicas.partitions(),
+            replicas.replication_factor().unwrap_or(1),
+            None,
+        ));
+    }
+    fluvio
+        .admin()
+        .await
+        .create(topic.clone(), false, spec)
+        .await?;
+
+    let spu_pool = fluvio.spu_pool().await?;
+    for partition in header.partitions.iter() {
+        wait_for_online(
+            &spu_pool,
+            &ReplicaKey::new(topic.as_str(), partition.partition),
+        )
+        .await?;
+    }
+
+    let mut imports: HashMap<PartitionId, PartitionImport> = HashMap::new();
+    while let Some((partition, batch)) = archive.next_batch().await? {
+        let import = imports
+            .entry(partition)
+            .or_insert_with(|| PartitionImport::new(ReplicaKey::new(topic.as_str(), partition)));
+        if config.preserve_offsets {
+            import.fill_gap(&batch)?;
+        }
+        import.push(batch);
+        if import.pending_bytes >= IMPORT_REQUEST_BYTES {
+            import.flush(&spu_pool).await?;
+        }
+    }
+    for import in imports.values_mut() {
+        import.flush(&spu_pool).await?;
+    }
+
+    for partition in header.partitions.iter() {
+        let next_offset = imports
+            .get(&partition.partition)
+            .map(|import| import.next_offset)
+            .unwrap_or(partition.start_offset);
+        if next_offset < partition.end_offset {
+            bail!(
+                "archive is truncated, partition {} has records up to offset {next_offset}, expected {}",
+                partition.partition,
+                partition.end_offset
+            );
+        }
+    }
+
+    if config.preserve_offsets {
+        for partition in header.partitions.iter() {
+            if partition.start_offset > 0 {
+                fluvio
+                    .delete_records(topic.as_str(), partition.partition, partition.start_offset)
+                    .await?;
+            }
+        }
+        for consumer in header.consumers.iter() {
+            restore_consumer_offset(fluvio, &header, consumer).await?;
+        }
+    } else if !header.consumers.is_empty() {
+        warn!(
+            consumers = header.consumers.len(),
+            "consumer offsets are restored only when offsets are preserved"
+        );
+    }
+
+    info!(%topic, records = header.records(), "topic imported");
+    Ok(header)
+}
+
+/// batches of partition waiting to be written
+struct PartitionImport {
+    replica: ReplicaKey,
+    /// offset after last batch in the archive
+    next_offset: Offset,
+    pending: Vec<Batch<RawRecords>>,
+    pending_bytes: usize,
+}
+
+impl PartitionImport {
+    fn new(replica: ReplicaKey) -> Self {
+        Self {
+            replica,
+            next_offset: 0,
+            pending: vec![],
+            pending_bytes: 0,
+        }
+    }
+
+    /// Offsets before first batch were deleted in exported topic.
+    /// Placeholder batch takes these offsets, so batches are written at their offsets.
+    /// It is deleted once import is done.
+    fn fill_gap(&mut self, batch: &Batch<RawRecords>) -> Result<()> {
+        let base_offset = batch.get_base_offset();
+        if base_offset == self.next_offset {
+            return Ok(());
+        }
+        if self.next_offset > 0 {
+            bail!(
+                "unable to preserve offsets of {}, batch at offset {base_offset} follows offset {}",
+                self.replica,
+                self.next_offset
+            );
+        }
+        let compression = batch.get_compression()?;
+        while self.next_offset < base_offset {
+            let len = (base_offset - self.next_offset).min(i32::MAX as Offset);
+            let mut placeholder = Batch::from(vec![Record::default()]);
+            placeholder.get_mut_header().set_compression(compression);
+            placeholder.set_base_offset(self.next_offset);
+            placeholder.set_offset_delta((len - 1) as i32);
+            self.push(placeholder.try_into()?);
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, batch: Batch<RawRecords>) {
+        self.next_offset = batch.get_last_offset() + 1;
+        self.pending_bytes += batch.batch_len() as usize;
+        self.pending.push(batch);
+    }
+
+    async fn flush(&mut self, spu_pool: &SpuSocketPool) -> Result<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let mut partition_request = DefaultPartitionRequest {
+            partition_index: self.replica.partition,
+            ..Default::default()
+        };
+        partition_request.records.batches = std::mem::take(&mut self.pending);
+        self.pending_bytes = 0;
+
+        let mut request = DefaultProduceRequest {
+            isolation: Isolation::ReadCommitted,
+            timeout: IMPORT_PRODUCE_TIMEOUT,
+            ..Default::default()
+        };
+        request.topics.push(DefaultTopicRequest {
+            name: self.replica.topic.clone(),
+            partitions: vec![partition_request],
+            ..Default::default()
+        });
+
+        let socket = spu_pool.create_serial_socket(&self.replica).await?;
+        let response = socket.send_receive(request).await?;
+        for partition in response
+            .responses
+            .into_iter()
+            .flat_map(|topic| topic.partitions)
+        {
+            if partition.error_code != ErrorCode::None {
+                bail!(
+                    "import into {} failed with: {}",
+                    self.replica,
+                    partition.error_code
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+async fn wait_for_online(spu_pool: &SpuSocketPool, replica: &ReplicaKey) -> Result<()> {
+    for _ in 0..PARTITION_ONLINE_RETRIES {
+        if let Ok(Some(partition)) = spu_pool.partitions().lookup_by_key(replica).await {
+            if partition.status.is_online() {
+                return Ok(());
+            }
+        }
+        sleep(PARTITION_ONLINE_RETRY_DELAY).await;
+    }
+    bail!("partition {replica} is not online")
+}
+
+/// consume record at consumer offset and commit it
+async fn restore_consumer_offset(
+    fluvio: &Fluvio,
+    header: &ArchiveHeader,
+    consumer: &ArchiveConsumerOffset,
+) -> Result<()> {
+    let Some(partition) = header
+        .partitions
+        .iter()
+        .find(|partition| partition.partition == consumer.partition)
+    else {
+        return Ok(());
+    };
+    if consumer.offset < partition.start_offset || consumer.offset >= partition.end_offset {
+        warn!(
+            consumer = consumer.consumer_id,
+            offset = consumer.offset,
+            "consumer offset is not in the archive, skipping"
+        );
+        return Ok(());
+    }
+
+    let config = ConsumerConfigExt::builder()
+        .topic(header.topic.clone())
+        .partition(consumer.partition)
+        .offset_consumer(consumer.consumer_id.clone())
+        .offset_start(crate::Offset::absolute(consumer.offset)?)
+        .offset_strategy(OffsetManagementStrategy::Manual)
+        .build()?;
+    let mut stream = fluvio.consumer_with_config(config).await?;
+    match stream.next().await {
+        Some(Ok(record)) if record.offset == consumer.offset => {
+            stream.offset_commit().await?;
+            stream.offset_flush().await?;
+            debug!(
+                consumer = consumer.consumer_id,
+                offset = consumer.offset,
+                "consumer offset restored"
+            );
+            Ok(())
+        }
+        Some(Err(err)) => Err(err.into()),
+        _ => bail!(
+            "unable to restore offset {} of consumer {}",
+            consumer.offset,
+            consumer.consumer_id
+        ),
+    }
+}
+
+/// version used to encode archive header
+fn metadata_version() -> Version {
+    <ObjectApiListRequest as Request>::DEFAULT_API_VERSION
+}
+
+async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, buf: &[u8]) -> Result<(), IoError> {
+    writer.write_all(&(buf.len() as u32).to_be_bytes()).await?;
+    writer.write_all(buf).await
+}
+
+async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Vec<u8>, IoError> {
+    let mut len = [0; 4];
+    reader.read_exact(&mut len).await?;
+    let mut buf = vec![0; u32::from_be_bytes(len) as usize];
+    reader.read_exact(&mut buf).await?;
+    Ok(buf)
+}
+
+async fn read_i16<R: AsyncRead + Unpin>(reader: &mut R) -> Result<i16, IoError> {
+    let mut buf = [0; 2];
+    reader.read_exact(&mut buf).await?;
+    Ok(i16::from_be_bytes(buf))
+}
+
+/// fill buffer, returns false if reader is at the end
+async fn read_or_eof<R: AsyncRead + Unpin>(
+    reader: &mut R,
+    buf: &mut [u8],
+) -> Result<bool, IoError> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]).await {
+            Ok(0) if read == 0 => return Ok(false),
+            Ok(0) => {
+                return Err(IoError::new(
+                    ErrorKind::UnexpectedEof,
+                    "archive is truncated",
+                ));
+            }
+            Ok(n) => read += n,
+            Err(err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_protocol::record::{Batch, RawRecords, Record};
+    use fluvio_sc_schema::topic::TopicSpec;
+
+    use super::*;
+
+    fn raw_batch(base_offset: Offset, values: &[&str]) -> Batch<RawRecords> {
+        let records: Vec<Record> = values.iter().map(|value| Record::new(*value)).collect();
+        let mut batch = Batch::from(records);
+        batch.set_base_offset(base_offset);
+        batch.try_into().expect("raw")
+    }
+
+    #[fluvio_future::test]
+    async fn test_archive_round_trip() {
+        let header = ArchiveHeader {
+            topic: "test".to_owned(),
+            spec: TopicSpec::new_computed(2, 1, None),
+            partitions: vec![
+                ArchivePartition {
+                    partition: 0,
+                    start_offset: 0,
+                    end_offset: 3,
+                },
+                ArchivePartition {
+                    partition: 1,
+                    start_offset: 5,
+                    end_offset: 6,
+                },
+            ],
+            consumers: vec![ArchiveConsumerOffset {
+                consumer_id: "c1".to_owned(),
+                partition: 0,
+                offset: 1,
+            }],
+        };
+        assert_eq!(header.records(), 4);
+
+        let mut writer = ArchiveWriter::new(vec![], &header).await.expect("writer");
+        writer
+            .write_batch(0, &raw_batch(0, &["a", "b", "c"]))
+            .await
+            .expect("write");
+        writer
+            .write_batch(1, &raw_batch(5, &["d"]))
+            .await
+            .expect("write");
+        let bytes = writer.finish().await.expect("finish");
+
+        let mut reader = ArchiveReader::new(bytes.as_slice()).await.expect("reader");
+        assert_eq!(reader.header(), &header);
+
+        let (partition, batch) = reader.next_batch().await.expect("read").expect("batch");
+        assert_eq!(partition, 0);
+        assert_eq!(batch.get_base_offset(), 0);
+        assert_eq!(batch.records_len(), 3);
+        let records = batch.memory_records().expect("records");
+        assert_eq!(records[2].value().as_ref(), b"c");
+
+        let (partition, batch) = reader.next_batch().await.expect("read").expect("batch");
+        assert_eq!(partition, 1);
+        assert_eq!(batch.get_last_offset(), 5);
+
+        assert!(reader.next_batch().await.expect("read").is_none());
+    }
+
+    #[fluvio_future::test]
+    async fn test_archive_invalid() {
+        assert!(
+            ArchiveReader::new(b"not an archive".as_slice())
+                .await
+                .is_err()
+        );
+
+        let header = ArchiveHeader::default();
+        let mut writer = ArchiveWriter::new(vec![], &header).await.expect("writer");
+        writer
+            .write_batch(0, &raw_batch(0, &["a"]))
+            .await
+            .expect("write");
+        let mut bytes = writer.finish().await.expect("finish");
+        bytes.truncate(bytes.len() - 1);
+
+        let mut reader = ArchiveReader::new(bytes.as_slice()).await.expect("reader");
+        assert!(reader.next_batch().await.is_err());
+    }
+
+    #[test]
+    fn test_fill_gap() {
+        let mut import = PartitionImport::new(ReplicaKey::new("test", 0u32));
+        let batch = raw_batch(10, &["a", "b"]);
+        import.fill_gap(&batch).expect("fill");
+        import.push(batch);
+        assert_eq!(import.pending.len(), 2);
+        assert_eq!(import.pending[0].records_len(), 10);
+        assert_eq!(import.next_offset, 12);
+
+        // gap after first batch can't be filled
+        assert!(import.fill_gap(&raw_batch(15, &["c"])).is_err());
+        assert!(import.fill_gap(&raw_batch(12, &["c"])).is_ok());
+    }
+
+    #[test]
+    fn test_fill_large_gap() {
+        let mut import = PartitionImport::new(ReplicaKey::new("test", 0u32));
+        let base_offset = 2 * i32::MAX as Offset + 10;
+        let batch = raw_batch(base_offset, &["a"]);
+        import.fill_gap(&batch).expect("fill");
+        assert_eq!(import.pending.len(), 3);
+        assert_eq!(import.pending[0].get_base_offset(), 0);
+        assert_eq!(import.pending[1].get_base_offset(), i32::MAX as Offset);
+        assert_eq!(import.pending[2].get_base_offset(), 2 * i32::MAX as Offset);
+        assert_eq!(import.pending[2].records_len(), 10);
+        assert_eq!(import.next_offset, base_offset);
+        import.push(batch);
+        assert_eq!(import.next_offset, base_offset + 1);
+    }
+}
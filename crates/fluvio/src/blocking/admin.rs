@@ -0,0 +1,130 @@
+use std::fmt::Debug;
+use std::io::Error as IoError;
+
+use anyhow::Result;
+use fluvio_future::task::run_block_on;
+use fluvio_protocol::{Decoder, Encoder};
+use fluvio_sc_schema::objects::{CommonCreateRequest, ListFilter, Metadata, WatchResponse};
+use fluvio_sc_schema::{AdminSpec, CreatableAdminSpec, DeletableAdminSpec, UpdatableAdminSpec};
+
+use crate::metadata::objects::ListRequest;
+use crate::FluvioClusterConfig;
+
+use super::BlockingIter;
+
+/// Blocking interface for managing a Fluvio cluster, see [`crate::FluvioAdmin`]
+pub struct FluvioAdmin {
+    inner: crate::FluvioAdmin,
+}
+
+impl FluvioAdmin {
+    /// Creates a new admin connection using the current profile from `~/.fluvio/config`
+    pub fn connect() -> Result<Self> {
+        run_block_on(crate::FluvioAdmin::connect()).map(Self::from)
+    }
+
+    /// Creates a new admin connection using custom configurations
+    pub fn connect_with_config(config: &FluvioClusterConfig) -> Result<Self> {
+        run_block_on(crate::FluvioAdmin::connect_with_config(config)).map(Self::from)
+    }
+
+    /// Create new object
+    pub fn create<S>(&self, name: String, dry_run: bool, spec: S) -> Result<()>
+    where
+        S: CreatableAdminSpec + Sync + Send,
+    {
+        run_block_on(self.inner.create(name, dry_run, spec))
+    }
+
+    pub fn create_with_config<S>(&self, config: CommonCreateRequest, spec: S) -> Result<()>
+    where
+        S: CreatableAdminSpec + Sync + Send,
+    {
+        run_block_on(self.inner.create_with_config(config, spec))
+    }
+
+    /// Delete object by key
+    pub fn delete<S>(&self, key: impl Into<S::DeleteKey>) -> Result<()>
+    where
+        S: DeletableAdminSpec + Sync + Send,
+    {
+        run_block_on(self.inner.delete::<S>(key))
+    }
+
+    /// Forcibly delete object by key, including objects marked as 'system'
+    pub fn force_delete<S>(&self, key: impl Into<S::DeleteKey>) -> Result<()>
+    where
+        S: DeletableAdminSpec + Sync + Send,
+    {
+        run_block_on(self.inner.force_delete::<S>(key))
+    }
+
+    /// Update object by key
+    pub fn update<S>(&self, key: impl Into<S::UpdateKey>, action: S::UpdateAction) -> Result<()>
+    where
+        S: UpdatableAdminSpec + Sync + Send,
+    {
+        run_block_on(self.inner.update::<S>(key, action))
+    }
+
+    /// return all instance of this spec
+    pub fn all<S>(&self) -> Result<Vec<Metadata<S>>>
+    where
+        S: AdminSpec,
+        S::Status: Encoder + Decoder + Debug,
+    {
+        run_block_on(self.inner.all::<S>())
+    }
+
+    /// return all instance of this spec by filter
+    pub fn list<S, F>(&self, filters: Vec<F>) -> Result<Vec<Metadata<S>>>
+    where
+        S: AdminSpec,
+        ListFilter: From<F>,
+        S::Status: Encoder + Decoder + Debug,
+    {
+        run_block_on(self.inner.list::<S, F>(filters))
+    }
+
+    pub fn list_with_params<S, F>(&self, filters: Vec<F>, summary: bool) -> Result<Vec<Metadata<S>>>
+    where
+        S: AdminSpec,
+        ListFilter: From<F>,
+        S::Status: Encoder + Decoder + Debug,
+    {
+        run_block_on(self.inner.list_with_params::<S, F>(filters, summary))
+    }
+
+    pub fn list_with_config<S, F>(&self, config: ListRequest<S>) -> Result<Vec<Metadata<S>>>
+    where
+        S: AdminSpec,
+        ListFilter: From<F>,
+        S::Status: Encoder + Decoder + Debug,
+    {
+        run_block_on(self.inner.list_with_config::<S, F>(config))
+    }
+
+    /// Iterator over the changes of metadata
+    pub fn watch<S>(&self) -> Result<impl Iterator<Item = Result<WatchResponse<S>, IoError>>>
+    where
+        S: AdminSpec,
+        S::Status: Encoder + Decoder,
+    {
+        run_block_on(self.inner.watch::<S>()).map(BlockingIter::new)
+    }
+
+    /// Async admin, for the parts of the API not mirrored here
+    pub fn inner(&self) -> &crate::FluvioAdmin {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> crate::FluvioAdmin {
+        self.inner
+    }
+}
+
+impl From<crate::FluvioAdmin> for FluvioAdmin {
+    fn from(inner: crate::FluvioAdmin) -> Self {
+        Self { inner }
+    }
+}
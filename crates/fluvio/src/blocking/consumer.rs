@@ -0,0 +1,103 @@
+use fluvio_future::task::run_block_on;
+use fluvio_protocol::link::ErrorCode;
+
+use crate::consumer::{BoxConsumerStream, ConsumerRecord, ConsumerStream};
+
+use super::BlockingIter;
+
+/// Iterator over the records of a consumer stream with offset management
+pub struct ConsumerIter {
+    inner: BlockingIter<BoxConsumerStream>,
+}
+
+impl ConsumerIter {
+    pub fn new(stream: BoxConsumerStream) -> Self {
+        Self {
+            inner: BlockingIter::new(stream),
+        }
+    }
+
+    /// See [`ConsumerStream::offset_commit`]
+    pub fn offset_commit(&mut self) -> Result<(), ErrorCode> {
+        run_block_on(self.inner.stream.offset_commit())
+    }
+
+    /// See [`ConsumerStream::offset_flush`]
+    pub fn offset_flush(&mut self) -> Result<(), ErrorCode> {
+        run_block_on(self.inner.stream.offset_flush())
+    }
+
+    pub fn into_inner(self) -> BoxConsumerStream {
+        self.inner.stream
+    }
+}
+
+impl Iterator for ConsumerIter {
+    type Item = Result<ConsumerRecord, ErrorCode>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use fluvio_protocol::record::{Batch, Record};
+    use futures_util::stream::{iter, Iter};
+    use futures_util::{Stream, StreamExt};
+
+    use crate::consumer::ConsumerBoxFuture;
+
+    use super::*;
+
+    struct TestStream {
+        records: Iter<std::vec::IntoIter<Result<ConsumerRecord, ErrorCode>>>,
+    }
+
+    impl Stream for TestStream {
+        type Item = Result<ConsumerRecord, ErrorCode>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.records.poll_next_unpin(cx)
+        }
+    }
+
+    impl ConsumerStream for TestStream {
+        fn offset_commit(&mut self) -> ConsumerBoxFuture<'_> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn offset_flush(&mut self) -> ConsumerBoxFuture<'_> {
+            Box::pin(async { Err(ErrorCode::OffsetManagementDisabled) })
+        }
+    }
+
+    #[test]
+    fn test_consumer_iter() {
+        //given
+        let mut records = vec![Record::new("1"), Record::new("2")];
+        let mut batch = Batch::default();
+        batch.add_records(&mut records);
+        let records: Vec<_> = batch.into_consumer_records_iter(0).map(Ok).collect();
+        let stream = TestStream {
+            records: iter(records),
+        };
+        let mut consumer = ConsumerIter::new(Box::pin(stream));
+
+        //when
+        let first = consumer.next().expect("record").expect("no error");
+        let commit = consumer.offset_commit();
+        let flush = consumer.offset_flush();
+        let rest: Vec<_> = consumer.by_ref().collect();
+
+        //then
+        assert_eq!(first.as_ref(), b"1");
+        assert!(commit.is_ok());
+        assert_eq!(flush, Err(ErrorCode::OffsetManagementDisabled));
+        assert_eq!(rest.len(), 1);
+        assert!(consumer.next().is_none());
+    }
+}
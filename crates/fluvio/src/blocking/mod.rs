@@ -0,0 +1,177 @@
+//! Synchronous client API.
+//!
+//! The types in this module mirror [`crate::Fluvio`], [`crate::TopicProducer`],
+//! [`crate::FluvioAdmin`] and consumer streams with blocking methods. Each call runs the
+//! async counterpart to completion on the `fluvio-future` executor, which also keeps
+//! running the client background tasks (metadata sync, batching, offset flushing) between
+//! calls. Timeouts and errors are the same as in the async API.
+//!
+//! Blocking methods must not be called from async code.
+//!
+//! ```no_run
+//! use fluvio::blocking::Fluvio;
+//! use fluvio::consumer::ConsumerConfigExtBuilder;
+//! use fluvio::{Offset, RecordKey};
+//!
+//! fn produce_and_consume() -> anyhow::Result<()> {
+//!     let fluvio = Fluvio::connect()?;
+//!     let producer = fluvio.topic_producer("my-topic")?;
+//!     producer.send(RecordKey::NULL, "Hello, Fluvio!")?;
+//!     producer.flush()?;
+//!
+//!     let consumer = fluvio.consumer_with_config(
+//!         ConsumerConfigExtBuilder::default()
+//!             .topic("my-topic")
+//!             .offset_start(Offset::beginning())
+//!             .build()?,
+//!     )?;
+//!     for record in consumer.take(1) {
+//!         println!("{}", String::from_utf8_lossy(record?.as_ref()));
+//!     }
+//!     Ok(())
+//! }
+//! ```
+
+mod admin;
+mod consumer;
+mod producer;
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use fluvio_future::task::run_block_on;
+use futures_util::{Stream, StreamExt};
+
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_types::PartitionId;
+
+use crate::consumer::{ConsumerConfigExt, ConsumerLag, ConsumerLagError, ConsumerOffset};
+use crate::metrics::ClientMetrics;
+use crate::{FluvioClusterConfig, TopicProducerConfig};
+
+pub use admin::FluvioAdmin;
+pub use consumer::ConsumerIter;
+pub use producer::{ProduceOutput, TopicProducer};
+
+/// Blocking interface for interacting with Fluvio streaming, see [`crate::Fluvio`]
+pub struct Fluvio {
+    inner: crate::Fluvio,
+}
+
+impl Fluvio {
+    /// Creates a new Fluvio client using the current profile from `~/.fluvio/config`
+    pub fn connect() -> Result<Self> {
+        run_block_on(crate::Fluvio::connect()).map(Self::from)
+    }
+
+    /// Creates a new Fluvio client with the given configuration
+    pub fn connect_with_config(config: &FluvioClusterConfig) -> Result<Self> {
+        run_block_on(crate::Fluvio::connect_with_config(config)).map(Self::from)
+    }
+
+    /// Creates a new Fluvio client with the given profile
+    pub fn connect_with_profile(profile: &str) -> Result<Self> {
+        run_block_on(crate::Fluvio::connect_with_profile(profile)).map(Self::from)
+    }
+
+    /// Creates a new `TopicProducer` for the given topic name
+    pub fn topic_producer(&self, topic: impl Into<String>) -> Result<TopicProducer> {
+        run_block_on(self.inner.topic_producer(topic)).map(TopicProducer::from)
+    }
+
+    /// Creates a new `TopicProducer` for the given topic name and configuration
+    pub fn topic_producer_with_config(
+        &self,
+        topic: impl Into<String>,
+        config: TopicProducerConfig,
+    ) -> Result<TopicProducer> {
+        run_block_on(self.inner.topic_producer_with_config(topic, config)).map(TopicProducer::from)
+    }
+
+    /// Creates an iterator over the records of the consumer stream,
+    /// see [`crate::Fluvio::consumer_with_config`]
+    pub fn consumer_with_config(&self, config: ConsumerConfigExt) -> Result<ConsumerIter> {
+        let stream = run_block_on(self.inner.consumer_with_config(config))?;
+        Ok(ConsumerIter::new(Box::pin(stream)))
+    }
+
+    /// Returns all consumers offsets that currently available in the cluster.
+    pub fn consumer_offsets(&self) -> Result<Vec<ConsumerOffset>> {
+        run_block_on(self.inner.consumer_offsets())
+    }
+
+    /// Returns lag of all consumers offsets, see [`crate::Fluvio::consumer_lag`]
+    pub fn consumer_lag(&self) -> Result<Vec<Result<ConsumerLag, ConsumerLagError>>> {
+        run_block_on(self.inner.consumer_lag())
+    }
+
+    /// Delete a consumer offset for the given name and the replica.
+    pub fn delete_consumer_offset(
+        &self,
+        consumer_id: impl Into<String>,
+        replica_id: impl Into<ReplicaKey>,
+    ) -> Result<()> {
+        run_block_on(self.inner.delete_consumer_offset(consumer_id, replica_id))
+    }
+
+    /// Delete all records before the given offset in a partition.
+    pub fn delete_records(
+        &self,
+        topic: impl Into<String>,
+        partition: PartitionId,
+        offset: fluvio_protocol::record::Offset,
+    ) -> Result<fluvio_protocol::record::Offset> {
+        run_block_on(self.inner.delete_records(topic, partition, offset))
+    }
+
+    /// Provides an interface for managing a Fluvio cluster
+    pub fn admin(&self) -> FluvioAdmin {
+        FluvioAdmin::from(run_block_on(self.inner.admin()))
+    }
+
+    /// Reports the Platform Version of the connected cluster.
+    pub fn platform_version(&self) -> &semver::Version {
+        self.inner.platform_version()
+    }
+
+    pub fn metrics(&self) -> Arc<ClientMetrics> {
+        self.inner.metrics()
+    }
+
+    /// Async client, for the parts of the API not mirrored here
+    pub fn inner(&self) -> &crate::Fluvio {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> crate::Fluvio {
+        self.inner
+    }
+}
+
+impl From<crate::Fluvio> for Fluvio {
+    fn from(inner: crate::Fluvio) -> Self {
+        Self { inner }
+    }
+}
+
+/// Iterator that blocks on each item of a stream
+pub struct BlockingIter<S> {
+    stream: S,
+}
+
+impl<S> BlockingIter<S> {
+    pub(crate) fn new(stream: S) -> Self {
+        Self { stream }
+    }
+}
+
+impl<S> Iterator for BlockingIter<S>
+where
+    S: Stream + Unpin,
+{
+    type Item = S::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        run_block_on(self.stream.next())
+    }
+}
@@ -0,0 +1,73 @@
+use anyhow::Result;
+use fluvio_future::task::run_block_on;
+use fluvio_protocol::record::RecordData;
+
+use crate::producer::RecordMetadata;
+use crate::{RecordKey, TopicProducerPool};
+
+/// Blocking producer, see [`crate::TopicProducer`]
+pub struct TopicProducer {
+    inner: TopicProducerPool,
+}
+
+impl TopicProducer {
+    /// Sends a key/value record to this producer's Topic.
+    ///
+    /// Depending on the producer configuration, the record may only be added to a batch.
+    /// Use [`TopicProducer::flush`] or [`ProduceOutput::wait`] to wait until it is sent.
+    pub fn send(
+        &self,
+        key: impl Into<RecordKey>,
+        value: impl Into<RecordData>,
+    ) -> Result<ProduceOutput> {
+        run_block_on(self.inner.send(key, value)).map(ProduceOutput)
+    }
+
+    pub fn send_all(
+        &self,
+        records: impl IntoIterator<Item = (impl Into<RecordKey>, impl Into<RecordData>)>,
+    ) -> Result<Vec<ProduceOutput>> {
+        let outputs = run_block_on(self.inner.send_all(records))?;
+        Ok(outputs.into_iter().map(ProduceOutput).collect())
+    }
+
+    /// Sends all the queued records in the producer batches
+    pub fn flush(&self) -> Result<()> {
+        run_block_on(self.inner.flush())
+    }
+
+    /// Async producer, for the parts of the API not mirrored here
+    pub fn inner(&self) -> &TopicProducerPool {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> TopicProducerPool {
+        self.inner
+    }
+}
+
+impl From<TopicProducerPool> for TopicProducer {
+    fn from(inner: TopicProducerPool) -> Self {
+        Self { inner }
+    }
+}
+
+/// Blocking version of [`crate::ProduceOutput`]
+pub struct ProduceOutput(crate::ProduceOutput);
+
+impl ProduceOutput {
+    /// Wait for the record metadata
+    pub fn wait(self) -> crate::error::Result<RecordMetadata> {
+        run_block_on(self.0.wait())
+    }
+
+    /// Wait for all record metadata of all records sent using smartmodule
+    #[cfg(feature = "smartengine")]
+    pub fn wait_all(self) -> crate::error::Result<Vec<RecordMetadata>> {
+        run_block_on(self.0.wait_all())
+    }
+
+    pub fn into_inner(self) -> crate::ProduceOutput {
+        self.0
+    }
+}
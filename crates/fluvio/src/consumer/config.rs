@@ -2,12 +2,16 @@ use std::{str::FromStr, time::Duration};
 
 use anyhow::Result;
 use derive_builder::Builder;
+use regex::Regex;
 
 use fluvio_spu_schema::{server::smartmodule::SmartModuleInvocation, Isolation};
 use fluvio_types::PartitionId;
 
+use crate::interceptor::{ConsumerInterceptors, SharedConsumerInterceptor};
 use crate::{FluvioError, Offset};
 
+#[cfg(feature = "dead-letter")]
+use super::DeadLetterPolicy;
 use super::MAX_FETCH_BYTES;
 
 const DEFAULT_OFFSET_FLUSH_PERIOD: Duration = Duration::from_secs(10);
@@ -26,6 +30,17 @@ pub struct ConsumerConfig {
     pub isolation: Isolation,
     #[builder(default)]
     pub smartmodule: Vec<SmartModuleInvocation>,
+    /// Rack of the consumer. With [`Isolation::ReadCommitted`], records are read from
+    /// follower replica in the same rack when it is in sync, otherwise from the leader.
+    /// Consumers with `consumer_id` always read from the leader, which manages their offsets.
+    ///
+    /// Once the follower fails, ends the stream or falls behind the leader, the stream
+    /// continues from the leader after the last records received.
+    #[builder(default, setter(strip_option, into))]
+    pub rack: Option<String>,
+    /// Interceptors that see each consumed record before it is yielded
+    #[builder(default, setter(custom))]
+    pub interceptors: ConsumerInterceptors,
 }
 
 impl ConsumerConfig {
@@ -41,6 +56,14 @@ impl ConsumerConfigBuilder {
         })?;
         Ok(config)
     }
+
+    /// Adds an interceptor, it runs after the interceptors added before
+    pub fn interceptor(&mut self, interceptor: SharedConsumerInterceptor) -> &mut Self {
+        self.interceptors
+            .get_or_insert_with(Default::default)
+            .push(interceptor);
+        self
+    }
 }
 
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
@@ -81,8 +104,13 @@ pub enum RetryMode {
 #[derive(Debug, Builder, Clone)]
 #[builder(build_fn(private, name = "build_impl"))]
 pub struct ConsumerConfigExt {
-    #[builder(setter(into))]
+    #[builder(default, setter(into))]
     pub topic: String,
+    /// Regular expression matched against the whole topic name. When set, the consumer
+    /// subscribes to every matching topic and follows topics and partitions as they are
+    /// created or deleted. Mutually exclusive with `topic`.
+    #[builder(default, setter(strip_option, into))]
+    pub topic_pattern: Option<String>,
     #[builder(default, setter(custom))]
     pub partition: Vec<PartitionId>,
     #[builder(default, setter(strip_option, into))]
@@ -106,6 +134,24 @@ pub struct ConsumerConfigExt {
     pub smartmodule: Vec<SmartModuleInvocation>,
     #[builder(default = "DEFAULT_RETRY_MODE")]
     pub retry_mode: RetryMode,
+    /// Rack of the consumer. With [`Isolation::ReadCommitted`], records are read from
+    /// follower replica in the same rack when it is in sync, otherwise from the leader.
+    /// Consumers with `consumer_id` always read from the leader, which manages their offsets.
+    ///
+    /// Once the follower fails, ends the stream or falls behind the leader, the stream
+    /// continues from the leader after the last records received.
+    #[builder(default, setter(strip_option, into))]
+    pub rack: Option<String>,
+    /// Records failing with a SmartModule error are retried and then published to the
+    /// dead-letter topic of the policy, the consumer continues with the next record.
+    /// Requires retry mode other than [`RetryMode::Disabled`]. Applies only to consumers
+    /// of a single partition, others get the SmartModule error.
+    #[cfg(feature = "dead-letter")]
+    #[builder(default, setter(strip_option))]
+    pub dead_letter: Option<DeadLetterPolicy>,
+    /// Interceptors that see each consumed record before it is yielded
+    #[builder(default, setter(custom))]
+    pub interceptors: ConsumerInterceptors,
 }
 
 impl ConsumerConfigExt {
@@ -125,6 +171,7 @@ impl ConsumerConfigExt {
     ) {
         let Self {
             topic: _,
+            topic_pattern: _,
             partition: _,
             mirror: _,
             offset_consumer,
@@ -137,6 +184,10 @@ impl ConsumerConfigExt {
             offset_flush,
             offset_flusher_check_period,
             retry_mode: _,
+            rack,
+            #[cfg(feature = "dead-letter")]
+            dead_letter: _,
+            interceptors,
         } = self;
 
         let config = ConsumerConfig {
@@ -144,6 +195,8 @@ impl ConsumerConfigExt {
             max_bytes,
             isolation,
             smartmodule,
+            rack,
+            interceptors,
         };
 
         (
@@ -172,6 +225,39 @@ impl ConsumerConfigExtBuilder {
             .into());
         }
 
+        match (config.topic.is_empty(), &config.topic_pattern) {
+            (true, None) => {
+                return Err(FluvioError::ConsumerConfig(
+                    "Either topic or topic pattern is required".to_owned(),
+                )
+                .into());
+            }
+            (false, Some(_)) => {
+                return Err(FluvioError::ConsumerConfig(
+                    "Topic and topic pattern are mutually exclusive".to_owned(),
+                )
+                .into());
+            }
+            (true, Some(pattern)) => {
+                topic_pattern_regex(pattern)?;
+                if config.mirror.is_some() {
+                    return Err(FluvioError::ConsumerConfig(
+                        "Mirror is not supported with topic pattern".to_owned(),
+                    )
+                    .into());
+                }
+            }
+            (false, None) => {}
+        }
+
+        #[cfg(feature = "dead-letter")]
+        if config.dead_letter.is_some() && config.retry_mode == RetryMode::Disabled {
+            return Err(FluvioError::ConsumerConfig(
+                "Dead-letter policy requires retry mode other than disabled".to_owned(),
+            )
+            .into());
+        }
+
         Ok(config)
     }
 
@@ -179,12 +265,28 @@ impl ConsumerConfigExtBuilder {
         self.partition.get_or_insert(Vec::new()).push(value);
         self
     }
+
+    /// Adds an interceptor, it runs after the interceptors added before
+    pub fn interceptor(&mut self, interceptor: SharedConsumerInterceptor) -> &mut Self {
+        self.interceptors
+            .get_or_insert_with(Default::default)
+            .push(interceptor);
+        self
+    }
+}
+
+/// Compiles topic pattern so it only matches whole topic names.
+pub(crate) fn topic_pattern_regex(pattern: &str) -> Result<Regex> {
+    Regex::new(&format!("^(?:{pattern})$")).map_err(|e| {
+        FluvioError::ConsumerConfig(format!("Invalid topic pattern \"{pattern}\": {e}")).into()
+    })
 }
 
 impl From<ConsumerConfigExt> for ConsumerConfig {
     fn from(value: ConsumerConfigExt) -> Self {
         let ConsumerConfigExt {
             topic: _,
+            topic_pattern: _,
             partition: _,
             mirror: _,
             offset_consumer: _,
@@ -197,6 +299,10 @@ impl From<ConsumerConfigExt> for ConsumerConfig {
             isolation,
             smartmodule,
             retry_mode: _,
+            rack,
+            #[cfg(feature = "dead-letter")]
+            dead_letter: _,
+            interceptors,
         } = value;
 
         Self {
@@ -204,6 +310,8 @@ impl From<ConsumerConfigExt> for ConsumerConfig {
             max_bytes,
             isolation,
             smartmodule,
+            rack,
+            interceptors,
         }
     }
 }
@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::Arc;
+use std::task::Waker;
+
+use anyhow::Result;
+use async_channel::Sender;
+use futures_util::task::AtomicWaker;
+use parking_lot::Mutex;
+use tracing::debug;
+
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_spu_schema::server::stream_fetch::DefaultStreamFetchResponse;
+use fluvio_types::PartitionId;
+
+use crate::offset::{fetch_offsets, Offset};
+use crate::spu::{SpuDirectory, SpuSocketPool};
+use crate::FluvioError;
+
+use super::StreamToServer;
+
+const NO_OFFSET: i64 = -1;
+
+/// Pause and seek state of a single partition stream.
+///
+/// A paused partition is not polled, so no offset update is sent back to the SPU
+/// and the SPU stops sending records after the response in flight.
+///
+/// Seek sends the new offset to the SPU as a regular offset update. Records fetched
+/// before the seek are dropped until the SPU answers from the new offset.
+#[derive(Debug)]
+pub(crate) struct PartitionControl {
+    paused: AtomicBool,
+    waker: AtomicWaker,
+    /// records below this offset are dropped
+    floor: AtomicI64,
+    seek_pending: AtomicBool,
+    seek_target: AtomicI64,
+    /// last offset sent to the SPU
+    last_reported: AtomicI64,
+    /// offset reported before the pending seek, responses sent before the seek start from it
+    stale_base: AtomicI64,
+    stream_to_server: Mutex<Option<Sender<StreamToServer>>>,
+}
+
+impl Default for PartitionControl {
+    fn default() -> Self {
+        Self {
+            paused: AtomicBool::new(false),
+            waker: AtomicWaker::new(),
+            floor: AtomicI64::new(0),
+            seek_pending: AtomicBool::new(false),
+            seek_target: AtomicI64::new(NO_OFFSET),
+            last_reported: AtomicI64::new(NO_OFFSET),
+            stale_base: AtomicI64::new(NO_OFFSET),
+            stream_to_server: Mutex::new(None),
+        }
+    }
+}
+
+impl PartitionControl {
+    pub(crate) fn set_stream_to_server(&self, sender: Sender<StreamToServer>) {
+        *self.stream_to_server.lock() = Some(sender);
+    }
+
+    pub(crate) fn set_floor(&self, offset: i64) {
+        self.floor.store(offset, Ordering::SeqCst);
+    }
+
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+        self.waker.wake();
+    }
+
+    /// Registers waker and returns true if the partition is still paused
+    pub(crate) fn poll_paused(&self, waker: &Waker) -> bool {
+        if !self.is_paused() {
+            return false;
+        }
+        self.waker.register(waker);
+        self.is_paused()
+    }
+
+    /// Records fetched before a pending seek and below the seek offset are skipped
+    pub(crate) fn skip_record(&self, offset: i64) -> bool {
+        self.seek_pending.load(Ordering::SeqCst) || offset < self.floor.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn reported(&self, offset: i64) {
+        self.last_reported.store(offset, Ordering::SeqCst);
+    }
+
+    /// Offset to continue from when the stream is moved to another replica
+    pub(crate) fn next_offset(&self) -> Option<i64> {
+        let offset = self.last_reported.load(Ordering::SeqCst);
+        (offset != NO_OFFSET).then_some(offset)
+    }
+
+    async fn seek(&self, offset: i64) -> Result<()> {
+        let sender = self
+            .stream_to_server
+            .lock()
+            .clone()
+            .ok_or_else(|| FluvioError::Other("partition stream is not started".to_owned()))?;
+        self.stale_base
+            .store(self.last_reported.load(Ordering::SeqCst), Ordering::SeqCst);
+        self.floor.store(offset, Ordering::SeqCst);
+        self.seek_target.store(offset, Ordering::SeqCst);
+        self.seek_pending.store(true, Ordering::SeqCst);
+        self.reported(offset);
+        sender
+            .send(StreamToServer::UpdateOffset(offset))
+            .await
+            .map_err(|_| FluvioError::Other("partition stream is closed".to_owned()))?;
+        self.waker.wake();
+        Ok(())
+    }
+
+    /// Returns false if the response was sent by the SPU before it received the seek offset.
+    /// Such response continues from the offset reported before the seek and must be dropped.
+    pub(crate) fn accept_response(&self, response: &DefaultStreamFetchResponse) -> bool {
+        if !self.seek_pending.load(Ordering::SeqCst) {
+            return true;
+        }
+        let batches = &response.partition.records.batches;
+        let Some(first) = batches.first() else {
+            return true;
+        };
+        let target = self.seek_target.load(Ordering::SeqCst);
+        let base = first.get_base_offset();
+        let next = response.partition.next_offset_for_fetch().unwrap_or(base);
+        let contains_target = base <= target && target < next;
+        if !contains_target && base == self.stale_base.load(Ordering::SeqCst) {
+            debug!(base, target, "dropping response fetched before seek");
+            return false;
+        }
+        self.seek_pending.store(false, Ordering::SeqCst);
+        true
+    }
+
+    #[cfg(test)]
+    fn is_seek_pending(&self) -> bool {
+        self.seek_pending.load(Ordering::SeqCst)
+    }
+}
+
+/// Controls of the partition streams of a consumer, kept across reconnects
+#[derive(Debug, Default, Clone)]
+pub(crate) struct ConsumerControls {
+    partitions: Arc<Mutex<HashMap<ReplicaKey, Arc<PartitionControl>>>>,
+}
+
+impl ConsumerControls {
+    /// Registers control of a new partition stream, which stays paused if it was
+    pub(crate) fn register(&self, replica: ReplicaKey, control: Arc<PartitionControl>) {
+        let mut partitions = self.partitions.lock();
+        if partitions
+            .get(&replica)
+            .is_some_and(|previous| previous.is_paused())
+        {
+            control.pause();
+        }
+        partitions.insert(replica, control);
+    }
+
+    pub(crate) fn remove(&self, replica: &ReplicaKey) {
+        self.partitions.lock().remove(replica);
+    }
+
+    fn get(&self, replica: &ReplicaKey) -> Result<Arc<PartitionControl>> {
+        self.partitions.lock().get(replica).cloned().ok_or_else(|| {
+            FluvioError::PartitionNotFound(replica.topic.clone(), replica.partition).into()
+        })
+    }
+}
+
+/// Handle to pause, resume and seek partitions of a live consumer stream.
+///
+/// The handle stays valid when the stream reconnects, paused partitions stay paused.
+#[derive(Clone)]
+pub struct ConsumerHandle {
+    controls: ConsumerControls,
+    pool: Arc<SpuSocketPool>,
+}
+
+impl ConsumerHandle {
+    pub(crate) fn new(controls: ConsumerControls, pool: Arc<SpuSocketPool>) -> Self {
+        Self { controls, pool }
+    }
+
+    /// Stops reading records from the partition, other partitions are not affected
+    pub fn pause(&self, topic: impl Into<String>, partition: PartitionId) -> Result<()> {
+        self.controls
+            .get(&ReplicaKey::new(topic, partition))?
+            .pause();
+        Ok(())
+    }
+
+    /// Continues reading records from the partition
+    pub fn resume(&self, topic: impl Into<String>, partition: PartitionId) -> Result<()> {
+        self.controls
+            .get(&ReplicaKey::new(topic, partition))?
+            .resume();
+        Ok(())
+    }
+
+    pub fn is_paused(&self, topic: impl Into<String>, partition: PartitionId) -> Result<bool> {
+        Ok(self
+            .controls
+            .get(&ReplicaKey::new(topic, partition))?
+            .is_paused())
+    }
+
+    /// Partitions currently read by the stream
+    pub fn partitions(&self) -> Vec<ReplicaKey> {
+        let mut partitions: Vec<_> = self.controls.partitions.lock().keys().cloned().collect();
+        partitions.sort();
+        partitions
+    }
+
+    /// Moves the partition to the given offset. The next record read from the partition
+    /// is the record at the offset. Records of other partitions are not affected.
+    pub async fn seek(
+        &self,
+        topic: impl Into<String>,
+        partition: PartitionId,
+        offset: Offset,
+    ) -> Result<()> {
+        let replica = ReplicaKey::new(topic, partition);
+        let control = self.controls.get(&replica)?;
+        let mut socket = self.pool.create_serial_socket(&replica).await?;
+        let offsets = fetch_offsets(&mut socket, &replica).await?;
+        let offset = offset.resolve(&offsets, None).await?;
+        debug!(%replica, offset, "seek partition");
+        control.seek(offset).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::record::{Batch, RecordSet};
+
+    use super::*;
+
+    fn response(base_offset: i64, records: usize) -> DefaultStreamFetchResponse {
+        let mut batch = Batch::default();
+        for _ in 0..records {
+            batch.add_record(fluvio_protocol::record::Record::new("x"));
+        }
+        batch.set_base_offset(base_offset);
+        let mut response = DefaultStreamFetchResponse::default();
+        response.partition.records = RecordSet::default().add(batch).try_into().expect("raw");
+        response
+    }
+
+    #[fluvio_future::test]
+    async fn test_seek_drops_response_fetched_before_seek() {
+        //given
+        let control = PartitionControl::default();
+        let (tx, rx) = async_channel::unbounded();
+        control.set_stream_to_server(tx);
+        control.reported(20);
+
+        //when
+        control.seek(5).await.expect("seek");
+
+        //then
+        assert!(matches!(
+            rx.recv().await,
+            Ok(StreamToServer::UpdateOffset(5))
+        ));
+        assert!(control.skip_record(21));
+        assert!(!control.accept_response(&response(20, 10)));
+        assert!(control.is_seek_pending());
+        assert!(control.accept_response(&response(0, 10)));
+        assert!(!control.is_seek_pending());
+        assert!(control.skip_record(4));
+        assert!(!control.skip_record(5));
+    }
+
+    #[fluvio_future::test]
+    async fn test_seek_forward_within_response_in_flight() {
+        let control = PartitionControl::default();
+        let (tx, _rx) = async_channel::unbounded();
+        control.set_stream_to_server(tx);
+        control.reported(20);
+
+        control.seek(25).await.expect("seek");
+
+        assert!(control.accept_response(&response(20, 10)));
+        assert!(control.skip_record(24));
+        assert!(!control.skip_record(25));
+    }
+
+    #[fluvio_future::test]
+    async fn test_next_offset_after_seek() {
+        let control = PartitionControl::default();
+        assert_eq!(control.next_offset(), None);
+        let (tx, _rx) = async_channel::unbounded();
+        control.set_stream_to_server(tx);
+        control.reported(20);
+        assert_eq!(control.next_offset(), Some(20));
+
+        // stream moved to another replica continues from the seek offset
+        control.seek(5).await.expect("seek");
+        assert_eq!(control.next_offset(), Some(5));
+    }
+
+    #[test]
+    fn test_controls_keep_pause_across_reconnect() {
+        let controls = ConsumerControls::default();
+        let replica = ReplicaKey::new("topic", 0u32);
+        let control = Arc::new(PartitionControl::default());
+        controls.register(replica.clone(), control.clone());
+        controls.get(&replica).expect("registered").pause();
+        assert!(control.is_paused());
+
+        let reconnected = Arc::new(PartitionControl::default());
+        controls.register(replica.clone(), reconnected.clone());
+
+        assert!(reconnected.is_paused());
+        controls.remove(&replica);
+        assert!(controls.get(&replica).is_err());
+    }
+}
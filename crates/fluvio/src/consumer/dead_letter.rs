@@ -0,0 +1,351 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::time::Duration;
+
+use anyhow::Result;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use fluvio_future::timer::sleep;
+use fluvio_protocol::link::smartmodule::SmartModuleTransformRuntimeError;
+use fluvio_protocol::record::{ConsumerRecord, RecordKey};
+use fluvio_types::{PartitionId, Timestamp};
+
+use crate::{Fluvio, TopicProducerPool};
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+const DEFAULT_BACKOFF: Duration = Duration::from_millis(100);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Configures how many times a failed record is tried before it is published
+/// to the dead-letter topic and the consumer moves on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadLetterPolicy {
+    /// Topic the failed records are published to
+    pub topic: String,
+    /// Attempts before the record is dead-lettered, including the first one
+    pub max_attempts: u32,
+    /// Wait after the first failed attempt, doubled after every next one
+    pub backoff: Duration,
+}
+
+impl DeadLetterPolicy {
+    pub fn new(topic: impl Into<String>) -> Self {
+        Self {
+            topic: topic.into(),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            backoff: DEFAULT_BACKOFF,
+        }
+    }
+
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Wait after the given failed attempt, starting from 1
+    pub(crate) fn backoff_after(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.backoff
+            .checked_mul(factor)
+            .map_or(MAX_BACKOFF, |backoff| backoff.min(MAX_BACKOFF))
+    }
+}
+
+/// Record published to the dead-letter topic, encoded as JSON.
+///
+/// Carries where the failed record comes from and why it failed.
+/// Key and value of the failed record are base64 encoded.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadLetterRecord {
+    /// `None` if the failure can't be tied to a topic, for example a SmartModule
+    /// error of a topic pattern consumer
+    pub topic: Option<String>,
+    /// `None` if the failure can't be tied to a partition, for example a SmartModule
+    /// error of a consumer reading several partitions
+    pub partition: Option<PartitionId>,
+    pub offset: i64,
+    pub timestamp: Timestamp,
+    pub error: String,
+    pub attempts: u32,
+    pub key: Option<String>,
+    pub value: String,
+}
+
+impl DeadLetterRecord {
+    pub fn from_record(record: &ConsumerRecord, error: impl Display, attempts: u32) -> Self {
+        Self {
+            topic: record.topic().map(str::to_owned),
+            partition: Some(record.partition),
+            offset: record.offset,
+            timestamp: record.timestamp(),
+            error: error.to_string(),
+            attempts,
+            key: record.key().map(|key| STANDARD.encode(key)),
+            value: STANDARD.encode(record.value()),
+        }
+    }
+
+    pub fn from_runtime_error(
+        topic: Option<String>,
+        partition: Option<PartitionId>,
+        error: &SmartModuleTransformRuntimeError,
+        attempts: u32,
+    ) -> Self {
+        Self {
+            topic,
+            partition,
+            offset: error.offset,
+            timestamp: -1,
+            error: error.to_string(),
+            attempts,
+            key: error
+                .record_key
+                .as_ref()
+                .map(|key| STANDARD.encode(key.as_ref())),
+            value: STANDARD.encode(error.record_value.as_ref()),
+        }
+    }
+
+    pub fn decode_key(&self) -> Result<Option<Vec<u8>>> {
+        Ok(self
+            .key
+            .as_ref()
+            .map(|key| STANDARD.decode(key))
+            .transpose()?)
+    }
+
+    pub fn decode_value(&self) -> Result<Vec<u8>> {
+        Ok(STANDARD.decode(&self.value)?)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+/// Publishes failed records to the dead-letter topic of a [`DeadLetterPolicy`]
+#[derive(Clone)]
+pub struct DeadLetterQueue {
+    policy: DeadLetterPolicy,
+    producer: TopicProducerPool,
+}
+
+impl DeadLetterQueue {
+    pub async fn new(fluvio: &Fluvio, policy: DeadLetterPolicy) -> Result<Self> {
+        let producer = fluvio.topic_producer(policy.topic.clone()).await?;
+        Ok(Self { policy, producer })
+    }
+
+    pub fn policy(&self) -> &DeadLetterPolicy {
+        &self.policy
+    }
+
+    /// Publishes the record and waits until it is stored.
+    /// The key of the failed record is kept, so its records land in the same partition.
+    pub async fn send(&self, record: &DeadLetterRecord) -> Result<()> {
+        let key = record
+            .decode_key()?
+            .map(RecordKey::from)
+            .unwrap_or(RecordKey::NULL);
+        info!(
+            topic = ?record.topic,
+            partition = ?record.partition,
+            offset = record.offset,
+            dead_letter_topic = %self.policy.topic,
+            error = %record.error,
+            "publishing record to dead-letter topic"
+        );
+        self.producer
+            .send(key, record.to_bytes()?)
+            .await?
+            .wait()
+            .await?;
+        Ok(())
+    }
+
+    /// Runs `process` with the record until it succeeds or the attempts of the policy
+    /// are used up, waiting the policy backoff in between. A record that still fails
+    /// is published to the dead-letter topic and `None` is returned, so the caller
+    /// can move on to the next record.
+    pub async fn process<F, Fut, T, E>(
+        &self,
+        record: &ConsumerRecord,
+        process: F,
+    ) -> Result<Option<T>>
+    where
+        F: FnMut(&ConsumerRecord) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        E: Display,
+    {
+        match with_attempts(&self.policy, record, process).await {
+            Ok(value) => Ok(Some(value)),
+            Err((err, attempts)) => {
+                self.send(&DeadLetterRecord::from_record(record, err, attempts))
+                    .await?;
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Returns the last error and the number of attempts if every attempt failed
+async fn with_attempts<F, Fut, T, E>(
+    policy: &DeadLetterPolicy,
+    record: &ConsumerRecord,
+    mut process: F,
+) -> Result<T, (E, u32)>
+where
+    F: FnMut(&ConsumerRecord) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: Display,
+{
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        match process(record).await {
+            Ok(value) => return Ok(value),
+            Err(err) if attempts >= policy.max_attempts => return Err((err, attempts)),
+            Err(err) => {
+                let backoff = policy.backoff_after(attempts);
+                warn!(
+                    partition = record.partition,
+                    offset = record.offset,
+                    attempts,
+                    %err,
+                    "failed to process record, retrying in {backoff:?}"
+                );
+                sleep(backoff).await;
+            }
+        }
+    }
+}
+
+/// Tracks failed attempts of the record a consumer stream is stuck on
+#[derive(Debug, Default, Clone)]
+pub(crate) struct FailedAttempts {
+    offset: Option<i64>,
+    attempts: u32,
+}
+
+impl FailedAttempts {
+    /// Counts a failure at the offset and returns the attempts so far
+    pub(crate) fn fail(&mut self, offset: i64) -> u32 {
+        if self.offset != Some(offset) {
+            self.offset = Some(offset);
+            self.attempts = 0;
+        }
+        self.attempts += 1;
+        self.attempts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use fluvio_protocol::record::{Batch, Record, RecordData};
+
+    use super::*;
+
+    fn record(key: &'static str, value: &'static str) -> ConsumerRecord {
+        let mut batch = Batch::default();
+        batch.add_record(Record::from((
+            RecordKey::from(key),
+            RecordData::from(value),
+        )));
+        batch.set_base_offset(7);
+        let mut record = batch.into_consumer_records_iter(2).next().expect("record");
+        record.set_topic("orders".into());
+        record
+    }
+
+    #[test]
+    fn test_backoff_is_doubled_and_capped() {
+        let policy = DeadLetterPolicy::new("dlq").backoff(Duration::from_millis(100));
+
+        assert_eq!(policy.backoff_after(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff_after(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff_after(4), Duration::from_millis(800));
+        assert_eq!(policy.backoff_after(40), MAX_BACKOFF);
+    }
+
+    #[test]
+    fn test_dead_letter_record_roundtrip() {
+        //given
+        let record = record("key", "value");
+
+        //when
+        let dead_letter = DeadLetterRecord::from_record(&record, "sink is down", 3);
+        let decoded =
+            DeadLetterRecord::from_bytes(&dead_letter.to_bytes().expect("encode")).expect("decode");
+
+        //then
+        assert_eq!(decoded, dead_letter);
+        assert_eq!(decoded.topic.as_deref(), Some("orders"));
+        assert_eq!(decoded.partition, Some(2));
+        assert_eq!(decoded.offset, 7);
+        assert_eq!(decoded.error, "sink is down");
+        assert_eq!(decoded.attempts, 3);
+        assert_eq!(
+            decoded.decode_key().expect("key").as_deref(),
+            Some(b"key".as_ref())
+        );
+        assert_eq!(decoded.decode_value().expect("value"), b"value");
+    }
+
+    #[fluvio_future::test]
+    async fn test_with_attempts_stops_after_max_attempts() {
+        let policy = DeadLetterPolicy::new("dlq")
+            .max_attempts(3)
+            .backoff(Duration::from_millis(1));
+        let record = record("key", "value");
+        let calls = AtomicU32::new(0);
+
+        let result: Result<(), (String, u32)> = with_attempts(&policy, &record, |_| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Err("failed".to_owned()) }
+        })
+        .await;
+
+        assert_eq!(result, Err(("failed".to_owned(), 3)));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[fluvio_future::test]
+    async fn test_with_attempts_succeeds_after_retry() {
+        let policy = DeadLetterPolicy::new("dlq").backoff(Duration::from_millis(1));
+        let record = record("key", "value");
+        let calls = AtomicU32::new(0);
+
+        let result = with_attempts(&policy, &record, |record| {
+            let call = calls.fetch_add(1, Ordering::SeqCst);
+            let offset = record.offset;
+            async move { if call == 0 { Err("failed") } else { Ok(offset) } }
+        })
+        .await;
+
+        assert_eq!(result, Ok(7));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_failed_attempts_reset_on_new_offset() {
+        let mut failed = FailedAttempts::default();
+
+        assert_eq!(failed.fail(5), 1);
+        assert_eq!(failed.fail(5), 2);
+        assert_eq!(failed.fail(6), 1);
+    }
+}
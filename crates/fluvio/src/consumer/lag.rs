@@ -0,0 +1,189 @@
+use anyhow::Result;
+use fluvio_types::PartitionId;
+use serde::Serialize;
+
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_spu_schema::server::fetch_offset::{
+    FetchOffsetPartitionResponse, FetchOffsetsRequest, CONSUMER_LAG_API,
+};
+
+use crate::offset::fetch_offsets_with_timestamp;
+use crate::spu::{SpuDirectory, SpuSocketPool};
+
+use super::ConsumerOffset;
+
+/// How far a consumer is behind the end of a partition
+#[derive(Debug, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ConsumerLag {
+    pub consumer_id: String,
+    pub topic: String,
+    pub partition: PartitionId,
+    /// last offset stored by the consumer
+    pub offset: i64,
+    /// end of committed records
+    pub high_watermark: i64,
+    /// end of all records, including not committed yet. Equal to high watermark for older SPUs.
+    pub log_end_offset: i64,
+    /// committed records the consumer has not read yet
+    pub lag: i64,
+    /// time between the next record to read and the last committed record, in milliseconds.
+    /// `None` if the batches have no timestamps.
+    pub time_lag_ms: Option<u64>,
+}
+
+/// Lag of a consumer offset could not be fetched, e.g. leader of the partition is offline
+#[derive(thiserror::Error, Debug, Serialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[error("consumer {consumer_id} on {topic}-{partition}: {error}")]
+pub struct ConsumerLagError {
+    pub consumer_id: String,
+    pub topic: String,
+    pub partition: PartitionId,
+    pub error: String,
+}
+
+impl ConsumerLagError {
+    fn new(consumer: ConsumerOffset, error: anyhow::Error) -> Self {
+        Self {
+            consumer_id: consumer.consumer_id,
+            topic: consumer.topic,
+            partition: consumer.partition,
+            error: error.to_string(),
+        }
+    }
+}
+
+impl ConsumerLag {
+    pub(crate) fn new(consumer: ConsumerOffset, offsets: &FetchOffsetPartitionResponse) -> Self {
+        let high_watermark = offsets.last_stable_offset;
+        // records below start offset are deleted, they can't be read anymore
+        let next = (consumer.offset + 1).max(offsets.start_offset);
+        let lag = (high_watermark - next).max(0);
+        let time_lag_ms = if lag == 0 {
+            Some(0)
+        } else {
+            match (offsets.offset_timestamp, offsets.last_timestamp) {
+                (Some(first), Some(last)) if first >= 0 && last >= 0 => {
+                    Some((last - first).max(0) as u64)
+                }
+                _ => None,
+            }
+        };
+        Self {
+            consumer_id: consumer.consumer_id,
+            topic: consumer.topic,
+            partition: consumer.partition,
+            offset: consumer.offset,
+            high_watermark,
+            log_end_offset: offsets.log_end_offset.max(high_watermark),
+            lag,
+            time_lag_ms,
+        }
+    }
+
+    /// Fetches end offsets of the consumer partition from its leader
+    pub(crate) async fn fetch(
+        consumer: ConsumerOffset,
+        pool: &SpuSocketPool,
+    ) -> Result<Self, ConsumerLagError> {
+        match Self::fetch_offsets(&consumer, pool).await {
+            Ok(offsets) => Ok(Self::new(consumer, &offsets)),
+            Err(err) => Err(ConsumerLagError::new(consumer, err)),
+        }
+    }
+
+    async fn fetch_offsets(
+        consumer: &ConsumerOffset,
+        pool: &SpuSocketPool,
+    ) -> Result<FetchOffsetPartitionResponse> {
+        let replica = ReplicaKey::new(consumer.topic.clone(), consumer.partition);
+        let mut socket = pool.create_serial_socket(&replica).await?;
+        let mut offsets =
+            fetch_offsets_with_timestamp(&mut socket, &replica, Some(consumer.offset + 1)).await?;
+        if offsets.error_code.is_error() {
+            return Err(offsets.error_code.into());
+        }
+        if socket
+            .lookup_version::<FetchOffsetsRequest>()
+            .is_none_or(|version| version < CONSUMER_LAG_API)
+        {
+            offsets.log_end_offset = offsets.last_stable_offset;
+        }
+        Ok(offsets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn consumer(offset: i64) -> ConsumerOffset {
+        ConsumerOffset {
+            consumer_id: "consumer".to_owned(),
+            topic: "topic".to_owned(),
+            partition: 0,
+            offset,
+            modified_time: 0,
+        }
+    }
+
+    fn offsets(start_offset: i64, hw: i64, leo: i64) -> FetchOffsetPartitionResponse {
+        FetchOffsetPartitionResponse {
+            start_offset,
+            last_stable_offset: hw,
+            log_end_offset: leo,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_consumer_lag() {
+        let mut end = offsets(0, 10, 12);
+        end.offset_timestamp = Some(1_000);
+        end.last_timestamp = Some(3_500);
+
+        let lag = ConsumerLag::new(consumer(4), &end);
+
+        assert_eq!(lag.lag, 5);
+        assert_eq!(lag.high_watermark, 10);
+        assert_eq!(lag.log_end_offset, 12);
+        assert_eq!(lag.time_lag_ms, Some(2_500));
+    }
+
+    #[test]
+    fn test_consumer_lag_caught_up() {
+        let lag = ConsumerLag::new(consumer(9), &offsets(0, 10, 10));
+
+        assert_eq!(lag.lag, 0);
+        assert_eq!(lag.time_lag_ms, Some(0));
+    }
+
+    #[test]
+    fn test_consumer_lag_without_timestamps() {
+        let mut end = offsets(0, 10, 10);
+        end.offset_timestamp = Some(-1);
+        end.last_timestamp = Some(3_500);
+
+        let lag = ConsumerLag::new(consumer(0), &end);
+
+        assert_eq!(lag.lag, 9);
+        assert_eq!(lag.time_lag_ms, None);
+    }
+
+    #[test]
+    fn test_consumer_lag_after_deleted_records() {
+        let lag = ConsumerLag::new(consumer(2), &offsets(6, 10, 10));
+
+        assert_eq!(lag.lag, 4);
+    }
+
+    #[test]
+    fn test_consumer_lag_error() {
+        let error = ConsumerLagError::new(consumer(2), anyhow::anyhow!("Spu not found: 5001"));
+
+        assert_eq!(error.partition, 0);
+        assert_eq!(
+            error.to_string(),
+            "consumer consumer on topic-0: Spu not found: 5001"
+        );
+    }
+}
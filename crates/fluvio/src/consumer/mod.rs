@@ -1,13 +1,24 @@
 #![allow(dead_code)]
 
 mod config;
+mod control;
+#[cfg(feature = "dead-letter")]
+mod dead_letter;
+mod lag;
 mod stream;
 mod offset;
+mod pattern;
 mod retry;
 
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
 
 use adaptive_backoff::prelude::{
     Backoff, BackoffBuilder, ExponentialBackoff, ExponentialBackoffBuilder,
@@ -16,35 +27,39 @@ use anyhow::Result;
 use async_channel::Sender;
 use async_lock::Mutex;
 use fluvio_future::timer::sleep;
-use fluvio_socket::VersionedSerialSocket;
+use fluvio_socket::{AsyncResponse, VersionedSerialSocket};
 use fluvio_spu_schema::server::consumer_offset::{
     FetchConsumerOffsetsRequest, UpdateConsumerOffsetRequest,
 };
 use tracing::{debug, error, trace, instrument, info, warn};
-use futures_util::stream::{Stream, select_all};
+use futures_util::stream::{BoxStream, Stream, select_all};
 use once_cell::sync::Lazy;
 use futures_util::future::{Either, err, try_join_all};
 use futures_util::stream::{StreamExt, once, iter};
 use futures_util::FutureExt;
 
-use fluvio_types::PartitionId;
+use fluvio_types::{PartitionId, SpuId};
 use fluvio_types::defaults::{
     CONSUMER_REPLICA_KEY, FLUVIO_CLIENT_MAX_FETCH_BYTES, FLUVIO_MAX_SIZE_TOPIC_NAME,
     RECONNECT_BACKOFF_FACTOR, RECONNECT_BACKOFF_MAX_DURATION, RECONNECT_BACKOFF_MIN_DURATION,
 };
 use fluvio_spu_schema::server::stream_fetch::{
     DefaultStreamFetchRequest, DefaultStreamFetchResponse, CHAIN_SMARTMODULE_API,
-    OFFSET_MANAGEMENT_API,
+    FOLLOWER_FETCH_API, OFFSET_MANAGEMENT_API,
 };
+use fluvio_spu_schema::Isolation;
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::link::ErrorCode;
-use fluvio_protocol::record::Batch;
+use fluvio_protocol::record::{Batch, RawRecords, NO_TIMESTAMP};
 
 use crate::FluvioError;
-use crate::metrics::ClientMetrics;
+use crate::dictionary::SharedDictionaryLoader;
+use crate::metrics::{ClientMetrics, FetchRoundTrip, PartitionLatency};
 use crate::offset::{Offset, fetch_offsets};
 use crate::spu::{SpuDirectory, SpuSocketPool};
 
+use control::PartitionControl;
+
 pub use config::{ConsumerConfig, ConsumerConfigBuilder};
 pub use config::{ConsumerConfigExt, ConsumerConfigExtBuilder, OffsetManagementStrategy, RetryMode};
 pub use stream::{
@@ -52,6 +67,16 @@ pub use stream::{
     ConsumerBoxFuture,
 };
 pub use offset::ConsumerOffset;
+pub use lag::{ConsumerLag, ConsumerLagError};
+pub use control::ConsumerHandle;
+#[cfg(feature = "dead-letter")]
+pub use dead_letter::{DeadLetterPolicy, DeadLetterQueue, DeadLetterRecord};
+#[cfg(feature = "dead-letter")]
+pub(crate) use dead_letter::FailedAttempts;
+pub(crate) use control::ConsumerControls;
+pub(crate) use config::topic_pattern_regex;
+pub(crate) use pattern::TopicPatternWatcher;
+pub(crate) use stream::SubscriptionChange;
 pub use retry::ConsumerRetryStream;
 pub use fluvio_protocol::record::ConsumerRecord;
 
@@ -63,8 +88,12 @@ pub use fluvio_spu_schema::server::smartmodule::SmartModuleContextData;
 pub use fluvio_smartmodule::dataplane::smartmodule::SmartModuleExtraParams;
 
 const STREAM_TO_SERVER_CHANNEL_SIZE: usize = 100;
+/// how often follower serving a stream is checked to be in sync with leader
+const FOLLOWER_LAG_CHECK_INTERVAL: Duration = Duration::from_secs(5);
 const MAX_ATTEMPTS_CONSUMER_OFFSET: usize = 30;
 
+type FetchResponseStream = BoxStream<'static, Result<DefaultStreamFetchResponse, ErrorCode>>;
+
 /// Type alias for the consumer record stream.
 #[cfg(target_arch = "wasm32")]
 pub type BoxConsumerStream =
@@ -98,6 +127,7 @@ pub struct PartitionConsumer<P = SpuSocketPool> {
     partition: PartitionId,
     pool: Arc<P>,
     metrics: Arc<ClientMetrics>,
+    dictionaries: Option<SharedDictionaryLoader>,
 }
 
 // Manually implement Clone because the derive macro would require the
@@ -109,13 +139,14 @@ impl<P> Clone for PartitionConsumer<P> {
             partition: self.partition,
             pool: self.pool.clone(),
             metrics: self.metrics.clone(),
+            dictionaries: self.dictionaries.clone(),
         }
     }
 }
 
 impl<P> PartitionConsumer<P>
 where
-    P: SpuDirectory + 'static,
+    P: SpuDirectory + Send + Sync + 'static,
 {
     pub fn new(
         topic: String,
@@ -128,9 +159,16 @@ where
             partition,
             pool,
             metrics,
+            dictionaries: None,
         }
     }
 
+    /// load compression dictionaries which are not registered yet when they show up in the stream
+    pub(crate) fn with_dictionaries(mut self, dictionaries: SharedDictionaryLoader) -> Self {
+        self.dictionaries = Some(dictionaries);
+        self
+    }
+
     /// Returns the name of the Topic that this consumer reads from
     pub fn topic(&self) -> &str {
         &self.topic
@@ -146,6 +184,13 @@ where
         self.metrics.clone()
     }
 
+    /// latency histograms of the partition
+    fn latency(&self) -> Arc<PartitionLatency> {
+        self.metrics
+            .latency()
+            .partition(&self.topic, self.partition)
+    }
+
     /// Continuously streams events from a particular offset in the consumer's partition
     ///
     /// Streaming is one of the two ways to consume events in Fluvio.
@@ -246,18 +291,24 @@ where
         offset: Offset,
         config: ConsumerConfig,
     ) -> Result<impl Stream<Item = Result<Record, ErrorCode>> + use<P>> {
+        let interceptors = config.interceptors.clone();
         let (stream, start_offset, _) = self
-            .inner_stream_batches_with_config(offset, config, None)
+            .inner_stream_batches_with_config(offset, config, None, Default::default())
             .await?;
         let partition = self.partition;
+        let topic: Arc<str> = self.topic.as_str().into();
         let flattened = stream.flat_map(move |result: Result<Batch, _>| match result {
             Err(e) => Either::Right(once(err(e))),
             Ok(batch) => {
+                let interceptors = interceptors.clone();
+                let topic = topic.clone();
                 let records =
                     batch
                         .into_consumer_records_iter(partition)
-                        .filter_map(move |record| {
+                        .filter_map(move |mut record| {
                             if record.offset >= start_offset {
+                                record.set_topic(topic.clone());
+                                interceptors.on_consume(&topic, &mut record);
                                 Some(Ok(record))
                             } else {
                                 None
@@ -302,27 +353,86 @@ where
         config: ConsumerConfig,
     ) -> Result<impl Stream<Item = Result<Batch, ErrorCode>> + use<P>> {
         let (stream, _start_offset, _) = self
-            .inner_stream_batches_with_config(offset, config, None)
+            .inner_stream_batches_with_config(offset, config, None, Default::default())
             .await?;
         Ok(stream)
     }
 
+    /// Continuously streams batches as they are stored by the SPU, starting an offset in the consumer's partition.
+    /// Records are not decompressed, only the crc of each batch is validated.
+    #[instrument(skip(self, offset, config))]
+    pub(crate) async fn stream_raw_batches_with_config(
+        &self,
+        offset: Offset,
+        config: ConsumerConfig,
+    ) -> Result<impl Stream<Item = Result<Batch<RawRecords>, ErrorCode>> + use<P>> {
+        let (stream, _start_offset, _) = self
+            .request_stream(offset, config, None, Default::default())
+            .await?;
+        let flattened = stream.flat_map(|batch_result: Result<DefaultStreamFetchResponse, _>| {
+            let response = match batch_result {
+                Ok(response) => response,
+                Err(e) => return Either::Right(once(err(e))),
+            };
+
+            let batches = response
+                .partition
+                .records
+                .batches
+                .into_iter()
+                .map(|raw_batch| {
+                    if let Err(err) = raw_batch.validate_crc() {
+                        tracing::error!(%err, "received corrupt batch");
+                        return Err(ErrorCode::CorruptRecordBatch);
+                    }
+                    Ok(raw_batch)
+                });
+            let error = match response.partition.error_code {
+                ErrorCode::None => None,
+                code => Some(Err(code)),
+            };
+
+            Either::Left(iter(batches.chain(error)))
+        });
+
+        Ok(flattened)
+    }
+
     /// Continuously streams batches of messages, starting an offset in the consumer's partition
     /// Returns both the stream and the start offset of the stream.
-    #[instrument(skip(self, offset, config))]
+    #[instrument(skip(self, offset, config, control))]
     async fn inner_stream_batches_with_config(
         &self,
         offset: Offset,
         config: ConsumerConfig,
         consumer_id: Option<String>,
+        control: Arc<PartitionControl>,
     ) -> Result<(
         impl Stream<Item = Result<Batch, ErrorCode>> + use<P>,
         fluvio_protocol::record::Offset,
         Sender<StreamToServer>,
     )> {
-        let (stream, start_offset, stream_to_server) =
-            self.request_stream(offset, config, consumer_id).await?;
+        let (stream, start_offset, stream_to_server) = self
+            .request_stream(offset, config, consumer_id, control)
+            .await?;
+        let stream = match self.dictionaries.clone() {
+            Some(dictionaries) => stream
+                .then(move |response| {
+                    let dictionaries = dictionaries.clone();
+                    async move {
+                        if let Ok(response) = &response {
+                            dictionaries
+                                .register_missing(&response.partition.records.batches)
+                                .await;
+                        }
+                        response
+                    }
+                })
+                .boxed(),
+            None => stream.boxed(),
+        };
         let metrics = self.metrics.clone();
+        let latency = self.latency();
         let flattened =
             stream.flat_map(move |batch_result: Result<DefaultStreamFetchResponse, _>| {
                 let response = match batch_result {
@@ -336,6 +446,7 @@ where
                 // processed before hitting an error, so that the error does not obscure those records.
 
                 let inner_metrics = metrics.clone();
+                let inner_latency = latency.clone();
                 let batches =
                     response
                         .partition
@@ -350,9 +461,17 @@ where
                                 .consumer()
                                 .add_bytes(raw_batch.batch_len() as u64);
 
+                            if let Err(err) = raw_batch.validate_crc() {
+                                tracing::error!(%err, "received corrupt batch");
+                                return Err(ErrorCode::CorruptRecordBatch);
+                            }
+
                             let batch: Result<Batch, _> = raw_batch.try_into();
                             match batch {
-                                Ok(batch) => Ok(batch),
+                                Ok(batch) => {
+                                    observe_delivery_delay(&inner_latency, &batch);
+                                    Ok(batch)
+                                }
                                 Err(err) => {
                                     tracing::error!("{err:?}");
                                     Err(ErrorCode::Other(err.to_string()))
@@ -378,20 +497,18 @@ where
     /// on the internal structure of the fetch response. New clients should use the
     /// `stream` and `stream_with_config` methods.
     /// Returns both the stream and the start offset of the stream.
-    #[instrument(skip(self, config))]
+    #[instrument(skip(self, config, control))]
     async fn request_stream(
         &self,
         offset: Offset,
         config: ConsumerConfig,
         consumer_id: Option<String>,
+        control: Arc<PartitionControl>,
     ) -> Result<(
         impl Stream<Item = Result<DefaultStreamFetchResponse, ErrorCode>> + use<P>,
         fluvio_protocol::record::Offset,
         Sender<StreamToServer>,
     )> {
-        use fluvio_future::task::spawn;
-        use futures_util::stream::empty;
-
         let replica = ReplicaKey::new(&self.topic, self.partition);
         let mut serial_socket = self.pool.create_serial_socket(&replica).await?;
 
@@ -417,7 +534,12 @@ where
             None
         };
 
+        let latency = self.latency();
+        let fetch_started = Instant::now();
         let offsets = fetch_offsets(&mut serial_socket, &replica).await?;
+        latency
+            .offset_round_trip()
+            .observe_duration(fetch_started.elapsed());
 
         let start_absolute_offset = offset.resolve(&offsets, consumer_offset).await?;
         let end_absolute_offset = offsets.last_stable_offset;
@@ -426,15 +548,18 @@ where
         debug!(start_absolute_offset, end_absolute_offset, record_count);
 
         let with_consumer_id = consumer_id.is_some();
-        let stream_request = DefaultStreamFetchRequest::builder()
-            .topic(self.topic.to_owned())
-            .partition(self.partition)
-            .fetch_offset(start_absolute_offset)
-            .isolation(config.isolation)
-            .max_bytes(config.max_bytes)
-            .smartmodules(config.smartmodule)
-            .consumer_id(consumer_id)
-            .build()?;
+        let stream_request = |read_from_follower: bool| {
+            DefaultStreamFetchRequest::builder()
+                .topic(self.topic.to_owned())
+                .partition(self.partition)
+                .fetch_offset(start_absolute_offset)
+                .isolation(config.isolation)
+                .max_bytes(config.max_bytes)
+                .smartmodules(config.smartmodule.clone())
+                .consumer_id(consumer_id.clone())
+                .read_from_follower(read_from_follower)
+                .build()
+        };
 
         let stream_fetch_version = serial_socket
             .versions()
@@ -450,126 +575,78 @@ where
             warn!("SPU does not support Offset Management API");
         }
 
-        let mut stream = self
-            .pool
-            .create_stream_with_version(&replica, stream_request, stream_fetch_version)
-            .await?;
-
-        let (server_sender, server_recv) =
-            async_channel::bounded::<StreamToServer>(STREAM_TO_SERVER_CHANNEL_SIZE);
-
-        let server_sender_clone = server_sender.clone();
-
-        let ft_stream = async move {
-            if let Some(Ok(raw_response)) = stream.next().await {
-                let response: DefaultStreamFetchResponse = raw_response;
-
-                let stream_id = response.stream_id;
-
-                trace!("first stream response: {:#?}", response);
-                debug!(
-                    stream_id,
-                    last_offset = ?response.partition.next_offset_for_fetch(),
-                    "first stream response"
-                );
-
-                // update stream with received offsets
-                spawn(async move {
-                    use fluvio_spu_schema::server::update_offset::{UpdateOffsetsRequest, OffsetUpdate};
-
-                    loop {
-                        match server_recv.recv().await {
-                            Ok(StreamToServer::UpdateOffset(fetch_last_value)) => {
-                                debug!(fetch_last_value, stream_id, "received end fetch");
-                                debug!(
-                                    offset = fetch_last_value,
-                                    session_id = stream_id,
-                                    "sending back offset to spu"
-                                );
-                                let request = UpdateOffsetsRequest {
-                                    offsets: vec![OffsetUpdate {
-                                        offset: fetch_last_value,
-                                        session_id: stream_id,
-                                    }],
-                                };
-                                debug!(?request, "Sending offset update request:");
-                                let response = serial_socket.send_receive(request).await;
-                                if let Err(err) = response {
-                                    error!("error sending offset: {:#?}", err);
-                                    break;
-                                }
-                            }
-                            Ok(StreamToServer::Close) => {
-                                debug!("fetch last is end, terminating");
-                                break;
-                            }
-                            Ok(StreamToServer::FlushManagedOffset { offset, callback }) => {
-                                debug!(offset, stream_id, "flush offset request");
-                                let request = UpdateConsumerOffsetRequest {
-                                    session_id: stream_id,
-                                    offset,
-                                };
-                                let response = serial_socket.send_receive(request).await;
-                                match response {
-                                    Ok(response) => callback.send(response.error_code).await,
-                                    Err(err) => {
-                                        error!("offset flush request error: {:?}", err);
-                                        callback
-                                            .send(ErrorCode::OffsetFlushRequestError(
-                                                err.to_string(),
-                                            ))
-                                            .await;
-                                        break;
-                                    }
-                                };
-                            }
-                            Err(err) => {
-                                debug!("stream to server channel closed: {err:?}");
-                                break;
-                            }
-                        }
-                    }
-                    debug!(stream_id, "offset fetch update loop end");
-                });
+        // follower stream moves to the leader once follower fails or falls behind
+        let follower = match follower_fetch_rack(&config, with_consumer_id, stream_fetch_version) {
+            Some(rack) => self
+                .pool
+                .find_follower_in_rack(&replica, rack)
+                .await
+                .unwrap_or_else(|err| {
+                    warn!(%err, rack, "failed to find follower in rack");
+                    None
+                }),
+            None => None,
+        };
 
-                // send back first offset records exists
-                if let Some(last_offset) = response.partition.next_offset_for_fetch() {
-                    debug!(last_offset, "notify new last offset");
-                    let _ = server_sender_clone
-                        .send(StreamToServer::UpdateOffset(last_offset))
-                        .await;
+        let follower_stream = match follower {
+            Some(follower) => match self
+                .pool
+                .create_follower_stream_with_version(
+                    follower,
+                    stream_request(true)?,
+                    stream_fetch_version,
+                )
+                .await
+            {
+                Ok((follower_socket, stream)) => {
+                    debug!(follower, "reading from follower");
+                    Some((follower, follower_socket, stream))
+                }
+                Err(err) => {
+                    warn!(%err, follower, "follower is not reachable, reading from leader");
+                    None
                 }
+            },
+            None => None,
+        };
 
-                let server_sender_clone2 = server_sender_clone.clone();
-                let update_stream = StreamExt::map(stream, move |item| {
-                    item.inspect(|response| {
-                        if let Some(last_offset) = response.partition.next_offset_for_fetch() {
-                            debug!(last_offset, stream_id, "received last offset from spu");
-                            let _ = server_sender_clone
-                                .try_send(StreamToServer::UpdateOffset(last_offset));
-                        }
-                    })
-                    .map_err(|e| {
-                        error!(?e, "error in stream");
-                        ErrorCode::Other(e.to_string())
-                    })
-                });
-                Either::Left(
-                    iter(vec![Ok(response)]).chain(publish_stream::EndPublishSt::new(
-                        update_stream,
-                        server_sender_clone2,
-                    )),
-                )
-            } else {
-                info!("stream ended");
-                Either::Right(empty())
+        let (stream, server_sender) = match follower_stream {
+            Some((follower, follower_socket, stream)) => {
+                // offset updates must go to the SPU which owns the stream
+                let (stream, server_sender) = fetch_response_stream(
+                    stream,
+                    follower_socket,
+                    control.clone(),
+                    latency.clone(),
+                );
+                let fallback = LeaderFallback {
+                    pool: self.pool.clone(),
+                    replica,
+                    request: stream_request(false)?,
+                    version: stream_fetch_version,
+                    control,
+                    latency,
+                    start_offset: start_absolute_offset,
+                };
+                (fallback.follow(follower, stream), server_sender)
+            }
+            None => {
+                let stream = self
+                    .pool
+                    .create_stream_with_version(
+                        &replica,
+                        stream_request(false)?,
+                        stream_fetch_version,
+                    )
+                    .await?;
+                fetch_response_stream(stream, serial_socket, control, latency)
             }
         };
 
         let stream = if config.disable_continuous {
-            TakeRecords::new(ft_stream.flatten_stream().boxed(), record_count).boxed()
+            TakeRecords::new(stream, record_count).boxed()
         } else {
-            ft_stream.flatten_stream().boxed()
+            stream
         };
 
         Ok((stream, start_absolute_offset, server_sender))
@@ -610,23 +687,30 @@ where
     {
         let (offset, config, consumer_id, strategy, flush_period, flusher_check_period) =
             config.into_parts();
+        let interceptors = config.interceptors.clone();
+        let control = Arc::new(PartitionControl::default());
         let (stream, start_offset, stream_to_server) = self
-            .inner_stream_batches_with_config(offset, config, consumer_id)
+            .inner_stream_batches_with_config(offset, config, consumer_id, control.clone())
             .await?;
+        control.set_floor(start_offset);
+        control.set_stream_to_server(stream_to_server.clone());
         let partition = self.partition;
+        let topic: Arc<str> = self.topic.as_str().into();
+        let record_control = control.clone();
         let flattened = stream.flat_map(move |result: Result<Batch, _>| match result {
             Err(e) => Either::Right(once(err(e))),
             Ok(batch) => {
-                let records =
-                    batch
-                        .into_consumer_records_iter(partition)
-                        .filter_map(move |record| {
-                            if record.offset >= start_offset {
-                                Some(Ok(record))
-                            } else {
-                                None
-                            }
-                        });
+                let control = record_control.clone();
+                let interceptors = interceptors.clone();
+                let topic = topic.clone();
+                let records = batch
+                    .into_consumer_records_iter(partition)
+                    .filter(move |record| !control.skip_record(record.offset))
+                    .map(move |mut record| {
+                        record.set_topic(topic.clone());
+                        interceptors.on_consume(&topic, &mut record);
+                        Ok(record)
+                    });
                 Either::Left(iter(records))
             }
         });
@@ -636,7 +720,298 @@ where
             flush_period,
             flusher_check_period,
             stream_to_server,
-        ))
+        )
+        .with_control(control))
+    }
+}
+
+/// Sends offsets of the responses back to the SPU which serves the stream.
+/// The fetch request of `stream` is timed from here.
+fn fetch_response_stream(
+    mut stream: AsyncResponse<DefaultStreamFetchRequest>,
+    serial_socket: VersionedSerialSocket,
+    control: Arc<PartitionControl>,
+    latency: Arc<PartitionLatency>,
+) -> (FetchResponseStream, Sender<StreamToServer>) {
+    use fluvio_future::task::spawn;
+    use futures_util::stream::empty;
+
+    let (server_sender, server_recv) =
+        async_channel::bounded::<StreamToServer>(STREAM_TO_SERVER_CHANNEL_SIZE);
+
+    let server_sender_clone = server_sender.clone();
+
+    let round_trip = Arc::new(FetchRoundTrip::default());
+    round_trip.requested();
+
+    let ft_stream = async move {
+        if let Some(Ok(raw_response)) = stream.next().await {
+            let response: DefaultStreamFetchResponse = raw_response;
+            round_trip.responded(&latency);
+
+            let stream_id = response.stream_id;
+
+            trace!("first stream response: {:#?}", response);
+            debug!(
+                stream_id,
+                last_offset = ?response.partition.next_offset_for_fetch(),
+                "first stream response"
+            );
+
+            // update stream with received offsets
+            let update_latency = latency.clone();
+            let update_round_trip = round_trip.clone();
+            spawn(async move {
+                use fluvio_spu_schema::server::update_offset::{UpdateOffsetsRequest, OffsetUpdate};
+
+                loop {
+                    match server_recv.recv().await {
+                        Ok(StreamToServer::UpdateOffset(fetch_last_value)) => {
+                            debug!(fetch_last_value, stream_id, "received end fetch");
+                            debug!(
+                                offset = fetch_last_value,
+                                session_id = stream_id,
+                                "sending back offset to spu"
+                            );
+                            let request = UpdateOffsetsRequest {
+                                offsets: vec![OffsetUpdate {
+                                    offset: fetch_last_value,
+                                    session_id: stream_id,
+                                }],
+                            };
+                            debug!(?request, "Sending offset update request:");
+                            update_round_trip.requested();
+                            let update_started = Instant::now();
+                            let response = serial_socket.send_receive(request).await;
+                            update_latency
+                                .offset_round_trip()
+                                .observe_duration(update_started.elapsed());
+                            if let Err(err) = response {
+                                error!("error sending offset: {:#?}", err);
+                                break;
+                            }
+                        }
+                        Ok(StreamToServer::Throttle(throttle)) => {
+                            // SPU sends more records only after they are acknowledged
+                            debug!(
+                                throttle_ms = throttle.as_millis(),
+                                stream_id, "throttled by SPU quota"
+                            );
+                            sleep(throttle).await;
+                        }
+                        Ok(StreamToServer::Close) => {
+                            debug!("fetch last is end, terminating");
+                            break;
+                        }
+                        Ok(StreamToServer::FlushManagedOffset { offset, callback }) => {
+                            debug!(offset, stream_id, "flush offset request");
+                            let request = UpdateConsumerOffsetRequest {
+                                session_id: stream_id,
+                                offset,
+                            };
+                            let response = serial_socket.send_receive(request).await;
+                            match response {
+                                Ok(response) => callback.send(response.error_code).await,
+                                Err(err) => {
+                                    error!("offset flush request error: {:?}", err);
+                                    callback
+                                        .send(ErrorCode::OffsetFlushRequestError(err.to_string()))
+                                        .await;
+                                    break;
+                                }
+                            };
+                        }
+                        Err(err) => {
+                            debug!("stream to server channel closed: {err:?}");
+                            break;
+                        }
+                    }
+                }
+                debug!(stream_id, "offset fetch update loop end");
+            });
+
+            // send back first offset records exists
+            if let Some(last_offset) = response.partition.next_offset_for_fetch() {
+                debug!(last_offset, "notify new last offset");
+                control.reported(last_offset);
+                if let Some(throttle) = stream_throttle(&response) {
+                    let _ = server_sender_clone
+                        .send(StreamToServer::Throttle(throttle))
+                        .await;
+                }
+                let _ = server_sender_clone
+                    .send(StreamToServer::UpdateOffset(last_offset))
+                    .await;
+            }
+
+            let server_sender_clone2 = server_sender_clone.clone();
+            let update_stream = StreamExt::map(stream, move |item| {
+                item.map(|mut response| {
+                    round_trip.responded(&latency);
+                    // the SPU already reads from the seek offset, so
+                    // a response sent before the seek is not acknowledged
+                    if !control.accept_response(&response) {
+                        response.partition.records.batches.clear();
+                        return response;
+                    }
+                    if let Some(last_offset) = response.partition.next_offset_for_fetch() {
+                        debug!(last_offset, stream_id, "received last offset from spu");
+                        control.reported(last_offset);
+                        if let Some(throttle) = stream_throttle(&response) {
+                            let _ =
+                                server_sender_clone.try_send(StreamToServer::Throttle(throttle));
+                        }
+                        let _ =
+                            server_sender_clone.try_send(StreamToServer::UpdateOffset(last_offset));
+                    }
+                    response
+                })
+                .map_err(|e| {
+                    error!(?e, "error in stream");
+                    ErrorCode::Other(e.to_string())
+                })
+            });
+            Either::Left(
+                iter(vec![Ok(response)]).chain(publish_stream::EndPublishSt::new(
+                    update_stream,
+                    server_sender_clone2,
+                )),
+            )
+        } else {
+            info!("stream ended");
+            Either::Right(empty())
+        }
+    };
+
+    (ft_stream.flatten_stream().boxed(), server_sender)
+}
+
+/// Leader stream which takes over the stream of follower in the consumer's rack
+struct LeaderFallback<P> {
+    pool: Arc<P>,
+    replica: ReplicaKey,
+    request: DefaultStreamFetchRequest,
+    version: i16,
+    control: Arc<PartitionControl>,
+    latency: Arc<PartitionLatency>,
+    start_offset: i64,
+}
+
+impl<P> LeaderFallback<P>
+where
+    P: SpuDirectory + Send + Sync + 'static,
+{
+    /// Reads from follower until it fails, ends the stream or falls behind the leader,
+    /// then continues reading from the leader after the last records received
+    fn follow(self, follower: SpuId, follower_stream: FetchResponseStream) -> FetchResponseStream {
+        use fluvio_future::task::spawn;
+
+        let (lagging_sender, lagging) = async_channel::bounded::<()>(1);
+        let pool = self.pool.clone();
+        let replica = self.replica.clone();
+        spawn(async move {
+            loop {
+                sleep(FOLLOWER_LAG_CHECK_INTERVAL).await;
+                if lagging_sender.is_closed() {
+                    break;
+                }
+                match pool.is_follower_in_sync(&replica, follower).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        let _ = lagging_sender.send(()).await;
+                        break;
+                    }
+                    Err(err) => debug!(%err, follower, "unable to check follower"),
+                }
+            }
+        });
+
+        let follower_stream = futures_util::stream::unfold(
+            Some((follower_stream, lagging)),
+            move |state| async move {
+                let (mut stream, lagging) = state?;
+                let next = {
+                    let lagged = std::pin::pin!(lagging.recv());
+                    match futures_util::future::select(stream.next(), lagged).await {
+                        Either::Left((next, _)) => Some(next),
+                        Either::Right(_) => None,
+                    }
+                };
+                match next {
+                    Some(Some(Ok(response)))
+                        if response.partition.error_code == ErrorCode::None =>
+                    {
+                        Some((Ok(response), Some((stream, lagging))))
+                    }
+                    Some(Some(Ok(mut response))) => {
+                        warn!(follower, error = %response.partition.error_code, "follower failed, reading from leader");
+                        // records of the response are kept, leader continues after them
+                        response.partition.error_code = ErrorCode::None;
+                        Some((Ok(response), None))
+                    }
+                    Some(Some(Err(err))) => {
+                        warn!(follower, %err, "follower stream failed, reading from leader");
+                        None
+                    }
+                    Some(None) => {
+                        warn!(follower, "follower ended stream, reading from leader");
+                        None
+                    }
+                    None => {
+                        warn!(follower, "follower is behind leader, reading from leader");
+                        None
+                    }
+                }
+            },
+        );
+
+        follower_stream.chain(self.open().flatten_stream()).boxed()
+    }
+
+    async fn open(self) -> FetchResponseStream {
+        use fluvio_future::task::spawn;
+
+        let Self {
+            pool,
+            replica,
+            mut request,
+            version,
+            control,
+            latency,
+            start_offset,
+        } = self;
+        request.fetch_offset = control.next_offset().unwrap_or(start_offset);
+        let offset = request.fetch_offset;
+
+        // stream is created by a task, as SPU directory futures may not be Send
+        let (sender, receiver) = async_channel::bounded(1);
+        let stream_replica = replica.clone();
+        spawn(async move {
+            let result = async {
+                let socket = pool.create_serial_socket(&stream_replica).await?;
+                let stream = pool
+                    .create_stream_with_version(&stream_replica, request, version)
+                    .await?;
+                Ok::<_, FluvioError>((socket, stream))
+            }
+            .await;
+            let _ = sender.send(result).await;
+        });
+
+        match receiver.recv().await {
+            Ok(Ok((socket, stream))) => {
+                debug!(%replica, offset, "reading from leader");
+                let (stream, server_sender) =
+                    fetch_response_stream(stream, socket, control.clone(), latency);
+                control.set_stream_to_server(server_sender);
+                stream
+            }
+            Ok(Err(error)) => {
+                error!(%error, %replica, "failed to read from leader");
+                once(err(ErrorCode::Other(error.to_string()))).boxed()
+            }
+            Err(_) => futures_util::stream::empty().boxed(),
+        }
     }
 }
 
@@ -800,6 +1175,7 @@ pub struct MultiplePartitionConsumer {
     strategy: PartitionSelectionStrategy,
     pool: Arc<SpuSocketPool>,
     metrics: Arc<ClientMetrics>,
+    dictionaries: Option<SharedDictionaryLoader>,
 }
 
 impl MultiplePartitionConsumer {
@@ -812,9 +1188,15 @@ impl MultiplePartitionConsumer {
             strategy,
             pool,
             metrics,
+            dictionaries: None,
         }
     }
 
+    pub(crate) fn with_dictionaries(mut self, dictionaries: SharedDictionaryLoader) -> Self {
+        self.dictionaries = Some(dictionaries);
+        self
+    }
+
     /// Continuously streams events from a particular offset in the selected partitions
     ///
     /// Streaming is one of the two ways to consume events in Fluvio.
@@ -920,12 +1302,16 @@ impl MultiplePartitionConsumer {
             .await?
             .into_iter()
             .map(|(topic, partition)| {
-                PartitionConsumer::new(
+                let consumer = PartitionConsumer::new(
                     topic,
                     partition as PartitionId,
                     self.pool.clone(),
                     self.metrics.clone(),
-                )
+                );
+                match &self.dictionaries {
+                    Some(dictionaries) => consumer.with_dictionaries(dictionaries.clone()),
+                    None => consumer,
+                }
             })
             .collect();
 
@@ -945,6 +1331,8 @@ impl MultiplePartitionConsumer {
 #[derive(Debug, Clone)]
 pub(crate) enum StreamToServer {
     UpdateOffset(i64),
+    /// hold back next offset update, SPU reported consumer over its quota
+    Throttle(Duration),
     FlushManagedOffset {
         offset: i64,
         callback: StreamToServerCallback<ErrorCode>,
@@ -952,6 +1340,11 @@ pub(crate) enum StreamToServer {
     Close,
 }
 
+/// time SPU asked consumer to back off before acknowledging response
+fn stream_throttle(response: &DefaultStreamFetchResponse) -> Option<Duration> {
+    (response.throttle_time_ms > 0).then(|| Duration::from_millis(response.throttle_time_ms as u64))
+}
+
 #[derive(Debug, Clone)]
 pub(crate) enum StreamToServerCallback<T> {
     NoOp,
@@ -971,6 +1364,36 @@ impl<T> StreamToServerCallback<T> {
     }
 }
 
+/// rack to look for a follower in, if the stream can read from follower.
+/// Consumer offsets are managed by leader, so only anonymous consumers read from follower
+fn follower_fetch_rack(
+    config: &ConsumerConfig,
+    with_consumer_id: bool,
+    stream_fetch_version: i16,
+) -> Option<&str> {
+    if with_consumer_id
+        || config.isolation != Isolation::ReadCommitted
+        || stream_fetch_version < FOLLOWER_FETCH_API
+    {
+        return None;
+    }
+    config.rack.as_deref()
+}
+
+/// observe time from the timestamp of each record of the batch until now
+fn observe_delivery_delay(latency: &PartitionLatency, batch: &Batch) {
+    let base_timestamp = batch.get_base_timestamp();
+    if base_timestamp == NO_TIMESTAMP {
+        return;
+    }
+    let now = chrono::Utc::now().timestamp_millis();
+    for record in batch.records() {
+        // clocks of producer and consumer may differ
+        let delay_ms = (now - (base_timestamp + record.timestamp_delta())).max(0);
+        latency.delivery_delay().observe(delay_ms as f64 / 1000.0);
+    }
+}
+
 /// Creates an exponential backoff configuration.
 fn create_backoff() -> Result<ExponentialBackoff> {
     ExponentialBackoffBuilder::default()
@@ -994,4 +1417,97 @@ mod tests {
     fn test_consumer_config_default() {
         let _config = ConsumerConfig::builder().build().unwrap();
     }
+
+    #[test]
+    fn test_consumer_config_rack() {
+        let config = ConsumerConfigExt::builder()
+            .topic("test")
+            .offset_start(Offset::beginning())
+            .rack("rack-a")
+            .build()
+            .unwrap();
+        let config: ConsumerConfig = config.into();
+        assert_eq!(config.rack.as_deref(), Some("rack-a"));
+    }
+
+    #[test]
+    fn test_follower_fetch_rack() {
+        let config = ConsumerConfig::builder()
+            .rack("rack-a")
+            .isolation(Isolation::ReadCommitted)
+            .build()
+            .unwrap();
+        assert_eq!(
+            follower_fetch_rack(&config, false, FOLLOWER_FETCH_API),
+            Some("rack-a")
+        );
+
+        // named consumers always read from leader
+        assert_eq!(follower_fetch_rack(&config, true, FOLLOWER_FETCH_API), None);
+        assert_eq!(
+            follower_fetch_rack(&config, false, FOLLOWER_FETCH_API - 1),
+            None
+        );
+
+        let uncommitted = ConsumerConfig::builder().rack("rack-a").build().unwrap();
+        assert_eq!(
+            follower_fetch_rack(&uncommitted, false, FOLLOWER_FETCH_API),
+            None
+        );
+    }
+
+    #[test]
+    fn test_consumer_config_topic_pattern() {
+        let config = ConsumerConfigExt::builder()
+            .topic_pattern("orders-.*")
+            .offset_start(Offset::beginning())
+            .build()
+            .unwrap();
+        assert_eq!(config.topic_pattern.as_deref(), Some("orders-.*"));
+
+        assert!(
+            ConsumerConfigExt::builder()
+                .offset_start(Offset::beginning())
+                .build()
+                .is_err()
+        );
+        assert!(
+            ConsumerConfigExt::builder()
+                .topic("orders")
+                .topic_pattern("orders-.*")
+                .offset_start(Offset::beginning())
+                .build()
+                .is_err()
+        );
+        assert!(
+            ConsumerConfigExt::builder()
+                .topic_pattern("orders-(")
+                .offset_start(Offset::beginning())
+                .build()
+                .is_err()
+        );
+    }
+
+    #[cfg(feature = "dead-letter")]
+    #[test]
+    fn test_consumer_config_dead_letter_requires_retry() {
+        let policy = DeadLetterPolicy::new("orders-dlq");
+        assert!(
+            ConsumerConfigExt::builder()
+                .topic("orders")
+                .offset_start(Offset::beginning())
+                .dead_letter(policy.clone())
+                .build()
+                .is_ok()
+        );
+        assert!(
+            ConsumerConfigExt::builder()
+                .topic("orders")
+                .offset_start(Offset::beginning())
+                .retry_mode(RetryMode::Disabled)
+                .dead_letter(policy)
+                .build()
+                .is_err()
+        );
+    }
 }
@@ -0,0 +1,160 @@
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use regex::Regex;
+use tokio::select;
+use tracing::debug;
+
+use fluvio_future::timer::sleep;
+use fluvio_protocol::record::ReplicaKey;
+use fluvio_stream_dispatcher::metadata::local::LocalMetadataItem;
+
+use crate::metadata::partition::PartitionSpec;
+use crate::metadata::store::ChangeListener;
+use crate::spu::SpuSocketPool;
+
+/// How long to wait for the initial partition metadata before
+/// subscribing to the partitions known so far
+const INITIAL_SYNC_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Tracks the partitions of the topics whose name matches a pattern.
+///
+/// Partition store is kept up to date by the metadata sync, so every store change
+/// is diffed against the set of partitions the consumer is subscribed to.
+pub(crate) struct TopicPatternWatcher {
+    pattern: Regex,
+    spu_pool: Arc<SpuSocketPool>,
+    listener: ChangeListener<PartitionSpec, LocalMetadataItem>,
+    subscribed: BTreeSet<ReplicaKey>,
+}
+
+/// Partitions to subscribe to and unsubscribe from
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct PatternChanges {
+    pub added: Vec<ReplicaKey>,
+    pub removed: Vec<ReplicaKey>,
+}
+
+impl TopicPatternWatcher {
+    pub(crate) fn new(pattern: Regex, spu_pool: Arc<SpuSocketPool>) -> Self {
+        let listener = spu_pool.metadata.partitions().store().change_listener();
+        Self {
+            pattern,
+            spu_pool,
+            listener,
+            subscribed: BTreeSet::new(),
+        }
+    }
+
+    /// Changes against the partitions currently in the store.
+    /// Waits a bit for the first metadata sync if nothing has been received yet.
+    pub(crate) async fn initial(&mut self) -> PatternChanges {
+        if !self.listener.has_change() {
+            select! {
+                _ = self.listener.listen() => {},
+                _ = sleep(INITIAL_SYNC_TIMEOUT) => {
+                    debug!("no partition metadata received, starting with empty subscription");
+                }
+            }
+        }
+        self.sync().await
+    }
+
+    /// Waits for the next partition store change and returns the changes
+    pub(crate) async fn next_changes(&mut self) -> PatternChanges {
+        self.listener.listen().await;
+        self.sync().await
+    }
+
+    /// Forgets about a partition, so it is reported as added on the next change.
+    /// Used when the partition stream could not be created yet.
+    pub(crate) fn unsubscribe(&mut self, replica: &ReplicaKey) {
+        self.subscribed.remove(replica);
+    }
+
+    async fn sync(&mut self) -> PatternChanges {
+        self.listener.load_last();
+        let current: BTreeSet<ReplicaKey> = self
+            .spu_pool
+            .metadata
+            .partitions()
+            .store()
+            .read()
+            .await
+            .values()
+            .filter(|partition| !partition.spec.system)
+            .map(|partition| partition.key.clone())
+            .filter(|replica| self.pattern.is_match(&replica.topic))
+            .collect();
+
+        let changes = diff_partitions(&self.subscribed, &current);
+        debug!(pattern = %self.pattern, ?changes, "topic pattern changes");
+        self.subscribed = current;
+        changes
+    }
+}
+
+fn diff_partitions(
+    subscribed: &BTreeSet<ReplicaKey>,
+    current: &BTreeSet<ReplicaKey>,
+) -> PatternChanges {
+    PatternChanges {
+        added: current.difference(subscribed).cloned().collect(),
+        removed: subscribed.difference(current).cloned().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::consumer::config::topic_pattern_regex;
+
+    use super::*;
+
+    #[test]
+    fn test_topic_pattern_matches_whole_name() {
+        let pattern = topic_pattern_regex("orders-.*").expect("valid pattern");
+        assert!(pattern.is_match("orders-eu"));
+        assert!(pattern.is_match("orders-"));
+        assert!(!pattern.is_match("old-orders-eu"));
+        assert!(!pattern.is_match("orders"));
+
+        let pattern = topic_pattern_regex("a|b").expect("valid pattern");
+        assert!(pattern.is_match("a"));
+        assert!(!pattern.is_match("ab"));
+
+        assert!(topic_pattern_regex("orders-(").is_err());
+    }
+
+    #[test]
+    fn test_diff_partitions() {
+        let subscribed: BTreeSet<ReplicaKey> = [
+            ReplicaKey::new("orders-eu", 0u32),
+            ReplicaKey::new("orders-us", 0u32),
+        ]
+        .into();
+        let current: BTreeSet<ReplicaKey> = [
+            ReplicaKey::new("orders-eu", 0u32),
+            ReplicaKey::new("orders-eu", 1u32),
+            ReplicaKey::new("orders-asia", 0u32),
+        ]
+        .into();
+
+        let changes = diff_partitions(&subscribed, &current);
+
+        assert_eq!(
+            changes,
+            PatternChanges {
+                added: vec![
+                    ReplicaKey::new("orders-asia", 0u32),
+                    ReplicaKey::new("orders-eu", 1u32),
+                ],
+                removed: vec![ReplicaKey::new("orders-us", 0u32)],
+            }
+        );
+        assert_eq!(
+            diff_partitions(&current, &current),
+            PatternChanges::default()
+        );
+    }
+}
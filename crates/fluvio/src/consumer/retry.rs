@@ -19,15 +19,21 @@ use tokio::select;
 use tracing::{debug, info, warn};
 
 use fluvio_future::timer::sleep;
+#[cfg(feature = "dead-letter")]
+use fluvio_protocol::link::smartmodule::SmartModuleTransformRuntimeError;
 use fluvio_protocol::record::ConsumerRecord;
 use fluvio_sc_schema::errors::ErrorCode;
+#[cfg(feature = "dead-letter")]
+use fluvio_types::PartitionId;
 
 use crate::consumer::RetryMode;
 use crate::{Fluvio, FluvioClusterConfig, Offset};
 use super::{
-    BoxConsumerFuture, BoxConsumerStream, ConsumerBoxFuture, ConsumerConfigExt,
+    BoxConsumerFuture, BoxConsumerStream, ConsumerBoxFuture, ConsumerConfigExt, ConsumerControls,
     ConsumerFutureOutput, ConsumerStream, ShararedConsumerStream,
 };
+#[cfg(feature = "dead-letter")]
+use super::{DeadLetterQueue, DeadLetterRecord, FailedAttempts};
 
 pub const SPAN_RETRY: &str = "fluvio::retry";
 
@@ -68,6 +74,13 @@ pub struct ConsumerRetryInner {
     client_config: Arc<ClientConfig>,
     strategy: Arc<dyn ReconnectStrategy>,
     backoff: ExponentialBackoff,
+    /// partition streams of every reconnect are registered here for [`super::ConsumerHandle`]
+    controls: ConsumerControls,
+    /// publishes records of the dead-letter policy of the consumer config
+    #[cfg(feature = "dead-letter")]
+    dead_letter: Option<DeadLetterQueue>,
+    #[cfg(feature = "dead-letter")]
+    failed_attempts: FailedAttempts,
 }
 impl ConsumerRetryInner {
     /// Determine the offset for reconnection.
@@ -78,6 +91,59 @@ impl ConsumerRetryInner {
             self.consumer_config.offset_start.clone()
         }
     }
+
+    /// Partition of the consumer, if it reads only one
+    #[cfg(feature = "dead-letter")]
+    fn single_partition(&self) -> Option<PartitionId> {
+        match self.consumer_config.partition.as_slice() {
+            [partition] => Some(*partition),
+            _ => None,
+        }
+    }
+
+    /// Retries the record failed by a SmartModule from its offset. Once the attempts of
+    /// the dead-letter policy are used up, the record is published to the dead-letter topic
+    /// and the stream continues after it.
+    ///
+    /// The reconnect offset applies to every partition of the consumer, so the error is
+    /// returned as is unless the consumer reads a single partition.
+    #[cfg(feature = "dead-letter")]
+    async fn dead_letter_failed(
+        &mut self,
+        error: Box<SmartModuleTransformRuntimeError>,
+    ) -> Result<(), ErrorCode> {
+        let Some(policy) = self.consumer_config.dead_letter.clone() else {
+            return Err(ErrorCode::SmartModuleRuntimeError(error));
+        };
+        let Some(partition) = self.single_partition() else {
+            warn!(target: SPAN_RETRY, offset = error.offset, "SmartModule failed on record of multi-partition consumer, dead-letter policy skipped");
+            return Err(ErrorCode::SmartModuleRuntimeError(error));
+        };
+        let attempts = self.failed_attempts.fail(error.offset);
+        if attempts < policy.max_attempts {
+            let backoff = policy.backoff_after(attempts);
+            warn!(target: SPAN_RETRY, offset = error.offset, attempts, "SmartModule failed on record, retrying in {backoff:?}");
+            sleep(backoff).await;
+            self.next_offset_to_read = Some(error.offset);
+            return Ok(());
+        }
+        let Some(queue) = &self.dead_letter else {
+            return Err(ErrorCode::SmartModuleRuntimeError(error));
+        };
+        // topic pattern consumers have no topic in config
+        let topic = &self.consumer_config.topic;
+        let record = DeadLetterRecord::from_runtime_error(
+            (!topic.is_empty()).then(|| topic.clone()),
+            Some(partition),
+            &error,
+            attempts,
+        );
+        queue.send(&record).await.map_err(|err| {
+            ErrorCode::Other(format!("failed to publish to dead-letter topic: {err}"))
+        })?;
+        self.next_offset_to_read = Some(error.offset + 1);
+        Ok(())
+    }
 }
 
 /// The internal state of our consumer.
@@ -123,8 +189,12 @@ impl ReconnectStrategy for DefaultReconnectStrategy {
         )
         .await?;
 
-        let new_stream =
-            ConsumerRetryStream::create_owned_stream(fluvio_client, new_config.clone()).await?;
+        let new_stream = ConsumerRetryStream::create_owned_stream(
+            fluvio_client,
+            new_config.clone(),
+            &inner.controls,
+        )
+        .await?;
 
         backoff.reset();
         Ok(Arc::new(Mutex::new(Box::pin(new_stream))))
@@ -182,6 +252,11 @@ impl ConsumerStream for ConsumerRetryStream {
             stream.offset_flush().await
         })
     }
+
+    #[cfg(feature = "dead-letter")]
+    fn dead_letter(&self) -> Option<&DeadLetterQueue> {
+        self.inner.dead_letter.as_ref()
+    }
 }
 
 impl ConsumerRetryStream {
@@ -189,12 +264,13 @@ impl ConsumerRetryStream {
     async fn create_owned_stream(
         fluvio: Fluvio,
         config: ConsumerConfigExt,
+        controls: &ConsumerControls,
     ) -> Result<
         impl ConsumerStream<
             Item = std::result::Result<ConsumerRecord, fluvio_protocol::link::ErrorCode>,
         >,
     > {
-        fluvio.consumer_with_config_inner(config).await
+        fluvio.consumer_with_config_inner(config, controls).await
     }
 
     /// Creates a new `ConsumerRetryStream` with the given configuration.
@@ -202,11 +278,27 @@ impl ConsumerRetryStream {
         fluvio: &Fluvio,
         cluster_config: FluvioClusterConfig,
         config: ConsumerConfigExt,
+    ) -> Result<Self> {
+        Self::with_controls(fluvio, cluster_config, config, Default::default()).await
+    }
+
+    pub(crate) async fn with_controls(
+        fluvio: &Fluvio,
+        cluster_config: FluvioClusterConfig,
+        config: ConsumerConfigExt,
+        controls: ConsumerControls,
     ) -> Result<Self> {
         let client_config = fluvio.client_config();
-        let stream = fluvio.consumer_with_config_inner(config.clone()).await?;
+        let stream = fluvio
+            .consumer_with_config_inner(config.clone(), &controls)
+            .await?;
 
         let backoff = create_backoff()?;
+        #[cfg(feature = "dead-letter")]
+        let dead_letter = match &config.dead_letter {
+            Some(policy) => Some(DeadLetterQueue::new(fluvio, policy.clone()).await?),
+            None => None,
+        };
 
         let retry_stream = Self {
             inner: ConsumerRetryInner {
@@ -216,6 +308,11 @@ impl ConsumerRetryStream {
                 consumer_config: config,
                 strategy: Arc::new(DefaultReconnectStrategy),
                 backoff,
+                controls,
+                #[cfg(feature = "dead-letter")]
+                dead_letter,
+                #[cfg(feature = "dead-letter")]
+                failed_attempts: Default::default(),
             },
             state: ConsumerRetryState::Idle,
             stream: Arc::new(Mutex::new(Box::pin(stream))),
@@ -317,6 +414,15 @@ impl ConsumerRetryStream {
                                     ));
                                 }
                             }
+                            #[cfg(feature = "dead-letter")]
+                            Err(ErrorCode::SmartModuleRuntimeError(error))
+                                if inner.consumer_config.dead_letter.is_some()
+                                    && inner.consumer_config.retry_mode != RetryMode::Disabled =>
+                            {
+                                if let Err(e) = inner.dead_letter_failed(error).await {
+                                    return Some((stream.clone(), Some(Err(e))));
+                                }
+                            }
                             Err(e) => {
                                 warn!(target: SPAN_RETRY, "Error consuming record: {}", e);
                                 if let RetryMode::Disabled = inner.consumer_config.retry_mode {
@@ -400,6 +506,8 @@ mod tests {
     use fluvio_types::PartitionId;
     use futures_util::{stream::Iter, FutureExt, StreamExt};
 
+    #[cfg(feature = "dead-letter")]
+    use crate::consumer::DeadLetterPolicy;
     use crate::consumer::{
         MultiplePartitionConsumerStream, OffsetManagementStrategy, SinglePartitionConsumerStream,
         StreamToServer,
@@ -457,6 +565,11 @@ mod tests {
                 .unwrap(),
             strategy,
             backoff: super::create_backoff().unwrap(),
+            controls: Default::default(),
+            #[cfg(feature = "dead-letter")]
+            dead_letter: None,
+            #[cfg(feature = "dead-letter")]
+            failed_attempts: Default::default(),
         };
 
         ConsumerRetryStream {
@@ -527,6 +640,11 @@ mod tests {
                     .expect("no error"),
                 strategy: Arc::new(DefaultReconnectStrategy),
                 backoff: ExponentialBackoff::default(),
+                controls: Default::default(),
+                #[cfg(feature = "dead-letter")]
+                dead_letter: None,
+                #[cfg(feature = "dead-letter")]
+                failed_attempts: Default::default(),
             },
             state: ConsumerRetryState::Idle,
             stream: Arc::new(Mutex::new(Box::pin(multi_stream))),
@@ -638,6 +756,107 @@ mod tests {
         assert!(matches!(retry_stream.state, ConsumerRetryState::Idle));
     }
 
+    #[cfg(feature = "dead-letter")]
+    fn runtime_error(offset: i64) -> ErrorCode {
+        ErrorCode::SmartModuleRuntimeError(Box::new(SmartModuleTransformRuntimeError {
+            offset,
+            ..Default::default()
+        }))
+    }
+
+    #[cfg(feature = "dead-letter")]
+    fn dead_letter_policy(max_attempts: u32) -> DeadLetterPolicy {
+        DeadLetterPolicy::new("dlq")
+            .max_attempts(max_attempts)
+            .backoff(Duration::from_millis(1))
+    }
+
+    #[cfg(feature = "dead-letter")]
+    #[fluvio_future::test]
+    async fn test_dead_letter_retries_record_failed_by_smartmodule() {
+        // given
+        let mut consumer_records_with_error = create_data(0, ["1", "2"]);
+        consumer_records_with_error.push(Err(runtime_error(2)));
+
+        let mut mock = MockReconnectStrategy::new();
+        mock.expect_reconnect()
+            .withf(|inner, new_config, _backoff| {
+                assert_eq!(inner.next_offset_to_read, Some(2));
+                assert_eq!(new_config.offset_start, Offset::absolute(2).unwrap());
+                true
+            })
+            .returning(|_, _, _| {
+                let retry_stream = make_basic_single_stream(
+                    futures_util::stream::iter(create_data(0, ["3"])),
+                    Arc::new(DefaultReconnectStrategy),
+                    RetryMode::Disabled,
+                );
+                futures_util::future::ready(Ok(retry_stream.stream)).boxed()
+            });
+
+        let mut retry_stream = make_basic_single_stream(
+            futures_util::stream::iter(consumer_records_with_error),
+            Arc::new(mock),
+            RetryMode::TryForever,
+        );
+        retry_stream.inner.consumer_config.partition = vec![0];
+        retry_stream.inner.consumer_config.dead_letter = Some(dead_letter_policy(2));
+
+        // when
+        let mut result = vec![];
+        for _ in 0..3 {
+            result.push(retry_stream.next().await.unwrap().unwrap());
+        }
+
+        // then
+        assert_eq!(
+            result
+                .iter()
+                .map(|r| String::from_utf8_lossy(r.as_ref()).to_string())
+                .collect::<Vec<_>>(),
+            ["1", "2", "3"]
+        );
+    }
+
+    #[cfg(feature = "dead-letter")]
+    #[fluvio_future::test]
+    async fn test_dead_letter_without_queue_returns_error_after_max_attempts() {
+        // given
+        let mut retry_stream = make_basic_single_stream(
+            futures_util::stream::iter(vec![Err(runtime_error(0))]),
+            Arc::new(FailingReconnectStrategy),
+            RetryMode::TryForever,
+        );
+        retry_stream.inner.consumer_config.partition = vec![0];
+        retry_stream.inner.consumer_config.dead_letter = Some(dead_letter_policy(1));
+
+        // when
+        let got = retry_stream.next().await.unwrap();
+
+        // then
+        assert_eq!(got.err().unwrap(), runtime_error(0));
+    }
+
+    #[cfg(feature = "dead-letter")]
+    #[fluvio_future::test]
+    async fn test_dead_letter_multi_partition_returns_error_without_retry() {
+        // given
+        let mut retry_stream = make_basic_single_stream(
+            futures_util::stream::iter(vec![Err(runtime_error(0))]),
+            Arc::new(FailingReconnectStrategy),
+            RetryMode::TryForever,
+        );
+        retry_stream.inner.consumer_config.partition = vec![0, 1];
+        retry_stream.inner.consumer_config.dead_letter = Some(dead_letter_policy(3));
+
+        // when
+        let got = retry_stream.next().await.unwrap();
+
+        // then
+        assert_eq!(got.err().unwrap(), runtime_error(0));
+        assert_eq!(retry_stream.inner.next_offset_to_read, None);
+    }
+
     #[fluvio_future::test]
     async fn retry_mode_disabled_ends_with_error() {
         // given
@@ -3,17 +3,21 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
-use async_channel::Sender;
+use async_channel::{Receiver, Sender};
 use fluvio_future::timer::sleep;
 use fluvio_protocol::{link::ErrorCode, record::ConsumerRecord as Record};
-use futures_util::stream::select_all;
+use fluvio_protocol::record::ReplicaKey;
+use futures_util::stream::{select_all, SelectAll};
 use futures_util::{future::try_join_all, ready, FutureExt};
-use futures_util::Stream;
+use futures_util::{Stream, StreamExt};
 use tokio::select;
 use tokio::sync::Notify;
 use tracing::{debug, info, warn};
 
 use super::config::OffsetManagementStrategy;
+use super::control::PartitionControl;
+#[cfg(feature = "dead-letter")]
+use super::DeadLetterQueue;
 use super::{offset::OffsetLocalStore, StreamToServer};
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -30,15 +34,41 @@ pub trait ConsumerStream: Stream<Item = Result<Record, ErrorCode>> + Unpin {
 
     /// Send the committed offset to the server. The method waits for the server's acknowledgment before it finishes.
     fn offset_flush(&mut self) -> ConsumerBoxFuture<'_>;
+
+    /// Dead-letter queue of the dead-letter policy the stream was configured with.
+    /// Records failing in the application can be published to it as well.
+    #[cfg(feature = "dead-letter")]
+    fn dead_letter(&self) -> Option<&DeadLetterQueue> {
+        None
+    }
 }
 
 pub struct MultiplePartitionConsumerStream<T> {
-    partition_streams: futures_util::stream::SelectAll<SinglePartitionConsumerStream<T>>,
+    partition_streams: SelectAll<SinglePartitionConsumerStream<T>>,
     offset_mgnts: Vec<Arc<OffsetManagement>>,
+    subscription: Option<Receiver<SubscriptionChange<T>>>,
+    /// notified when stream is dropped, so the task following the subscription ends
+    stop_subscription: Option<Arc<Notify>>,
+}
+
+impl<T> Drop for MultiplePartitionConsumerStream<T> {
+    fn drop(&mut self) {
+        if let Some(stop_subscription) = &self.stop_subscription {
+            stop_subscription.notify_one();
+        }
+    }
+}
+
+/// Change of the partition set followed by a topic pattern subscription
+pub(crate) enum SubscriptionChange<T> {
+    Add(Vec<SinglePartitionConsumerStream<T>>),
+    Remove(Vec<ReplicaKey>),
 }
 
 pub struct SinglePartitionConsumerStream<T> {
     offset_mngt: Arc<OffsetManagement>,
+    replica: Option<ReplicaKey>,
+    control: Arc<PartitionControl>,
     inner: T,
 }
 
@@ -92,6 +122,53 @@ impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> MultiplePartitionConsu
         Self {
             partition_streams,
             offset_mgnts,
+            subscription: None,
+            stop_subscription: None,
+        }
+    }
+
+    /// Creates stream whose partitions are added and removed by the subscription changes.
+    /// The stream only ends when the subscription is closed and all partition streams are done.
+    /// `stop` is notified once the stream is dropped.
+    pub(crate) fn with_subscription<I>(
+        streams: I,
+        changes: Receiver<SubscriptionChange<T>>,
+        stop: Arc<Notify>,
+    ) -> Self
+    where
+        I: IntoIterator<Item = SinglePartitionConsumerStream<T>>,
+    {
+        let mut stream = Self::new(streams);
+        stream.subscription = Some(changes);
+        stream.stop_subscription = Some(stop);
+        stream
+    }
+
+    fn apply_change(&mut self, change: SubscriptionChange<T>) {
+        match change {
+            SubscriptionChange::Add(streams) => {
+                for partition_stream in streams {
+                    debug!(replica = ?partition_stream.replica, "adding partition stream");
+                    self.offset_mgnts.push(partition_stream.offset_mngt.clone());
+                    self.partition_streams.push(partition_stream);
+                }
+            }
+            SubscriptionChange::Remove(replicas) => {
+                let streams = std::mem::replace(&mut self.partition_streams, SelectAll::new());
+                for partition_stream in streams {
+                    let removed = partition_stream
+                        .replica
+                        .as_ref()
+                        .is_some_and(|replica| replicas.contains(replica));
+                    if removed {
+                        debug!(replica = ?partition_stream.replica, "removing partition stream");
+                        self.offset_mgnts
+                            .retain(|mngt| !Arc::ptr_eq(mngt, &partition_stream.offset_mngt));
+                    } else {
+                        self.partition_streams.push(partition_stream);
+                    }
+                }
+            }
         }
     }
 }
@@ -127,7 +204,28 @@ impl<T> SinglePartitionConsumerStream<T> {
             });
         }
 
-        Self { offset_mngt, inner }
+        Self {
+            offset_mngt,
+            replica: None,
+            control: Default::default(),
+            inner,
+        }
+    }
+
+    /// Sets the replica this stream reads from, so it can be removed from a subscription
+    pub(crate) fn with_replica(mut self, replica: ReplicaKey) -> Self {
+        self.replica = Some(replica);
+        self
+    }
+
+    pub(crate) fn with_control(mut self, control: Arc<PartitionControl>) -> Self {
+        self.control = control;
+        self
+    }
+
+    /// Pause and seek state, shared with [`super::ConsumerHandle`]
+    pub(crate) fn control(&self) -> Arc<PartitionControl> {
+        self.control.clone()
     }
 }
 
@@ -141,6 +239,11 @@ impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> Stream
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
         let self_mut = self.get_mut();
+        // the partition is not read while paused, so the SPU does not get
+        // offset updates and stops sending records
+        if self_mut.control.poll_paused(cx.waker()) {
+            return std::task::Poll::Pending;
+        }
         let pinned = std::pin::pin!(&mut self_mut.inner);
         match ready!(pinned.poll_next(cx)) {
             Some(Ok(last)) => {
@@ -164,6 +267,11 @@ impl ConsumerStream for Pin<Box<dyn ConsumerStream + Send>> {
     fn offset_flush(&mut self) -> ConsumerBoxFuture {
         Box::pin(async move { self.as_mut().offset_flush().await })
     }
+
+    #[cfg(feature = "dead-letter")]
+    fn dead_letter(&self) -> Option<&DeadLetterQueue> {
+        (**self).dead_letter()
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
@@ -175,6 +283,11 @@ impl ConsumerStream for Pin<Box<dyn ConsumerStream>> {
     fn offset_flush(&mut self) -> ConsumerBoxFuture {
         Box::pin(async move { self.as_mut().offset_flush().await })
     }
+
+    #[cfg(feature = "dead-letter")]
+    fn dead_letter(&self) -> Option<&DeadLetterQueue> {
+        (**self).dead_letter()
+    }
 }
 
 impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> ConsumerStream
@@ -218,8 +331,32 @@ impl<T: Stream<Item = Result<Record, ErrorCode>> + Unpin> Stream
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
         let self_mut = self.get_mut();
-        let pinned = std::pin::pin!(&mut self_mut.partition_streams);
-        pinned.poll_next(cx)
+        if let Some(changes) = self_mut.subscription.as_mut() {
+            loop {
+                match changes.poll_next_unpin(cx) {
+                    std::task::Poll::Ready(Some(change)) => self_mut.apply_change(change),
+                    std::task::Poll::Ready(None) => {
+                        self_mut.subscription = None;
+                        break;
+                    }
+                    std::task::Poll::Pending => break,
+                }
+            }
+        }
+
+        if self_mut.subscription.is_none() {
+            return self_mut.partition_streams.poll_next_unpin(cx);
+        }
+
+        // with subscription, partitions come and go, so deleted topics and
+        // an empty partition set do not end the stream
+        loop {
+            match ready!(self_mut.partition_streams.poll_next_unpin(cx)) {
+                Some(Err(ErrorCode::TopicDeleted)) => continue,
+                None => return std::task::Poll::Pending,
+                item => return std::task::Poll::Ready(item),
+            }
+        }
     }
 }
 
@@ -347,7 +484,8 @@ mod tests {
     use fluvio_protocol::record::Batch;
     use fluvio_smartmodule::RecordData;
     use fluvio_types::PartitionId;
-    use futures_util::{stream::Iter, StreamExt};
+    use futures_util::stream::{Chain, Iter, Pending};
+    use futures_util::StreamExt;
 
     use super::*;
 
@@ -882,6 +1020,143 @@ mod tests {
         assert_eq!(flush_res, Err(ErrorCode::SpuOffline), "{flush_res:?}");
     }
 
+    #[fluvio_future::test]
+    async fn test_multi_partition_stream_follows_subscription() {
+        //given
+        let (changes_tx, changes_rx) = async_channel::unbounded();
+        let (tx, _rx) = async_channel::unbounded();
+        let partition_stream1 = SinglePartitionConsumerStream::new(
+            endless_records_stream(0, ["1"]),
+            Default::default(),
+            Default::default(),
+            Duration::from_millis(100),
+            tx,
+        )
+        .with_replica(ReplicaKey::new("topic-a", 0u32));
+        let stop = Arc::new(Notify::new());
+        let mut multi_stream = MultiplePartitionConsumerStream::with_subscription(
+            [partition_stream1],
+            changes_rx,
+            stop.clone(),
+        );
+        assert_eq!(next_value(&mut multi_stream).await, "1");
+
+        //when
+        let (tx, _rx) = async_channel::unbounded();
+        let partition_stream2 = SinglePartitionConsumerStream::new(
+            endless_records_stream(0, ["2"]),
+            Default::default(),
+            Default::default(),
+            Duration::from_millis(100),
+            tx,
+        )
+        .with_replica(ReplicaKey::new("topic-b", 0u32));
+        changes_tx
+            .send(SubscriptionChange::Add(vec![partition_stream2]))
+            .await
+            .expect("send");
+
+        //then
+        assert_eq!(next_value(&mut multi_stream).await, "2");
+        assert_eq!(multi_stream.partition_streams.len(), 2);
+        assert_eq!(multi_stream.offset_mgnts.len(), 2);
+
+        //when
+        changes_tx
+            .send(SubscriptionChange::Remove(vec![ReplicaKey::new(
+                "topic-a", 0u32,
+            )]))
+            .await
+            .expect("send");
+
+        //then
+        assert!(multi_stream.next().now_or_never().is_none());
+        assert_eq!(multi_stream.partition_streams.len(), 1);
+        assert_eq!(multi_stream.offset_mgnts.len(), 1);
+
+        //when
+        changes_tx
+            .send(SubscriptionChange::Remove(vec![ReplicaKey::new(
+                "topic-b", 0u32,
+            )]))
+            .await
+            .expect("send");
+
+        //then
+        assert!(
+            multi_stream.next().now_or_never().is_none(),
+            "empty subscription waits for new partitions"
+        );
+
+        //when
+        drop(changes_tx);
+
+        //then
+        assert!(multi_stream.next().await.is_none());
+        assert!(stop.notified().now_or_never().is_none());
+
+        //when
+        drop(multi_stream);
+
+        //then
+        assert!(
+            stop.notified().now_or_never().is_some(),
+            "dropped stream stops subscription"
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_multi_partition_stream_pause_and_resume_partition() {
+        //given
+        let (tx, _rx) = async_channel::unbounded();
+        let partition_stream1 = SinglePartitionConsumerStream::new(
+            endless_records_stream(0, ["1", "2"]),
+            Default::default(),
+            Default::default(),
+            Duration::from_millis(100),
+            tx.clone(),
+        );
+        let partition_stream2 = SinglePartitionConsumerStream::new(
+            endless_records_stream(1, ["a"]),
+            Default::default(),
+            Default::default(),
+            Duration::from_millis(100),
+            tx,
+        );
+        let control = partition_stream1.control();
+        let mut multi_stream =
+            MultiplePartitionConsumerStream::new([partition_stream1, partition_stream2]);
+
+        //when
+        control.pause();
+
+        //then
+        assert_eq!(next_value(&mut multi_stream).await, "a");
+        assert!(multi_stream.next().now_or_never().is_none());
+
+        //when
+        control.resume();
+
+        //then
+        assert_eq!(next_value(&mut multi_stream).await, "1");
+        assert_eq!(next_value(&mut multi_stream).await, "2");
+    }
+
+    async fn next_value<S>(stream: &mut S) -> String
+    where
+        S: Stream<Item = Result<Record, ErrorCode>> + Unpin,
+    {
+        let record = stream.next().await.expect("record").expect("no error");
+        String::from_utf8_lossy(record.as_ref()).to_string()
+    }
+
+    fn endless_records_stream(
+        partition: PartitionId,
+        input: impl IntoIterator<Item = &'static str>,
+    ) -> Chain<Iter<IntoIter<Result<Record, ErrorCode>>>, Pending<Result<Record, ErrorCode>>> {
+        records_stream(partition, input).chain(futures_util::stream::pending())
+    }
+
     fn records_stream(
         partition: PartitionId,
         input: impl IntoIterator<Item = &'static str>,
@@ -0,0 +1,122 @@
+//!
+//! # Compression dictionaries
+//!
+//! Zstd dictionaries of the cluster are registered in the process wide registry,
+//! so batches compressed with them can be decompressed.
+//!
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use anyhow::Result;
+use async_lock::Mutex;
+use tracing::{debug, warn};
+
+use fluvio_compression::{DEFAULT_ZSTD_LEVEL, ZstdDictionary, lookup_dictionary, register_dictionary};
+use fluvio_protocol::record::{Batch, RawRecords};
+use fluvio_sc_schema::dictionary::DictionarySpec;
+use fluvio_sc_schema::objects::Metadata;
+use fluvio_sc_schema::topic::TopicSpec;
+
+use crate::{FluvioAdmin, FluvioError};
+
+pub(crate) type SharedDictionaryLoader = Arc<DictionaryLoader>;
+
+/// Loads dictionaries from SC and registers them
+pub(crate) struct DictionaryLoader {
+    admin: FluvioAdmin,
+    /// lock also serializes loads
+    state: Mutex<LoaderState>,
+}
+
+#[derive(Default)]
+struct LoaderState {
+    /// registered dictionaries by name
+    loaded: HashMap<String, ZstdDictionary>,
+    /// ids not found after last reload
+    not_found: HashSet<u32>,
+}
+
+impl LoaderState {
+    fn register(&mut self, dictionary: Metadata<DictionarySpec>) -> Result<ZstdDictionary> {
+        let zstd = ZstdDictionary::new(dictionary.spec.data.to_vec())?
+            .with_level(dictionary.spec.level.unwrap_or(DEFAULT_ZSTD_LEVEL));
+        self.not_found.remove(&zstd.id());
+        register_dictionary(zstd.clone());
+        self.loaded.insert(dictionary.name, zstd.clone());
+        Ok(zstd)
+    }
+}
+
+impl DictionaryLoader {
+    pub(crate) fn shared(admin: FluvioAdmin) -> SharedDictionaryLoader {
+        Arc::new(Self {
+            admin,
+            state: Mutex::new(LoaderState::default()),
+        })
+    }
+
+    /// Registers the dictionary used by the topic and returns it.
+    /// Only this dictionary is fetched, once per client.  Records compressed with
+    /// other dictionaries, e.g. rotated out ones, are handled by [`Self::register_missing`].
+    pub(crate) async fn topic_dictionary(
+        &self,
+        topic_spec: &TopicSpec,
+    ) -> Result<Option<ZstdDictionary>> {
+        let Some(name) = topic_spec.get_compression_dictionary() else {
+            return Ok(None);
+        };
+        let mut state = self.state.lock().await;
+        if let Some(dictionary) = state.loaded.get(name) {
+            return Ok(Some(dictionary.clone()));
+        }
+        let dictionary = self
+            .admin
+            .list::<DictionarySpec, _>(vec![name.to_owned()])
+            .await?
+            .into_iter()
+            .find(|dictionary| dictionary.name == name)
+            .ok_or_else(|| {
+                FluvioError::Other(format!("compression dictionary {name} not found"))
+            })?;
+        let zstd = state.register(dictionary)?;
+        debug!(name, id = zstd.id(), "registered compression dictionary");
+        Ok(Some(zstd))
+    }
+
+    /// Reloads dictionaries if any batch is compressed with dictionary which is not registered,
+    /// e.g. dictionary created after the stream started.
+    /// Ids which are still unknown after reload are not reloaded again.
+    pub(crate) async fn register_missing(&self, batches: &[Batch<RawRecords>]) {
+        let missing = |not_found: &HashSet<u32>| {
+            batches
+                .iter()
+                .filter_map(Batch::dictionary_id)
+                .any(|id| lookup_dictionary(id).is_none() && !not_found.contains(&id))
+        };
+        let mut state = self.state.lock().await;
+        // another stream may have reloaded while waiting for the lock
+        if !missing(&state.not_found) {
+            return;
+        }
+        match self.load_all(&mut state).await {
+            Ok(()) => {
+                let ids = batches.iter().filter_map(Batch::dictionary_id);
+                state
+                    .not_found
+                    .extend(ids.filter(|id| lookup_dictionary(*id).is_none()));
+            }
+            Err(err) => warn!(%err, "failed to reload compression dictionaries"),
+        }
+    }
+
+    /// register all dictionaries of the cluster
+    async fn load_all(&self, state: &mut LoaderState) -> Result<()> {
+        let dictionaries = self.admin.all::<DictionarySpec>().await?;
+        let count = dictionaries.len();
+        for dictionary in dictionaries {
+            state.register(dictionary)?;
+        }
+        debug!(count, "registered compression dictionaries");
+        Ok(())
+    }
+}
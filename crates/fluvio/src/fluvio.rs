@@ -1,26 +1,33 @@
+use std::collections::HashSet;
 use std::convert::TryFrom;
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
+use futures_util::future::join_all;
 use fluvio_types::defaults::CONSUMER_REPLICA_KEY;
 use semver::Version;
-use tokio::sync::OnceCell;
-use tracing::{debug, info};
+use tokio::select;
+use tokio::sync::{Notify, OnceCell};
+use tracing::{debug, info, warn};
 
 use fluvio_future::net::DomainConnector;
 use fluvio_sc_schema::partition::PartitionMirrorConfig;
 use fluvio_sc_schema::topic::{MirrorConfig, PartitionMap, ReplicaSpec};
 use fluvio_sc_schema::objects::ObjectApiWatchRequest;
 use fluvio_types::PartitionId;
+use fluvio_protocol::record::Offset;
 use fluvio_socket::{
     ClientConfig, Versions, VersionedSerialSocket, SharedMultiplexerSocket, MultiplexerSocket,
 };
 
 use crate::admin::FluvioAdmin;
 use crate::consumer::{
-    ConsumerConfigExt, ConsumerOffset, ConsumerRetryStream, ConsumerStream,
-    MultiplePartitionConsumer, MultiplePartitionConsumerStream, PartitionSelectionStrategy, Record,
+    ConsumerConfigExt, ConsumerControls, ConsumerHandle, ConsumerLag, ConsumerLagError,
+    ConsumerOffset, ConsumerRetryStream, ConsumerStream, MultiplePartitionConsumer,
+    MultiplePartitionConsumerStream, PartitionSelectionStrategy, Record, SubscriptionChange,
+    TopicPatternWatcher, topic_pattern_regex,
 };
+use crate::dictionary::{DictionaryLoader, SharedDictionaryLoader};
 use crate::error::anyhow_version_error;
 use crate::metrics::ClientMetrics;
 use crate::producer::{TopicProducerPool, TopicProducerConfig};
@@ -38,6 +45,7 @@ pub struct Fluvio {
     metadata: MetadataStores,
     watch_version: i16,
     metric: Arc<ClientMetrics>,
+    dictionaries: SharedDictionaryLoader,
 }
 
 impl Fluvio {
@@ -137,6 +145,10 @@ impl Fluvio {
 
             let socket = MultiplexerSocket::shared(socket);
             let metadata = MetadataStores::start(socket.clone(), watch_version).await?;
+            let dictionaries = DictionaryLoader::shared(FluvioAdmin::new(
+                VersionedSerialSocket::new(socket.clone(), config.clone(), versions.clone()),
+                metadata.clone(),
+            ));
 
             let spu_pool = OnceCell::new();
             Ok(Self {
@@ -148,6 +160,7 @@ impl Fluvio {
                 metadata,
                 watch_version,
                 metric: Arc::new(ClientMetrics::new()),
+                dictionaries,
             })
         } else {
             let platform_version = versions.platform_version().to_string();
@@ -156,7 +169,7 @@ impl Fluvio {
     }
 
     /// lazy get spu pool
-    async fn spu_pool(&self) -> Result<Arc<SpuSocketPool>> {
+    pub(crate) async fn spu_pool(&self) -> Result<Arc<SpuSocketPool>> {
         self.spu_pool
             .get_or_try_init(|| async {
                 let metadata =
@@ -223,8 +236,23 @@ impl Fluvio {
         if !spu_pool.topic_exists(topic.clone()).await? {
             return Err(FluvioError::TopicNotFound(topic).into());
         }
+        let topic_spec = spu_pool
+            .metadata
+            .topics()
+            .lookup_by_key(&topic)
+            .await?
+            .ok_or_else(|| FluvioError::TopicNotFound(topic.to_string()))?
+            .spec;
+        let dictionary = self.dictionaries.topic_dictionary(&topic_spec).await?;
 
-        TopicProducer::new(topic, spu_pool, Arc::new(config), self.metric.clone()).await
+        TopicProducer::new(
+            topic,
+            spu_pool,
+            Arc::new(config),
+            self.metric.clone(),
+            dictionary,
+        )
+        .await
     }
 
     /// Creates a new `PartitionConsumer` for the given topic and partition
@@ -246,7 +274,8 @@ impl Fluvio {
             partition,
             self.spu_pool().await?,
             self.metric.clone(),
-        ))
+        )
+        .with_dictionaries(self.dictionaries.clone()))
     }
 
     /// Creates a new `MultiplePartitionConsumer`
@@ -274,11 +303,10 @@ impl Fluvio {
         &self,
         strategy: PartitionSelectionStrategy,
     ) -> Result<MultiplePartitionConsumer> {
-        Ok(MultiplePartitionConsumer::new(
-            strategy,
-            self.spu_pool().await?,
-            self.metric.clone(),
-        ))
+        Ok(
+            MultiplePartitionConsumer::new(strategy, self.spu_pool().await?, self.metric.clone())
+                .with_dictionaries(self.dictionaries.clone()),
+        )
     }
 
     /// Creates a new [ConsumerStream] instance.
@@ -358,15 +386,192 @@ impl Fluvio {
         ConsumerRetryStream::new(self, self.cluster_config.clone(), config).await
     }
 
+    /// Creates a new [ConsumerStream] instance together with a [ConsumerHandle], which pauses,
+    /// resumes and seeks individual partitions while the stream is being consumed.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use fluvio::{consumer::ConsumerConfigExtBuilder, Fluvio, Offset};
+    /// use futures_util::StreamExt;
+    /// async fn do_consume_with_handle(fluvio: &Fluvio) -> anyhow::Result<()> {
+    ///    let (mut stream, handle) = fluvio
+    ///        .consumer_with_handle(ConsumerConfigExtBuilder::default().topic("my-topic").build()?)
+    ///        .await?;
+    ///    // slow sink for partition 0, keep reading other partitions
+    ///    handle.pause("my-topic", 0)?;
+    ///    handle.seek("my-topic", 1, Offset::from_end(10)).await?;
+    ///    while let Some(Ok(record)) = stream.next().await {
+    ///        println!("{}", String::from_utf8_lossy(record.as_ref()));
+    ///    }
+    ///    Ok(())
+    /// }
+    /// ```
+    pub async fn consumer_with_handle(
+        &self,
+        config: ConsumerConfigExt,
+    ) -> Result<(
+        impl ConsumerStream<Item = std::result::Result<Record, fluvio_protocol::link::ErrorCode>>
+        + use<>,
+        ConsumerHandle,
+    )> {
+        let controls = ConsumerControls::default();
+        let stream = ConsumerRetryStream::with_controls(
+            self,
+            self.cluster_config.clone(),
+            config,
+            controls.clone(),
+        )
+        .await?;
+        Ok((
+            stream,
+            ConsumerHandle::new(controls, self.spu_pool().await?),
+        ))
+    }
+
     /// Creates a new [ConsumerStream] instance without retry logic.
+    /// Partition streams are registered in `controls`.
     pub(crate) async fn consumer_with_config_inner(
         &self,
         config: ConsumerConfigExt,
+        controls: &ConsumerControls,
     ) -> Result<
         impl ConsumerStream<Item = std::result::Result<Record, fluvio_protocol::link::ErrorCode>>
         + use<>,
     > {
         let spu_pool = self.spu_pool().await?;
+        if let Some(pattern) = &config.topic_pattern {
+            let pattern = topic_pattern_regex(pattern)?;
+            let dictionaries = self.dictionaries.clone();
+            let metrics = self.metrics();
+            let mut watcher = TopicPatternWatcher::new(pattern, spu_pool.clone());
+            let mut loaded_topics = HashSet::new();
+
+            // partitions which can't be subscribed to yet are retried on next partition change,
+            // e.g. once the leader is online, but still read from the configured offset
+            let mut pending_initial = HashSet::new();
+            let mut partition_streams = vec![];
+            for replica in watcher.initial().await.added {
+                let mut config = config.clone();
+                config.topic = replica.topic.clone();
+                let consumer = PartitionConsumer::new(
+                    replica.topic.clone(),
+                    replica.partition,
+                    spu_pool.clone(),
+                    metrics.clone(),
+                )
+                .with_dictionaries(dictionaries.clone());
+                let stream = match load_topic_dictionaries(
+                    &dictionaries,
+                    &spu_pool,
+                    &replica.topic,
+                    &mut loaded_topics,
+                )
+                .await
+                {
+                    Ok(()) => consumer.consumer_stream_with_config(config).await,
+                    Err(err) => Err(err),
+                };
+                match stream {
+                    Ok(stream) => {
+                        controls.register(replica.clone(), stream.control());
+                        partition_streams.push(stream.with_replica(replica));
+                    }
+                    Err(err) => {
+                        warn!(%replica, %err, "could not subscribe to partition");
+                        watcher.unsubscribe(&replica);
+                        pending_initial.insert(replica);
+                    }
+                }
+            }
+
+            // partitions that show up later are read from the beginning,
+            // so records produced before they were noticed are not missed
+            let initial_config = config.clone();
+            let mut new_partition_config = config.clone();
+            new_partition_config.offset_start = crate::Offset::beginning();
+            let (sender, receiver) = async_channel::unbounded();
+            let stop = Arc::new(Notify::new());
+            let stop_subscription = stop.clone();
+            let controls = controls.clone();
+            fluvio_future::task::spawn(async move {
+                loop {
+                    let changes = select! {
+                        changes = watcher.next_changes() => changes,
+                        _ = stop_subscription.notified() => break,
+                    };
+                    if !changes.removed.is_empty() {
+                        for replica in &changes.removed {
+                            loaded_topics.remove(&replica.topic);
+                            pending_initial.remove(replica);
+                            controls.remove(replica);
+                        }
+                        if sender
+                            .send(SubscriptionChange::Remove(changes.removed))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+
+                    let mut streams = vec![];
+                    for replica in changes.added {
+                        let initial = pending_initial.remove(&replica);
+                        let mut config = if initial {
+                            initial_config.clone()
+                        } else {
+                            new_partition_config.clone()
+                        };
+                        config.topic = replica.topic.clone();
+                        let consumer = PartitionConsumer::new(
+                            replica.topic.clone(),
+                            replica.partition,
+                            spu_pool.clone(),
+                            metrics.clone(),
+                        )
+                        .with_dictionaries(dictionaries.clone());
+                        let stream = match load_topic_dictionaries(
+                            &dictionaries,
+                            &spu_pool,
+                            &replica.topic,
+                            &mut loaded_topics,
+                        )
+                        .await
+                        {
+                            Ok(()) => consumer.consumer_stream_with_config(config).await,
+                            Err(err) => Err(err),
+                        };
+                        match stream {
+                            Ok(stream) => {
+                                controls.register(replica.clone(), stream.control());
+                                streams.push(stream.with_replica(replica));
+                            }
+                            Err(err) => {
+                                warn!(%replica, %err, "could not subscribe to partition");
+                                watcher.unsubscribe(&replica);
+                                if initial {
+                                    pending_initial.insert(replica);
+                                }
+                            }
+                        }
+                    }
+                    if !streams.is_empty()
+                        && sender.send(SubscriptionChange::Add(streams)).await.is_err()
+                    {
+                        break;
+                    }
+                }
+                debug!("topic pattern subscription closed");
+            });
+
+            return Ok(MultiplePartitionConsumerStream::with_subscription(
+                partition_streams,
+                receiver,
+                stop,
+            ));
+        }
+
         let topic = &config.topic;
         let topics = spu_pool.metadata.topics();
         let topic_spec = topics
@@ -374,6 +579,7 @@ impl Fluvio {
             .await?
             .ok_or_else(|| FluvioError::TopicNotFound(topic.to_string()))?
             .spec;
+        self.dictionaries.topic_dictionary(&topic_spec).await?;
 
         let mirror_partition = if let Some(mirror) = &config.mirror {
             match topic_spec.replicas() {
@@ -417,8 +623,14 @@ impl Fluvio {
         let mut partition_streams = Vec::with_capacity(partitions.len());
         for partition in partitions {
             let consumer =
-                PartitionConsumer::new(topic.clone(), partition, spu_pool.clone(), self.metrics());
-            partition_streams.push(consumer.consumer_stream_with_config(config.clone()).await?);
+                PartitionConsumer::new(topic.clone(), partition, spu_pool.clone(), self.metrics())
+                    .with_dictionaries(self.dictionaries.clone());
+            let stream = consumer.consumer_stream_with_config(config.clone()).await?;
+            controls.register(
+                fluvio_protocol::record::ReplicaKey::new(topic.clone(), partition),
+                stream.control(),
+            );
+            partition_streams.push(stream);
         }
         Ok(MultiplePartitionConsumerStream::new(partition_streams))
     }
@@ -452,6 +664,36 @@ impl Fluvio {
             .collect())
     }
 
+    /// Returns lag of all consumers offsets: how many committed records and how much time
+    /// each consumer is behind the end of its partition.
+    ///
+    /// Offsets of partitions which no longer exist are skipped. Partitions whose lag
+    /// can't be fetched, e.g. because their leader is offline, are returned as errors
+    /// without failing the lag of other partitions.
+    pub async fn consumer_lag(&self) -> Result<Vec<Result<ConsumerLag, ConsumerLagError>>> {
+        let spu_pool = self.spu_pool().await?;
+        let partitions = spu_pool.metadata.partitions();
+        let mut consumers = vec![];
+        for consumer in self.consumer_offsets().await? {
+            let replica = fluvio_protocol::record::ReplicaKey::new(
+                consumer.topic.clone(),
+                consumer.partition,
+            );
+            if partitions.lookup_by_key(&replica).await?.is_some() {
+                consumers.push(consumer);
+            } else {
+                debug!(%replica, consumer_id = consumer.consumer_id, "partition not found");
+            }
+        }
+        consumers.sort();
+        Ok(join_all(
+            consumers
+                .into_iter()
+                .map(|consumer| ConsumerLag::fetch(consumer, &spu_pool)),
+        )
+        .await)
+    }
+
     /// Delete a consumer offset for the given name and the replica.
     pub async fn delete_consumer_offset(
         &self,
@@ -483,6 +725,34 @@ impl Fluvio {
         Ok(())
     }
 
+    /// Delete all records before the given offset in a partition.
+    ///
+    /// The log start offset of the partition is advanced to `offset`, which can't be
+    /// greater than the high watermark. Returns the new log start offset.
+    pub async fn delete_records(
+        &self,
+        topic: impl Into<String>,
+        partition: PartitionId,
+        offset: Offset,
+    ) -> Result<Offset> {
+        use fluvio_protocol::link::ErrorCode;
+        use fluvio_spu_schema::server::delete_records::DeleteRecordsRequest;
+
+        use crate::spu::SpuDirectory;
+
+        let request = DeleteRecordsRequest::new(topic, partition, offset);
+        let spu_pool = self.spu_pool().await?;
+        let socket = spu_pool.create_serial_socket(&request.replica_id).await?;
+        if socket.lookup_version::<DeleteRecordsRequest>().is_none() {
+            anyhow::bail!("delete records is not supported by the cluster");
+        }
+        let response = socket.send_receive(request).await?;
+        if response.error_code != ErrorCode::None {
+            anyhow::bail!("delete records failed with: {}", response.error_code);
+        }
+        Ok(response.log_start_offset)
+    }
+
     /// Provides an interface for managing a Fluvio cluster
     ///
     /// # Example
@@ -524,6 +794,28 @@ impl Fluvio {
     }
 }
 
+/// Loads dictionaries of a topic consumed through a topic pattern, once per topic
+async fn load_topic_dictionaries(
+    dictionaries: &DictionaryLoader,
+    spu_pool: &SpuSocketPool,
+    topic: &str,
+    loaded_topics: &mut HashSet<String>,
+) -> Result<()> {
+    if loaded_topics.contains(topic) {
+        return Ok(());
+    }
+    let topic_spec = spu_pool
+        .metadata
+        .topics()
+        .lookup_by_key(&topic.to_string())
+        .await?
+        .ok_or_else(|| FluvioError::TopicNotFound(topic.to_string()))?
+        .spec;
+    dictionaries.topic_dictionary(&topic_spec).await?;
+    loaded_topics.insert(topic.to_string());
+    Ok(())
+}
+
 /// The remote cluster is compatible with this client if its
 /// platform version is greater than this crate's
 /// `MINIMUM_PLATFORM_VERSION`.
@@ -0,0 +1,281 @@
+//! Interceptors hook into records between the application and the wire.
+//!
+//! A [`ProducerInterceptor`] sees each record before it enters the batch of its partition
+//! and the delivery result of each record. A [`ConsumerInterceptor`] sees each consumed
+//! record before it is yielded by the stream. Interceptors chain in the order they are
+//! added. An interceptor error is logged and the record continues through the chain, so a
+//! failing interceptor never stops the producer or the consumer.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use fluvio::{Fluvio, TopicProducerConfigBuilder};
+//! use fluvio::interceptor::{InterceptedRecord, ProducerInterceptor};
+//!
+//! struct Redact;
+//!
+//! impl ProducerInterceptor for Redact {
+//!     fn on_send(&self, _topic: &str, record: &mut InterceptedRecord) -> anyhow::Result<()> {
+//!         record.record.value = "<redacted>".into();
+//!         Ok(())
+//!     }
+//! }
+//!
+//! async fn produce(fluvio: &Fluvio) -> anyhow::Result<()> {
+//!     let config = TopicProducerConfigBuilder::default()
+//!         .interceptor(Arc::new(Redact))
+//!         .build()?;
+//!     let producer = fluvio.topic_producer_with_config("my-topic", config).await?;
+//!     producer.send("key", "secret").await?;
+//!     Ok(())
+//! }
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+
+use anyhow::Result;
+use tracing::error;
+
+use fluvio_protocol::record::{ConsumerRecord, Offset, Record};
+use fluvio_types::PartitionId;
+
+use crate::producer::ProducerError;
+
+/// Record passed to producer interceptors before it enters the batch of its partition
+#[derive(Debug)]
+pub struct InterceptedRecord {
+    pub record: Record,
+    /// Partition chosen by an interceptor. The partitioner of the producer is used when `None`.
+    pub partition: Option<PartitionId>,
+}
+
+impl InterceptedRecord {
+    pub(crate) fn new(record: Record) -> Self {
+        Self {
+            record,
+            partition: None,
+        }
+    }
+}
+
+/// Delivery result of a produced record
+#[derive(Debug)]
+pub struct DeliveryResult<'a> {
+    pub topic: &'a str,
+    pub partition: PartitionId,
+    /// Offset of the record, or the error its batch failed with
+    pub offset: std::result::Result<Offset, &'a ProducerError>,
+}
+
+/// Hooks of a [`crate::TopicProducer`]
+pub trait ProducerInterceptor: Send + Sync {
+    /// Called on each record before it enters the batch of its partition.
+    fn on_send(&self, _topic: &str, _record: &mut InterceptedRecord) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called with the delivery result of each record, once its batch is sent.
+    fn on_delivery(&self, _result: &DeliveryResult<'_>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Hooks of a consumer stream
+pub trait ConsumerInterceptor: Send + Sync {
+    /// Called on each consumed record before it is yielded.
+    fn on_consume(&self, topic: &str, record: &mut ConsumerRecord) -> Result<()>;
+}
+
+pub type SharedProducerInterceptor = Arc<dyn ProducerInterceptor>;
+pub type SharedConsumerInterceptor = Arc<dyn ConsumerInterceptor>;
+
+/// Producer interceptors, run in the order they were added
+#[derive(Clone, Default)]
+pub struct ProducerInterceptors(Arc<Vec<SharedProducerInterceptor>>);
+
+impl ProducerInterceptors {
+    pub(crate) fn push(&mut self, interceptor: SharedProducerInterceptor) {
+        Arc::make_mut(&mut self.0).push(interceptor);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn on_send(&self, topic: &str, record: &mut InterceptedRecord) {
+        for (index, interceptor) in self.0.iter().enumerate() {
+            if let Err(err) = interceptor.on_send(topic, record) {
+                error!(topic, index, %err, "producer interceptor failed on record");
+            }
+        }
+    }
+
+    pub(crate) fn on_delivery(&self, result: &DeliveryResult<'_>) {
+        for (index, interceptor) in self.0.iter().enumerate() {
+            if let Err(err) = interceptor.on_delivery(result) {
+                error!(topic = result.topic, index, %err, "producer interceptor failed on delivery");
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ProducerInterceptors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ProducerInterceptors({})", self.0.len())
+    }
+}
+
+/// Consumer interceptors, run in the order they were added
+#[derive(Clone, Default)]
+pub struct ConsumerInterceptors(Arc<Vec<SharedConsumerInterceptor>>);
+
+impl ConsumerInterceptors {
+    pub(crate) fn push(&mut self, interceptor: SharedConsumerInterceptor) {
+        Arc::make_mut(&mut self.0).push(interceptor);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn on_consume(&self, topic: &str, record: &mut ConsumerRecord) {
+        for (index, interceptor) in self.0.iter().enumerate() {
+            if let Err(err) = interceptor.on_consume(topic, record) {
+                error!(
+                    topic,
+                    index,
+                    offset = record.offset,
+                    %err,
+                    "consumer interceptor failed on record"
+                );
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ConsumerInterceptors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ConsumerInterceptors({})", self.0.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use fluvio_protocol::record::{Batch, RecordData, RecordKey};
+
+    use super::*;
+
+    struct Tag(&'static str);
+
+    impl ProducerInterceptor for Tag {
+        fn on_send(&self, _topic: &str, record: &mut InterceptedRecord) -> Result<()> {
+            let mut value = record.record.value.as_ref().to_vec();
+            value.extend_from_slice(self.0.as_bytes());
+            record.record.value = RecordData::from(value);
+            Ok(())
+        }
+    }
+
+    impl ConsumerInterceptor for Tag {
+        fn on_consume(&self, _topic: &str, record: &mut ConsumerRecord) -> Result<()> {
+            let mut value = record.value().to_vec();
+            value.extend_from_slice(self.0.as_bytes());
+            record.record.value = RecordData::from(value);
+            Ok(())
+        }
+    }
+
+    struct Failing;
+
+    impl ProducerInterceptor for Failing {
+        fn on_send(&self, _topic: &str, _record: &mut InterceptedRecord) -> Result<()> {
+            anyhow::bail!("broken")
+        }
+
+        fn on_delivery(&self, _result: &DeliveryResult<'_>) -> Result<()> {
+            anyhow::bail!("broken")
+        }
+    }
+
+    impl ConsumerInterceptor for Failing {
+        fn on_consume(&self, _topic: &str, _record: &mut ConsumerRecord) -> Result<()> {
+            anyhow::bail!("broken")
+        }
+    }
+
+    #[derive(Default)]
+    struct Deliveries(Mutex<Vec<(PartitionId, Option<Offset>)>>);
+
+    impl ProducerInterceptor for Deliveries {
+        fn on_delivery(&self, result: &DeliveryResult<'_>) -> Result<()> {
+            self.0
+                .lock()
+                .unwrap()
+                .push((result.partition, result.offset.ok()));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_producer_interceptors_chain_in_order() {
+        //given
+        let mut interceptors = ProducerInterceptors::default();
+        interceptors.push(Arc::new(Tag("-a")));
+        interceptors.push(Arc::new(Failing));
+        interceptors.push(Arc::new(Tag("-b")));
+        let mut record = InterceptedRecord::new(Record::from((RecordKey::NULL, "v")));
+
+        //when
+        interceptors.on_send("topic", &mut record);
+
+        //then
+        assert_eq!(record.record.value.as_ref(), b"v-a-b");
+        assert_eq!(record.partition, None);
+    }
+
+    #[test]
+    fn test_producer_interceptors_on_delivery() {
+        let deliveries = Arc::new(Deliveries::default());
+        let mut interceptors = ProducerInterceptors::default();
+        interceptors.push(Arc::new(Failing));
+        interceptors.push(deliveries.clone());
+        let error = ProducerError::Internal("spu down".to_owned());
+
+        interceptors.on_delivery(&DeliveryResult {
+            topic: "topic",
+            partition: 1,
+            offset: Ok(10),
+        });
+        interceptors.on_delivery(&DeliveryResult {
+            topic: "topic",
+            partition: 2,
+            offset: Err(&error),
+        });
+
+        assert_eq!(
+            *deliveries.0.lock().unwrap(),
+            vec![(1, Some(10)), (2, None)]
+        );
+    }
+
+    #[test]
+    fn test_consumer_interceptors_chain_in_order() {
+        //given
+        let mut interceptors = ConsumerInterceptors::default();
+        interceptors.push(Arc::new(Failing));
+        interceptors.push(Arc::new(Tag("-a")));
+        interceptors.push(Arc::new(Tag("-b")));
+        let mut batch = Batch::default();
+        batch.add_record(Record::new("v"));
+        let mut record = batch.into_consumer_records_iter(0).next().expect("record");
+
+        //when
+        interceptors.on_consume("topic", &mut record);
+
+        //then
+        assert_eq!(record.value(), b"v-a-b");
+        assert_eq!(format!("{interceptors:?}"), "ConsumerInterceptors(3)");
+    }
+}
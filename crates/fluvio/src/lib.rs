@@ -5,16 +5,22 @@
 #![doc = include_str!("../README.md")]
 
 mod admin;
+mod dictionary;
 mod error;
 mod fluvio;
 mod offset;
 mod producer;
 mod sync;
 
+pub mod archive;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod blocking;
 pub mod config;
 pub mod consumer;
+pub mod interceptor;
 pub mod metrics;
 pub mod spu;
+pub mod typed;
 
 pub use error::FluvioError;
 pub use config::{FluvioClusterConfig, FluvioConfig};
@@ -22,7 +28,7 @@ pub use producer::{
     ProducerCallback, SharedProducerCallback, ProduceCompletionBatchEvent,
     TopicProducerConfigBuilder, TopicProducerConfig, TopicProducer, TopicProducerPool, RecordKey,
     ProduceOutput, FutureRecordMetadata, RecordMetadata, DeliverySemantic, RetryPolicy,
-    RetryStrategy, Partitioner, PartitionerConfig, ProducerError,
+    RetryStrategy, Partitioner, PartitionerConfig, PartitionerKind, ProducerError,
 };
 #[cfg(feature = "smartengine")]
 pub use producer::{SmartModuleChainBuilder, SmartModuleConfig, SmartModuleInitialData};
@@ -39,9 +45,12 @@ pub use offset::Offset;
 pub use crate::admin::FluvioAdmin;
 pub use crate::fluvio::Fluvio;
 
-pub use fluvio_compression::Compression;
+pub use fluvio_compression::{Compression, ZstdDictionary};
 
 pub use fluvio_types::PartitionId;
+
+/// Propagate trace context of client spans to SPU and SC
+pub use fluvio_socket::trace_context;
 use tracing::instrument;
 
 /// The minimum VERSION of the Fluvio Platform that this client is compatible with.
@@ -196,6 +205,10 @@ pub mod metadata {
         pub use fluvio_sc_schema::tableformat::*;
     }
 
+    pub mod dictionary {
+        pub use fluvio_sc_schema::dictionary::*;
+    }
+
     pub mod core {
         pub use fluvio_sc_schema::core::*;
     }
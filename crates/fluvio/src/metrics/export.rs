@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use fluvio_types::PartitionId;
+use fluvio_types::openmetrics::{Histogram, MetricType, MetricsEncoder, OPENMETRICS_CONTENT_TYPE};
+
+use super::{ClientMetrics, PartitionLatency, RecordCounter};
+
+/// Exports client metrics, so services embedding the client can expose them
+/// to their monitoring system.
+pub trait MetricsExporter {
+    type Output;
+
+    fn export(&self, metrics: &ClientMetrics) -> Self::Output;
+}
+
+/// Exports client metrics in OpenMetrics text format
+///
+/// ```no_run
+/// use fluvio::Fluvio;
+/// use fluvio::metrics::{MetricsExporter, OpenMetricsExporter};
+///
+/// async fn scrape(fluvio: &Fluvio) -> String {
+///     OpenMetricsExporter.export(&fluvio.metrics())
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OpenMetricsExporter;
+
+impl OpenMetricsExporter {
+    /// content type of exported text
+    pub const CONTENT_TYPE: &'static str = OPENMETRICS_CONTENT_TYPE;
+}
+
+impl MetricsExporter for OpenMetricsExporter {
+    type Output = String;
+
+    fn export(&self, metrics: &ClientMetrics) -> String {
+        let mut encoder = MetricsEncoder::new();
+
+        let counters = [
+            ("consumer", metrics.consumer()),
+            ("producer_client", metrics.producer_client()),
+            ("producer_connector", metrics.producer_connector()),
+        ];
+        encode_counters(
+            &mut encoder,
+            "fluvio_client_records",
+            "records produced or consumed",
+            &counters,
+            RecordCounter::records,
+        );
+        encode_counters(
+            &mut encoder,
+            "fluvio_client_bytes",
+            "bytes produced or consumed",
+            &counters,
+            RecordCounter::bytes,
+        );
+
+        let partitions = metrics.latency().partitions();
+        encode_latency(
+            &mut encoder,
+            "fluvio_client_send_to_ack_seconds",
+            "time from first record of batch is sent until batch is acknowledged",
+            &partitions,
+            PartitionLatency::send_to_ack,
+        );
+        encode_latency(
+            &mut encoder,
+            "fluvio_client_batch_fill_seconds",
+            "time from first record of batch is sent until batch is sent to SPU",
+            &partitions,
+            PartitionLatency::batch_fill,
+        );
+        encode_latency(
+            &mut encoder,
+            "fluvio_client_offset_round_trip_seconds",
+            "round trip of offset requests of consumer streams",
+            &partitions,
+            PartitionLatency::offset_round_trip,
+        );
+        encode_latency(
+            &mut encoder,
+            "fluvio_client_fetch_round_trip_seconds",
+            "time from fetch request or offset update of consumer stream until next fetch response",
+            &partitions,
+            PartitionLatency::fetch_round_trip,
+        );
+        encode_latency(
+            &mut encoder,
+            "fluvio_client_delivery_delay_seconds",
+            "time from record timestamp until record is consumed",
+            &partitions,
+            PartitionLatency::delivery_delay,
+        );
+
+        encoder.finish()
+    }
+}
+
+fn encode_counters(
+    encoder: &mut MetricsEncoder,
+    name: &str,
+    help: &str,
+    counters: &[(&str, &RecordCounter)],
+    value: fn(&RecordCounter) -> u64,
+) {
+    encoder.family(name, MetricType::Counter, help);
+    for (source, counter) in counters {
+        encoder.sample(&[("source", *source)], value(counter));
+    }
+}
+
+fn encode_latency(
+    encoder: &mut MetricsEncoder,
+    name: &str,
+    help: &str,
+    partitions: &[(String, PartitionId, Arc<PartitionLatency>)],
+    histogram: fn(&PartitionLatency) -> &Histogram,
+) {
+    encoder.family(name, MetricType::Histogram, help);
+    for (topic, partition, latency) in partitions {
+        let partition = partition.to_string();
+        encoder.histogram(
+            &[("topic", topic.as_str()), ("partition", partition.as_str())],
+            histogram(latency),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_open_metrics_export() {
+        //given
+        let metrics = ClientMetrics::new();
+        metrics.producer_client().add_records(3);
+        metrics.producer_client().add_bytes(120);
+        let latency = metrics.latency().partition("orders", 1);
+        latency.send_to_ack().observe(0.002);
+        latency.delivery_delay().observe(30.0);
+        latency.fetch_round_trip().observe(0.5);
+
+        //when
+        let text = OpenMetricsExporter.export(&metrics);
+
+        //then
+        assert!(text.contains("# TYPE fluvio_client_records counter\n"));
+        assert!(text.contains("fluvio_client_records_total{source=\"producer_client\"} 3\n"));
+        assert!(text.contains("fluvio_client_bytes_total{source=\"producer_client\"} 120\n"));
+        assert!(text.contains("fluvio_client_records_total{source=\"consumer\"} 0\n"));
+        assert!(text.contains(
+            "fluvio_client_send_to_ack_seconds_bucket{topic=\"orders\",partition=\"1\",le=\"0.0025\"} 1\n"
+        ));
+        assert!(text.contains(
+            "fluvio_client_send_to_ack_seconds_count{topic=\"orders\",partition=\"1\"} 1\n"
+        ));
+        assert!(text.contains(
+            "fluvio_client_delivery_delay_seconds_bucket{topic=\"orders\",partition=\"1\",le=\"60.0\"} 1\n"
+        ));
+        assert!(text.contains(
+            "fluvio_client_batch_fill_seconds_count{topic=\"orders\",partition=\"1\"} 0\n"
+        ));
+        assert!(text.contains(
+            "fluvio_client_fetch_round_trip_seconds_count{topic=\"orders\",partition=\"1\"} 1\n"
+        ));
+        assert!(text.ends_with("# EOF\n"));
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, RwLock, Weak};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use fluvio_types::PartitionId;
+use fluvio_types::openmetrics::Histogram;
+
+/// buckets for delivery delay in seconds, records may be consumed long after they are produced
+pub const DELIVERY_DELAY_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 60.0, 300.0, 3600.0,
+];
+
+/// Latency histograms of a topic partition, in seconds
+#[derive(Debug)]
+pub struct PartitionLatency {
+    send_to_ack: Histogram,
+    batch_fill: Histogram,
+    offset_round_trip: Histogram,
+    fetch_round_trip: Histogram,
+    delivery_delay: Histogram,
+}
+
+impl Default for PartitionLatency {
+    fn default() -> Self {
+        Self {
+            send_to_ack: Histogram::latency(),
+            batch_fill: Histogram::latency(),
+            offset_round_trip: Histogram::latency(),
+            // includes waiting for new records, like delivery delay
+            fetch_round_trip: Histogram::new(DELIVERY_DELAY_BUCKETS),
+            delivery_delay: Histogram::new(DELIVERY_DELAY_BUCKETS),
+        }
+    }
+}
+
+impl PartitionLatency {
+    /// time from the first record of a batch is sent until the SPU acknowledges the batch.
+    /// Not recorded for `AtMostOnce` delivery, which doesn't wait for acknowledgement.
+    pub fn send_to_ack(&self) -> &Histogram {
+        &self.send_to_ack
+    }
+
+    /// time from the first record of a batch is sent until the batch is sent to the SPU
+    pub fn batch_fill(&self) -> &Histogram {
+        &self.batch_fill
+    }
+
+    /// round trip of offset requests of consumer streams: offsets fetched when the stream starts
+    /// and offset updates sent to the SPU as records are consumed.
+    pub fn offset_round_trip(&self) -> &Histogram {
+        &self.offset_round_trip
+    }
+
+    /// time from the fetch request of a consumer stream, or from an offset update sent to the SPU,
+    /// until the next fetch response. The SPU responds once new records are available,
+    /// so it includes waiting for records to be produced.
+    pub fn fetch_round_trip(&self) -> &Histogram {
+        &self.fetch_round_trip
+    }
+
+    /// time from the record timestamp until the record is received by the consumer
+    pub fn delivery_delay(&self) -> &Histogram {
+        &self.delivery_delay
+    }
+}
+
+/// Times [`PartitionLatency::fetch_round_trip`] of a consumer stream.
+/// Requests sent before a response arrives are timed from the first of them.
+#[derive(Debug, Default)]
+pub(crate) struct FetchRoundTrip {
+    requested: Mutex<Option<Instant>>,
+}
+
+impl FetchRoundTrip {
+    /// fetch request or offset update is sent
+    pub(crate) fn requested(&self) {
+        self.requested
+            .lock()
+            .expect("Poisoned lock")
+            .get_or_insert_with(Instant::now);
+    }
+
+    /// fetch response is received
+    pub(crate) fn responded(&self, latency: &PartitionLatency) {
+        let requested = self.requested.lock().expect("Poisoned lock").take();
+        if let Some(requested) = requested {
+            latency
+                .fetch_round_trip()
+                .observe_duration(requested.elapsed());
+        }
+    }
+}
+
+/// Latency histograms by topic and partition.
+///
+/// Histograms are kept while producers and consumers of the partition hold them,
+/// so partitions which are no longer produced or consumed are removed.
+#[derive(Debug, Default)]
+pub struct LatencyMetrics {
+    partitions: RwLock<BTreeMap<(String, PartitionId), Weak<PartitionLatency>>>,
+}
+
+impl LatencyMetrics {
+    /// histograms of the partition, created on first use
+    pub fn partition(&self, topic: &str, partition: PartitionId) -> Arc<PartitionLatency> {
+        let key = (topic.to_owned(), partition);
+        if let Some(latency) = self
+            .partitions
+            .read()
+            .expect("Poisoned lock")
+            .get(&key)
+            .and_then(Weak::upgrade)
+        {
+            return latency;
+        }
+
+        let mut partitions = self.partitions.write().expect("Poisoned lock");
+        if let Some(latency) = partitions.get(&key).and_then(Weak::upgrade) {
+            return latency;
+        }
+        // remove partitions which are no longer used
+        partitions.retain(|_, latency| latency.strong_count() > 0);
+        let latency = Arc::new(PartitionLatency::default());
+        partitions.insert(key, Arc::downgrade(&latency));
+        latency
+    }
+
+    /// histograms of all partitions in use, ordered by topic and partition
+    pub fn partitions(&self) -> Vec<(String, PartitionId, Arc<PartitionLatency>)> {
+        self.partitions
+            .read()
+            .expect("Poisoned lock")
+            .iter()
+            .filter_map(|((topic, partition), latency)| {
+                latency
+                    .upgrade()
+                    .map(|latency| (topic.clone(), *partition, latency))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_latency_is_shared() {
+        let metrics = LatencyMetrics::default();
+
+        let b0 = metrics.partition("b", 0);
+        b0.batch_fill().observe(0.5);
+        let a1 = metrics.partition("a", 1);
+        a1.send_to_ack().observe(0.1);
+        metrics.partition("b", 0).batch_fill().observe(0.25);
+
+        let partitions = metrics.partitions();
+        let keys: Vec<_> = partitions
+            .iter()
+            .map(|(topic, partition, _)| (topic.as_str(), *partition))
+            .collect();
+        assert_eq!(keys, vec![("a", 1), ("b", 0)]);
+        assert_eq!(partitions[1].2.batch_fill().count(), 2);
+        assert_eq!(partitions[1].2.batch_fill().sum(), 0.75);
+        assert_eq!(partitions[1].2.send_to_ack().count(), 0);
+    }
+
+    #[test]
+    fn test_fetch_round_trip_from_first_request() {
+        let latency = PartitionLatency::default();
+        let round_trip = FetchRoundTrip::default();
+
+        // responses without request are not timed
+        round_trip.responded(&latency);
+        assert_eq!(latency.fetch_round_trip().count(), 0);
+
+        round_trip.requested();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        round_trip.requested();
+        round_trip.responded(&latency);
+        round_trip.responded(&latency);
+
+        assert_eq!(latency.fetch_round_trip().count(), 1);
+        assert!(latency.fetch_round_trip().sum() >= 0.02);
+        assert_eq!(latency.offset_round_trip().count(), 0);
+    }
+
+    #[test]
+    fn test_unused_partition_latency_is_removed() {
+        let metrics = LatencyMetrics::default();
+
+        let a0 = metrics.partition("a", 0);
+        a0.batch_fill().observe(0.5);
+        drop(a0);
+        assert!(metrics.partitions().is_empty());
+
+        // histograms start over once partition is used again
+        let a0 = metrics.partition("a", 0);
+        assert_eq!(a0.batch_fill().count(), 0);
+        let _b0 = metrics.partition("b", 0);
+        assert_eq!(metrics.partitions.read().expect("Poisoned lock").len(), 2);
+    }
+}
@@ -1,5 +1,12 @@
 use serde::{Serialize, Deserialize};
 
+mod export;
+mod latency;
+
+pub use export::{MetricsExporter, OpenMetricsExporter};
+pub use latency::{DELIVERY_DELAY_BUCKETS, LatencyMetrics, PartitionLatency};
+pub(crate) use latency::FetchRoundTrip;
+
 #[cfg(feature = "smartengine")]
 use std::collections::HashMap;
 
@@ -11,6 +18,8 @@ pub struct ClientMetrics {
     consumer: RecordCounter,
     producer_connector: RecordCounter,
     producer_client: RecordCounter,
+    #[serde(skip)]
+    latency: LatencyMetrics,
     #[cfg(feature = "smartengine")]
     smartmodules: Mutex<HashMap<String, fluvio_smartengine::metrics::SmartModuleChainMetrics>>,
 }
@@ -37,6 +46,12 @@ impl ClientMetrics {
         &self.producer_client
     }
 
+    /// latency histograms by topic and partition
+    #[inline]
+    pub fn latency(&self) -> &LatencyMetrics {
+        &self.latency
+    }
+
     #[cfg(feature = "smartengine")]
     pub(crate) fn metrics_append(
         &self,
@@ -70,6 +85,16 @@ cfg_if::cfg_if! {
             #[inline]
             pub(crate) fn add_bytes(&self, _value: u64) {
             }
+
+            #[inline]
+            pub fn records(&self) -> u64 {
+                0
+            }
+
+            #[inline]
+            pub fn bytes(&self) -> u64 {
+                0
+            }
         }
 
     } else {
@@ -91,6 +116,16 @@ cfg_if::cfg_if! {
             pub(crate) fn add_bytes(&self, value: u64) {
                 self.bytes.fetch_add(value, Ordering::SeqCst);
             }
+
+            #[inline]
+            pub fn records(&self) -> u64 {
+                self.records.load(Ordering::SeqCst)
+            }
+
+            #[inline]
+            pub fn bytes(&self) -> u64 {
+                self.bytes.load(Ordering::SeqCst)
+            }
         }
 
     }
@@ -312,15 +312,26 @@ impl Offset {
 pub(crate) async fn fetch_offsets(
     client: &mut VersionedSerialSocket,
     replica: &ReplicaKey,
+) -> Result<FetchOffsetPartitionResponse, FluvioError> {
+    fetch_offsets_with_timestamp(client, replica, None).await
+}
+
+/// Fetches offsets together with timestamps of the batch containing `timestamp_offset`
+/// and the last committed batch
+pub(crate) async fn fetch_offsets_with_timestamp(
+    client: &mut VersionedSerialSocket,
+    replica: &ReplicaKey,
+    timestamp_offset: Option<i64>,
 ) -> Result<FetchOffsetPartitionResponse, FluvioError> {
     debug!("fetching offset for replica: {}", replica);
 
-    let response = client
-        .send_receive(FetchOffsetsRequest::new(
-            replica.topic.to_owned(),
-            replica.partition,
-        ))
-        .await?;
+    let mut request = FetchOffsetsRequest::new(replica.topic.to_owned(), replica.partition);
+    for topic in request.topics.iter_mut() {
+        for partition in topic.partitions.iter_mut() {
+            partition.timestamp_offset = timestamp_offset;
+        }
+    }
+    let response = client.send_receive(request).await?;
 
     trace!(
         "receive fetch response replica: {}, {:#?}",
@@ -352,6 +363,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromBeginning(3);
@@ -366,6 +378,7 @@ mod tests {
             partition_index: 0,
             start_offset: 5,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromBeginning(3);
@@ -380,6 +393,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromBeginning(15);
@@ -394,6 +408,7 @@ mod tests {
             partition_index: 0,
             start_offset: 5,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromBeginning(15);
@@ -408,6 +423,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromEnd(3);
@@ -422,6 +438,7 @@ mod tests {
             partition_index: 0,
             start_offset: 6,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromEnd(6);
@@ -436,6 +453,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromEnd(100);
@@ -452,6 +470,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::Absolute(4);
@@ -467,6 +486,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 15,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromBeginning(3);
@@ -482,6 +502,7 @@ mod tests {
             partition_index: 0,
             start_offset: 10,
             last_stable_offset: 22,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromBeginning(5);
@@ -497,6 +518,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 15,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromEnd(3);
@@ -512,6 +534,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 15,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromEnd(10);
@@ -13,14 +13,15 @@ use fluvio_compression::Compression;
 use fluvio_types::PartitionId;
 use serde::{Serialize, Deserialize};
 
-use crate::producer::partitioning::{Partitioner, SiphashRoundRobinPartitioner};
+use crate::interceptor::{ProducerInterceptors, SharedProducerInterceptor};
+use crate::producer::partitioning::{Partitioner, PartitionerKind};
 
 use super::accumulator::SharedProducerCallback;
 use super::partitioning::SpecificPartitioner;
 
 const DEFAULT_LINGER_MS: u64 = 0;
 const DEFAULT_TIMEOUT_MS: u64 = 1500;
-const DEFAULT_BATCH_SIZE_BYTES: usize = 16_384;
+pub(crate) const DEFAULT_BATCH_SIZE_BYTES: usize = 16_384;
 const DEFAULT_BATCH_QUEUE_SIZE: usize = 100;
 const DEFAULT_MAX_REQUEST_SIZE: usize = 1_048_576;
 
@@ -46,7 +47,7 @@ fn default_linger_duration() -> Duration {
 }
 
 fn default_partitioner() -> Arc<dyn Partitioner + Send + Sync> {
-    Arc::new(SiphashRoundRobinPartitioner::new())
+    PartitionerKind::default().partitioner()
 }
 
 fn default_timeout() -> Duration {
@@ -87,7 +88,10 @@ pub struct TopicProducerConfig {
     #[builder(default = "default_linger_duration()")]
     pub(crate) linger: Duration,
     /// Partitioner assigns the partition to each record that needs to be send
-    #[builder(default = "default_partitioner()")]
+    #[builder(
+        setter(custom),
+        field(ty = "Option<BuilderPartitioner>", build = "self.build_partitioner()")
+    )]
     pub(crate) partitioner: Arc<dyn Partitioner + Send + Sync>,
 
     /// Compression algorithm used by Fluvio producer to compress data.
@@ -124,12 +128,53 @@ pub struct TopicProducerConfig {
     /// Callback that will be called after the record is sent to the server.
     #[builder(setter(into, strip_option), default)]
     pub(crate) callback: Option<SharedProducerCallback>,
+
+    /// Interceptors that see each record before it is batched, and its delivery result.
+    #[builder(setter(custom), default)]
+    pub(crate) interceptors: ProducerInterceptors,
+}
+
+/// Partitioner set on the builder, built-in partitioners are created with the batch size
+#[derive(Clone)]
+pub(crate) enum BuilderPartitioner {
+    Custom(Arc<dyn Partitioner + Send + Sync>),
+    Kind(PartitionerKind),
 }
 
 impl TopicProducerConfigBuilder {
+    /// Partitioner assigns the partition to each record that needs to be send
+    pub fn partitioner(&mut self, partitioner: Arc<dyn Partitioner + Send + Sync>) -> &mut Self {
+        self.partitioner = Some(BuilderPartitioner::Custom(partitioner));
+        self
+    }
+
     pub fn set_specific_partitioner(&mut self, partition_id: PartitionId) -> &mut Self {
         self.partitioner(Arc::new(SpecificPartitioner::new(partition_id)))
     }
+
+    /// Uses one of the built-in partitioners, configured with the batch size of the producer
+    pub fn set_partitioner_kind(&mut self, kind: PartitionerKind) -> &mut Self {
+        self.partitioner = Some(BuilderPartitioner::Kind(kind));
+        self
+    }
+
+    fn build_partitioner(&self) -> Arc<dyn Partitioner + Send + Sync> {
+        match &self.partitioner {
+            Some(BuilderPartitioner::Custom(partitioner)) => partitioner.clone(),
+            Some(BuilderPartitioner::Kind(kind)) => {
+                kind.partitioner_with_batch_size(self.batch_size.unwrap_or_else(default_batch_size))
+            }
+            None => default_partitioner(),
+        }
+    }
+
+    /// Adds an interceptor, it runs after the interceptors added before
+    pub fn interceptor(&mut self, interceptor: SharedProducerInterceptor) -> &mut Self {
+        self.interceptors
+            .get_or_insert_with(Default::default)
+            .push(interceptor);
+        self
+    }
 }
 
 impl TopicProducerConfig {
@@ -184,6 +229,7 @@ impl Default for TopicProducerConfig {
             delivery_semantic: default_delivery(),
             smartmodules: vec![],
             callback: None,
+            interceptors: Default::default(),
         }
     }
 }
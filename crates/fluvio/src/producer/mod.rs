@@ -21,6 +21,7 @@ use fluvio_future::timer::sleep;
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::record::Record;
 use fluvio_compression::Compression;
+use fluvio_compression::ZstdDictionary;
 #[cfg(feature = "compress")]
 use fluvio_sc_schema::topic::CompressionAlgorithm;
 use fluvio_sc_schema::topic::TopicSpec;
@@ -45,10 +46,14 @@ use crate::spu::SpuPool;
 use crate::spu::SpuSocketPool;
 use crate::sync::StoreContext;
 use crate::FluvioError;
+use crate::interceptor::InterceptedRecord;
 use crate::metrics::ClientMetrics;
 use crate::producer::accumulator::{RecordAccumulator, PushRecord};
 
-pub use crate::producer::partitioning::{Partitioner, PartitionerConfig};
+pub use crate::producer::partitioning::{
+    Partitioner, PartitionerConfig, PartitionerKind, StickyPartitioner, Murmur2Partitioner,
+    ConsistentPartitioner,
+};
 
 use self::accumulator::BatchEvents;
 use self::accumulator::BatchHandler;
@@ -87,6 +92,7 @@ where
     batch_events: Arc<BatchEvents>,
     client_metric: Arc<ClientMetrics>,
     callback: Option<SharedProducerCallback>,
+    dictionary: Option<ZstdDictionary>,
 }
 
 impl ProducerPool {
@@ -97,6 +103,7 @@ impl ProducerPool {
         batches: Arc<HashMap<PartitionId, BatchHandler>>,
         client_metric: Arc<ClientMetrics>,
         callback: Option<SharedProducerCallback>,
+        dictionary: Option<ZstdDictionary>,
     ) -> Self
     where
         S: SpuPool + Send + Sync + 'static,
@@ -117,6 +124,7 @@ impl ProducerPool {
                 batch_events: batch_events.clone(),
                 client_metric: client_metric.clone(),
                 callback: callback.clone(),
+                dictionary: dictionary.clone(),
             };
 
             PartitionProducer::start(
@@ -245,6 +253,7 @@ where
     partition_tracker: Arc<PartitionAvailabilityTracker>,
     producer_pool: Arc<RwLock<ProducerPool>>,
     metrics: Arc<ClientMetrics>,
+    dictionary: Option<ZstdDictionary>,
 }
 
 impl<S> InnerTopicProducer<S>
@@ -258,24 +267,16 @@ where
     }
 
     async fn push_record(self: Arc<Self>, record: Record) -> Result<PushRecord> {
-        let partition_count = self.partition_tracker.partition_count();
-        let available_partitions = self.partition_tracker.available_partitions();
-        let available_partitions_lock = available_partitions.read().await;
-
-        let partition_config = PartitionerConfig {
-            partition_count,
-            available_partitions: available_partitions_lock.clone(),
+        let mut intercepted = InterceptedRecord::new(record);
+        self.config
+            .interceptors
+            .on_send(&self.topic, &mut intercepted);
+        let InterceptedRecord { record, partition } = intercepted;
+        let partition = match partition {
+            Some(partition) => partition,
+            None => self.select_partition(&record).await,
         };
 
-        drop(available_partitions_lock);
-
-        let key = record.key.as_ref().map(|k| k.as_ref());
-        let value = record.value.as_ref();
-        let partition = self
-            .config
-            .partitioner
-            .partition(&partition_config, key, value);
-
         let mut producer_pool = self.producer_pool.write().await;
 
         if let Some(error) = producer_pool.last_error(partition).await {
@@ -289,6 +290,7 @@ where
             batch_events: BatchEvents::shared(),
             client_metric: self.metrics.clone(),
             callback: self.config.callback.clone(),
+            dictionary: self.dictionary.clone(),
         };
 
         let _ = producer_pool
@@ -308,6 +310,25 @@ where
         Ok(push_record)
     }
 
+    async fn select_partition(&self, record: &Record) -> PartitionId {
+        let partition_count = self.partition_tracker.partition_count();
+        let available_partitions = self.partition_tracker.available_partitions();
+        let available_partitions_lock = available_partitions.read().await;
+
+        let partition_config = PartitionerConfig {
+            partition_count,
+            available_partitions: available_partitions_lock.clone(),
+        };
+
+        drop(available_partitions_lock);
+
+        let key = record.key.as_ref().map(|k| k.as_ref());
+        let value = record.value.as_ref();
+        self.config
+            .partitioner
+            .partition(&partition_config, key, value)
+    }
+
     async fn clear_errors(&self) {
         self.producer_pool.read().await.clear_errors().await;
     }
@@ -518,6 +539,7 @@ where
         spu_pool: Arc<S>,
         config: Arc<TopicProducerConfig>,
         metrics: Arc<ClientMetrics>,
+        dictionary: Option<ZstdDictionary>,
     ) -> Result<Self> {
         let topic_store = spu_pool.topics();
         let topic_spec = topic_store
@@ -553,6 +575,7 @@ where
             Arc::new(record_accumulator.batches().await),
             metrics.clone(),
             config.callback.clone(),
+            dictionary.clone(),
         );
 
         let partition_tracker = PartitionAvailabilityTracker::start(
@@ -571,6 +594,7 @@ where
                 record_accumulator: Arc::new(record_accumulator),
                 partition_tracker,
                 metrics: metrics.clone(),
+                dictionary,
             }),
             #[cfg(feature = "smartengine")]
             sm_chain: Default::default(),
@@ -717,6 +741,13 @@ fn determine_producer_compression_algo(
     config: Arc<TopicProducerConfig>,
     topic_spec: fluvio_sc_schema::topic::TopicSpec,
 ) -> Result<Compression> {
+    // SPU converts batches to topic compression
+    if topic_spec.is_recompress() {
+        if let Some(compression) = config.compression {
+            return Ok(compression);
+        }
+    }
+
     let result = match topic_spec.get_compression_type() {
         CompressionAlgorithm::Any => config.compression.unwrap_or_default(),
         CompressionAlgorithm::Gzip => match config.compression {
@@ -848,7 +879,7 @@ mod tests {
         let spu_pool = Arc::new(SpuPoolMock { topics, partitions });
         spu_pool.topics().store().sync_all(topic_2_partitions).await;
         spu_pool.partitions().store().sync_all(partition_2).await;
-        let producer = TopicProducer::new(topic.clone(), spu_pool.clone(), config, metrics)
+        let producer = TopicProducer::new(topic.clone(), spu_pool.clone(), config, metrics, None)
             .await
             .expect("producer");
 
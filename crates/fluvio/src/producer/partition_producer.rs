@@ -1,4 +1,9 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
 
 use adaptive_backoff::prelude::{
     Backoff, BackoffBuilder, ExponentialBackoff, ExponentialBackoffBuilder,
@@ -9,15 +14,18 @@ use fluvio_types::defaults::{
 };
 use tracing::{debug, info, instrument, error, trace};
 
+use fluvio_compression::ZstdDictionary;
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::record::{RawRecords, Batch};
 use fluvio_spu_schema::produce::{DefaultPartitionRequest, DefaultTopicRequest, DefaultProduceRequest};
+use fluvio_future::task::spawn;
 use fluvio_future::timer::sleep;
 use fluvio_types::SpuId;
 use fluvio_types::event::StickyEvent;
 
 use crate::error::{Result, FluvioError};
-use crate::metrics::ClientMetrics;
+use crate::interceptor::DeliveryResult;
+use crate::metrics::{ClientMetrics, PartitionLatency};
 use crate::producer::accumulator::ProducePartitionResponseFuture;
 use crate::producer::config::DeliverySemantic;
 use fluvio_socket::VersionedSerialSocket;
@@ -28,6 +36,7 @@ use super::{
     PartitionProducerParams, ProduceCompletionBatchEvent, SharedProducerCallback, ProducerError,
 };
 use super::accumulator::{BatchEvents, BatchesDeque};
+use super::record::BatchMetadata;
 use super::event::EventHandler;
 
 /// Struct that is responsible for sending produce requests to the SPU in a given partition.
@@ -42,7 +51,11 @@ where
     batch_events: Arc<BatchEvents>,
     last_error: Arc<RwLock<Option<ProducerError>>>,
     metrics: Arc<ClientMetrics>,
+    latency: Arc<PartitionLatency>,
     callback: Option<SharedProducerCallback>,
+    dictionary: Option<ZstdDictionary>,
+    /// set when SPU reported this client over its quota
+    throttled_until: Arc<Mutex<Option<Instant>>>,
 }
 
 impl<S> PartitionProducer<S>
@@ -54,6 +67,10 @@ where
         replica: ReplicaKey,
         last_error: Arc<RwLock<Option<ProducerError>>>,
     ) -> Self {
+        let latency = params
+            .client_metric
+            .latency()
+            .partition(&replica.topic, replica.partition);
         Self {
             config: params.config,
             replica,
@@ -62,7 +79,10 @@ where
             batch_events: params.batch_events,
             last_error,
             metrics: params.client_metric,
+            latency,
             callback: params.callback,
+            dictionary: params.dictionary,
+            throttled_until: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -165,6 +185,7 @@ where
     /// Flush all the batches that are full or have reached the linger time.
     /// If force is set to true, flush all batches regardless of linger time.
     pub(crate) async fn flush(&self, force: bool) -> Result<()> {
+        self.wait_for_throttle().await;
         let spu_socket = self.connect_spu_with_reconnect().await?;
 
         let mut batches_ready = vec![];
@@ -198,6 +219,10 @@ where
 
         let mut events_to_callback = vec![];
 
+        let mut deliveries = vec![];
+
+        let mut batches_created_at = vec![];
+
         for p_batch in batches_ready {
             let mut partition_request = DefaultPartitionRequest {
                 partition_index: self.replica.partition,
@@ -207,7 +232,13 @@ where
             let metadata = p_batch.metadata().clone();
             let batch = p_batch.batch();
 
-            let raw_batch: Batch<RawRecords> = batch.try_into()?;
+            self.latency
+                .batch_fill()
+                .observe_duration(metadata.created_at.elapsed());
+            batches_created_at.push(metadata.created_at);
+
+            let raw_batch: Batch<RawRecords> =
+                batch.compress_with_dictionary(self.dictionary.as_ref())?;
 
             let producer_metrics = self.metrics.producer_client();
             let records_len = raw_batch.records_len() as u64;
@@ -217,6 +248,9 @@ where
 
             partition_request.records.batches.push(raw_batch);
             batch_notifiers.push(notify);
+            if !self.config.interceptors.is_empty() {
+                deliveries.push((metadata.clone(), records_len));
+            }
             topic_request.partitions.push(partition_request);
 
             if self.callback.is_some() {
@@ -239,8 +273,21 @@ where
         request.smartmodules.clone_from(&self.config.smartmodules);
         request.topics.push(topic_request);
 
+        for (metadata, records_len) in deliveries {
+            self.report_delivery(metadata, records_len);
+        }
+
         let (response, _) = self.send_to_socket(spu_socket, request).await?;
 
+        // acknowledged only when the response is awaited
+        if let DeliverySemantic::AtLeastOnce(_) = self.config.delivery_semantic {
+            for created_at in batches_created_at {
+                self.latency
+                    .send_to_ack()
+                    .observe_duration(created_at.elapsed());
+            }
+        }
+
         for (batch_notifier, partition_response_fut) in
             batch_notifiers.into_iter().zip(response.into_iter())
         {
@@ -260,6 +307,40 @@ where
         Ok(())
     }
 
+    /// Reports the delivery result of each record of the batch to the interceptors,
+    /// once the batch is sent or failed.
+    fn report_delivery(&self, metadata: Arc<BatchMetadata>, records_len: u64) {
+        let interceptors = self.config.interceptors.clone();
+        let topic = self.replica.topic.clone();
+        let partition = self.replica.partition;
+        spawn(async move {
+            let result = metadata.result().await;
+            for relative_offset in 0..records_len as i64 {
+                interceptors.on_delivery(&DeliveryResult {
+                    topic: &topic,
+                    partition,
+                    offset: result
+                        .as_ref()
+                        .map(|base_offset| base_offset + relative_offset),
+                });
+            }
+        });
+    }
+
+    /// SPU doesn't delay requests over quota, it only reports throttle time.
+    /// Hold back next produce request until that time has passed.
+    async fn wait_for_throttle(&self) {
+        let throttled_until = self.throttled_until.lock().unwrap().take();
+        if let Some(wait) =
+            throttled_until.map(|until| until.saturating_duration_since(Instant::now()))
+        {
+            if !wait.is_zero() {
+                debug!(throttle_ms = wait.as_millis(), "throttled by SPU quota");
+                sleep(wait).await;
+            }
+        }
+    }
+
     async fn connect_spu(&self) -> Result<VersionedSerialSocket> {
         let leader = self.current_leader().await?;
         self.spu_pool.create_serial_socket_from_leader(leader).await
@@ -294,6 +375,13 @@ where
                 use futures_util::FutureExt;
                 let async_response = socket.send_async(request).await?;
                 let shared = FutureExt::map(async_response, Arc::new).boxed().shared();
+                let response = shared.clone();
+                let throttled_until = self.throttled_until.clone();
+                spawn(async move {
+                    if let Ok(response) = response.await.as_ref() {
+                        record_throttle(&throttled_until, response.throttle_time_ms);
+                    }
+                });
                 (0..partition_count)
                     .map(|index| ProducePartitionResponseFuture::from(shared.clone(), index))
                     .collect()
@@ -305,6 +393,7 @@ where
                     .timeout(policy.timeout)
                     .await
                     .map_err(|timeout_err| FluvioError::Producer(timeout_err.into()))??;
+                record_throttle(&self.throttled_until, produce_response.throttle_time_ms);
 
                 let mut futures = Vec::with_capacity(partition_count);
                 for topic in produce_response.responses.into_iter() {
@@ -323,6 +412,15 @@ where
     }
 }
 
+/// Remember until when SPU asked client to back off
+fn record_throttle(throttled_until: &Mutex<Option<Instant>>, throttle_time_ms: i32) {
+    if throttle_time_ms > 0 {
+        let until = Instant::now() + Duration::from_millis(throttle_time_ms as u64);
+        let mut throttled_until = throttled_until.lock().unwrap();
+        *throttled_until = Some(throttled_until.map_or(until, |current| current.max(until)));
+    }
+}
+
 /// Creates an exponential backoff configuration.
 fn create_backoff() -> anyhow::Result<ExponentialBackoff> {
     ExponentialBackoffBuilder::default()
@@ -1,7 +1,16 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
 use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use serde::{Serialize, Deserialize};
 use siphasher::sip::SipHasher;
+use fluvio_protocol::EncoderVarInt;
+use fluvio_protocol::record::{Batch, RawRecords};
+use fluvio_protocol::Encoder;
 use fluvio_types::{PartitionId, PartitionCount};
 
+use super::config::DEFAULT_BATCH_SIZE_BYTES;
+
 /// A trait for defining a partitioning strategy for key/value records.
 ///
 /// A Partitioner is given a slice of potential keys, and the number of
@@ -63,22 +72,31 @@ impl Partitioner for SiphashRoundRobinPartitioner {
                 // Atomic increment. This will wrap on overflow, which is fine
                 // because we are only interested in the modulus anyway
                 let index = self.index.fetch_add(1, Ordering::Relaxed);
-                if config.available_partitions.is_empty() {
-                    return index % config.partition_count;
-                }
-                let partition = index as usize % config.available_partitions.len();
-                config.available_partitions[partition]
+                round_robin(index, config)
             }
         }
     }
 }
 
-fn partition_siphash(key: &[u8], partition_count: PartitionCount) -> PartitionId {
+/// Partition at the index, skipping partitions that are not available
+fn round_robin(index: u32, config: &PartitionerConfig) -> PartitionId {
+    if config.available_partitions.is_empty() {
+        return index % config.partition_count;
+    }
+    let partition = index as usize % config.available_partitions.len();
+    config.available_partitions[partition]
+}
+
+fn siphash(key: &[u8]) -> u64 {
     use std::hash::{Hash, Hasher};
 
     let mut hasher = SipHasher::new();
     key.hash(&mut hasher);
-    let hashed = hasher.finish();
+    hasher.finish()
+}
+
+fn partition_siphash(key: &[u8], partition_count: PartitionCount) -> PartitionId {
+    let hashed = siphash(key);
 
     let partition_id = hashed % partition_count as u64;
     match PartitionId::try_from(partition_id) {
@@ -109,6 +127,285 @@ impl Partitioner for SpecificPartitioner {
     }
 }
 
+/// A [`Partitioner`] which sends keyless records to one partition until
+/// the batch of that partition is filled, then moves on to the next one.
+///
+/// Fewer and larger batches are sent than with round-robin assignment.
+/// Records with keys get their keys hashed with siphash, like [`PartitionerKind::Siphash`].
+pub struct StickyPartitioner {
+    batch_size: usize,
+    state: Mutex<StickyState>,
+}
+
+#[derive(Default)]
+struct StickyState {
+    index: u32,
+    partition: Option<PartitionId>,
+    bytes: usize,
+}
+
+impl StickyPartitioner {
+    /// Moves to the next partition once a record no longer fits in a batch of `batch_size` bytes,
+    /// which should be the batch size of the producer.
+    /// Encoded size of records is counted, including key and record overhead.
+    pub fn new(batch_size: usize) -> Self {
+        Self {
+            batch_size,
+            state: Mutex::new(StickyState::default()),
+        }
+    }
+}
+
+impl Default for StickyPartitioner {
+    fn default() -> Self {
+        Self::new(DEFAULT_BATCH_SIZE_BYTES)
+    }
+}
+
+/// Size of an empty batch, as counted against the batch size by the producer
+fn empty_batch_size() -> usize {
+    Batch::<RawRecords>::default().write_size(0) + Vec::<RawRecords>::default().write_size(0)
+}
+
+/// Encoded size of a record with the key and value, offset and timestamp deltas
+/// are estimated as zero
+fn record_size(key: Option<&[u8]>, value: &[u8]) -> usize {
+    fn data_size(data: &[u8]) -> usize {
+        (data.len() as i64).var_write_size() + data.len()
+    }
+
+    // attributes, timestamp and offset deltas
+    const RECORD_HEADER_SIZE: usize = 3;
+    // key is optional, so it is prefixed by a flag
+    let key_size = 1 + key.map(data_size).unwrap_or_default();
+    // no headers
+    let headers_size = 0i64.var_write_size();
+
+    let inner_size = RECORD_HEADER_SIZE + key_size + data_size(value) + headers_size;
+    (inner_size as i64).var_write_size() + inner_size
+}
+
+impl Partitioner for StickyPartitioner {
+    fn partition(
+        &self,
+        config: &PartitionerConfig,
+        maybe_key: Option<&[u8]>,
+        value: &[u8],
+    ) -> PartitionId {
+        if let Some(key) = maybe_key {
+            return partition_siphash(key, config.partition_count());
+        }
+
+        let size = record_size(maybe_key, value);
+        let mut state = self.state.lock().expect("sticky partitioner lock");
+        let partition = match state.partition {
+            Some(partition)
+                if state.bytes + size <= self.batch_size
+                    && partition < config.partition_count
+                    && (config.available_partitions.is_empty()
+                        || config.available_partitions.contains(&partition)) =>
+            {
+                partition
+            }
+            _ => {
+                let partition = round_robin(state.index, config);
+                state.index = state.index.wrapping_add(1);
+                state.partition = Some(partition);
+                state.bytes = empty_batch_size();
+                partition
+            }
+        };
+        state.bytes += size;
+        partition
+    }
+}
+
+/// A [`Partitioner`] compatible with the default partitioner of Kafka clients
+///
+/// - Records with keys get their keys hashed with murmur2, so keyed records
+///   land on the same partition as when produced by a Kafka client
+/// - Records without keys get assigned to partitions using round-robin
+pub struct Murmur2Partitioner {
+    index: AtomicU32,
+}
+
+impl Murmur2Partitioner {
+    pub fn new() -> Self {
+        Self {
+            index: AtomicU32::new(0),
+        }
+    }
+}
+
+impl Default for Murmur2Partitioner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Partitioner for Murmur2Partitioner {
+    fn partition(
+        &self,
+        config: &PartitionerConfig,
+        maybe_key: Option<&[u8]>,
+        _value: &[u8],
+    ) -> PartitionId {
+        match maybe_key {
+            // positive part of the hash, as Kafka does
+            Some(key) => (murmur2(key) & 0x7fffffff) % config.partition_count(),
+            None => round_robin(self.index.fetch_add(1, Ordering::Relaxed), config),
+        }
+    }
+}
+
+/// Murmur2 hash, as implemented by Kafka clients
+fn murmur2(data: &[u8]) -> u32 {
+    const SEED: u32 = 0x9747b28c;
+    const M: u32 = 0x5bd1e995;
+    const R: u32 = 24;
+
+    let mut h = SEED ^ data.len() as u32;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M);
+        h ^= k;
+    }
+
+    let tail = chunks.remainder();
+    if tail.len() >= 3 {
+        h ^= (tail[2] as u32) << 16;
+    }
+    if tail.len() >= 2 {
+        h ^= (tail[1] as u32) << 8;
+    }
+    if !tail.is_empty() {
+        h ^= tail[0] as u32;
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h
+}
+
+/// A [`Partitioner`] which uses consistent hashing
+///
+/// - Records with keys get their keys hashed with jump consistent hash. When partitions
+///   are added, only the keys moving to the new partitions change their partition.
+/// - Records without keys get assigned to partitions using round-robin
+pub struct ConsistentPartitioner {
+    index: AtomicU32,
+}
+
+impl ConsistentPartitioner {
+    pub fn new() -> Self {
+        Self {
+            index: AtomicU32::new(0),
+        }
+    }
+}
+
+impl Default for ConsistentPartitioner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Partitioner for ConsistentPartitioner {
+    fn partition(
+        &self,
+        config: &PartitionerConfig,
+        maybe_key: Option<&[u8]>,
+        _value: &[u8],
+    ) -> PartitionId {
+        match maybe_key {
+            Some(key) => jump_consistent_hash(siphash(key), config.partition_count()),
+            None => round_robin(self.index.fetch_add(1, Ordering::Relaxed), config),
+        }
+    }
+}
+
+/// Jump consistent hash from "A Fast, Minimal Memory, Consistent Hash Algorithm"
+/// by Lamping and Veach
+fn jump_consistent_hash(mut key: u64, buckets: PartitionCount) -> PartitionId {
+    let mut bucket: i64 = -1;
+    let mut next: i64 = 0;
+    while next < buckets as i64 {
+        bucket = next;
+        key = key.wrapping_mul(2862933555777941757).wrapping_add(1);
+        next = ((bucket + 1) as f64 * ((1u64 << 31) as f64 / ((key >> 33) + 1) as f64)) as i64;
+    }
+    bucket.max(0) as PartitionId
+}
+
+/// Built-in partitioners, selectable by name
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, Hash)]
+#[serde(rename_all = "kebab-case")]
+pub enum PartitionerKind {
+    /// Keys hashed with siphash, keyless records assigned using round-robin
+    #[default]
+    Siphash,
+    /// Keys hashed with siphash, keyless records sent to one partition per batch
+    Sticky,
+    /// Keys hashed with murmur2 like Kafka clients, keyless records assigned using round-robin
+    Murmur2,
+    /// Keys hashed with jump consistent hash, keyless records assigned using round-robin
+    Consistent,
+}
+
+impl PartitionerKind {
+    /// Partitioner for producers with the default batch size
+    pub fn partitioner(&self) -> Arc<dyn Partitioner + Send + Sync> {
+        self.partitioner_with_batch_size(DEFAULT_BATCH_SIZE_BYTES)
+    }
+
+    /// Partitioner for producers with `batch_size`, which is used by [`StickyPartitioner`]
+    pub fn partitioner_with_batch_size(
+        &self,
+        batch_size: usize,
+    ) -> Arc<dyn Partitioner + Send + Sync> {
+        match self {
+            Self::Siphash => Arc::new(SiphashRoundRobinPartitioner::new()),
+            Self::Sticky => Arc::new(StickyPartitioner::new(batch_size)),
+            Self::Murmur2 => Arc::new(Murmur2Partitioner::new()),
+            Self::Consistent => Arc::new(ConsistentPartitioner::new()),
+        }
+    }
+}
+
+impl Display for PartitionerKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Siphash => "siphash",
+            Self::Sticky => "sticky",
+            Self::Murmur2 => "murmur2",
+            Self::Consistent => "consistent",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for PartitionerKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "siphash" | "default" => Ok(Self::Siphash),
+            "sticky" => Ok(Self::Sticky),
+            "murmur2" | "kafka" => Ok(Self::Murmur2),
+            "consistent" => Ok(Self::Consistent),
+            _ => Err(format!(
+                "unrecognized partitioner: {s}. Supported: siphash, sticky, murmur2, consistent"
+            )),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,4 +493,88 @@ mod tests {
         let key6_partition = partitioner.partition(&config, None, &[]);
         assert_eq!(key6_partition, 2);
     }
+
+    #[test]
+    fn test_murmur2_matches_kafka() {
+        // expected values from the Kafka client test suite
+        let cases: [(&[u8], i32); 6] = [
+            (b"21", -973932308),
+            (b"foobar", -790332482),
+            (b"a-little-bit-long-string", -985981536),
+            (b"a-little-bit-longer-string", -1486304829),
+            (
+                b"lkjh234lh9fiuh90y23oiuhsafujhadof229phr9h19h89h8",
+                -58897971,
+            ),
+            (b"abc", 479470107),
+        ];
+        for (key, expected) in cases {
+            assert_eq!(murmur2(key) as i32, expected);
+        }
+
+        let config = PartitionerConfig {
+            partition_count: 3,
+            available_partitions: vec![0, 1, 2],
+        };
+        let partitioner = Murmur2Partitioner::new();
+        assert_eq!(partitioner.partition(&config, Some(b"key"), &[]), 1);
+    }
+
+    #[test]
+    fn test_consistent_moves_keys_only_to_new_partition() {
+        for key in 0u32..1000 {
+            let hashed = siphash(&key.to_be_bytes());
+            let before = jump_consistent_hash(hashed, 10);
+            let after = jump_consistent_hash(hashed, 11);
+            assert!(before < 10);
+            assert!(after == before || after == 10);
+        }
+    }
+
+    #[test]
+    fn test_record_size() {
+        use fluvio_protocol::record::Record;
+
+        let value = [0u8; 200];
+        assert_eq!(
+            record_size(None, &value),
+            Record::new(value.to_vec()).write_size(0)
+        );
+        assert_eq!(
+            record_size(Some(b"key"), &value),
+            Record::new_key_value(b"key".to_vec(), value.to_vec()).write_size(0)
+        );
+    }
+
+    #[test]
+    fn test_sticky_switches_after_batch() {
+        let config = PartitionerConfig {
+            partition_count: 3,
+            available_partitions: vec![0, 2],
+        };
+        let value = [0u8; 4];
+        // three records fit in a batch
+        let batch_size = empty_batch_size() + 3 * record_size(None, &value);
+        let partitioner = StickyPartitioner::new(batch_size);
+
+        let partitions: Vec<_> = (0..7)
+            .map(|_| partitioner.partition(&config, None, &value))
+            .collect();
+
+        assert_eq!(partitions, vec![0, 0, 0, 2, 2, 2, 0]);
+    }
+
+    #[test]
+    fn test_partitioner_kind_from_str() {
+        for kind in [
+            PartitionerKind::Siphash,
+            PartitionerKind::Sticky,
+            PartitionerKind::Murmur2,
+            PartitionerKind::Consistent,
+        ] {
+            assert_eq!(kind.to_string().parse::<PartitionerKind>(), Ok(kind));
+        }
+        assert_eq!("Kafka".parse(), Ok(PartitionerKind::Murmur2));
+        assert!("random".parse::<PartitionerKind>().is_err());
+    }
 }
@@ -71,6 +71,11 @@ impl BatchMetadata {
     /// Wait for the base offset of the batch. This is the offset of the first
     /// record in the batch and it is known once the batch is sent to the server.
     pub(crate) async fn base_offset(&self) -> Result<Offset> {
+        Ok(self.result().await?)
+    }
+
+    /// Same as [`Self::base_offset`], keeping the producer error
+    pub(crate) async fn result(&self) -> Result<Offset, ProducerError> {
         let mut state = self.state.write().await;
         match &*state {
             BatchMetadataState::Buffered(receiver) => {
@@ -88,17 +93,17 @@ impl BatchMetadata {
                         } else {
                             let error = ProducerError::SpuErrorCode(error);
                             *state = BatchMetadataState::Failed(error.clone());
-                            Err(error.into())
+                            Err(error)
                         }
                     }
                     Err(err) => {
                         *state = BatchMetadataState::Failed(err.clone());
-                        Err(err.into())
+                        Err(err)
                     }
                 }
             }
             BatchMetadataState::Sent(offset) => Ok(*offset),
-            BatchMetadataState::Failed(error) => Err(error.clone().into()),
+            BatchMetadataState::Failed(error) => Err(error.clone()),
         }
     }
 }
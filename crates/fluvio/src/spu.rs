@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use anyhow::Result;
 
 use fluvio_sc_schema::partition::PartitionSpec;
+use fluvio_sc_schema::spu::SpuSpec;
 use fluvio_sc_schema::topic::TopicSpec;
 use tracing::{debug, trace, instrument};
 use async_lock::Mutex;
@@ -41,6 +42,39 @@ pub trait SpuDirectory {
     ) -> Result<AsyncResponse<R>, FluvioError>
     where
         R: Sync + Send;
+
+    /// Find follower replica in rack which is in sync with leader
+    ///
+    /// Returns none when leader itself is in rack or there is no such follower.
+    async fn find_follower_in_rack(
+        &self,
+        _replica: &ReplicaKey,
+        _rack: &str,
+    ) -> Result<Option<SpuId>, FluvioError> {
+        Ok(None)
+    }
+
+    /// Check that follower replica has replicated everything leader has committed
+    async fn is_follower_in_sync(
+        &self,
+        _replica: &ReplicaKey,
+        _follower: SpuId,
+    ) -> Result<bool, FluvioError> {
+        Ok(false)
+    }
+
+    /// create request/response socket and stream to follower SPU
+    async fn create_follower_stream_with_version<R: Request>(
+        &self,
+        follower: SpuId,
+        _request: R,
+        _version: i16,
+    ) -> Result<(VersionedSerialSocket, AsyncResponse<R>), FluvioError>
+    where
+        R: Sync + Send,
+    {
+        Err(FluvioError::SPUNotFound(follower))
+    }
 }
 
 /// connection pool to spu
@@ -233,4 +267,95 @@ impl SpuDirectory for SpuSocketPool {
 
         Ok(stream)
     }
+
+    #[instrument(skip(self, replica))]
+    async fn find_follower_in_rack(
+        &self,
+        replica: &ReplicaKey,
+        rack: &str,
+    ) -> Result<Option<SpuId>, FluvioError> {
+        let Some(partition) = self.metadata.partitions().lookup_by_key(replica).await? else {
+            return Err(FluvioError::PartitionNotFound(
+                replica.topic.to_owned(),
+                replica.partition,
+            ));
+        };
+        let spus = self.metadata.spus();
+        let in_rack = |spu: &SpuSpec| spu.rack.as_deref() == Some(rack);
+
+        let leader = spus.look_up_by_id(partition.spec.leader).await?;
+        if in_rack(&leader.spec) {
+            return Ok(None);
+        }
+
+        // follower is in sync once it has replicated everything leader has committed
+        let leader_hw = partition.status.leader.hw;
+        for follower in &partition.status.replicas {
+            if follower.leo < leader_hw {
+                continue;
+            }
+            let Ok(spu) = spus.look_up_by_id(follower.spu).await else {
+                continue;
+            };
+            if in_rack(&spu.spec) && spu.status.is_online() {
+                debug!(follower = follower.spu, rack, "found follower in rack");
+                return Ok(Some(follower.spu));
+            }
+        }
+        Ok(None)
+    }
+
+    #[instrument(skip(self, replica))]
+    async fn is_follower_in_sync(
+        &self,
+        replica: &ReplicaKey,
+        follower: SpuId,
+    ) -> Result<bool, FluvioError> {
+        let Some(partition) = self.metadata.partitions().lookup_by_key(replica).await? else {
+            return Err(FluvioError::PartitionNotFound(
+                replica.topic.to_owned(),
+                replica.partition,
+            ));
+        };
+        let leader_hw = partition.status.leader.hw;
+        Ok(partition
+            .status
+            .replicas
+            .iter()
+            .any(|replica| replica.spu == follower && replica.leo >= leader_hw))
+    }
+
+    #[instrument(skip(self, request, version))]
+    async fn create_follower_stream_with_version<R: Request>(
+        &self,
+        follower: SpuId,
+        request: R,
+        version: i16,
+    ) -> Result<(VersionedSerialSocket, AsyncResponse<R>), FluvioError>
+    where
+        R: Sync + Send,
+    {
+        let mut client_lock = self.spu_clients.lock().await;
+
+        if let Some(spu_socket) = client_lock.get_mut(&follower) {
+            if !spu_socket.is_stale() {
+                let serial_socket = spu_socket.create_serial_socket().await;
+                let stream = spu_socket
+                    .create_stream_with_version(request, version)
+                    .await?;
+                return Ok((serial_socket, stream));
+            } else {
+                client_lock.remove(&follower);
+            }
+        }
+
+        let mut spu_socket = self.connect_to_leader(follower).await?;
+        let serial_socket = spu_socket.create_serial_socket().await;
+        let stream = spu_socket
+            .create_stream_with_version(request, version)
+            .await?;
+        client_lock.insert(follower, spu_socket);
+
+        Ok((serial_socket, stream))
+    }
 }
@@ -0,0 +1,303 @@
+use std::marker::PhantomData;
+
+/// Error from encoding or decoding a record key or value
+#[derive(thiserror::Error, Debug)]
+#[error("{codec} codec: {source}")]
+pub struct CodecError {
+    codec: &'static str,
+    #[source]
+    source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl CodecError {
+    pub fn new(
+        codec: &'static str,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Self {
+            codec,
+            source: source.into(),
+        }
+    }
+
+    /// Name of the codec that failed
+    pub fn codec(&self) -> &'static str {
+        self.codec
+    }
+}
+
+/// Converts values of type `T` to and from record bytes
+pub trait Codec<T> {
+    fn encode(&self, value: &T) -> Result<Vec<u8>, CodecError>;
+
+    fn decode(&self, bytes: &[u8]) -> Result<T, CodecError>;
+}
+
+/// Raw bytes, no conversion
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BytesCodec;
+
+impl Codec<Vec<u8>> for BytesCodec {
+    fn encode(&self, value: &Vec<u8>) -> Result<Vec<u8>, CodecError> {
+        Ok(value.clone())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Vec<u8>, CodecError> {
+        Ok(bytes.to_vec())
+    }
+}
+
+/// UTF-8 strings, typically used for keys
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StringCodec;
+
+impl Codec<String> for StringCodec {
+    fn encode(&self, value: &String) -> Result<Vec<u8>, CodecError> {
+        Ok(value.as_bytes().to_vec())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<String, CodecError> {
+        String::from_utf8(bytes.to_vec()).map_err(|err| CodecError::new("string", err))
+    }
+}
+
+/// JSON using serde
+#[cfg(feature = "json")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsonCodec;
+
+#[cfg(feature = "json")]
+impl<T> Codec<T> for JsonCodec
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn encode(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        serde_json::to_vec(value).map_err(|err| CodecError::new("json", err))
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<T, CodecError> {
+        serde_json::from_slice(bytes).map_err(|err| CodecError::new("json", err))
+    }
+}
+
+/// MessagePack using serde. Structs are encoded as maps, so fields can be added
+/// and reordered without breaking existing readers.
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MsgPackCodec;
+
+#[cfg(feature = "msgpack")]
+impl<T> Codec<T> for MsgPackCodec
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn encode(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        rmp_serde::to_vec_named(value).map_err(|err| CodecError::new("msgpack", err))
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<T, CodecError> {
+        rmp_serde::from_slice(bytes).map_err(|err| CodecError::new("msgpack", err))
+    }
+}
+
+/// Protocol Buffers using prost generated messages
+#[cfg(feature = "protobuf")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ProtobufCodec;
+
+#[cfg(feature = "protobuf")]
+impl<T> Codec<T> for ProtobufCodec
+where
+    T: prost::Message + Default,
+{
+    fn encode(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        Ok(value.encode_to_vec())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<T, CodecError> {
+        T::decode(bytes).map_err(|err| CodecError::new("protobuf", err))
+    }
+}
+
+/// Avro datum encoding of serde types with a schema known to both producer and consumer.
+/// The schema is not embedded in the record.
+#[cfg(feature = "avro")]
+#[derive(Debug, Clone)]
+pub struct AvroCodec {
+    schema: apache_avro::Schema,
+}
+
+#[cfg(feature = "avro")]
+impl AvroCodec {
+    /// Creates codec from schema in JSON form
+    pub fn new(schema: &str) -> Result<Self, CodecError> {
+        let schema =
+            apache_avro::Schema::parse_str(schema).map_err(|err| CodecError::new("avro", err))?;
+        Ok(Self { schema })
+    }
+
+    pub fn schema(&self) -> &apache_avro::Schema {
+        &self.schema
+    }
+}
+
+#[cfg(feature = "avro")]
+impl<T> Codec<T> for AvroCodec
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    fn encode(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        let value = apache_avro::to_value(value).map_err(|err| CodecError::new("avro", err))?;
+        let value = value
+            .resolve(&self.schema)
+            .map_err(|err| CodecError::new("avro", err))?;
+        apache_avro::to_avro_datum(&self.schema, value).map_err(|err| CodecError::new("avro", err))
+    }
+
+    fn decode(&self, mut bytes: &[u8]) -> Result<T, CodecError> {
+        let value = apache_avro::from_avro_datum(&self.schema, &mut bytes, None)
+            .map_err(|err| CodecError::new("avro", err))?;
+        apache_avro::from_value(&value).map_err(|err| CodecError::new("avro", err))
+    }
+}
+
+/// Codec from a pair of functions, for formats without a built-in codec
+pub struct FnCodec<T, E, D> {
+    encode: E,
+    decode: D,
+    data: PhantomData<fn() -> T>,
+}
+
+impl<T, E, D> FnCodec<T, E, D>
+where
+    E: Fn(&T) -> Result<Vec<u8>, CodecError>,
+    D: Fn(&[u8]) -> Result<T, CodecError>,
+{
+    pub fn new(encode: E, decode: D) -> Self {
+        Self {
+            encode,
+            decode,
+            data: PhantomData,
+        }
+    }
+}
+
+impl<T, E, D> Codec<T> for FnCodec<T, E, D>
+where
+    E: Fn(&T) -> Result<Vec<u8>, CodecError>,
+    D: Fn(&[u8]) -> Result<T, CodecError>,
+{
+    fn encode(&self, value: &T) -> Result<Vec<u8>, CodecError> {
+        (self.encode)(value)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<T, CodecError> {
+        (self.decode)(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[allow(dead_code)]
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Order {
+        id: u64,
+        item: String,
+        quantity: i32,
+    }
+
+    #[allow(dead_code)]
+    fn order() -> Order {
+        Order {
+            id: 7,
+            item: "book".to_owned(),
+            quantity: 2,
+        }
+    }
+
+    #[test]
+    fn test_string_codec() {
+        let bytes = StringCodec.encode(&"key".to_owned()).expect("encode");
+        assert_eq!(bytes, b"key");
+        assert_eq!(StringCodec.decode(&bytes).expect("decode"), "key");
+
+        let err = StringCodec.decode(&[0xff, 0xfe]).expect_err("invalid utf8");
+        assert_eq!(err.codec(), "string");
+    }
+
+    #[test]
+    fn test_fn_codec() {
+        let codec = FnCodec::new(
+            |value: &u32| Ok(value.to_be_bytes().to_vec()),
+            |bytes: &[u8]| {
+                let bytes: [u8; 4] = bytes
+                    .try_into()
+                    .map_err(|err| CodecError::new("u32", err))?;
+                Ok(u32::from_be_bytes(bytes))
+            },
+        );
+        let bytes = codec.encode(&42).expect("encode");
+        assert_eq!(codec.decode(&bytes).expect("decode"), 42);
+        assert!(codec.decode(&[1]).is_err());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_codec() {
+        let bytes = JsonCodec.encode(&order()).expect("encode");
+        assert_eq!(
+            std::str::from_utf8(&bytes).expect("utf8"),
+            r#"{"id":7,"item":"book","quantity":2}"#
+        );
+        let decoded: Order = JsonCodec.decode(&bytes).expect("decode");
+        assert_eq!(decoded, order());
+
+        let err = Codec::<Order>::decode(&JsonCodec, b"{\"id\":7}").expect_err("missing fields");
+        assert_eq!(err.codec(), "json");
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn test_msgpack_codec() {
+        let bytes = MsgPackCodec.encode(&order()).expect("encode");
+        let decoded: Order = MsgPackCodec.decode(&bytes).expect("decode");
+        assert_eq!(decoded, order());
+        assert!(Codec::<Order>::decode(&MsgPackCodec, b"\xc1").is_err());
+    }
+
+    #[cfg(feature = "protobuf")]
+    #[test]
+    fn test_protobuf_codec() {
+        let bytes = ProtobufCodec.encode(&"hello".to_owned()).expect("encode");
+        let decoded: String = ProtobufCodec.decode(&bytes).expect("decode");
+        assert_eq!(decoded, "hello");
+        assert!(Codec::<String>::decode(&ProtobufCodec, &[0x0a, 0x05, b'h']).is_err());
+    }
+
+    #[cfg(feature = "avro")]
+    #[test]
+    fn test_avro_codec() {
+        let codec = AvroCodec::new(
+            r#"{
+                "type": "record",
+                "name": "Order",
+                "fields": [
+                    {"name": "id", "type": "long"},
+                    {"name": "item", "type": "string"},
+                    {"name": "quantity", "type": "int"}
+                ]
+            }"#,
+        )
+        .expect("valid schema");
+        let bytes = codec.encode(&order()).expect("encode");
+        let decoded: Order = codec.decode(&bytes).expect("decode");
+        assert_eq!(decoded, order());
+        assert!(Codec::<Order>::decode(&codec, &[0x0e]).is_err());
+
+        assert!(AvroCodec::new("{").is_err());
+    }
+}
@@ -0,0 +1,322 @@
+use std::fmt;
+#[cfg(feature = "dead-letter")]
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use futures_util::{ready, Stream};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_types::{PartitionId, Timestamp};
+
+use crate::consumer::{BoxConsumerStream, ConsumerRecord, ConsumerStream};
+#[cfg(feature = "dead-letter")]
+use crate::consumer::{DeadLetterQueue, DeadLetterRecord};
+
+use super::{Codec, CodecError, SharedCodec};
+
+/// Record with decoded key and value
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedRecord<K, V> {
+    pub partition: PartitionId,
+    pub offset: i64,
+    pub timestamp: Timestamp,
+    pub key: Option<K>,
+    pub value: V,
+}
+
+/// Part of the record that failed to decode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordPart {
+    Key,
+    Value,
+}
+
+impl fmt::Display for RecordPart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Key => write!(f, "key"),
+            Self::Value => write!(f, "value"),
+        }
+    }
+}
+
+/// Possible errors from [`TypedConsumerStream`]
+#[derive(thiserror::Error, Debug)]
+pub enum TypedConsumerError {
+    #[error("Consumer error: {0}")]
+    Consumer(#[from] ErrorCode),
+    /// Record could not be decoded. The stream continues with the next record.
+    #[error(
+        "failed to decode {part} of record at partition: {partition}, offset: {offset}: {source}"
+    )]
+    Decode {
+        partition: PartitionId,
+        offset: i64,
+        part: RecordPart,
+        #[source]
+        source: CodecError,
+    },
+    #[cfg(feature = "dead-letter")]
+    #[error("failed to publish record to dead-letter topic: {0}")]
+    DeadLetter(anyhow::Error),
+}
+
+#[cfg(all(feature = "dead-letter", not(target_arch = "wasm32")))]
+type DeadLetterFuture = futures_util::future::BoxFuture<'static, anyhow::Result<()>>;
+#[cfg(all(feature = "dead-letter", target_arch = "wasm32"))]
+type DeadLetterFuture = futures_util::future::LocalBoxFuture<'static, anyhow::Result<()>>;
+
+/// Consumer stream that decodes keys and values of records with codecs
+pub struct TypedConsumerStream<K, V, S = BoxConsumerStream> {
+    stream: S,
+    key_codec: SharedCodec<K>,
+    value_codec: SharedCodec<V>,
+    /// queue of records that failed to decode
+    #[cfg(feature = "dead-letter")]
+    dead_letter: Option<DeadLetterQueue>,
+    #[cfg(feature = "dead-letter")]
+    sending: Option<DeadLetterFuture>,
+}
+
+impl<K, V, S> TypedConsumerStream<K, V, S>
+where
+    S: ConsumerStream,
+{
+    pub fn new(
+        stream: S,
+        key_codec: impl Codec<K> + Send + Sync + 'static,
+        value_codec: impl Codec<V> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            stream,
+            key_codec: Arc::new(key_codec),
+            value_codec: Arc::new(value_codec),
+            #[cfg(feature = "dead-letter")]
+            dead_letter: None,
+            #[cfg(feature = "dead-letter")]
+            sending: None,
+        }
+    }
+
+    /// Publishes records that fail to decode to the dead-letter queue and continues
+    /// with the next record instead of returning [`TypedConsumerError::Decode`].
+    /// Decoding is not retried, the result would be the same.
+    #[cfg(feature = "dead-letter")]
+    pub fn with_dead_letter(mut self, queue: DeadLetterQueue) -> Self {
+        self.dead_letter = Some(queue);
+        self
+    }
+
+    /// Uses the same codec for keys and values
+    pub fn with_codec<C>(stream: S, codec: C) -> Self
+    where
+        C: Codec<K> + Codec<V> + Clone + Send + Sync + 'static,
+    {
+        Self::new(stream, codec.clone(), codec)
+    }
+
+    /// See [`ConsumerStream::offset_commit`]
+    pub async fn offset_commit(&mut self) -> Result<(), ErrorCode> {
+        self.stream.offset_commit().await
+    }
+
+    /// See [`ConsumerStream::offset_flush`]
+    pub async fn offset_flush(&mut self) -> Result<(), ErrorCode> {
+        self.stream.offset_flush().await
+    }
+
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+
+    fn decode(&self, record: &ConsumerRecord) -> Result<TypedRecord<K, V>, TypedConsumerError> {
+        let decode_error = |part, source| TypedConsumerError::Decode {
+            partition: record.partition,
+            offset: record.offset,
+            part,
+            source,
+        };
+        let key = record
+            .key()
+            .map(|key| self.key_codec.decode(key))
+            .transpose()
+            .map_err(|err| decode_error(RecordPart::Key, err))?;
+        let value = self
+            .value_codec
+            .decode(record.value())
+            .map_err(|err| decode_error(RecordPart::Value, err))?;
+        Ok(TypedRecord {
+            partition: record.partition,
+            offset: record.offset,
+            timestamp: record.timestamp(),
+            key,
+            value,
+        })
+    }
+}
+
+impl<K, V, S> Stream for TypedConsumerStream<K, V, S>
+where
+    S: ConsumerStream,
+{
+    type Item = Result<TypedRecord<K, V>, TypedConsumerError>;
+
+    #[cfg(not(feature = "dead-letter"))]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let self_mut = self.get_mut();
+        let item = ready!(Pin::new(&mut self_mut.stream).poll_next(cx));
+        Poll::Ready(item.map(|result| match result {
+            Ok(record) => self_mut.decode(&record),
+            Err(err) => Err(err.into()),
+        }))
+    }
+
+    #[cfg(feature = "dead-letter")]
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let self_mut = self.get_mut();
+        loop {
+            if let Some(sending) = self_mut.sending.as_mut() {
+                let result = ready!(sending.as_mut().poll(cx));
+                self_mut.sending = None;
+                if let Err(err) = result {
+                    return Poll::Ready(Some(Err(TypedConsumerError::DeadLetter(err))));
+                }
+            }
+
+            let item = ready!(Pin::new(&mut self_mut.stream).poll_next(cx));
+            let record = match item {
+                Some(Ok(record)) => record,
+                Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+                None => return Poll::Ready(None),
+            };
+            let decoded = self_mut.decode(&record);
+            let Some(queue) = &self_mut.dead_letter else {
+                return Poll::Ready(Some(decoded));
+            };
+            let err = match decoded {
+                Err(err @ TypedConsumerError::Decode { .. }) => err,
+                decoded => return Poll::Ready(Some(decoded)),
+            };
+            let dead_letter = DeadLetterRecord::from_record(&record, err, 1);
+            let queue = queue.clone();
+            self_mut.sending = Some(Box::pin(async move { queue.send(&dead_letter).await }));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fluvio_protocol::record::{Batch, Record, RecordData, RecordKey};
+    use futures_util::stream::{iter, Iter};
+    use futures_util::StreamExt;
+
+    use crate::consumer::ConsumerBoxFuture;
+    use crate::typed::{BytesCodec, FnCodec, StringCodec};
+
+    use super::*;
+
+    struct TestStream(Iter<std::vec::IntoIter<Result<ConsumerRecord, ErrorCode>>>);
+
+    impl Stream for TestStream {
+        type Item = Result<ConsumerRecord, ErrorCode>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.0.poll_next_unpin(cx)
+        }
+    }
+
+    impl ConsumerStream for TestStream {
+        fn offset_commit(&mut self) -> ConsumerBoxFuture<'_> {
+            Box::pin(async { Ok(()) })
+        }
+
+        fn offset_flush(&mut self) -> ConsumerBoxFuture<'_> {
+            Box::pin(async { Ok(()) })
+        }
+    }
+
+    fn test_stream(records: Vec<(RecordKey, &'static str)>) -> TestStream {
+        let mut records: Vec<_> = records
+            .into_iter()
+            .map(|(key, value)| Record::from((key, RecordData::from(value))))
+            .collect();
+        let mut batch = Batch::default();
+        batch.add_records(&mut records);
+        let mut records: Vec<_> = batch.into_consumer_records_iter(0).map(Ok).collect();
+        records.push(Err(ErrorCode::TopicDeleted));
+        TestStream(iter(records))
+    }
+
+    fn u32_codec() -> impl Codec<u32> + Send + Sync + 'static {
+        FnCodec::new(
+            |value: &u32| Ok(value.to_string().into_bytes()),
+            |bytes: &[u8]| {
+                std::str::from_utf8(bytes)
+                    .map_err(|err| CodecError::new("u32", err))?
+                    .parse()
+                    .map_err(|err| CodecError::new("u32", err))
+            },
+        )
+    }
+
+    #[fluvio_future::test]
+    async fn test_typed_stream_decodes_records() {
+        //given
+        let stream = test_stream(vec![
+            (RecordKey::from("a"), "1"),
+            (RecordKey::NULL, "2"),
+            (RecordKey::from("c"), "three"),
+            (RecordKey::from(vec![0xff]), "4"),
+        ]);
+        let mut typed = TypedConsumerStream::new(stream, StringCodec, u32_codec());
+
+        //when
+        let first = typed.next().await.expect("record").expect("decoded");
+        let second = typed.next().await.expect("record").expect("decoded");
+        let third = typed.next().await.expect("record");
+        let fourth = typed.next().await.expect("record");
+        let fifth = typed.next().await.expect("record");
+
+        //then
+        assert_eq!(first.key.as_deref(), Some("a"));
+        assert_eq!(first.value, 1);
+        assert_eq!(first.offset, 0);
+        assert_eq!(second.key, None);
+        assert_eq!(second.value, 2);
+        assert!(matches!(
+            third,
+            Err(TypedConsumerError::Decode {
+                offset: 2,
+                part: RecordPart::Value,
+                ..
+            })
+        ));
+        assert!(matches!(
+            fourth,
+            Err(TypedConsumerError::Decode {
+                offset: 3,
+                part: RecordPart::Key,
+                ..
+            })
+        ));
+        assert!(matches!(
+            fifth,
+            Err(TypedConsumerError::Consumer(ErrorCode::TopicDeleted))
+        ));
+        assert!(typed.next().await.is_none());
+    }
+
+    #[fluvio_future::test]
+    async fn test_typed_stream_with_shared_codec() {
+        let stream = test_stream(vec![(RecordKey::from("k"), "v")]);
+        let mut typed = TypedConsumerStream::<Vec<u8>, Vec<u8>, _>::with_codec(stream, BytesCodec);
+
+        let record = typed.next().await.expect("record").expect("decoded");
+
+        assert_eq!(record.key, Some(b"k".to_vec()));
+        assert_eq!(record.value, b"v".to_vec());
+        assert!(typed.offset_commit().await.is_ok());
+    }
+}
@@ -0,0 +1,63 @@
+//! Typed producer and consumer.
+//!
+//! [`TypedProducer`] and [`TypedConsumerStream`] wrap [`crate::TopicProducer`] and
+//! [`crate::consumer::ConsumerStream`], converting record keys and values with a [`Codec`].
+//! Built-in codecs for JSON, MessagePack, Protocol Buffers and Avro are enabled by the
+//! `json`, `msgpack`, `protobuf` and `avro` features.
+//!
+//! ```no_run
+//! # #[cfg(feature = "json")]
+//! # async fn example(fluvio: &fluvio::Fluvio) -> anyhow::Result<()> {
+//! use fluvio::consumer::ConsumerConfigExtBuilder;
+//! use fluvio::typed::{JsonCodec, StringCodec, TypedConsumerStream, TypedProducer};
+//! use fluvio::Offset;
+//! use futures_util::StreamExt;
+//!
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Order {
+//!     id: u64,
+//!     item: String,
+//! }
+//!
+//! let producer = fluvio.topic_producer("orders").await?;
+//! let producer = TypedProducer::new(producer, StringCodec, JsonCodec);
+//! let order = Order { id: 1, item: "book".to_owned() };
+//! producer.send(&"customer-1".to_owned(), &order).await?;
+//! producer.flush().await?;
+//!
+//! let stream = fluvio
+//!     .consumer_with_config(
+//!         ConsumerConfigExtBuilder::default()
+//!             .topic("orders")
+//!             .offset_start(Offset::beginning())
+//!             .build()?,
+//!     )
+//!     .await?;
+//! let mut stream = TypedConsumerStream::<String, Order, _>::new(stream, StringCodec, JsonCodec);
+//! while let Some(record) = stream.next().await {
+//!     let record = record?;
+//!     println!("{:?}: {}", record.key, record.value.item);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod codec;
+mod consumer;
+mod producer;
+
+use std::sync::Arc;
+
+pub use codec::{BytesCodec, Codec, CodecError, FnCodec, StringCodec};
+#[cfg(feature = "json")]
+pub use codec::JsonCodec;
+#[cfg(feature = "msgpack")]
+pub use codec::MsgPackCodec;
+#[cfg(feature = "protobuf")]
+pub use codec::ProtobufCodec;
+#[cfg(feature = "avro")]
+pub use codec::AvroCodec;
+pub use consumer::{RecordPart, TypedConsumerError, TypedConsumerStream, TypedRecord};
+pub use producer::TypedProducer;
+
+type SharedCodec<T> = Arc<dyn Codec<T> + Send + Sync>;
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+
+use fluvio_protocol::record::RecordKey;
+
+use crate::producer::ProduceOutput;
+use crate::spu::{SpuPool, SpuSocketPool};
+use crate::TopicProducer;
+
+use super::{Codec, SharedCodec};
+
+/// Producer that encodes keys and values with codecs before sending them
+pub struct TypedProducer<K, V, S = SpuSocketPool>
+where
+    S: SpuPool + Send + Sync + 'static,
+{
+    producer: TopicProducer<S>,
+    key_codec: SharedCodec<K>,
+    value_codec: SharedCodec<V>,
+}
+
+impl<K, V, S> TypedProducer<K, V, S>
+where
+    S: SpuPool + Send + Sync + 'static,
+{
+    pub fn new(
+        producer: TopicProducer<S>,
+        key_codec: impl Codec<K> + Send + Sync + 'static,
+        value_codec: impl Codec<V> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            producer,
+            key_codec: Arc::new(key_codec),
+            value_codec: Arc::new(value_codec),
+        }
+    }
+
+    /// Uses the same codec for keys and values
+    pub fn with_codec<C>(producer: TopicProducer<S>, codec: C) -> Self
+    where
+        C: Codec<K> + Codec<V> + Clone + Send + Sync + 'static,
+    {
+        Self::new(producer, codec.clone(), codec)
+    }
+
+    /// Sends key/value record, the partition is derived from the encoded key
+    pub async fn send(&self, key: &K, value: &V) -> Result<ProduceOutput> {
+        let key = self.key_codec.encode(key)?;
+        let value = self.value_codec.encode(value)?;
+        self.producer.send(key, value).await
+    }
+
+    /// Sends record without key
+    pub async fn send_value(&self, value: &V) -> Result<ProduceOutput> {
+        let value = self.value_codec.encode(value)?;
+        self.producer.send(RecordKey::NULL, value).await
+    }
+
+    /// Sends all records, stops at the first record that can not be encoded or sent
+    pub async fn send_all<'a>(
+        &self,
+        records: impl IntoIterator<Item = (&'a K, &'a V)>,
+    ) -> Result<Vec<ProduceOutput>>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        let mut results = vec![];
+        for (key, value) in records {
+            results.push(self.send(key, value).await?);
+        }
+        Ok(results)
+    }
+
+    pub async fn flush(&self) -> Result<()> {
+        self.producer.flush().await
+    }
+
+    pub fn inner(&self) -> &TopicProducer<S> {
+        &self.producer
+    }
+
+    pub fn into_inner(self) -> TopicProducer<S> {
+        self.producer
+    }
+}
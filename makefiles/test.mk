@@ -98,6 +98,11 @@ election-test: TEST_ARG_EXTRA=--local $(EXTRA_ARG)
 election-test: test-setup
 	$(TEST_BIN) election  ${TEST_ARG_COMMON}
 
+# divergence test only runs on local
+divergence-test: TEST_ARG_EXTRA=--local $(EXTRA_ARG)
+divergence-test: test-setup
+	$(TEST_BIN) divergence  ${TEST_ARG_COMMON}
+
 multiple-partition-test: TEST_ARG_EXTRA=--local $(EXTRA_ARG)
 multiple-partition-test: test-setup
 	$(TEST_BIN) multiple_partition --partition 10 \